@@ -40,6 +40,22 @@ impl MicCapture {
         Self::from_device(device)
     }
 
+    /// The configured microphone (`[audio] device_mic`), or the default input
+    /// when none is set or it isn't plugged in
+    pub fn from_config(name: Option<&str>) -> Result<Self> {
+        let Some(name) = name else {
+            return Self::from_default();
+        };
+        Self::from_device_name(name).or_else(|e| {
+            tracing::warn!(
+                "Microphone \"{}\" unavailable ({}); using the default",
+                name,
+                e
+            );
+            Self::from_default()
+        })
+    }
+
     /// Create capture from cpal device
     pub fn from_device(device: Device) -> Result<Self> {
         let supported_config = device
@@ -228,4 +244,16 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_mic_capture_missing_device_falls_back() {
+        let configured = MicCapture::from_config(Some("muesli-test-no-such-device"));
+        assert_eq!(configured.is_ok(), MicCapture::from_default().is_ok());
+        if let (Ok(configured), Ok(default)) = (configured, MicCapture::from_default()) {
+            assert_eq!(
+                configured.device_info().unwrap().name,
+                default.device_info().unwrap().name
+            );
+        }
+    }
 }
@@ -0,0 +1,233 @@
+//! Opus compression of finished recordings (`[audio] compress_after_recording`).
+//!
+//! A 16kHz mono WAV grows by about 115 MB an hour; speech-rate Opus needs a
+//! tenth of that. Files are standard Ogg Opus (RFC 7845), so any player opens
+//! them, and everything that reads recordings decodes them back to samples.
+
+use crate::audio::convert::{to_mono, WHISPER_SAMPLE_RATE};
+use crate::error::{MuesliError, Result};
+use ogg::writing::PacketWriteEndInfo;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// 20 ms at 16 kHz, the frame size Opus is tuned for
+const FRAME_SAMPLES: usize = 320;
+/// Largest decoded frame (120 ms at 16 kHz)
+const MAX_FRAME_SAMPLES: usize = 1920;
+/// Plenty for speech, about 11 MB an hour
+const BITRATE: i32 = 24_000;
+/// Ogg Opus granule positions count 48 kHz samples whatever the input rate
+const GRANULES_PER_SAMPLE: u64 = 48_000 / WHISPER_SAMPLE_RATE as u64;
+const MAX_PACKET_BYTES: usize = 4000;
+const STREAM_SERIAL: u32 = 1;
+
+/// Whether `path` names an Opus recording rather than a WAV
+pub fn is_opus(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "opus")
+}
+
+/// Encode the WAV at `wav_path` into an `.opus` file next to it, returning its
+/// path. The WAV is left in place; the Opus file is removed again unless it
+/// decodes back to exactly as many samples.
+pub fn compress(wav_path: &Path) -> Result<PathBuf> {
+    let samples = crate::audio::wav::read_mono_16k(wav_path)?;
+    let opus_path = wav_path.with_extension("opus");
+    write_opus(&opus_path, &samples)?;
+
+    let verified = match read_opus(&opus_path) {
+        Ok(decoded) if decoded.len() == samples.len() => Ok(()),
+        Ok(decoded) => Err(MuesliError::Audio(format!(
+            "Opus file decoded to {} samples, expected {}",
+            decoded.len(),
+            samples.len()
+        ))),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = verified {
+        let _ = std::fs::remove_file(&opus_path);
+        return Err(e);
+    }
+    Ok(opus_path)
+}
+
+/// Write 16kHz mono samples as Ogg Opus. Goes through a temp file, so `path` may be the file that was read.
+pub fn write_opus(path: &Path, samples: &[f32]) -> Result<()> {
+    let mut encoder = opus::Encoder::new(
+        WHISPER_SAMPLE_RATE,
+        opus::Channels::Mono,
+        opus::Application::Voip,
+    )
+    .map_err(opus_error)?;
+    encoder
+        .set_bitrate(opus::Bitrate::Bits(BITRATE))
+        .map_err(opus_error)?;
+    let lookahead = encoder.get_lookahead().map_err(opus_error)?.max(0) as usize;
+    let pre_skip = lookahead as u64 * GRANULES_PER_SAMPLE;
+
+    let temp_path = path.with_extension("opus.tmp");
+    let mut writer = ogg::writing::PacketWriter::new(BufWriter::new(File::create(&temp_path)?));
+    writer.write_packet(
+        opus_head(pre_skip as u16),
+        STREAM_SERIAL,
+        PacketWriteEndInfo::EndPage,
+        0,
+    )?;
+    writer.write_packet(opus_tags(), STREAM_SERIAL, PacketWriteEndInfo::EndPage, 0)?;
+
+    // The encoder holds back `lookahead` samples, so pad with silence until
+    // the last real sample is out
+    let frames = (samples.len() + lookahead).div_ceil(FRAME_SAMPLES).max(1);
+    let mut frame = [0.0f32; FRAME_SAMPLES];
+    let mut packet = vec![0u8; MAX_PACKET_BYTES];
+    for i in 0..frames {
+        let start = (i * FRAME_SAMPLES).min(samples.len());
+        let end = ((i + 1) * FRAME_SAMPLES).min(samples.len());
+        frame.fill(0.0);
+        frame[..end - start].copy_from_slice(&samples[start..end]);
+
+        let len = encoder
+            .encode_float(&frame, &mut packet)
+            .map_err(opus_error)?;
+        let (end_info, granule) = if i + 1 == frames {
+            // The last page's position trims the padding off again
+            (
+                PacketWriteEndInfo::EndStream,
+                pre_skip + samples.len() as u64 * GRANULES_PER_SAMPLE,
+            )
+        } else {
+            (
+                PacketWriteEndInfo::NormalPacket,
+                ((i + 1) * FRAME_SAMPLES) as u64 * GRANULES_PER_SAMPLE,
+            )
+        };
+        writer.write_packet(packet[..len].to_vec(), STREAM_SERIAL, end_info, granule)?;
+    }
+
+    writer.into_inner().flush()?;
+    std::fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+/// Read an Ogg Opus file as 16kHz mono samples
+pub fn read_opus(path: &Path) -> Result<Vec<f32>> {
+    let mut reader = ogg::reading::PacketReader::new(BufReader::new(File::open(path)?));
+
+    let head = reader
+        .read_packet()
+        .map_err(ogg_error)?
+        .filter(|p| p.data.len() >= 19 && p.data.starts_with(b"OpusHead"))
+        .ok_or_else(|| MuesliError::Audio(format!("Not an Ogg Opus file: {:?}", path)))?;
+    let channels = head.data[9];
+    let pre_skip = u16::from_le_bytes([head.data[10], head.data[11]]) as u64;
+    let opus_channels = match channels {
+        1 => opus::Channels::Mono,
+        2 => opus::Channels::Stereo,
+        n => {
+            return Err(MuesliError::Audio(format!(
+                "Unsupported Opus channel count: {}",
+                n
+            )))
+        }
+    };
+    // OpusTags
+    reader.read_packet().map_err(ogg_error)?;
+
+    let mut decoder = opus::Decoder::new(WHISPER_SAMPLE_RATE, opus_channels).map_err(opus_error)?;
+    let mut frame = vec![0.0f32; MAX_FRAME_SAMPLES * channels as usize];
+    let mut decoded = Vec::new();
+    let mut last_granule = 0;
+    while let Some(packet) = reader.read_packet().map_err(ogg_error)? {
+        let frames = decoder
+            .decode_float(&packet.data, &mut frame, false)
+            .map_err(opus_error)?;
+        decoded.extend_from_slice(&frame[..frames * channels as usize]);
+        last_granule = packet.absgp_page();
+    }
+
+    let samples = to_mono(&decoded, channels as u16);
+    let skip = ((pre_skip / GRANULES_PER_SAMPLE) as usize).min(samples.len());
+    let len = (last_granule.saturating_sub(pre_skip) / GRANULES_PER_SAMPLE) as usize;
+    let end = (skip + len).min(samples.len());
+    Ok(samples[skip..end].to_vec())
+}
+
+/// Identification header (RFC 7845 section 5.1)
+fn opus_head(pre_skip: u16) -> Vec<u8> {
+    let mut head = b"OpusHead".to_vec();
+    head.push(1); // version
+    head.push(1); // channels
+    head.extend_from_slice(&pre_skip.to_le_bytes());
+    head.extend_from_slice(&WHISPER_SAMPLE_RATE.to_le_bytes());
+    head.extend_from_slice(&0i16.to_le_bytes()); // output gain
+    head.push(0); // channel mapping family
+    head
+}
+
+/// Comment header (RFC 7845 section 5.2), with no comments
+fn opus_tags() -> Vec<u8> {
+    let vendor = concat!("muesli ", env!("CARGO_PKG_VERSION"));
+    let mut tags = b"OpusTags".to_vec();
+    tags.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+    tags.extend_from_slice(vendor.as_bytes());
+    tags.extend_from_slice(&0u32.to_le_bytes());
+    tags
+}
+
+fn opus_error(e: opus::Error) -> MuesliError {
+    MuesliError::Audio(format!("Opus: {}", e))
+}
+
+fn ogg_error(e: ogg::OggReadError) -> MuesliError {
+    MuesliError::Audio(format!("Failed to read Ogg Opus file: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::recorder::BitDepth;
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_opus_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let wav_path = dir.path().join("sine.wav");
+        let sine: Vec<f32> = (0..WHISPER_SAMPLE_RATE as usize)
+            .map(|i| 0.5 * (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 16000.0).sin())
+            .collect();
+        crate::audio::wav::write_mono_16k(&wav_path, &sine, BitDepth::F32).unwrap();
+
+        let opus_path = compress(&wav_path).unwrap();
+        assert_eq!(opus_path, dir.path().join("sine.opus"));
+        assert!(wav_path.exists());
+        assert!(
+            std::fs::metadata(&opus_path).unwrap().len() * 4
+                < std::fs::metadata(&wav_path).unwrap().len()
+        );
+
+        // Read back through the same path recordings are loaded by
+        let decoded = crate::audio::wav::read_mono_16k(&opus_path).unwrap();
+        assert_eq!(decoded.len(), sine.len());
+
+        // Lossy, so compare past the codec's warm-up by level and shape
+        let (original, decoded) = (&sine[1600..], &decoded[1600..]);
+        assert!((rms(decoded) - rms(original)).abs() < 0.2 * rms(original));
+        let error: Vec<f32> = original.iter().zip(decoded).map(|(a, b)| a - b).collect();
+        assert!(rms(&error) < 0.5 * rms(original));
+    }
+
+    #[test]
+    fn test_read_opus_rejects_other_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fake.opus");
+        crate::audio::wav::write_mono_16k(&dir.path().join("real.wav"), &[0.0; 10], BitDepth::I16)
+            .unwrap();
+        std::fs::copy(dir.path().join("real.wav"), &path).unwrap();
+        assert!(read_opus(&path).is_err());
+        assert!(is_opus(&path));
+        assert!(!is_opus(&dir.path().join("real.wav")));
+    }
+}
@@ -62,6 +62,67 @@ pub fn normalize(samples: &mut [f32]) {
     }
 }
 
+/// Loudness target for `normalize_loudness` (RMS, about -20 dBFS)
+const LOUDNESS_TARGET_RMS: f32 = 0.1;
+/// Windows quieter than this (about -50 dBFS) are treated as silence and not boosted
+const LOUDNESS_GATE_RMS: f32 = 0.003;
+const LOUDNESS_MAX_GAIN: f32 = 10.0;
+const LOUDNESS_MIN_GAIN: f32 = 0.25;
+const LOUDNESS_PEAK_CEILING: f32 = 0.99;
+
+/// Even out loudness across a recording (e.g. a quiet remote party and a loud
+/// local mic): each half-second window is gain-adjusted toward a common RMS,
+/// with gains interpolated between windows, silence left alone, and peaks
+/// kept below full scale. Returns the average gain applied to non-silent audio.
+pub fn normalize_loudness(samples: &mut [f32], sample_rate: u32) -> f32 {
+    let window = (sample_rate as usize / 2).max(1);
+    if samples.is_empty() {
+        return 1.0;
+    }
+
+    let mut gains = Vec::with_capacity(samples.len().div_ceil(window));
+    let mut voiced_gain_sum = 0.0;
+    let mut voiced_windows = 0;
+    let mut previous = 1.0;
+    for chunk in samples.chunks(window) {
+        let rms = (chunk.iter().map(|s| s * s).sum::<f32>() / chunk.len() as f32).sqrt();
+        let peak = chunk.iter().map(|s| s.abs()).fold(0.0f32, f32::max);
+
+        let gain = if rms < LOUDNESS_GATE_RMS {
+            previous.min(1.0)
+        } else {
+            let gain = (LOUDNESS_TARGET_RMS / rms)
+                .clamp(LOUDNESS_MIN_GAIN, LOUDNESS_MAX_GAIN)
+                .min(LOUDNESS_PEAK_CEILING / peak);
+            voiced_gain_sum += gain;
+            voiced_windows += 1;
+            gain
+        };
+        gains.push(gain);
+        previous = gain;
+    }
+
+    for (i, sample) in samples.iter_mut().enumerate() {
+        let w = i / window;
+        let frac = (i % window) as f32 / window as f32;
+        let next = gains.get(w + 1).copied().unwrap_or(gains[w]);
+        // Interpolate from the middle of one window to the middle of the next
+        let gain = if frac < 0.5 {
+            let prev = if w > 0 { gains[w - 1] } else { gains[w] };
+            prev + (gains[w] - prev) * (frac + 0.5)
+        } else {
+            gains[w] + (next - gains[w]) * (frac - 0.5)
+        };
+        *sample = (*sample * gain).clamp(-LOUDNESS_PEAK_CEILING, LOUDNESS_PEAK_CEILING);
+    }
+
+    if voiced_windows == 0 {
+        1.0
+    } else {
+        voiced_gain_sum / voiced_windows as f32
+    }
+}
+
 /// Convert AudioChunk to Whisper-compatible format (16kHz, mono, normalized f32)
 pub fn prepare_for_whisper(chunk: &AudioChunk) -> Result<Vec<f32>> {
     // Step 1: Convert to mono
@@ -133,6 +194,39 @@ mod tests {
         assert!((samples[0] - 1.0).abs() < 0.01);
     }
 
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    fn tone(amplitude: f32, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| amplitude * (i as f32 * 0.05).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_normalize_loudness_evens_out_parties() {
+        // 3s of a quiet speaker followed by 3s of a loud one
+        let mut samples = tone(0.02, 48000);
+        samples.extend(tone(0.6, 48000));
+
+        normalize_loudness(&mut samples, 16000);
+
+        // Compare the settled middles of each half
+        let quiet = rms(&samples[16000..32000]);
+        let loud = rms(&samples[64000..80000]);
+        assert!((quiet / loud - 1.0).abs() < 0.2, "{} vs {}", quiet, loud);
+        assert!(samples.iter().all(|s| s.abs() <= LOUDNESS_PEAK_CEILING));
+    }
+
+    #[test]
+    fn test_normalize_loudness_leaves_silence() {
+        let mut samples = tone(0.001, 16000);
+        let before = samples.clone();
+        assert_eq!(normalize_loudness(&mut samples, 16000), 1.0);
+        assert_eq!(samples, before);
+    }
+
     #[test]
     fn test_resample_same_rate() {
         let samples = vec![0.5; 1000];
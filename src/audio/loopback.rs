@@ -48,6 +48,22 @@ impl LoopbackCapture {
         ))
     }
 
+    /// The configured monitor (`[audio] device_loopback`), or the detected one
+    /// when none is set or it isn't available
+    pub fn from_config(name: Option<&str>) -> Result<Self> {
+        let Some(name) = name else {
+            return Self::find_monitor();
+        };
+        Self::from_device_name(name).or_else(|e| {
+            tracing::warn!(
+                "Loopback device \"{}\" unavailable ({}); detecting a monitor instead",
+                name,
+                e
+            );
+            Self::find_monitor()
+        })
+    }
+
     pub fn from_device_name(name: &str) -> Result<Self> {
         let host = cpal::default_host();
 
@@ -224,4 +240,10 @@ mod tests {
             Err(e) => panic!("Unexpected error type: {:?}", e),
         }
     }
+
+    #[test]
+    fn test_missing_device_falls_back_to_monitor() {
+        let configured = LoopbackCapture::from_config(Some("muesli-test-no-such-device"));
+        assert_eq!(configured.is_ok(), LoopbackCapture::find_monitor().is_ok());
+    }
 }
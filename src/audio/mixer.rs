@@ -5,20 +5,33 @@ use crate::audio::AudioChunk;
 use std::collections::VecDeque;
 use tokio::sync::broadcast;
 
+/// Highest gain either source may be given; 1.0 leaves it at its recorded level
+pub const MAX_GAIN: f32 = 2.0;
+
 pub struct AudioMixer {
     mic_buffer: VecDeque<AudioChunk>,
     loopback_buffer: VecDeque<AudioChunk>,
     output_sample_rate: u32,
     output_channels: u16,
+    mic_gain: f32,
+    loopback_gain: f32,
 }
 
 impl AudioMixer {
-    pub fn new(output_sample_rate: u32, output_channels: u16) -> Self {
+    /// Gains are clamped to `0.0..=MAX_GAIN`
+    pub fn new(
+        output_sample_rate: u32,
+        output_channels: u16,
+        mic_gain: f32,
+        loopback_gain: f32,
+    ) -> Self {
         Self {
             mic_buffer: VecDeque::new(),
             loopback_buffer: VecDeque::new(),
             output_sample_rate,
             output_channels,
+            mic_gain: mic_gain.clamp(0.0, MAX_GAIN),
+            loopback_gain: loopback_gain.clamp(0.0, MAX_GAIN),
         }
     }
 
@@ -51,13 +64,13 @@ impl AudioMixer {
 
         for (i, sample) in mic.samples.iter().enumerate() {
             if i < len {
-                mixed[i] += sample * 0.5;
+                mixed[i] += sample * self.mic_gain;
             }
         }
 
         for (i, sample) in loopback.samples.iter().enumerate() {
             if i < len {
-                mixed[i] += sample * 0.5;
+                mixed[i] += sample * self.loopback_gain;
             }
         }
 
@@ -118,8 +131,10 @@ pub async fn mix_streams(
     output_tx: broadcast::Sender<AudioChunk>,
     output_sample_rate: u32,
     output_channels: u16,
+    mic_gain: f32,
+    loopback_gain: f32,
 ) {
-    let mut mixer = AudioMixer::new(output_sample_rate, output_channels);
+    let mut mixer = AudioMixer::new(output_sample_rate, output_channels, mic_gain, loopback_gain);
 
     loop {
         tokio::select! {
@@ -161,7 +176,7 @@ mod tests {
 
     #[test]
     fn test_mixer_mic_only() {
-        let mut mixer = AudioMixer::new(16000, 1);
+        let mut mixer = AudioMixer::new(16000, 1, 0.5, 0.5);
         let chunk = AudioChunk::new(vec![0.5; 100], 16000, 1, 0);
         mixer.add_mic_chunk(chunk);
 
@@ -172,7 +187,7 @@ mod tests {
 
     #[test]
     fn test_mixer_loopback_only() {
-        let mut mixer = AudioMixer::new(16000, 1);
+        let mut mixer = AudioMixer::new(16000, 1, 0.5, 0.5);
         let chunk = AudioChunk::new(vec![0.3; 100], 16000, 1, 0);
         mixer.add_loopback_chunk(chunk);
 
@@ -183,7 +198,7 @@ mod tests {
 
     #[test]
     fn test_mixer_both_sources() {
-        let mut mixer = AudioMixer::new(16000, 1);
+        let mut mixer = AudioMixer::new(16000, 1, 0.7, 0.3);
 
         let mic = AudioChunk::new(vec![0.5; 100], 16000, 1, 0);
         let loopback = AudioChunk::new(vec![0.3; 100], 16000, 1, 0);
@@ -194,13 +209,25 @@ mod tests {
         let output = mixer.mix();
         assert!(output.is_some());
         let mixed = output.unwrap();
-        // expected: (0.5 * 0.5) + (0.3 * 0.5) = 0.4
-        assert!((mixed.samples[0] - 0.4).abs() < 0.01);
+        // expected: (0.5 * 0.7) + (0.3 * 0.3) = 0.44
+        assert!((mixed.samples[0] - (0.5 * 0.7 + 0.3 * 0.3)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_mixer_clamps_gains() {
+        let mut mixer = AudioMixer::new(16000, 1, 5.0, -1.0);
+
+        mixer.add_mic_chunk(AudioChunk::new(vec![0.25; 100], 16000, 1, 0));
+        mixer.add_loopback_chunk(AudioChunk::new(vec![0.3; 100], 16000, 1, 0));
+
+        let mixed = mixer.mix().unwrap();
+        // mic at MAX_GAIN, loopback silenced
+        assert!((mixed.samples[0] - 0.5).abs() < 1e-6);
     }
 
     #[test]
     fn test_mixer_different_lengths() {
-        let mut mixer = AudioMixer::new(16000, 1);
+        let mut mixer = AudioMixer::new(16000, 1, 0.5, 0.5);
 
         let mic = AudioChunk::new(vec![0.5; 50], 16000, 1, 0);
         let loopback = AudioChunk::new(vec![0.3; 100], 16000, 1, 0);
@@ -219,14 +246,14 @@ mod tests {
 
     #[test]
     fn test_mixer_empty() {
-        let mut mixer = AudioMixer::new(16000, 1);
+        let mut mixer = AudioMixer::new(16000, 1, 0.5, 0.5);
         let output = mixer.mix();
         assert!(output.is_none());
     }
 
     #[test]
     fn test_mixer_drain() {
-        let mut mixer = AudioMixer::new(16000, 1);
+        let mut mixer = AudioMixer::new(16000, 1, 0.5, 0.5);
 
         mixer.add_mic_chunk(AudioChunk::new(vec![0.5; 100], 16000, 1, 0));
         mixer.add_mic_chunk(AudioChunk::new(vec![0.5; 100], 16000, 1, 10));
@@ -247,7 +274,7 @@ mod tests {
 
     #[test]
     fn test_timestamp_uses_earlier() {
-        let mut mixer = AudioMixer::new(16000, 1);
+        let mut mixer = AudioMixer::new(16000, 1, 0.5, 0.5);
 
         let mic = AudioChunk::new(vec![0.5; 100], 16000, 1, 100);
         let loopback = AudioChunk::new(vec![0.3; 100], 16000, 1, 50);
@@ -261,7 +288,7 @@ mod tests {
 
     #[test]
     fn test_mixer_converts_mic_only_to_output_format() {
-        let mut mixer = AudioMixer::new(16000, 1);
+        let mut mixer = AudioMixer::new(16000, 1, 0.5, 0.5);
 
         let mic = AudioChunk::new(vec![0.5; 44100 * 2], 44100, 2, 123);
         mixer.add_mic_chunk(mic);
@@ -275,7 +302,7 @@ mod tests {
 
     #[test]
     fn test_mixer_converts_both_inputs_before_mixing() {
-        let mut mixer = AudioMixer::new(16000, 1);
+        let mut mixer = AudioMixer::new(16000, 1, 0.5, 0.5);
 
         let mic = AudioChunk::new(vec![0.4; 44100 * 2], 44100, 2, 300);
         let loopback = AudioChunk::new(vec![0.2; 44100 * 2], 44100, 2, 250);
@@ -3,10 +3,12 @@
 use serde::{Deserialize, Serialize};
 
 pub mod capture;
+pub mod compress;
 pub mod convert;
 pub mod loopback;
 pub mod mixer;
 pub mod recorder;
+pub mod wav;
 
 /// Audio sample format
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -7,9 +7,43 @@ use std::io::BufWriter;
 use std::path::{Path, PathBuf};
 use tokio::sync::broadcast;
 
+/// Sample format of recorded WAV files (`[audio] bit_depth`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BitDepth {
+    /// 16-bit integer PCM
+    #[default]
+    I16,
+    /// 32-bit float, lossless from the capture pipeline
+    F32,
+}
+
+impl BitDepth {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "i16" => Some(Self::I16),
+            "f32" => Some(Self::F32),
+            _ => None,
+        }
+    }
+
+    fn spec(self) -> WavSpec {
+        let (bits_per_sample, sample_format) = match self {
+            Self::I16 => (16, hound::SampleFormat::Int),
+            Self::F32 => (32, hound::SampleFormat::Float),
+        };
+        WavSpec {
+            channels: 1,
+            sample_rate: WHISPER_SAMPLE_RATE,
+            bits_per_sample,
+            sample_format,
+        }
+    }
+}
+
 /// WAV recorder that writes audio chunks to file
 pub struct WavRecorder {
     writer: Option<WavWriter<BufWriter<File>>>,
+    bit_depth: BitDepth,
     temp_path: PathBuf,
     final_path: PathBuf,
     samples_written: u64,
@@ -18,6 +52,11 @@ pub struct WavRecorder {
 impl WavRecorder {
     /// Create a new recorder that will write to the given path
     pub fn new<P: AsRef<Path>>(output_path: P) -> Result<Self> {
+        Self::with_bit_depth(output_path, BitDepth::default())
+    }
+
+    /// Create a new recorder writing samples in the given format
+    pub fn with_bit_depth<P: AsRef<Path>>(output_path: P, bit_depth: BitDepth) -> Result<Self> {
         let final_path = output_path.as_ref().to_path_buf();
         let temp_path = final_path.with_extension("wav.tmp");
 
@@ -26,19 +65,13 @@ impl WavRecorder {
             fs::create_dir_all(parent)?;
         }
 
-        let spec = WavSpec {
-            channels: 1,
-            sample_rate: WHISPER_SAMPLE_RATE,
-            bits_per_sample: 32,
-            sample_format: hound::SampleFormat::Float,
-        };
-
         let file = File::create(&temp_path)?;
-        let writer = WavWriter::new(BufWriter::new(file), spec)
+        let writer = WavWriter::new(BufWriter::new(file), bit_depth.spec())
             .map_err(|e| MuesliError::Audio(format!("Failed to create WAV writer: {}", e)))?;
 
         Ok(Self {
             writer: Some(writer),
+            bit_depth,
             temp_path,
             final_path,
             samples_written: 0,
@@ -59,9 +92,16 @@ impl WavRecorder {
             .ok_or_else(|| MuesliError::Audio("Recorder already finalized".to_string()))?;
 
         for &sample in samples {
-            writer
-                .write_sample(sample)
-                .map_err(|e| MuesliError::Audio(format!("Failed to write sample: {}", e)))?;
+            let written = match self.bit_depth {
+                // Scaled by 32768 to match how the WAV readers normalize integer samples
+                BitDepth::I16 => writer.write_sample(
+                    (sample * 32768.0)
+                        .round()
+                        .clamp(i16::MIN as f32, i16::MAX as f32) as i16,
+                ),
+                BitDepth::F32 => writer.write_sample(sample),
+            };
+            written.map_err(|e| MuesliError::Audio(format!("Failed to write sample: {}", e)))?;
         }
 
         self.samples_written += samples.len() as u64;
@@ -199,9 +239,49 @@ mod tests {
 
         assert_eq!(spec.channels, 1);
         assert_eq!(spec.sample_rate, 16000);
-        assert_eq!(spec.bits_per_sample, 32);
+        assert_eq!(spec.bits_per_sample, 16);
 
-        let samples: Vec<f32> = reader.into_samples().map(|s| s.unwrap()).collect();
+        let samples: Vec<i16> = reader.into_samples().map(|s| s.unwrap()).collect();
         assert_eq!(samples.len(), 5);
     }
+
+    #[test]
+    fn test_bit_depth_roundtrip() {
+        let dir = tempdir().unwrap();
+        let samples = [0.0f32, 0.1, -0.5, 0.999, -1.0, 1.5];
+
+        for (bit_depth, bits, tolerance) in
+            [(BitDepth::I16, 16, 1.0 / 32768.0), (BitDepth::F32, 32, 0.0)]
+        {
+            let path = dir.path().join(format!("{:?}.wav", bit_depth));
+            let mut recorder = WavRecorder::with_bit_depth(&path, bit_depth).unwrap();
+            recorder.write_samples(&samples).unwrap();
+            recorder.finalize().unwrap();
+
+            assert_eq!(
+                hound::WavReader::open(&path)
+                    .unwrap()
+                    .spec()
+                    .bits_per_sample,
+                bits
+            );
+            let (read, _) = crate::audio::wav::read_mono(&path).unwrap();
+            assert_eq!(read.len(), samples.len());
+            for (got, want) in read.iter().zip(samples) {
+                // 16-bit clips out-of-range samples; float keeps them
+                let want = if bit_depth == BitDepth::I16 {
+                    want.clamp(-1.0, 1.0)
+                } else {
+                    want
+                };
+                assert!(
+                    (got - want).abs() <= tolerance,
+                    "{:?}: {} vs {}",
+                    bit_depth,
+                    got,
+                    want
+                );
+            }
+        }
+    }
 }
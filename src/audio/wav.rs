@@ -0,0 +1,117 @@
+//! Reading and writing recorded WAV files (and their `.opus` compressed form).
+
+use crate::audio::compress;
+use crate::audio::convert::{resample, to_mono, WHISPER_SAMPLE_RATE};
+use crate::audio::recorder::{BitDepth, WavRecorder};
+use crate::error::{MuesliError, Result};
+use std::path::Path;
+
+/// Read a WAV file as mono f32 samples, returning them with the file's sample rate
+pub fn read_mono(path: &Path) -> Result<(Vec<f32>, u32)> {
+    if compress::is_opus(path) {
+        return Ok((compress::read_opus(path)?, WHISPER_SAMPLE_RATE));
+    }
+
+    let mut reader = hound::WavReader::open(path)
+        .map_err(|e| MuesliError::Audio(format!("Failed to open WAV: {}", e)))?;
+
+    let spec = reader.spec();
+    let samples: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Int => {
+            let max_val = (1 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .filter_map(|s| s.ok())
+                .map(|s| s as f32 / max_val)
+                .collect()
+        }
+        hound::SampleFormat::Float => reader.samples::<f32>().filter_map(|s| s.ok()).collect(),
+    };
+
+    Ok((to_mono(&samples, spec.channels), spec.sample_rate))
+}
+
+/// Read a WAV file as 16kHz mono samples (the format recordings are stored in)
+pub fn read_mono_16k(path: &Path) -> Result<Vec<f32>> {
+    let (samples, sample_rate) = read_mono(path)?;
+    resample(&samples, sample_rate, WHISPER_SAMPLE_RATE)
+}
+
+/// Write 16kHz mono samples. Goes through a temp file, so `path` may be the file that was read.
+/// An `.opus` path is written compressed (`bit_depth` doesn't apply).
+pub fn write_mono_16k(path: &Path, samples: &[f32], bit_depth: BitDepth) -> Result<()> {
+    if compress::is_opus(path) {
+        return compress::write_opus(path, samples);
+    }
+    let mut recorder = WavRecorder::with_bit_depth(path, bit_depth)?;
+    recorder.write_samples(samples)?;
+    recorder.finalize()?;
+    Ok(())
+}
+
+/// Join `inputs` end to end into one 16kHz mono file, whatever their own rate
+/// and channel count. Returns each input's duration in ms, in order.
+pub fn concat_mono_16k(inputs: &[&Path], output: &Path, bit_depth: BitDepth) -> Result<Vec<u64>> {
+    let mut recorder = WavRecorder::with_bit_depth(output, bit_depth)?;
+    let mut durations_ms = Vec::with_capacity(inputs.len());
+    // One part in memory at a time; recordings can be hours long
+    for input in inputs {
+        let samples = read_mono_16k(input)?;
+        durations_ms.push(samples.len() as u64 * 1000 / WHISPER_SAMPLE_RATE as u64);
+        recorder.write_samples(&samples)?;
+    }
+    recorder.finalize()?;
+    Ok(durations_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_and_read_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("roundtrip.wav");
+        let samples = vec![0.0, 0.25, -0.5, 0.75];
+
+        write_mono_16k(&path, &samples, BitDepth::F32).unwrap();
+        let (read, sample_rate) = read_mono(&path).unwrap();
+        assert_eq!(sample_rate, WHISPER_SAMPLE_RATE);
+        assert_eq!(read, samples);
+
+        // Rewriting in place replaces the file atomically
+        write_mono_16k(&path, &samples[..2], BitDepth::I16).unwrap();
+        assert_eq!(read_mono_16k(&path).unwrap(), vec![0.0, 0.25]);
+    }
+
+    #[test]
+    fn test_concat_mono_16k() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("first.wav");
+        let second = dir.path().join("second.wav");
+        write_mono_16k(&first, &vec![0.5; 16000], BitDepth::F32).unwrap();
+
+        // A stereo part is mixed down before joining
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: WHISPER_SAMPLE_RATE,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+        let mut writer = hound::WavWriter::create(&second, spec).unwrap();
+        for _ in 0..8000 {
+            writer.write_sample(0.25f32).unwrap();
+            writer.write_sample(-0.25f32).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let joined = dir.path().join("joined.wav");
+        let durations = concat_mono_16k(&[&first, &second], &joined, BitDepth::F32).unwrap();
+        assert_eq!(durations, vec![1000, 500]);
+
+        let samples = read_mono_16k(&joined).unwrap();
+        assert_eq!(samples.len(), 24000);
+        assert_eq!(samples[0], 0.5);
+        assert_eq!(samples[16000], 0.0);
+    }
+}
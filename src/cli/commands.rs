@@ -1,4 +1,5 @@
-use clap::{Parser, Subcommand};
+use crate::llm::prompts::SummaryTemplate;
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 const VERSION: &str = concat!(
     env!("CARGO_PKG_VERSION"),
@@ -18,12 +19,25 @@ const VERSION: &str = concat!(
     long_about = "Automatically record, transcribe, and summarize meetings with AI-powered note generation"
 )]
 pub struct Cli {
+    /// Omitted: `[general] default_command` runs, or help is printed
     #[command(subcommand)]
-    pub command: Commands,
+    pub command: Option<Commands>,
 
-    /// Increase verbosity (-v, -vv, -vvv)
+    /// Increase verbosity (-v debug logs, -vv trace)
     #[arg(short, long, action = clap::ArgAction::Count, global = true)]
     pub verbose: u8,
+
+    /// Daemon socket to listen on / connect to (overrides `[daemon] socket_path`)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub socket: Option<std::path::PathBuf>,
+
+    /// Only print results and errors (no progress or informational output)
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Machine-readable output: errors as JSON on stderr, results as JSON on stdout where supported
+    #[arg(long, global = true)]
+    pub json: bool,
 }
 
 #[derive(Subcommand)]
@@ -33,35 +47,183 @@ pub enum Commands {
         /// Meeting title (auto-detected if not provided)
         #[arg(short, long)]
         title: Option<String>,
+
+        /// Automatically stop recording after this many seconds
+        #[arg(long, value_name = "SECS")]
+        detach_after: Option<u64>,
+
+        /// Take the title and attendees from the calendar event happening now (see `[calendar]`)
+        #[arg(long)]
+        calendar: bool,
+
+        /// Record only the microphone, not system audio (overrides `[audio] capture_system_audio`)
+        #[arg(long)]
+        mic_only: bool,
     },
 
     /// Stop recording and process notes
     Stop,
 
+    /// Pause the current recording (audio is not captured until resumed)
+    Pause,
+
+    /// Resume a paused recording
+    Resume,
+
+    /// Record and process a meeting in this process, without the daemon (stop with Ctrl-C)
+    Record {
+        /// Meeting title
+        #[arg(short, long)]
+        title: Option<String>,
+
+        /// Stop automatically after this many seconds
+        #[arg(long, value_name = "SECS")]
+        duration: Option<u64>,
+    },
+
     /// Show current recording status
-    Status,
+    Status {
+        /// Block until the daemon reaches this state
+        #[arg(long, value_name = "STATE")]
+        wait_for: Option<WaitState>,
+        /// Seconds to wait before giving up (0 = wait forever)
+        #[arg(long, default_value = "60", requires = "wait_for")]
+        timeout: u64,
+        /// Print the status as Waybar JSON (same as `muesli waybar`)
+        #[arg(long, conflicts_with = "wait_for")]
+        waybar: bool,
+    },
 
     /// List recorded meetings
     List {
         /// Maximum number of meetings to show
         #[arg(short, long, default_value = "10")]
         limit: usize,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ListFormat::Table)]
+        format: ListFormat,
+        /// Only show meetings with this status (recording, processing, complete, failed)
+        #[arg(long)]
+        status: Option<crate::storage::MeetingStatus>,
+        /// Only show meetings started on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<chrono::NaiveDate>,
+        /// Only show meetings whose title or summary contains this text
+        #[arg(long, value_name = "TEXT")]
+        search: Option<String>,
     },
 
     /// View meeting notes and summary
     Notes {
         /// Meeting ID (interactive selection if omitted)
         id: Option<String>,
+
+        /// Show what changed between two meetings' summaries (e.g. last week's standup vs this week's)
+        #[arg(
+            long,
+            num_args = 2,
+            value_names = ["OLD_ID", "NEW_ID"],
+            conflicts_with_all = ["id", "combine"]
+        )]
+        diff: Vec<String>,
+
+        /// Merge the transcripts of several meetings (in time order) and summarize them as one
+        #[arg(long, num_args = 2.., value_name = "ID", conflicts_with = "id")]
+        combine: Vec<String>,
+
+        /// With --combine, write the notes to this file instead of creating a new meeting
+        #[arg(short, long, requires = "combine")]
+        output: Option<std::path::PathBuf>,
+
+        /// Re-summarize with a built-in layout and keep it for the meeting
+        /// (general, interview, standup, one-on-one, decision-review)
+        #[arg(
+            long,
+            value_name = "NAME",
+            value_parser = parse_template,
+            conflicts_with_all = ["diff", "combine"]
+        )]
+        template: Option<SummaryTemplate>,
+
+        /// Re-summarize in this language (e.g. German) and keep it for the meeting
+        #[arg(long, value_name = "LANGUAGE", conflicts_with_all = ["diff", "combine"])]
+        lang: Option<String>,
+
+        /// Read the notes formatted instead of in the terminal
+        #[arg(long, value_enum, value_name = "VIEWER", conflicts_with_all = ["diff", "combine"])]
+        open_in: Option<NotesViewer>,
     },
 
     /// View meeting transcript
     Transcript {
         /// Meeting ID (interactive selection if omitted)
         id: Option<String>,
+
+        /// Only show segments from this speaker (e.g. SPEAKER_1)
+        #[arg(short, long)]
+        speaker: Option<String>,
+
+        /// Show wall-clock times (14:42:30) instead of offsets from the meeting start
+        #[arg(long)]
+        clock: bool,
+
+        /// Dialogue view: one speaker header per turn with the turn's combined text
+        #[arg(long)]
+        collapse_speakers: bool,
+
+        /// `off` prints plain speaker-attributed dialogue, for pasting into documents
+        #[arg(
+            long,
+            value_name = "on|off",
+            default_value = "on",
+            action = clap::ArgAction::Set,
+            value_parser = clap::builder::BoolishValueParser::new()
+        )]
+        timestamps: bool,
+
+        /// Only show the segments around this point in the meeting (HH:MM:SS, MM:SS or seconds)
+        #[arg(long, value_name = "TIME", value_parser = parse_offset)]
+        at: Option<u64>,
+
+        /// With --at, segments to show before that point
+        #[arg(long, default_value = "3", requires = "at")]
+        before: usize,
+
+        /// With --at, segments to show after that point
+        #[arg(long, default_value = "3", requires = "at")]
+        after: usize,
+
+        /// Relabel one speaker as another in the stored transcript (fixes one person split in two)
+        #[arg(long, value_name = "FROM=TO", conflicts_with = "speaker")]
+        merge_speakers: Option<SpeakerMerge>,
+
+        /// Print as a subtitle or JSON file to share instead of the reading view
+        #[arg(long, value_enum, default_value = "text")]
+        format: TranscriptOutput,
+
+        /// Scrub emails, phone and card numbers and IP addresses from the output
+        /// (the stored transcript is left as is)
+        #[arg(long, conflicts_with = "merge_speakers")]
+        redact: bool,
+    },
+
+    /// Replace a meeting's transcript with one from another tool (.srt, .vtt or .json segments)
+    ImportTranscript {
+        /// Meeting ID
+        id: String,
+        /// Transcript file
+        file: std::path::PathBuf,
+        /// Replace an existing transcript without asking
+        #[arg(short, long)]
+        yes: bool,
     },
 
     /// Run daemon mode (background meeting detection)
-    Daemon,
+    Daemon {
+        /// Scan the open windows once, report what detection would do, and exit (no socket)
+        #[arg(long)]
+        once: bool,
+    },
 
     /// Configuration management
     Config {
@@ -81,8 +243,14 @@ pub enum Commands {
         action: AudioCommands,
     },
 
+    /// Enrolled voices, used to name speakers consistently across meetings
+    Speakers {
+        #[command(subcommand)]
+        action: SpeakerCommands,
+    },
+
     /// Interactive setup wizard for first-time configuration
-    Setup,
+    Setup(SetupArgs),
 
     /// Uninstall muesli completely
     Uninstall,
@@ -96,10 +264,102 @@ pub enum Commands {
     /// Re-process a meeting (re-summarize, or full re-transcribe with --clean)
     Redo {
         /// Meeting ID (interactive selection if omitted)
+        #[arg(conflicts_with = "all")]
         id: Option<String>,
+        /// Re-process every meeting with --status, continuing past failures
+        #[arg(long, requires = "status")]
+        all: bool,
+        /// With --all, which meetings to re-process (e.g. failed, processing)
+        #[arg(long, requires = "all")]
+        status: Option<crate::storage::MeetingStatus>,
+        /// With --all, re-process at most this many meetings (newest first)
+        #[arg(long, default_value = "50", requires = "all")]
+        limit: usize,
         /// Re-transcribe and re-diarize from scratch
         #[arg(long)]
         clean: bool,
+        /// Summarize with this built-in layout from now on (see `notes --template`)
+        #[arg(long, value_name = "NAME", value_parser = parse_template)]
+        template: Option<SummaryTemplate>,
+        /// Write the notes in this language from now on (see `llm.output_language`)
+        #[arg(long, value_name = "LANGUAGE")]
+        lang: Option<String>,
+        /// Replace the title with one generated from the new summary (by
+        /// default only an untitled meeting gets a new title)
+        #[arg(long)]
+        retitle: bool,
+    },
+
+    /// Delete a meeting with its recording, notes and transcript
+    Delete {
+        /// Meeting ID (interactive selection if omitted)
+        id: Option<String>,
+        /// Leave the audio file on disk
+        #[arg(long)]
+        keep_audio: bool,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Inspect the prompts sent to the LLM
+    Prompt {
+        #[command(subcommand)]
+        action: PromptCommands,
+    },
+
+    /// Repair a meeting's stored data
+    Repair {
+        /// Meeting ID (interactive selection if omitted)
+        id: Option<String>,
+        /// Make segment timestamps monotonic (fixes resets from resumed or concatenated audio)
+        #[arg(long)]
+        timestamps: bool,
+    },
+
+    /// Show environment diagnostics (GPU backend, daemon, integrations)
+    Doctor,
+
+    /// Export a meeting's notes, or its transcript as subtitles, to a standalone file
+    Export {
+        /// Meeting ID (interactive selection if omitted)
+        id: Option<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ExportFormat::Markdown)]
+        format: ExportFormat,
+        /// Destination file, or `-` for stdout (default: <meeting-id>.<ext> in the current directory)
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+        /// Append the full transcript, whatever `[storage] notes_transcript` says
+        #[arg(long)]
+        include_transcript: bool,
+        /// Start with the notes file's YAML frontmatter (markdown only)
+        #[arg(long)]
+        include_metadata: bool,
+    },
+
+    /// Email a meeting's notes over SMTP (see `[smtp]`)
+    Email {
+        /// Meeting ID (interactive selection if omitted)
+        id: Option<String>,
+        /// Recipient address, repeatable (default: `[smtp] to`)
+        #[arg(long, value_name = "ADDR")]
+        to: Vec<String>,
+    },
+
+    /// Back up the meeting database (metadata, transcripts, summaries; no audio)
+    Backup {
+        /// Destination file
+        file: std::path::PathBuf,
+    },
+
+    /// Restore the meeting database from a backup (current database is backed up first)
+    Restore {
+        /// Backup file to restore from
+        file: std::path::PathBuf,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
     },
 
     /// Search across meeting notes using qmd
@@ -120,15 +380,252 @@ pub enum Commands {
     Ask {
         /// Your question (multiple words allowed)
         question: Vec<String>,
+        /// Keep the conversation under this name so follow-up questions see earlier answers
+        #[arg(long, value_name = "NAME")]
+        session: Option<String>,
+    },
+
+    /// List open action items from every meeting, soonest due first
+    Todos {
+        #[command(subcommand)]
+        action: Option<TodosCommands>,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum WaitState {
+    /// A recording is in progress
+    Recording,
+    /// Not recording and no meeting is being processed
+    Idle,
+    /// A stopped meeting is still being transcribed or summarized
+    Processing,
+}
+
+/// `FROM=TO` speaker labels for `transcript --merge-speakers`
+#[derive(Clone, Debug)]
+pub struct SpeakerMerge {
+    pub from: String,
+    pub to: String,
+}
+
+impl std::str::FromStr for SpeakerMerge {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((from, to)) if !from.trim().is_empty() && !to.trim().is_empty() => Ok(Self {
+                from: from.trim().to_string(),
+                to: to.trim().to_string(),
+            }),
+            _ => Err(format!(
+                "expected FROM=TO (e.g. SPEAKER_2=SPEAKER_1), got '{}'",
+                s
+            )),
+        }
+    }
+}
+
+/// Meeting offset for `transcript --at`, in milliseconds
+fn parse_offset(s: &str) -> std::result::Result<u64, String> {
+    crate::transcription::parse_offset(s)
+        .ok_or_else(|| format!("expected HH:MM:SS, MM:SS or seconds, got '{}'", s))
+}
+
+/// Built-in summary layout for `notes --template` and `redo --template`
+fn parse_template(s: &str) -> std::result::Result<SummaryTemplate, String> {
+    SummaryTemplate::parse(s).ok_or_else(|| {
+        let names: Vec<_> = SummaryTemplate::ALL.iter().map(|t| t.name()).collect();
+        format!("expected one of: {}", names.join(", "))
+    })
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ListFormat {
+    /// Human-readable table
+    Table,
+    /// CSV with a header row (id,title,date,duration_seconds,status,app)
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// Summary markdown with a metadata header
+    Markdown,
+    /// Standalone HTML page of the markdown export
+    Html,
+    /// PDF rendered via pandoc or wkhtmltopdf
+    Pdf,
+    /// SubRip subtitles of the transcript
+    Srt,
+    /// WebVTT subtitles of the transcript
+    Vtt,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Pdf => "pdf",
+            ExportFormat::Srt => "srt",
+            ExportFormat::Vtt => "vtt",
+        }
+    }
+}
+
+/// Choices for `muesli setup --noninteractive`, each also settable from the
+/// environment for provisioning scripts. The interactive wizard ignores them.
+#[derive(Debug, Clone, Args)]
+pub struct SetupArgs {
+    /// Apply the choices below without prompting
+    #[arg(long, env = "MUESLI_SETUP_NONINTERACTIVE")]
+    pub noninteractive: bool,
+
+    /// Whisper model to download and transcribe with (`none` to skip)
+    #[arg(
+        long,
+        value_name = "MODEL",
+        default_value = "base",
+        env = "MUESLI_SETUP_MODEL"
+    )]
+    pub model: String,
+
+    /// Enable GPU acceleration
+    #[arg(long, env = "MUESLI_SETUP_GPU")]
+    pub gpu: bool,
+
+    /// Don't download the speaker diarization model
+    #[arg(long, env = "MUESLI_SETUP_NO_DIARIZATION")]
+    pub no_diarization: bool,
+
+    /// local (LM Studio), anthropic, openai, moonshot, openrouter or none. Cloud
+    /// providers read their key from MUESLI_<PROVIDER>_API_KEY.
+    #[arg(
+        long,
+        value_name = "PROVIDER",
+        default_value = "none",
+        env = "MUESLI_SETUP_LLM_PROVIDER"
+    )]
+    pub llm_provider: String,
+
+    /// LLM model (default: the provider's default, or LM Studio's first model)
+    #[arg(long, value_name = "MODEL", env = "MUESLI_SETUP_LLM_MODEL")]
+    pub llm_model: Option<String>,
+
+    /// LLM sampling temperature (default: 0.3)
+    #[arg(long, value_name = "T", env = "MUESLI_SETUP_LLM_TEMPERATURE")]
+    pub llm_temperature: Option<f32>,
+
+    /// Longest LLM response to ask for, in tokens (default: the provider's)
+    #[arg(long, value_name = "N", env = "MUESLI_SETUP_LLM_MAX_TOKENS")]
+    pub llm_max_tokens: Option<u32>,
+
+    /// Turn off meeting detection prompts
+    #[arg(long, env = "MUESLI_SETUP_NO_AUTO_PROMPT")]
+    pub no_auto_prompt: bool,
+
+    /// Play a sound when recording starts and stops
+    #[arg(long, env = "MUESLI_SETUP_AUDIO_CUES")]
+    pub audio_cues: bool,
+
+    /// Install the systemd user service
+    #[arg(long, env = "MUESLI_SETUP_INSTALL_SERVICE")]
+    pub install_service: bool,
+
+    /// Set up qmd search (installs qmd with bun if missing)
+    #[arg(long, env = "MUESLI_SETUP_QMD")]
+    pub qmd: bool,
+
+    /// Don't start the daemon when done
+    #[arg(long, env = "MUESLI_SETUP_NO_START")]
+    pub no_start: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum NotesViewer {
+    /// The default browser (`xdg-open` on the HTML export)
+    Browser,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TranscriptOutput {
+    /// Timestamped lines for reading (honors --clock and --collapse-speakers)
+    Text,
+    /// SubRip subtitles
+    Srt,
+    /// WebVTT captions, speakers as voice tags
+    Vtt,
+    /// muesli's JSON segments (as accepted by `import-transcript`)
+    Json,
+}
+
+impl TranscriptOutput {
+    /// The file format to render, or `None` for the reading view
+    pub fn file_format(self) -> Option<crate::transcription::import::TranscriptFormat> {
+        use crate::transcription::import::TranscriptFormat;
+        match self {
+            TranscriptOutput::Text => None,
+            TranscriptOutput::Srt => Some(TranscriptFormat::Srt),
+            TranscriptOutput::Vtt => Some(TranscriptFormat::Vtt),
+            TranscriptOutput::Json => Some(TranscriptFormat::Json),
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum PromptCommands {
+    /// Print the summarization prompt for a meeting without calling the LLM
+    Show {
+        /// Meeting ID (interactive selection if omitted)
+        id: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TodosCommands {
+    /// Mark an action item done (its number in `muesli todos`)
+    Done {
+        /// Item number from the list
+        number: usize,
     },
 }
 
 #[derive(Subcommand)]
 pub enum SearchCommands {
     /// Re-index all meeting notes with qmd
-    Reindex,
+    Reindex {
+        /// Report stale or missing index entries instead of rebuilding
+        #[arg(long)]
+        verify: bool,
+        /// With --verify, update the index to fix what was found
+        #[arg(long, requires = "verify")]
+        fix: bool,
+    },
     /// Show qmd index status
     Status,
+    /// Ask a question (across all notes, or about one meeting's transcript with --meeting)
+    Ask {
+        /// Your question (multiple words allowed)
+        #[arg(required = true)]
+        question: Vec<String>,
+        /// Answer from this meeting's full transcript only (works without qmd)
+        #[arg(long, value_name = "ID")]
+        meeting: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SpeakerCommands {
+    /// Store a person's voiceprint from a WAV sample of them speaking (a few seconds or more)
+    Enroll {
+        /// Name to give this speaker in transcripts (re-enrolling replaces the voiceprint)
+        name: String,
+        /// WAV recording of only this person speaking
+        sample: std::path::PathBuf,
+    },
+    /// List enrolled speakers
+    ListProfiles,
 }
 
 #[derive(Subcommand)]
@@ -136,11 +633,43 @@ pub enum ConfigCommands {
     /// Show current configuration
     Show,
     /// Open config file in editor
-    Edit,
+    Edit {
+        /// Reject the edit if the result doesn't parse or validate (reopen or restore the last good config)
+        #[arg(long)]
+        validate: bool,
+    },
+    /// Set one value (e.g. `llm.model gpt-4o`); pass `-` to read a secret from stdin
+    Set {
+        /// Dotted setting name, e.g. llm.api_key
+        key: String,
+        /// New value, or `-` to read it from stdin
+        #[arg(required_unless_present = "stdin")]
+        value: Option<String>,
+        /// Read the value from stdin (hidden prompt on a terminal) so it stays out of shell history
+        #[arg(long, conflicts_with = "value")]
+        stdin: bool,
+    },
+    /// Custom LLM prompt templates
+    Prompts {
+        #[command(subcommand)]
+        action: PromptsAction,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PromptsAction {
+    /// Write the built-in prompts to the prompts directory for editing
+    Init {
+        /// Overwrite prompt files that already exist
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum ModelEngine {
+    /// List every model (transcription and diarization) with install status
+    List,
     /// Whisper models (whisper.cpp)
     Whisper {
         #[command(subcommand)]
@@ -158,9 +687,24 @@ pub enum ModelAction {
     /// List available models
     List,
     /// Download a model
-    Download { model: String },
+    Download {
+        model: String,
+        /// Limit the download rate (MB/s)
+        #[arg(long, value_name = "MB/s")]
+        max_rate: Option<f64>,
+        /// Download from this HuggingFace mirror instead of huggingface.co (default: $HF_ENDPOINT)
+        #[arg(long, value_name = "URL")]
+        mirror: Option<String>,
+    },
     /// Delete a downloaded model
     Delete { model: String },
+    /// Make a downloaded model the one used for transcription (updates the config)
+    Default { model: String },
+    /// Verify installed model files against their download checksums, re-downloading any that fail
+    Verify {
+        /// Model to verify (all installed models if omitted)
+        model: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -168,4 +712,25 @@ pub enum AudioCommands {
     /// List available audio devices
     #[command(name = "list-devices")]
     ListDevices,
+    /// Even out a recording's loudness (writes <recording>.normalized.wav unless --in-place)
+    Normalize {
+        /// Meeting ID (interactive selection if omitted)
+        id: Option<String>,
+        /// Overwrite the meeting's recording
+        #[arg(long)]
+        in_place: bool,
+        /// Write the normalized audio here
+        #[arg(short, long, conflicts_with = "in_place")]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Join several meetings' recordings (in time order) into a new meeting,
+    /// carrying their transcripts over when they all have one
+    Concat {
+        /// Meetings to join
+        #[arg(num_args = 2.., required = true, value_name = "ID")]
+        ids: Vec<String>,
+        /// Title of the new meeting (default: the first meeting's title + "(joined)")
+        #[arg(long, value_name = "TITLE")]
+        into: Option<String>,
+    },
 }
@@ -1,10 +1,12 @@
 use crate::cli::commands::*;
+use crate::cli::output::{self, status};
 use crate::config;
 use crate::daemon::{DaemonClient, DaemonRequest, DaemonResponse};
-use crate::error::Result;
+use crate::error::{MuesliError, Result};
 use crate::llm::local::find_lms_binary;
+use crate::llm::prompts::SummaryTemplate;
 use crate::storage::database::Database;
-use crate::storage::MeetingId;
+use crate::storage::{MeetingId, MeetingStatus};
 use crate::transcription::diarization_models::{DiarizationModel, DiarizationModelManager};
 use crate::transcription::models::{ModelManager, WhisperModel};
 use crate::waybar::WaybarStatus;
@@ -12,64 +14,340 @@ use cpal::traits::{DeviceTrait, HostTrait};
 use std::io::Write;
 
 pub async fn handle_command(cli: Cli) -> Result<()> {
-    match cli.command {
-        Commands::Start { title } => handle_start(title).await,
+    output::set_quiet(cli.quiet || cli.json);
+    output::set_json(cli.json);
+
+    let cli = match cli.command {
+        Some(_) => cli,
+        None => match default_command(cli)? {
+            Some(cli) => cli,
+            None => {
+                <Cli as clap::CommandFactory>::command().print_help()?;
+                return Ok(());
+            }
+        },
+    };
+    output::set_quiet(cli.quiet || cli.json);
+    output::set_json(cli.json);
+    if let Some(socket) = cli.socket {
+        config::loader::set_socket_path_override(socket);
+    }
+    let Some(command) = cli.command else {
+        return Ok(());
+    };
+
+    match command {
+        Commands::Start {
+            title,
+            detach_after,
+            calendar,
+            mic_only,
+        } => handle_start(title, detach_after, calendar, mic_only).await,
         Commands::Stop => handle_stop().await,
-        Commands::Status => handle_status().await,
-        Commands::List { limit } => handle_list(limit).await,
-        Commands::Notes { id } => handle_notes(id).await,
-        Commands::Transcript { id } => handle_transcript(id).await,
-        Commands::Daemon => handle_daemon().await,
+        Commands::Pause => handle_pause(true).await,
+        Commands::Resume => handle_pause(false).await,
+        Commands::Record { title, duration } => handle_record(title, duration).await,
+        Commands::Status { waybar: true, .. } => handle_waybar().await,
+        Commands::Status {
+            wait_for, timeout, ..
+        } => match wait_for {
+            Some(state) => handle_status_wait(state, timeout).await,
+            None => handle_status().await,
+        },
+        Commands::List {
+            limit,
+            format,
+            status,
+            since,
+            search,
+        } => handle_list(limit, format, status, since, search).await,
+        Commands::Notes {
+            id,
+            diff,
+            combine,
+            output,
+            template,
+            lang,
+            open_in,
+        } => {
+            if !diff.is_empty() {
+                handle_diff_notes(&diff[0], &diff[1]).await
+            } else if combine.is_empty() {
+                handle_notes(id, template, lang, open_in).await
+            } else {
+                handle_combine_notes(combine, output).await
+            }
+        }
+        Commands::Transcript {
+            id,
+            speaker,
+            clock,
+            collapse_speakers,
+            timestamps,
+            at,
+            before,
+            after,
+            merge_speakers,
+            format,
+            redact,
+        } => match merge_speakers {
+            Some(merge) => handle_merge_speakers(id, merge).await,
+            None => {
+                let window = at.map(|at_ms| (at_ms, before, after));
+                handle_transcript(
+                    id,
+                    speaker,
+                    clock,
+                    collapse_speakers,
+                    timestamps,
+                    window,
+                    format,
+                    redact,
+                )
+                .await
+            }
+        },
+        Commands::ImportTranscript { id, file, yes } => {
+            handle_import_transcript(&id, &file, yes).await
+        }
+        Commands::Daemon { once: true } => handle_daemon_once(),
+        Commands::Daemon { once: false } => handle_daemon().await,
         Commands::Config { action } => handle_config(action).await,
         Commands::Models { engine } => handle_models(engine).await,
         Commands::Audio { action } => handle_audio(action).await,
-        Commands::Setup => handle_setup().await,
+        Commands::Speakers { action } => handle_speakers(action),
+        Commands::Setup(args) => handle_setup(args).await,
         Commands::Uninstall => handle_uninstall().await,
         Commands::Update => handle_update().await,
         Commands::Waybar => handle_waybar().await,
-        Commands::Redo { id, clean } => handle_redo(id, clean).await,
+        Commands::Prompt { action } => handle_prompt(action).await,
+        Commands::Repair { id, timestamps } => handle_repair(id, timestamps).await,
+        Commands::Doctor => handle_doctor().await,
+        Commands::Redo {
+            all: true,
+            status: Some(status),
+            limit,
+            clean,
+            template,
+            lang,
+            retitle,
+            ..
+        } => handle_redo_all(status, limit, clean, template, lang, retitle).await,
+        Commands::Redo {
+            id,
+            clean,
+            template,
+            lang,
+            retitle,
+            ..
+        } => handle_redo(id, clean, template, lang, retitle).await,
+        Commands::Export {
+            id,
+            format,
+            output,
+            include_transcript,
+            include_metadata,
+        } => handle_export(id, format, output, include_transcript, include_metadata).await,
+        Commands::Email { id, to } => handle_email(id, to).await,
+        Commands::Backup { file } => handle_backup(file).await,
+        Commands::Restore { file, yes } => handle_restore(file, yes).await,
+        Commands::Delete {
+            id,
+            keep_audio,
+            yes,
+        } => handle_delete(id, keep_audio, yes),
         Commands::Search {
             query,
             limit,
             keyword,
             action,
         } => handle_search(query, limit, keyword, action).await,
-        Commands::Ask { question } => handle_ask(question).await,
+        Commands::Ask { question, session } => handle_ask(question, session).await,
+        Commands::Todos { action } => handle_todos(action),
     }
 }
 
-async fn handle_start(title: Option<String>) -> Result<()> {
-    let mut client = match DaemonClient::connect().await {
-        Ok(c) => c,
-        Err(_) => {
-            eprintln!("Error: Daemon is not running. Start it with: muesli daemon");
-            return Ok(());
+fn handle_todos(action: Option<TodosCommands>) -> Result<()> {
+    let db = Database::open(config::loader::database_path()?)?;
+    if let Some(TodosCommands::Done { number }) = action {
+        let text = db.complete_open_action_item(number)?.ok_or_else(|| {
+            MuesliError::NoResults(format!("open action item {} (see: muesli todos)", number))
+        })?;
+        status!("Done: {}", text);
+        return Ok(());
+    }
+
+    let items = db.get_open_action_items()?;
+
+    if output::is_json() {
+        let items: Vec<_> = items
+            .iter()
+            .enumerate()
+            .map(|(i, (id, title, item))| {
+                serde_json::json!({
+                    "number": i + 1,
+                    "meeting_id": id.0,
+                    "meeting": title,
+                    "text": item.text,
+                    "owner": item.owner,
+                    "due_date": item.due_date,
+                })
+            })
+            .collect();
+        return output::print_json(&items);
+    }
+    if items.is_empty() {
+        println!("No open action items.");
+        return Ok(());
+    }
+
+    println!(
+        "{:>3}  {:<10}  {:<16}  {:<50}  Meeting",
+        "#", "Due", "Owner", "Action item"
+    );
+    println!("{}", "-".repeat(105));
+    for (i, (_, title, item)) in items.into_iter().enumerate() {
+        println!(
+            "{:>3}  {:<10}  {:<16}  {:<50}  {}",
+            i + 1,
+            item.due_date.as_deref().unwrap_or("-"),
+            truncate(item.owner.as_deref().unwrap_or("-"), 16),
+            truncate(&item.text, 50),
+            truncate(&title, 30)
+        );
+    }
+    Ok(())
+}
+
+async fn handle_start(
+    title: Option<String>,
+    detach_after: Option<u64>,
+    calendar: bool,
+    mic_only: bool,
+) -> Result<()> {
+    let mut title = title;
+    let mut participants = Vec::new();
+    if calendar {
+        let config = config::loader::load_config()?;
+        match crate::detection::calendar::current_event(&config.calendar)? {
+            Some(event) => {
+                status!("Calendar event: {}", event.summary);
+                title = title.or(Some(event.summary));
+                participants = event.attendees;
+            }
+            None => status!("No calendar event in progress, using the default title"),
         }
-    };
+    }
+
+    let mut client = DaemonClient::connect().await?;
 
-    let request = DaemonRequest::StartRecording { title };
+    let request = DaemonRequest::StartRecording {
+        title,
+        auto_stop_secs: detach_after,
+        participants,
+        mic_only,
+    };
     match client.send(request).await? {
         DaemonResponse::RecordingStarted { meeting_id } => {
             println!("Recording started (ID: {})", meeting_id);
+            if let Some(secs) = detach_after {
+                println!("Will stop automatically in {}s", secs);
+            }
         }
-        DaemonResponse::Error { message } => {
-            eprintln!("Error: {}", message);
-        }
-        _ => {
-            eprintln!("Unexpected response from daemon");
+        DaemonResponse::Error {
+            message,
+            error_kind,
+        } => {
+            return Err(MuesliError::DaemonRequest {
+                kind: error_kind,
+                message,
+            })
         }
+        _ => return Err(unexpected_response()),
     }
     Ok(())
 }
 
-async fn handle_stop() -> Result<()> {
-    let mut client = match DaemonClient::connect().await {
-        Ok(c) => c,
-        Err(_) => {
-            eprintln!("Error: Daemon is not running.");
-            return Ok(());
+async fn handle_record(title: Option<String>, duration: Option<u64>) -> Result<()> {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let db = Database::open(config::loader::database_path()?)?;
+    let recordings_dir = config::loader::recordings_dir()?;
+    std::fs::create_dir_all(&recordings_dir)?;
+
+    let mut meeting =
+        crate::storage::Meeting::new(title.unwrap_or_else(|| "Untitled Meeting".to_string()));
+    let audio_path = recordings_dir.join(format!("{}.wav", meeting.id));
+    meeting.audio_path = Some(audio_path.clone());
+    db.insert_meeting(&meeting)?;
+
+    let is_running = std::sync::Arc::new(AtomicBool::new(true));
+    let recorder = crate::daemon::server::record_in_process(audio_path.clone(), is_running.clone());
+
+    match duration {
+        Some(secs) => status!(
+            "Recording \"{}\" for {}s (Ctrl-C to stop early)...",
+            meeting.title,
+            secs
+        ),
+        None => status!("Recording \"{}\" (Ctrl-C to stop)...", meeting.title),
+    }
+    let limit = async {
+        match duration {
+            Some(secs) => tokio::time::sleep(std::time::Duration::from_secs(secs)).await,
+            None => std::future::pending().await,
         }
     };
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = limit => {}
+    }
+
+    is_running.store(false, Ordering::Relaxed);
+    tokio::task::spawn_blocking(move || recorder.join())
+        .await
+        .map_err(|e| MuesliError::Audio(format!("Recording task failed: {}", e)))?
+        .map_err(|_| MuesliError::Audio("Recording thread panicked".to_string()))?;
+
+    if !audio_path.exists() {
+        meeting.status = crate::storage::MeetingStatus::Failed;
+        db.update_meeting(&meeting)?;
+        return Err(MuesliError::Audio(
+            "Nothing was recorded (check the microphone with: muesli audio list-devices)"
+                .to_string(),
+        ));
+    }
+
+    let ended = chrono::Utc::now();
+    meeting.ended_at = Some(ended);
+    meeting.duration_seconds = Some((ended - meeting.started_at).num_seconds().max(0) as u64);
+    meeting.status = crate::storage::MeetingStatus::Processing;
+    db.update_meeting(&meeting)?;
+    status!(
+        "\nRecorded {}m {}s",
+        meeting.duration_seconds.unwrap_or(0) / 60,
+        meeting.duration_seconds.unwrap_or(0) % 60
+    );
+
+    handle_redo(Some(meeting.id.to_string()), true, None, None, false).await?;
+
+    if let Some(mut processed) = db.get_meeting(&meeting.id)? {
+        processed.status = crate::storage::MeetingStatus::Complete;
+        db.update_meeting(&processed)?;
+        if config::loader::load_config()?
+            .audio
+            .compress_after_recording
+        {
+            if let Some(path) = crate::pipeline::compress_recording(&db, &mut processed)? {
+                status!("Recording compressed: {}", path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn handle_stop() -> Result<()> {
+    let mut client = DaemonClient::connect().await?;
 
     match client.send(DaemonRequest::StopRecording).await? {
         DaemonResponse::RecordingStopped { meeting_id } => {
@@ -98,12 +376,44 @@ async fn handle_stop() -> Result<()> {
                 println!("View transcript with: muesli view {}", meeting_id);
             }
         }
-        DaemonResponse::Error { message } => {
-            eprintln!("Error: {}", message);
+        DaemonResponse::Error {
+            message,
+            error_kind,
+        } => {
+            return Err(MuesliError::DaemonRequest {
+                kind: error_kind,
+                message,
+            })
         }
-        _ => {
-            eprintln!("Unexpected response from daemon");
+        _ => return Err(unexpected_response()),
+    }
+    Ok(())
+}
+
+async fn handle_pause(paused: bool) -> Result<()> {
+    let mut client = DaemonClient::connect().await?;
+    let request = if paused {
+        DaemonRequest::PauseRecording
+    } else {
+        DaemonRequest::ResumeRecording
+    };
+
+    match client.send(request).await? {
+        DaemonResponse::Ok if output::is_json() => {
+            output::print_json(&serde_json::json!({ "paused": paused }))?
+        }
+        DaemonResponse::Ok if paused => println!("Recording paused (resume with: muesli resume)"),
+        DaemonResponse::Ok => println!("Recording resumed"),
+        DaemonResponse::Error {
+            message,
+            error_kind,
+        } => {
+            return Err(MuesliError::DaemonRequest {
+                kind: error_kind,
+                message,
+            })
         }
+        _ => return Err(unexpected_response()),
     }
     Ok(())
 }
@@ -111,6 +421,9 @@ async fn handle_stop() -> Result<()> {
 async fn handle_status() -> Result<()> {
     let mut client = match DaemonClient::connect().await {
         Ok(c) => c,
+        Err(_) if output::is_json() => {
+            return output::print_json(&serde_json::json!({ "running": false }));
+        }
         Err(_) => {
             println!("Daemon: not running");
             println!("Status: offline");
@@ -119,10 +432,12 @@ async fn handle_status() -> Result<()> {
     };
 
     match client.send(DaemonRequest::GetStatus).await? {
+        DaemonResponse::Status(status) if output::is_json() => output::print_json(&status)?,
         DaemonResponse::Status(status) => {
             println!("Daemon: running (uptime: {}s)", status.uptime_seconds);
             if status.recording {
-                println!("Status: recording");
+                let state = if status.paused { "paused" } else { "recording" };
+                println!("Status: {}", state);
                 if let Some(meeting) = status.current_meeting {
                     println!("Meeting: {}", meeting);
                 }
@@ -133,26 +448,111 @@ async fn handle_status() -> Result<()> {
                 println!("Detected: {} meeting window", app);
             }
         }
-        _ => {
-            eprintln!("Unexpected response from daemon");
-        }
+        _ => return Err(unexpected_response()),
     }
     Ok(())
 }
 
-async fn handle_list(limit: usize) -> Result<()> {
+async fn handle_status_wait(target: WaitState, timeout_secs: u64) -> Result<()> {
+    let poll_interval = std::time::Duration::from_millis(500);
+    let deadline = (timeout_secs > 0)
+        .then(|| std::time::Instant::now() + std::time::Duration::from_secs(timeout_secs));
+    loop {
+        if current_wait_state().await? == Some(target) {
+            if output::is_json() {
+                return output::print_json(
+                    &serde_json::json!({ "status": wait_state_name(target) }),
+                );
+            }
+            println!("Status: {}", wait_state_name(target));
+            return Ok(());
+        }
+
+        if deadline.is_some_and(|d| std::time::Instant::now() >= d) {
+            return Err(MuesliError::Timeout(format!(
+                "daemon did not become {} within {}s",
+                wait_state_name(target),
+                timeout_secs
+            )));
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+/// Daemon state as seen by `status --wait-for`, or `None` while the daemon is
+/// unreachable (it may still be starting up)
+async fn current_wait_state() -> Result<Option<WaitState>> {
+    let Ok(mut client) = DaemonClient::connect().await else {
+        return Ok(None);
+    };
+    let status = match client.send(DaemonRequest::GetStatus).await? {
+        DaemonResponse::Status(status) => status,
+        _ => return Err(unexpected_response()),
+    };
+
+    if status.recording {
+        return Ok(Some(WaitState::Recording));
+    }
+
+    // Only the daemon's own jobs count: a meeting left `processing` by a crash
+    // or a failed job would otherwise never let this reach idle
+    Ok(Some(if status.background_jobs > 0 {
+        WaitState::Processing
+    } else {
+        WaitState::Idle
+    }))
+}
+
+fn wait_state_name(state: WaitState) -> &'static str {
+    match state {
+        WaitState::Recording => "recording",
+        WaitState::Idle => "idle",
+        WaitState::Processing => "processing",
+    }
+}
+
+async fn handle_list(
+    limit: usize,
+    format: ListFormat,
+    status: Option<crate::storage::MeetingStatus>,
+    since: Option<chrono::NaiveDate>,
+    search: Option<String>,
+) -> Result<()> {
     let db_path = config::loader::database_path()?;
 
     if !db_path.exists() {
-        println!("No meetings recorded yet.");
+        if output::is_json() {
+            return output::print_json(&Vec::<crate::storage::Meeting>::new());
+        }
+        if let ListFormat::Table = format {
+            println!("No meetings recorded yet.");
+        }
         return Ok(());
     }
 
+    let since = since.and_then(|date| {
+        date.and_hms_opt(0, 0, 0)?
+            .and_local_timezone(chrono::Local)
+            .earliest()
+            .map(|t| t.with_timezone(&chrono::Utc))
+    });
+
     let db = Database::open(&db_path)?;
-    let meetings = db.list_meetings(limit)?;
+    let meetings = db.list_meetings_filtered(limit, status, since, search.as_deref())?;
+
+    if output::is_json() {
+        return output::print_json(&meetings);
+    }
+    if let ListFormat::Csv = format {
+        return write_meetings_csv(&meetings);
+    }
 
     if meetings.is_empty() {
-        println!("No meetings recorded yet.");
+        if let Some(text) = search {
+            println!("No meetings with \"{}\" in the title or summary.", text);
+        } else {
+            println!("No meetings recorded yet.");
+        }
         return Ok(());
     }
 
@@ -180,7 +580,40 @@ async fn handle_list(limit: usize) -> Result<()> {
     Ok(())
 }
 
-async fn handle_notes(id: Option<String>) -> Result<()> {
+fn write_meetings_csv(meetings: &[crate::storage::Meeting]) -> Result<()> {
+    let csv_error = |e: csv::Error| MuesliError::Io(e.into());
+
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    writer
+        .write_record(["id", "title", "date", "duration_seconds", "status", "app"])
+        .map_err(csv_error)?;
+
+    for meeting in meetings {
+        writer
+            .write_record([
+                meeting.id.to_string(),
+                meeting.title.clone(),
+                meeting.started_at.to_rfc3339(),
+                meeting
+                    .duration_seconds
+                    .map(|d| d.to_string())
+                    .unwrap_or_default(),
+                meeting.status.to_string(),
+                meeting.detected_app.clone().unwrap_or_default(),
+            ])
+            .map_err(csv_error)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+async fn handle_notes(
+    id: Option<String>,
+    template: Option<SummaryTemplate>,
+    lang: Option<String>,
+    open_in: Option<NotesViewer>,
+) -> Result<()> {
     let db_path = config::loader::database_path()?;
     let db = Database::open(&db_path)?;
 
@@ -189,10 +622,72 @@ async fn handle_notes(id: Option<String>) -> Result<()> {
         None => select_meeting_interactive(&db)?,
     };
 
-    let meeting = db
+    let mut meeting = db
         .get_meeting(&MeetingId::from_string(meeting_id.clone()))?
         .ok_or_else(|| crate::error::MuesliError::MeetingNotFound(meeting_id))?;
 
+    // Nothing to open yet: offer to summarize first
+    let generate_first = open_in.is_some()
+        && template.is_none()
+        && lang.is_none()
+        && db.get_summary(&meeting.id)?.is_none()
+        && confirm_generate_notes(&meeting.title);
+
+    if template.is_some() || lang.is_some() || generate_first {
+        let config = config::loader::load_config()?;
+        if config.llm.provider == "none" {
+            return Err(MuesliError::NotConfigured(
+                "LLM is not configured. Set [llm] provider in the config to re-summarize"
+                    .to_string(),
+            ));
+        }
+        let segments = db.get_transcript_segments(&meeting.id)?;
+        if segments.is_empty() {
+            return Err(MuesliError::Transcription(format!(
+                "No transcript for this meeting. Run: muesli redo {}",
+                meeting.id
+            )));
+        }
+
+        if let Some(template) = template {
+            meeting.template = Some(template.name().to_string());
+        }
+        if let Some(lang) = lang {
+            meeting.output_language = Some(lang);
+        }
+        status!(
+            "Re-summarizing with the {} template{}...",
+            crate::pipeline::summary_template(&meeting),
+            meeting
+                .output_language
+                .as_deref()
+                .map(|l| format!(" in {}", l))
+                .unwrap_or_default()
+        );
+        let transcript = crate::transcription::Transcript::new(segments);
+        meeting = crate::pipeline::summarize_and_generate_notes(
+            &db,
+            &meeting,
+            &transcript,
+            &config,
+            false,
+        )
+        .await?
+        .meeting;
+    }
+
+    if let Some(NotesViewer::Browser) = open_in {
+        let summary = db.get_summary(&meeting.id)?.ok_or_else(|| {
+            MuesliError::NoResults(format!(
+                "No notes for '{}'. Run: muesli redo {}",
+                meeting.title, meeting.id
+            ))
+        })?;
+        let path = crate::notes::export::open_in_browser(&meeting, &summary)?;
+        status!("Opened {}", path.display());
+        return Ok(());
+    }
+
     if let Ok(Some(summary)) = db.get_summary(&meeting.id) {
         println!("\n# {}\n", meeting.title);
         println!(
@@ -212,119 +707,1121 @@ async fn handle_notes(id: Option<String>) -> Result<()> {
     Ok(())
 }
 
-async fn handle_transcript(id: Option<String>) -> Result<()> {
-    let db_path = config::loader::database_path()?;
-    let db = Database::open(&db_path)?;
+/// Ask whether to summarize a meeting that has no notes yet (never when quiet
+/// or without a terminal to answer on)
+fn confirm_generate_notes(title: &str) -> bool {
+    use dialoguer::{theme::ColorfulTheme, Confirm};
+    use std::io::IsTerminal;
 
-    let meeting_id = match id {
-        Some(id) => id,
-        None => select_meeting_interactive(&db)?,
-    };
+    if output::is_quiet() || !std::io::stdin().is_terminal() {
+        return false;
+    }
+    Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!("'{}' has no notes yet. Summarize it now?", title))
+        .default(true)
+        .interact()
+        .unwrap_or(false)
+}
 
-    let meeting = db
-        .get_meeting(&MeetingId::from_string(meeting_id.clone()))?
-        .ok_or_else(|| crate::error::MuesliError::MeetingNotFound(meeting_id))?;
+async fn handle_diff_notes(old_id: &str, new_id: &str) -> Result<()> {
+    use std::io::IsTerminal;
 
-    println!("\n{}", "=".repeat(60));
-    println!("  {} - Transcript", meeting.title);
-    println!("{}", "=".repeat(60));
-    println!();
+    let db_path = config::loader::database_path()?;
+    let db = Database::open(&db_path)?;
 
-    let segments = db.get_transcript_segments(&meeting.id)?;
-    if segments.is_empty() {
-        println!("No transcript available.");
-        return Ok(());
+    let mut sides = Vec::with_capacity(2);
+    let mut missing = Vec::new();
+    for id in [old_id, new_id] {
+        let meeting = db
+            .get_meeting(&MeetingId::from_string(id.to_string()))?
+            .ok_or_else(|| MuesliError::MeetingNotFound(id.to_string()))?;
+        match db.get_summary(&meeting.id)? {
+            Some(summary) => sides.push((meeting, summary)),
+            None => missing.push(format!("'{}' ({})", meeting.title, meeting.id)),
+        }
+    }
+    if !missing.is_empty() {
+        return Err(MuesliError::NoResults(format!(
+            "No summary for {}. Run: muesli redo <id>",
+            missing.join(" and ")
+        )));
     }
 
-    println!("{} segments\n", segments.len());
-
-    for segment in segments {
-        print_segment(&segment);
+    let label = |meeting: &crate::storage::Meeting| {
+        format!(
+            "{} ({})",
+            meeting.title,
+            meeting.started_at.format("%Y-%m-%d %H:%M")
+        )
+    };
+    let (old_meeting, old_summary) = &sides[0];
+    let (new_meeting, new_summary) = &sides[1];
+
+    let rendered = crate::notes::diff::render_diff(
+        &label(old_meeting),
+        &label(new_meeting),
+        &old_summary.markdown,
+        &new_summary.markdown,
+        std::io::stdout().is_terminal(),
+    );
+    if rendered.is_empty() {
+        status!("Summaries are identical.");
+    } else {
+        print!("{}", rendered);
     }
 
     Ok(())
 }
 
-fn select_meeting_interactive(db: &Database) -> Result<String> {
-    use dialoguer::{theme::ColorfulTheme, Select};
-
-    let meetings = db.list_meetings(20)?;
-
-    if meetings.is_empty() {
-        return Err(crate::error::MuesliError::Config(
-            "No meetings found".to_string(),
+async fn handle_combine_notes(ids: Vec<String>, output: Option<std::path::PathBuf>) -> Result<()> {
+    let config = config::loader::load_config()?;
+    if config.llm.provider == "none" {
+        return Err(MuesliError::NotConfigured(
+            "LLM is not configured. Run 'muesli setup' to set up an LLM provider.".to_string(),
         ));
     }
 
-    let items: Vec<String> = meetings
-        .iter()
-        .map(|m| {
-            let date = m.started_at.format("%Y-%m-%d %H:%M");
-            let duration = m
-                .duration_seconds
-                .map(|d| format!("{}m", d / 60))
-                .unwrap_or_else(|| "?".to_string());
-            format!("{} | {} | {}", date, duration, truncate(&m.title, 40))
-        })
-        .collect();
+    let db_path = config::loader::database_path()?;
+    let db = Database::open(&db_path)?;
 
-    let selection = Select::with_theme(&ColorfulTheme::default())
-        .with_prompt("Select a meeting")
-        .items(&items)
-        .default(0)
-        .interact()
-        .map_err(|e| crate::error::MuesliError::Config(format!("Selection cancelled: {}", e)))?;
+    let mut meetings = Vec::with_capacity(ids.len());
+    for id in ids {
+        let meeting = db
+            .get_meeting(&MeetingId::from_string(id.clone()))?
+            .ok_or(MuesliError::MeetingNotFound(id))?;
+        meetings.push(meeting);
+    }
+    meetings.sort_by_key(|m| m.started_at);
 
-    Ok(meetings[selection].id.0.clone())
-}
+    let mut parts = Vec::with_capacity(meetings.len());
+    let mut durations = Vec::with_capacity(meetings.len());
+    for meeting in &meetings {
+        let segments = db.get_transcript_segments(&meeting.id)?;
+        if segments.is_empty() {
+            return Err(MuesliError::NoResults(format!(
+                "Meeting '{}' ({}) has no transcript",
+                meeting.title, meeting.id
+            )));
+        }
+        status!(
+            "  {}  {}  ({} segments)",
+            meeting.started_at.format("%Y-%m-%d %H:%M"),
+            meeting.title,
+            segments.len()
+        );
+        parts.push(segments);
+        durations.push(meeting.duration_seconds.map(|d| d * 1000));
+    }
 
-async fn handle_daemon() -> Result<()> {
-    if DaemonClient::ping().await? {
-        eprintln!("Error: Daemon is already running.");
+    let segments = crate::transcription::concat_segments(&parts, &durations);
+    let transcript = crate::transcription::Transcript::new(segments);
+
+    let first = &meetings[0];
+    let mut combined = crate::storage::Meeting::new(format!("{} (combined)", first.title));
+    combined.started_at = first.started_at;
+    combined.ended_at = meetings.iter().filter_map(|m| m.ended_at).max();
+    combined.duration_seconds = Some(transcript.duration_ms / 1000);
+    combined.detected_app = first.detected_app.clone();
+    combined.template = first.template.clone();
+    combined.output_language = first.output_language.clone();
+    combined.status = crate::storage::MeetingStatus::Complete;
+
+    status!(
+        "\nSummarizing {} meetings ({} segments)...",
+        meetings.len(),
+        transcript.segments.len()
+    );
+    let llm = crate::pipeline::summary_llm_config(&combined, &config.llm);
+    let summary = crate::llm::summarize_transcript(
+        &llm,
+        &transcript,
+        crate::pipeline::summary_template(&combined),
+        Some(&db),
+    )
+    .await
+    .map_err(|e| MuesliError::Api(format!("Summarization failed: {}", e)))?;
+
+    if let Some(path) = output {
+        let content = crate::notes::markdown::NoteGenerator::from_config(
+            std::path::PathBuf::new(),
+            &config.storage,
+        )
+        .render(&combined, &transcript, &summary);
+        std::fs::write(&path, content)?;
+        println!("Notes written to {}", path.display());
         return Ok(());
     }
 
-    println!("Starting muesli daemon...");
-    crate::daemon::run_daemon().await
-}
-
-async fn handle_config(action: ConfigCommands) -> Result<()> {
-    match action {
-        ConfigCommands::Show => {
-            let cfg = config::loader::load_config()?;
-            println!("{}", toml::to_string_pretty(&cfg)?);
-        }
-        ConfigCommands::Edit => {
-            let path = config::loader::config_path()?;
-            let editor = std::env::var("EDITOR").unwrap_or_else(|_| "nano".to_string());
-            std::process::Command::new(&editor).arg(&path).status()?;
-        }
+    match crate::llm::generate_title(&llm, &summary.markdown).await {
+        Ok(title) => combined.title = title,
+        Err(e) => eprintln!("  Title generation failed: {}", e),
     }
+
+    db.insert_meeting(&combined)?;
+    // Each meeting's transcript was merged when it was stored
+    db.insert_transcript_segments(&combined.id, &transcript.segments, 0)?;
+    db.insert_summary(&combined.id, &summary)?;
+    crate::pipeline::store_action_items(&db, &combined, &llm, &summary).await?;
+
+    let notes_dir = config::loader::notes_dir()?;
+    let generator = crate::notes::markdown::NoteGenerator::from_config(notes_dir, &config.storage);
+    let notes_path = generator.generate(&combined, &transcript, &summary)?;
+    combined.notes_path = Some(notes_path.clone());
+    db.update_meeting(&combined)?;
+
+    println!("Created meeting: {}", combined.title);
+    println!("  Notes saved: {:?}", notes_path);
+    println!("\nView with: muesli notes {}", combined.id);
     Ok(())
 }
 
-async fn handle_models(engine: ModelEngine) -> Result<()> {
-    match engine {
-        ModelEngine::Whisper { action } => handle_whisper_models(action).await,
-        ModelEngine::Diarization { action } => handle_diarization_models(action).await,
+async fn handle_export(
+    id: Option<String>,
+    format: ExportFormat,
+    output: Option<std::path::PathBuf>,
+    include_transcript: bool,
+    include_metadata: bool,
+) -> Result<()> {
+    use crate::notes::export::{render_document, ExportSections};
+
+    if include_metadata && format != ExportFormat::Markdown {
+        return Err(MuesliError::Usage(
+            "--include-metadata only applies to --format markdown".to_string(),
+        ));
+    }
+    let to_stdout = output.as_deref() == Some(std::path::Path::new("-"));
+    if to_stdout && format == ExportFormat::Pdf {
+        return Err(MuesliError::Usage(
+            "PDF export needs a file; pass --output FILE".to_string(),
+        ));
     }
-}
 
-async fn handle_whisper_models(action: ModelAction) -> Result<()> {
-    let models_dir = config::loader::models_dir()?;
-    let manager = ModelManager::new(models_dir);
+    let db_path = config::loader::database_path()?;
+    let db = Database::open(&db_path)?;
 
-    match action {
-        ModelAction::List => {
-            println!("{:<10} {:<12} {:<10}", "Model", "Size (MB)", "Downloaded");
-            println!("{}", "-".repeat(35));
+    let meeting_id = match id {
+        Some(id) => id,
+        None => select_meeting_interactive(&db)?,
+    };
+
+    let meeting = db
+        .get_meeting(&MeetingId::from_string(meeting_id.clone()))?
+        .ok_or(MuesliError::MeetingNotFound(meeting_id))?;
+
+    if matches!(format, ExportFormat::Srt | ExportFormat::Vtt) {
+        let segments = db.get_transcript_segments(&meeting.id)?;
+        if segments.is_empty() {
+            return Err(MuesliError::Transcription(format!(
+                "No transcript for '{}'. Run: muesli redo {}",
+                meeting.title, meeting.id
+            )));
+        }
+        let content = match format {
+            ExportFormat::Srt => crate::notes::subtitles::to_srt(&segments),
+            _ => crate::notes::subtitles::to_vtt(&segments),
+        };
+        return write_export(&meeting, format, output, &content);
+    }
+
+    let summary = db.get_summary(&meeting.id)?.ok_or_else(|| {
+        MuesliError::NoResults(format!(
+            "No summary for '{}'. Run: muesli redo {}",
+            meeting.title, meeting.id
+        ))
+    })?;
+
+    let transcript = if include_transcript {
+        let segments = db.get_transcript_segments(&meeting.id)?;
+        if segments.is_empty() {
+            return Err(MuesliError::Transcription(format!(
+                "No transcript for '{}'. Run: muesli redo {}",
+                meeting.title, meeting.id
+            )));
+        }
+        Some(crate::transcription::Transcript::new(segments))
+    } else {
+        None
+    };
+    let sections = ExportSections {
+        frontmatter: include_metadata,
+        transcript: transcript.as_ref(),
+        times: crate::notes::markdown::TranscriptTimes::from_config(
+            &config::loader::load_config()?.storage,
+        ),
+    };
+    let markdown = render_document(&meeting, &summary, sections);
+    if format == ExportFormat::Pdf {
+        let path = export_path(&meeting, format, output);
+        crate::notes::export::write_pdf(&meeting.title, &markdown, &path)?;
+        println!("Exported {}", path.display());
+        return Ok(());
+    }
+
+    let content = match format {
+        ExportFormat::Html => crate::notes::export::markdown_to_html(&meeting.title, &markdown),
+        _ => markdown,
+    };
+    write_export(&meeting, format, output, &content)
+}
+
+/// `--output` or `<meeting-id>.<ext>` in the current directory
+fn export_path(
+    meeting: &crate::storage::Meeting,
+    format: ExportFormat,
+    output: Option<std::path::PathBuf>,
+) -> std::path::PathBuf {
+    output.unwrap_or_else(|| {
+        std::path::PathBuf::from(format!("{}.{}", meeting.id, format.extension()))
+    })
+}
+
+/// Write an export to its file, or to stdout for `--output -`
+fn write_export(
+    meeting: &crate::storage::Meeting,
+    format: ExportFormat,
+    output: Option<std::path::PathBuf>,
+    content: &str,
+) -> Result<()> {
+    if output.as_deref() == Some(std::path::Path::new("-")) {
+        print!("{}", content);
+        return Ok(());
+    }
+
+    let path = export_path(meeting, format, output);
+    std::fs::write(&path, content)?;
+    println!("Exported {}", path.display());
+    Ok(())
+}
+
+async fn handle_email(id: Option<String>, to: Vec<String>) -> Result<()> {
+    let config = config::loader::load_config()?;
+    let db = Database::open(config::loader::database_path()?)?;
+
+    let meeting_id = match id {
+        Some(id) => id,
+        None => select_meeting_interactive(&db)?,
+    };
+
+    let meeting = db
+        .get_meeting(&MeetingId::from_string(meeting_id.clone()))?
+        .ok_or(MuesliError::MeetingNotFound(meeting_id))?;
+    let summary = db.get_summary(&meeting.id)?.ok_or_else(|| {
+        MuesliError::NoResults(format!(
+            "No summary for '{}'. Run: muesli redo {}",
+            meeting.title, meeting.id
+        ))
+    })?;
+
+    let recipients = if to.is_empty() { &config.smtp.to } else { &to };
+    status!(
+        "Sending \"{}\" to {}...",
+        meeting.title,
+        recipients.join(", ")
+    );
+    crate::notes::email::send_notes(&config.smtp, &meeting, &summary, recipients)?;
+    println!("Sent notes to {}", recipients.join(", "));
+    Ok(())
+}
+
+/// `[general] default_command`, parsed like a command line typed after
+/// `muesli`, with the global flags of the bare `muesli` run (`typed`) added to
+/// its own. `None` when it names no subcommand. `-v` only counts when typed,
+/// since logging is set up before the config is read.
+fn default_command(typed: Cli) -> Result<Option<Cli>> {
+    let configured = config::loader::load_config()?.general.default_command;
+    let args: Vec<&str> = configured.split_whitespace().collect();
+    if args.is_empty() {
+        return Ok(None);
+    }
+
+    let mut cli = <Cli as clap::Parser>::try_parse_from(std::iter::once("muesli").chain(args))
+        .map_err(|e| {
+            // `--help` / `--version` aren't errors; print them as clap would
+            if !e.use_stderr() {
+                e.exit();
+            }
+            MuesliError::Config(format!(
+                "general.default_command = \"{}\" is not a valid muesli command: {}",
+                configured,
+                e.to_string().trim().trim_start_matches("error: ")
+            ))
+        })?;
+    if cli.command.is_none() {
+        return Ok(None);
+    }
+
+    cli.quiet |= typed.quiet;
+    cli.json |= typed.json;
+    cli.socket = typed.socket.or(cli.socket);
+    Ok(Some(cli))
+}
+
+/// `window` is `(at_ms, before, after)` from `--at`/`--before`/`--after`
+async fn handle_transcript(
+    id: Option<String>,
+    speaker: Option<String>,
+    clock: bool,
+    collapse_speakers: bool,
+    timestamps: bool,
+    window: Option<(u64, usize, usize)>,
+    format: TranscriptOutput,
+    redact: bool,
+) -> Result<()> {
+    if !timestamps && format.file_format().is_some() {
+        return Err(MuesliError::Usage(
+            "--timestamps off only applies to the text view; srt, vtt and json carry segment times"
+                .to_string(),
+        ));
+    }
+
+    let db_path = config::loader::database_path()?;
+    let db = Database::open(&db_path)?;
+
+    let meeting_id = match id {
+        Some(id) => id,
+        None => select_meeting_interactive(&db)?,
+    };
+
+    let meeting = db
+        .get_meeting(&MeetingId::from_string(meeting_id.clone()))?
+        .ok_or_else(|| crate::error::MuesliError::MeetingNotFound(meeting_id))?;
+
+    let print_header = || {
+        println!("\n{}", "=".repeat(60));
+        println!("  {} - Transcript", meeting.title);
+        println!("{}", "=".repeat(60));
+        println!();
+    };
+
+    let mut segments = db.get_transcript_segments(&meeting.id)?;
+    if segments.is_empty() {
+        if format.file_format().is_some() {
+            return Err(MuesliError::NoResults(format!(
+                "Meeting '{}' has no transcript",
+                meeting.title
+            )));
+        }
+        print_header();
+        println!("No transcript available.");
+        return Ok(());
+    }
+
+    if let Some(speaker) = speaker {
+        segments = filter_segments_by_speaker(segments, &speaker)?;
+    }
+
+    if let Some((at_ms, before, after)) = window {
+        segments.sort_by_key(|s| s.start_ms);
+        let range = crate::transcription::segment_window(&segments, at_ms, before, after);
+        segments = segments[range].to_vec();
+    }
+
+    if redact {
+        for segment in &mut segments {
+            segment.text = crate::llm::redaction::redact(&segment.text);
+        }
+    }
+
+    if let Some(file_format) = format.file_format() {
+        print!(
+            "{}",
+            crate::transcription::import::render(&segments, file_format)?
+        );
+        return Ok(());
+    }
+
+    print_header();
+    println!("{} segments\n", segments.len());
+
+    let started_at = meeting.started_at.with_timezone(&chrono::Local);
+    let timestamp = |segment: &crate::transcription::TranscriptSegment| {
+        if !timestamps {
+            None
+        } else if clock {
+            Some(segment.format_clock_timestamp(&started_at))
+        } else {
+            Some(segment.format_timestamp())
+        }
+    };
+
+    if collapse_speakers {
+        print_dialogue(&segments, timestamp);
+        return Ok(());
+    }
+    for segment in segments {
+        print_segment_at(&segment, timestamp(&segment).as_deref());
+    }
+
+    Ok(())
+}
+
+/// `transcript --merge-speakers FROM=TO`: relabel every segment of one speaker
+/// as another, then offer to re-summarize with the merged labels
+async fn handle_merge_speakers(id: Option<String>, merge: SpeakerMerge) -> Result<()> {
+    use dialoguer::{theme::ColorfulTheme, Confirm};
+
+    let db_path = config::loader::database_path()?;
+    let db = Database::open(&db_path)?;
+
+    let meeting_id = match id {
+        Some(id) => id,
+        None => select_meeting_interactive(&db)?,
+    };
+
+    let meeting = db
+        .get_meeting(&MeetingId::from_string(meeting_id.clone()))?
+        .ok_or_else(|| MuesliError::MeetingNotFound(meeting_id))?;
+
+    let segments = db.get_transcript_segments(&meeting.id)?;
+    let available = speaker_labels(&segments);
+    let find = |label: &str| {
+        let wanted = normalize_speaker_label(label);
+        available
+            .iter()
+            .find(|l| l.eq_ignore_ascii_case(&wanted))
+            .cloned()
+            .ok_or_else(|| {
+                MuesliError::NoResults(format!(
+                    "speaker '{}' (available: {})",
+                    label,
+                    if available.is_empty() {
+                        "none".to_string()
+                    } else {
+                        available.join(", ")
+                    }
+                ))
+            })
+    };
+    let from = find(&merge.from)?;
+    let to = find(&merge.to)?;
+    if from == to {
+        return Err(MuesliError::Usage(format!(
+            "{} and {} are the same speaker",
+            merge.from, merge.to
+        )));
+    }
+
+    let changed = db.relabel_speaker(&meeting.id, &from, &to)?;
+    println!("Relabeled {} segments from {} to {}", changed, from, to);
+
+    let config = config::loader::load_config()?;
+    let has_audio = meeting.audio_path.as_ref().is_some_and(|p| p.exists());
+    if config.llm.provider == "none" || !has_audio || output::is_quiet() {
+        return Ok(());
+    }
+
+    let resummarize = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Re-summarize with the merged speakers?")
+        .default(true)
+        .interact()
+        .map_err(|e| MuesliError::Config(e.to_string()))?;
+    if resummarize {
+        handle_redo(Some(meeting.id.to_string()), false, None, None, false).await?;
+    }
+    Ok(())
+}
+
+async fn handle_import_transcript(id: &str, file: &std::path::Path, yes: bool) -> Result<()> {
+    use crate::transcription::import::{self, TranscriptFormat};
+    use dialoguer::{theme::ColorfulTheme, Confirm};
+
+    let format = TranscriptFormat::from_path(file)?;
+    let contents = std::fs::read_to_string(file)?;
+    let segments = import::parse(&contents, format)?;
+
+    let db = Database::open(config::loader::database_path()?)?;
+    let meeting = db
+        .get_meeting(&MeetingId::from_string(id.to_string()))?
+        .ok_or_else(|| MuesliError::MeetingNotFound(id.to_string()))?;
+
+    let existing = db.get_transcript_segments(&meeting.id)?.len();
+    if existing > 0 && !yes {
+        let replace = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Replace the {} existing transcript segments of '{}'?",
+                existing, meeting.title
+            ))
+            .default(false)
+            .interact()
+            .unwrap_or(false);
+        if !replace {
+            println!("Import cancelled.");
+            return Ok(());
+        }
+    }
+
+    db.delete_transcript_segments(&meeting.id)?;
+    db.insert_transcript_segments(&meeting.id, &segments, 0)?;
+    println!(
+        "Imported {} segments from {} into '{}'",
+        segments.len(),
+        file.display(),
+        meeting.title
+    );
+
+    let config = config::loader::load_config()?;
+    if config.llm.provider == "none" || output::is_quiet() {
+        return Ok(());
+    }
+    let summarize = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Summarize the imported transcript?")
+        .default(true)
+        .interact()
+        .unwrap_or(false);
+    if summarize {
+        status!("Summarizing...");
+        let transcript = crate::transcription::Transcript::new(segments);
+        let generated = crate::pipeline::summarize_and_generate_notes(
+            &db,
+            &meeting,
+            &transcript,
+            &config,
+            false,
+        )
+        .await?;
+        println!("Notes written to {}", generated.notes_path.display());
+    }
+    Ok(())
+}
+
+fn handle_speakers(action: SpeakerCommands) -> Result<()> {
+    let db = Database::open(config::loader::database_path()?)?;
+
+    match action {
+        SpeakerCommands::Enroll { name, sample } => {
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(MuesliError::Usage("Speaker name is empty".to_string()));
+            }
+            let samples = crate::audio::wav::read_mono_16k(&sample)?;
+            let voiceprint =
+                crate::transcription::voiceprint::voiceprint(&samples).ok_or_else(|| {
+                    MuesliError::Usage(format!(
+                        "{} has less than {}s of speech; record a longer sample",
+                        sample.display(),
+                        crate::transcription::voiceprint::MIN_SPEECH_SECS
+                    ))
+                })?;
+            db.upsert_speaker_profile(name, &voiceprint)?;
+            println!("Enrolled {} from {}", name, sample.display());
+            println!(
+                "Meetings diarized from now on will name this speaker when the voice matches."
+            );
+        }
+        SpeakerCommands::ListProfiles => {
+            let profiles = db.list_speaker_profiles()?;
+            if output::is_json() {
+                let names: Vec<_> = profiles
+                    .iter()
+                    .map(|(name, _, enrolled_at)| {
+                        serde_json::json!({ "name": name, "enrolled_at": enrolled_at })
+                    })
+                    .collect();
+                return output::print_json(&names);
+            }
+            if profiles.is_empty() {
+                println!("No speakers enrolled. Add one with: muesli speakers enroll <name> <sample.wav>");
+                return Ok(());
+            }
+            for (name, _, enrolled_at) in profiles {
+                let enrolled = chrono::DateTime::parse_from_rfc3339(&enrolled_at)
+                    .map(|t| {
+                        t.with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d")
+                            .to_string()
+                    })
+                    .unwrap_or(enrolled_at);
+                println!("{:<24} enrolled {}", name, enrolled);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `2` -> `SPEAKER_2`; anything else is taken as a literal label
+fn normalize_speaker_label(speaker: &str) -> String {
+    if speaker.chars().all(|c| c.is_ascii_digit()) {
+        format!("SPEAKER_{}", speaker)
+    } else {
+        speaker.to_string()
+    }
+}
+
+/// Distinct speaker labels in a transcript, sorted
+fn speaker_labels(segments: &[crate::transcription::TranscriptSegment]) -> Vec<String> {
+    let mut labels: Vec<String> = segments.iter().filter_map(|s| s.speaker.clone()).collect();
+    labels.sort();
+    labels.dedup();
+    labels
+}
+
+fn handle_audio_normalize(
+    id: Option<String>,
+    in_place: bool,
+    output: Option<std::path::PathBuf>,
+) -> Result<()> {
+    let db_path = config::loader::database_path()?;
+    let db = Database::open(&db_path)?;
+
+    let meeting_id = match id {
+        Some(id) => id,
+        None => select_meeting_with_audio(&db)?,
+    };
+
+    let meeting = db
+        .get_meeting(&MeetingId::from_string(meeting_id.clone()))?
+        .ok_or_else(|| MuesliError::MeetingNotFound(meeting_id))?;
+    let audio_path = meeting
+        .audio_path
+        .filter(|p| p.exists())
+        .ok_or_else(|| MuesliError::Audio("No audio file for this meeting".into()))?;
+
+    let destination = match (in_place, output) {
+        (true, _) => audio_path.clone(),
+        (false, Some(path)) => path,
+        (false, None) => audio_path.with_extension("normalized.wav"),
+    };
+
+    status!("Normalizing {}...", audio_path.display());
+    let mut samples = crate::audio::wav::read_mono_16k(&audio_path)?;
+    let gain = crate::audio::convert::normalize_loudness(
+        &mut samples,
+        crate::audio::convert::WHISPER_SAMPLE_RATE,
+    );
+    let bit_depth = config::loader::load_config()
+        .ok()
+        .and_then(|c| crate::audio::recorder::BitDepth::parse(&c.audio.bit_depth))
+        .unwrap_or_default();
+    crate::audio::wav::write_mono_16k(&destination, &samples, bit_depth)?;
+
+    println!(
+        "Wrote {} (average gain {:.1} dB)",
+        destination.display(),
+        20.0 * gain.log10()
+    );
+    if in_place {
+        status!("Re-transcribe with: muesli redo {} --clean", meeting.id);
+    }
+    Ok(())
+}
+
+fn handle_audio_concat(ids: Vec<String>, into: Option<String>) -> Result<()> {
+    let db = Database::open(config::loader::database_path()?)?;
+
+    let mut meetings = Vec::with_capacity(ids.len());
+    for id in ids {
+        let meeting = db
+            .get_meeting(&MeetingId::from_string(id.clone()))?
+            .ok_or(MuesliError::MeetingNotFound(id))?;
+        if !meeting.audio_path.as_ref().is_some_and(|p| p.exists()) {
+            return Err(MuesliError::Audio(format!(
+                "Meeting '{}' ({}) has no audio file",
+                meeting.title, meeting.id
+            )));
+        }
+        meetings.push(meeting);
+    }
+    meetings.sort_by_key(|m| m.started_at);
+
+    let first = &meetings[0];
+    let mut joined =
+        crate::storage::Meeting::new(into.unwrap_or_else(|| format!("{} (joined)", first.title)));
+    joined.started_at = first.started_at;
+    joined.ended_at = meetings.iter().filter_map(|m| m.ended_at).max();
+    joined.detected_app = first.detected_app.clone();
+    joined.template = first.template.clone();
+    joined.output_language = first.output_language.clone();
+    joined.status = MeetingStatus::Complete;
+
+    let recordings_dir = config::loader::recordings_dir()?;
+    let audio_path = recordings_dir.join(format!("{}.wav", joined.id));
+    let bit_depth = config::loader::load_config()
+        .ok()
+        .and_then(|c| crate::audio::recorder::BitDepth::parse(&c.audio.bit_depth))
+        .unwrap_or_default();
+
+    status!("Joining {} recordings...", meetings.len());
+    let inputs: Vec<&std::path::Path> = meetings
+        .iter()
+        .filter_map(|m| m.audio_path.as_deref())
+        .collect();
+    let durations_ms = crate::audio::wav::concat_mono_16k(&inputs, &audio_path, bit_depth)?;
+
+    let mut parts = Vec::with_capacity(meetings.len());
+    let mut offset_ms = 0;
+    for (meeting, &duration_ms) in meetings.iter().zip(&durations_ms) {
+        status!(
+            "  {}  {}  (at {}m {}s)",
+            meeting.started_at.format("%Y-%m-%d %H:%M"),
+            meeting.title,
+            offset_ms / 60_000,
+            offset_ms / 1000 % 60
+        );
+        parts.push(crate::storage::MeetingPart {
+            source_id: meeting.id.clone(),
+            offset_ms,
+            duration_ms,
+        });
+        offset_ms += duration_ms;
+    }
+    joined.audio_path = Some(audio_path.clone());
+    joined.duration_seconds = Some(offset_ms / 1000);
+
+    // Transcripts are only carried over when every part has one; a gap would
+    // leave part of the recording silently untranscribed
+    let mut transcripts = Vec::with_capacity(meetings.len());
+    for meeting in &meetings {
+        transcripts.push(db.get_transcript_segments(&meeting.id)?);
+    }
+    let segments = if transcripts.iter().all(|t| !t.is_empty()) {
+        let durations: Vec<Option<u64>> = durations_ms.iter().copied().map(Some).collect();
+        crate::transcription::concat_segments(&transcripts, &durations)
+    } else {
+        Vec::new()
+    };
+
+    db.insert_meeting(&joined)?;
+    db.insert_meeting_parts(&joined.id, &parts)?;
+    if !segments.is_empty() {
+        // Each part's transcript was merged when it was stored
+        db.insert_transcript_segments(&joined.id, &segments, 0)?;
+    }
+
+    println!("Created meeting: {} ({})", joined.title, joined.id);
+    println!("  Audio: {}", audio_path.display());
+    if segments.is_empty() {
+        println!("\nTranscribe and summarize with: muesli redo {}", joined.id);
+    } else {
+        println!("  {} transcript segments carried over", segments.len());
+        println!("\nSummarize with: muesli redo {}", joined.id);
+    }
+    Ok(())
+}
+
+/// Keep only segments spoken by `speaker`. Matches labels case-insensitively and
+/// accepts a bare number as shorthand for `SPEAKER_<n>`.
+fn filter_segments_by_speaker(
+    segments: Vec<crate::transcription::TranscriptSegment>,
+    speaker: &str,
+) -> Result<Vec<crate::transcription::TranscriptSegment>> {
+    let wanted = normalize_speaker_label(speaker);
+    let available = speaker_labels(&segments);
+
+    let filtered: Vec<_> = segments
+        .into_iter()
+        .filter(|s| {
+            s.speaker
+                .as_deref()
+                .is_some_and(|label| label.eq_ignore_ascii_case(&wanted))
+        })
+        .collect();
+
+    if filtered.is_empty() {
+        let hint = if available.is_empty() {
+            "transcript has no speaker labels; run diarization with: muesli redo".to_string()
+        } else {
+            format!("available: {}", available.join(", "))
+        };
+        return Err(MuesliError::NoResults(format!(
+            "speaker '{}' ({})",
+            speaker, hint
+        )));
+    }
+
+    Ok(filtered)
+}
+
+fn select_meeting_interactive(db: &Database) -> Result<String> {
+    use dialoguer::{theme::ColorfulTheme, Select};
+
+    let meetings = db.list_meetings(20)?;
+
+    if meetings.is_empty() {
+        return Err(crate::error::MuesliError::Config(
+            "No meetings found".to_string(),
+        ));
+    }
+
+    let items: Vec<String> = meetings
+        .iter()
+        .map(|m| {
+            let date = m.started_at.format("%Y-%m-%d %H:%M");
+            let duration = m
+                .duration_seconds
+                .map(|d| format!("{}m", d / 60))
+                .unwrap_or_else(|| "?".to_string());
+            format!("{} | {} | {}", date, duration, truncate(&m.title, 40))
+        })
+        .collect();
+
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Select a meeting")
+        .items(&items)
+        .default(0)
+        .interact()
+        .map_err(|e| crate::error::MuesliError::Config(format!("Selection cancelled: {}", e)))?;
+
+    Ok(meetings[selection].id.0.clone())
+}
+
+async fn handle_daemon() -> Result<()> {
+    if DaemonClient::ping().await? {
+        return Err(MuesliError::Daemon("Daemon is already running".to_string()));
+    }
+
+    println!("Starting muesli daemon...");
+    crate::daemon::run_daemon().await
+}
+
+/// One detection pass over the current windows, reporting instead of acting
+fn handle_daemon_once() -> Result<()> {
+    if !crate::detection::hyprland::is_hyprland_running() {
+        println!("No compositor: Hyprland is not running, so meetings can't be detected.");
+        return Ok(());
+    }
+
+    let config = config::loader::load_config()?;
+    let windows = crate::detection::hyprland::list_all_windows()?;
+    let detected: Vec<_> = windows
+        .iter()
+        .filter_map(|w| {
+            crate::detection::patterns::detect_meeting_app(&w.class, &w.title).map(|app| (app, w))
+        })
+        .collect();
+
+    let Some((app, window)) = detected.first() else {
+        println!("No meeting windows among {} open windows.", windows.len());
+        return Ok(());
+    };
+
+    for (app, window) in &detected {
+        println!("Detected {}: [{}] {}", app, window.class, window.title);
+    }
+
+    if config.detection.auto_prompt {
+        let timeout = match config.detection.prompt_timeout_secs {
+            0 => "no timeout".to_string(),
+            secs => format!("{}s timeout", secs),
+        };
+        println!("Would prompt to record \"{}\" ({})", window.title, timeout);
+    } else {
+        println!(
+            "Would notify that {} was detected (auto_prompt is off, no recording prompt)",
+            app
+        );
+    }
+    Ok(())
+}
+
+async fn handle_config(action: ConfigCommands) -> Result<()> {
+    match action {
+        ConfigCommands::Show => {
+            let cfg = config::loader::load_config()?;
+            println!("{}", toml::to_string_pretty(&cfg)?);
+        }
+        ConfigCommands::Edit { validate } => handle_config_edit(validate)?,
+        // --stdin only relaxes the required VALUE; a missing value is read the same way
+        ConfigCommands::Set { key, value, .. } => {
+            let value = match value {
+                Some(value) if value != "-" => value,
+                _ => read_secret(&key)?,
+            };
+            let cfg = config::loader::load_config()?;
+            let value = if matches!(key.as_str(), "llm.api_key" | "llm.fallback_api_key") {
+                keyring_or_plaintext(cfg.llm.use_keyring, &key, value)
+            } else {
+                value
+            };
+            // Validate against the whole config, but only rewrite this key's line
+            let cfg = cfg.with_value(&key, &value)?;
+            let (section, leaf) = key.rsplit_once('.').unwrap_or_default();
+            let stored = toml::Value::try_from(&cfg)?;
+            let stored = key
+                .split('.')
+                .try_fold(&stored, |table, part| table.get(part))
+                .ok_or_else(|| MuesliError::Config(format!("Unknown setting: {}", key)))?;
+            update_section_value(section, leaf, &stored.to_string())?;
+            status!("Set {}", key);
+        }
+        ConfigCommands::Prompts {
+            action: PromptsAction::Init { force },
+        } => handle_prompts_init(force)?,
+    }
+    Ok(())
+}
+
+fn handle_prompts_init(force: bool) -> Result<()> {
+    let dir = config::loader::prompts_dir()?;
+    std::fs::create_dir_all(&dir)?;
+
+    for template in crate::llm::prompts::TEMPLATES {
+        let path = dir.join(template.file);
+        if path.exists() && !force {
+            status!("Kept {} (use --force to overwrite)", path.display());
+            continue;
+        }
+        std::fs::write(&path, template.builtin)?;
+        status!("Wrote {}", path.display());
+    }
+    status!("\nEdit these to change the prompts; delete one to go back to the built-in text.");
+    Ok(())
+}
+
+/// With `llm.use_keyring`, move `secret` into the keyring and return the reference
+/// to write to the config; falls back to the plaintext secret if the keyring fails
+fn keyring_or_plaintext(use_keyring: bool, name: &str, secret: String) -> String {
+    if !use_keyring {
+        return secret;
+    }
+    match config::secrets::store(name, &secret) {
+        Ok(reference) => reference,
+        Err(e) => {
+            eprintln!("Warning: {}. Storing {} in plaintext instead", e, name);
+            secret
+        }
+    }
+}
+
+/// Read a value from stdin: a hidden prompt on a terminal, otherwise the first line
+fn read_secret(key: &str) -> Result<String> {
+    use std::io::IsTerminal;
+
+    if std::io::stdin().is_terminal() {
+        return dialoguer::Password::new()
+            .with_prompt(key)
+            .interact()
+            .map_err(|e| MuesliError::Config(e.to_string()));
+    }
+
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line)?;
+    let value = line.trim_end_matches(['\r', '\n']).to_string();
+    if value.is_empty() {
+        return Err(MuesliError::Usage(format!("No value for {} on stdin", key)));
+    }
+    Ok(value)
+}
+
+fn handle_config_edit(validate: bool) -> Result<()> {
+    use dialoguer::{theme::ColorfulTheme, Confirm};
+
+    let path = config::loader::config_path()?;
+    let backup = config::loader::config_backup_path()?;
+    let editor = std::env::var("EDITOR").unwrap_or_else(|_| "nano".to_string());
+
+    // Remember the last-known-good config before touching it
+    if config::loader::check_config_file(&path).is_ok() {
+        std::fs::copy(&path, &backup)?;
+    }
+
+    loop {
+        std::process::Command::new(&editor).arg(&path).status()?;
+
+        let error = match config::loader::check_config_file(&path) {
+            Ok(_) => {
+                std::fs::copy(&path, &backup)?;
+                return Ok(());
+            }
+            Err(e) => e,
+        };
+
+        eprintln!("\nConfig error: {}", error);
+        if !validate {
+            eprintln!("Fix it with: muesli config edit --validate");
+            return Ok(());
+        }
+
+        let reopen = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Reopen the editor to fix it?")
+            .default(true)
+            .interact()
+            .map_err(|e| MuesliError::Config(e.to_string()))?;
+        if reopen {
+            continue;
+        }
+
+        if backup.exists() {
+            std::fs::copy(&backup, &path)?;
+            eprintln!("Restored the last working config from {}", backup.display());
+        } else {
+            eprintln!(
+                "No working config backup found; {} was left as is",
+                path.display()
+            );
+        }
+        return Err(error);
+    }
+}
+
+async fn handle_models(engine: ModelEngine) -> Result<()> {
+    crate::interrupt::install();
+    match engine {
+        ModelEngine::List => handle_list_all_models(),
+        ModelEngine::Whisper { action } => handle_whisper_models(action).await,
+        ModelEngine::Diarization { action } => handle_diarization_models(action).await,
+    }
+}
+
+fn handle_list_all_models() -> Result<()> {
+    let models_dir = config::loader::models_dir()?;
+    let whisper = ModelManager::new(models_dir.clone());
+    let diarization = DiarizationModelManager::new(models_dir);
+
+    println!(
+        "{:<14} {:<20} {:<12} {:<10}",
+        "Type", "Model", "Size (MB)", "Downloaded"
+    );
+    println!("{}", "-".repeat(58));
+
+    let whisper_rows = whisper
+        .list_all()
+        .into_iter()
+        .map(|(model, exists, size)| ("whisper", model.to_string(), size, exists));
+    let diarization_rows = diarization
+        .list_all()
+        .into_iter()
+        .map(|(model, exists, size)| ("diarization", model.to_string(), size, exists));
+
+    for (kind, model, size, exists) in whisper_rows.chain(diarization_rows) {
+        let status = if exists { "✓" } else { "-" };
+        println!("{:<14} {:<20} {:<12} {:<10}", kind, model, size, status);
+    }
+
+    println!();
+    println!("Manage with: muesli models whisper|diarization download|delete <model>");
+    Ok(())
+}
+
+/// Download settings from `models ... download` flags, on top of the environment
+fn download_options(
+    max_rate: Option<f64>,
+    mirror: Option<String>,
+) -> crate::transcription::download::DownloadOptions {
+    crate::transcription::download::DownloadOptions::from_env()
+        .with_max_rate_mbps(max_rate)
+        .with_mirror(mirror)
+}
+
+async fn handle_whisper_models(action: ModelAction) -> Result<()> {
+    let models_dir = config::loader::models_dir()?;
+    let manager = ModelManager::new(models_dir);
+
+    match action {
+        ModelAction::List => {
+            println!("{:<10} {:<12} {:<10}", "Model", "Size (MB)", "Downloaded");
+            println!("{}", "-".repeat(35));
 
             for (model, exists, size) in manager.list_all() {
                 let status = if exists { "✓" } else { "-" };
                 println!("{:<10} {:<12} {:<10}", model, size, status);
             }
         }
-        ModelAction::Download { model } => {
+        ModelAction::Download {
+            model,
+            max_rate,
+            mirror,
+        } => {
+            let manager = manager.with_download_options(download_options(max_rate, mirror));
             let whisper_model = WhisperModel::parse(&model).ok_or_else(|| {
                 crate::error::MuesliError::Config(format!(
                     "Unknown model: {}. Use: tiny, base, small, medium, large, large-v3-turbo, distil-large-v3",
@@ -332,21 +1829,14 @@ async fn handle_whisper_models(action: ModelAction) -> Result<()> {
                 ))
             })?;
 
-            println!(
+            status!(
                 "Downloading {} model (~{} MB)...",
                 whisper_model,
                 whisper_model.size_mb()
             );
 
             let path = manager.download_model(whisper_model, |downloaded, total| {
-                let percent = (downloaded as f64 / total as f64 * 100.0) as u32;
-                print!(
-                    "\rProgress: {}% ({}/{} MB)",
-                    percent,
-                    downloaded / 1024 / 1024,
-                    total / 1024 / 1024
-                );
-                std::io::stdout().flush().ok();
+                output::download_progress("", downloaded, total)
             })?;
 
             println!("\nDownloaded to: {}", path.display());
@@ -359,6 +1849,120 @@ async fn handle_whisper_models(action: ModelAction) -> Result<()> {
             manager.delete_model(whisper_model)?;
             println!("Deleted {} model", model);
         }
+        ModelAction::Default { model } => {
+            let whisper_model = WhisperModel::parse(&model).ok_or_else(|| {
+                crate::error::MuesliError::Config(format!("Unknown model: {}", model))
+            })?;
+            if !manager.model_exists(whisper_model) {
+                return Err(MuesliError::Config(format!(
+                    "Whisper model {} is not downloaded. Run: muesli models whisper download {}",
+                    whisper_model, whisper_model
+                )));
+            }
+
+            let mut config = config::loader::load_config()?;
+            let before = format!(
+                "{} ({})",
+                config.transcription.effective_model(),
+                config.transcription.engine
+            );
+            config.transcription.engine = "whisper".to_string();
+            config.transcription.model = whisper_model.to_string();
+            config.transcription.whisper_model = None;
+            config.validate()?;
+            update_transcription_config("whisper", &whisper_model.to_string())?;
+
+            println!(
+                "Transcription model: {} -> {} (whisper)",
+                before, whisper_model
+            );
+        }
+        ModelAction::Verify { model } => {
+            let models = match model {
+                Some(name) => vec![WhisperModel::parse(&name).ok_or_else(|| {
+                    crate::error::MuesliError::Config(format!("Unknown model: {}", name))
+                })?],
+                None => manager.list_available(),
+            };
+
+            let mut failed = 0;
+            for whisper_model in models {
+                let verification = manager.verify_model(whisper_model)?;
+                if !report_verification(&whisper_model.to_string(), &verification) {
+                    continue;
+                }
+                manager.delete_model(whisper_model)?;
+                match manager.download_model(whisper_model, |downloaded, total| {
+                    output::download_progress("  ", downloaded, total)
+                }) {
+                    Ok(_) => println!("\n  {}: re-downloaded and verified", whisper_model),
+                    Err(e) => {
+                        eprintln!("\n  {}: re-download failed: {}", whisper_model, e);
+                        failed += 1;
+                    }
+                }
+            }
+            ensure_models_verified(failed)?;
+        }
+    }
+    Ok(())
+}
+
+/// Print one line for a model's verification result. Returns true when the
+/// file is corrupt and should be re-downloaded.
+fn report_verification(
+    name: &str,
+    verification: &crate::transcription::checksum::Verification,
+) -> bool {
+    use crate::transcription::checksum::Verification;
+
+    match verification {
+        Verification::Ok => {
+            println!("  {}: OK", name);
+            false
+        }
+        Verification::Missing => {
+            println!("  {}: not installed", name);
+            false
+        }
+        Verification::Unrecorded => {
+            println!(
+                "  {}: no checksum recorded (downloaded by an older version; delete and download again to verify)",
+                name
+            );
+            false
+        }
+        Verification::Mismatch { expected, actual } => {
+            println!(
+                "  {}: CORRUPT (expected {}, got {}), re-downloading...",
+                name, expected, actual
+            );
+            true
+        }
+        Verification::Truncated { expected, actual } => {
+            println!(
+                "  {}: TRUNCATED ({} of {} bytes), re-downloading...",
+                name, actual, expected
+            );
+            true
+        }
+        Verification::Incomplete { missing } => {
+            println!(
+                "  {}: INCOMPLETE (missing {}), re-downloading...",
+                name,
+                missing.join(", ")
+            );
+            true
+        }
+    }
+}
+
+fn ensure_models_verified(failed: usize) -> Result<()> {
+    if failed > 0 {
+        return Err(MuesliError::Api(format!(
+            "{} model(s) could not be repaired",
+            failed
+        )));
     }
     Ok(())
 }
@@ -377,7 +1981,12 @@ async fn handle_diarization_models(action: ModelAction) -> Result<()> {
                 println!("{:<20} {:<12} {:<10}", model, size, status);
             }
         }
-        ModelAction::Download { model } => {
+        ModelAction::Download {
+            model,
+            max_rate,
+            mirror,
+        } => {
+            let manager = manager.with_download_options(download_options(max_rate, mirror));
             let diar_model = DiarizationModel::parse(&model).ok_or_else(|| {
                 crate::error::MuesliError::Config(format!(
                     "Unknown model: {}. Use: sortformer-v2",
@@ -385,7 +1994,7 @@ async fn handle_diarization_models(action: ModelAction) -> Result<()> {
                 ))
             })?;
 
-            println!(
+            status!(
                 "Downloading {} (~{} MB)...",
                 diar_model,
                 diar_model.size_mb()
@@ -393,14 +2002,7 @@ async fn handle_diarization_models(action: ModelAction) -> Result<()> {
 
             let path = tokio::task::spawn_blocking(move || {
                 manager.download_model(diar_model, |downloaded, total| {
-                    let percent = (downloaded as f64 / total as f64 * 100.0) as u32;
-                    print!(
-                        "\rProgress: {}% ({}/{} MB)",
-                        percent,
-                        downloaded / 1024 / 1024,
-                        total / 1024 / 1024
-                    );
-                    std::io::stdout().flush().ok();
+                    output::download_progress("", downloaded, total)
                 })
             })
             .await
@@ -418,61 +2020,467 @@ async fn handle_diarization_models(action: ModelAction) -> Result<()> {
             manager.delete_model(diar_model)?;
             println!("Deleted {} model", model);
         }
+        ModelAction::Default { .. } => {
+            return Err(MuesliError::Usage(
+                "Diarization always uses the installed sortformer-v2 model; there is no default to choose"
+                    .to_string(),
+            ));
+        }
+        ModelAction::Verify { model } => {
+            let models = match model {
+                Some(name) => vec![DiarizationModel::parse(&name).ok_or_else(|| {
+                    crate::error::MuesliError::Config(format!("Unknown model: {}", name))
+                })?],
+                // Partly installed models too: a missing or truncated file is what verify repairs
+                None => DiarizationModel::all()
+                    .iter()
+                    .copied()
+                    .filter(|m| manager.has_any_files(*m))
+                    .collect(),
+            };
+
+            let failed = tokio::task::spawn_blocking(move || -> Result<usize> {
+                let mut failed = 0;
+                for diar_model in models {
+                    let verification = manager.verify_model(diar_model)?;
+                    if !report_verification(&diar_model.to_string(), &verification) {
+                        continue;
+                    }
+                    // Complete files of a partial bundle are kept; only the rest is fetched
+                    if !matches!(
+                        verification,
+                        crate::transcription::checksum::Verification::Incomplete { .. }
+                    ) {
+                        manager.delete_model(diar_model)?;
+                    }
+                    match manager.download_model(diar_model, |downloaded, total| {
+                        output::download_progress("  ", downloaded, total)
+                    }) {
+                        Ok(_) => println!("\n  {}: re-downloaded and verified", diar_model),
+                        Err(e) => {
+                            eprintln!("\n  {}: re-download failed: {}", diar_model, e);
+                            failed += 1;
+                        }
+                    }
+                }
+                Ok(failed)
+            })
+            .await
+            .map_err(|e| {
+                crate::error::MuesliError::Config(format!("Verify task failed: {}", e))
+            })??;
+            ensure_models_verified(failed)?;
+        }
+    }
+    Ok(())
+}
+
+async fn handle_audio(action: AudioCommands) -> Result<()> {
+    match action {
+        AudioCommands::Normalize {
+            id,
+            in_place,
+            output,
+        } => handle_audio_normalize(id, in_place, output)?,
+        AudioCommands::Concat { ids, into } => handle_audio_concat(ids, into)?,
+        AudioCommands::ListDevices => {
+            println!("Input Devices (Microphones):");
+            println!("{}", "-".repeat(50));
+
+            let host = cpal::default_host();
+            if let Ok(devices) = host.input_devices() {
+                for device in devices {
+                    if let Ok(name) = device.name() {
+                        if let Ok(config) = device.default_input_config() {
+                            let sample_rate = config.sample_rate().0;
+                            let channels = config.channels();
+                            println!("  {} ({}Hz, {} ch)", name, sample_rate, channels);
+                        }
+                    }
+                }
+            }
+
+            println!("\nLoopback Devices (System Audio):");
+            println!("{}", "-".repeat(50));
+
+            if let Ok(devices) = host.input_devices() {
+                for device in devices {
+                    if let Ok(name) = device.name() {
+                        let name_lower = name.to_lowercase();
+                        if name_lower.contains("monitor") || name_lower.contains("loopback") {
+                            if let Ok(config) = device.default_input_config() {
+                                let sample_rate = config.sample_rate().0;
+                                let channels = config.channels();
+                                println!("  {} ({}Hz, {} ch)", name, sample_rate, channels);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Cloud LLM providers the setup wizard offers, after LM Studio
+struct CloudProvider {
+    id: &'static str,
+    name: &'static str,
+    menu: &'static str,
+    default_model: &'static str,
+}
+
+const CLOUD_PROVIDERS: &[CloudProvider] = &[
+    CloudProvider {
+        id: "anthropic",
+        name: "Anthropic",
+        menu: "Anthropic (Claude)",
+        default_model: "claude-sonnet-4-20250514",
+    },
+    CloudProvider {
+        id: "openai",
+        name: "OpenAI",
+        menu: "OpenAI (GPT)",
+        default_model: "gpt-4o",
+    },
+    CloudProvider {
+        id: "moonshot",
+        name: "Moonshot (Kimi)",
+        menu: "Moonshot (Kimi)",
+        default_model: "kimi-k2.5",
+    },
+    CloudProvider {
+        id: "openrouter",
+        name: "OpenRouter",
+        menu: "OpenRouter (multi-provider)",
+        default_model: "anthropic/claude-sonnet-4",
+    },
+];
+
+/// Every choice the setup wizard makes. `--noninteractive` resolves them all
+/// from flags before anything is written; the wizard asks for each one right
+/// before its step. Either way the steps below do the work.
+struct SetupChoices {
+    use_gpu: bool,
+    /// `None` skips the download
+    whisper_model: Option<WhisperModel>,
+    diarization: bool,
+    llm: LlmSetup,
+    llm_tuning: LlmTuning,
+    auto_prompt: bool,
+    audio_cues: bool,
+    install_service: bool,
+    qmd: bool,
+    start_daemon: bool,
+}
+
+enum LlmSetup {
+    Disabled,
+    /// LM Studio; without a model, the first one it has downloaded
+    Local {
+        model: Option<String>,
+    },
+    Cloud {
+        provider: &'static CloudProvider,
+        model: String,
+        api_key: String,
+    },
+}
+
+/// Advanced `[llm]` request options; `None` leaves the built-in default
+#[derive(Default)]
+struct LlmTuning {
+    temperature: Option<f32>,
+    max_tokens: Option<u32>,
+}
+
+impl SetupChoices {
+    fn from_args(args: &SetupArgs) -> Result<Self> {
+        let whisper_model = match args.model.as_str() {
+            "none" => None,
+            name => Some(WhisperModel::parse(name).ok_or_else(|| {
+                MuesliError::Usage(format!(
+                    "--model {} is not a Whisper model (see: muesli models whisper list) or none",
+                    name
+                ))
+            })?),
+        };
+
+        let llm = match args.llm_provider.as_str() {
+            "none" => LlmSetup::Disabled,
+            "local" => LlmSetup::Local {
+                model: args.llm_model.clone(),
+            },
+            id => {
+                let provider = CLOUD_PROVIDERS.iter().find(|p| p.id == id).ok_or_else(|| {
+                    MuesliError::Usage(format!(
+                        "--llm-provider {} is not supported (local, anthropic, openai, moonshot, openrouter or none)",
+                        id
+                    ))
+                })?;
+                let env_var = format!("MUESLI_{}_API_KEY", provider.id.to_uppercase());
+                let api_key = std::env::var(&env_var)
+                    .ok()
+                    .filter(|key| !key.trim().is_empty())
+                    .ok_or_else(|| {
+                        MuesliError::Usage(format!(
+                            "--llm-provider {} needs the API key in {}",
+                            id, env_var
+                        ))
+                    })?;
+                LlmSetup::Cloud {
+                    provider,
+                    model: args
+                        .llm_model
+                        .clone()
+                        .unwrap_or_else(|| provider.default_model.to_string()),
+                    api_key,
+                }
+            }
+        };
+
+        Ok(Self {
+            use_gpu: args.gpu,
+            whisper_model,
+            diarization: !args.no_diarization,
+            llm,
+            llm_tuning: LlmTuning {
+                temperature: args.llm_temperature,
+                max_tokens: args.llm_max_tokens,
+            },
+            auto_prompt: !args.no_auto_prompt,
+            audio_cues: args.audio_cues,
+            install_service: args.install_service,
+            qmd: args.qmd,
+            start_daemon: !args.no_start,
+        })
+    }
+}
+
+async fn handle_setup(args: SetupArgs) -> Result<()> {
+    use dialoguer::{theme::ColorfulTheme, Confirm, Select};
+
+    // Resolved before the first step, so a bad flag leaves nothing half set up
+    let choices = if args.noninteractive {
+        Some(SetupChoices::from_args(&args)?)
+    } else {
+        None
+    };
+    let confirm = |prompt: &str, default: bool| {
+        Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .default(default)
+            .interact()
+            .unwrap_or(false)
+    };
+
+    println!();
+    println!("==========================================");
+    println!("  muesli Setup Wizard");
+    println!("==========================================");
+    println!();
+
+    println!("[1/11] Creating directories...");
+    let models_dir = setup_directories()?;
+    println!();
+
+    println!("[2/11] Initializing configuration...");
+    setup_config_file()?;
+    println!();
+
+    println!("[3/11] GPU Acceleration");
+    println!("  GPU acceleration provides faster transcription.");
+    println!();
+    let use_gpu = match &choices {
+        Some(c) => c.use_gpu,
+        None => confirm(
+            "Enable GPU acceleration? (requires Vulkan/CUDA/Metal)",
+            false,
+        ),
+    };
+    update_config_value("use_gpu", if use_gpu { "true" } else { "false" })?;
+    println!(
+        "  GPU acceleration: {}",
+        if use_gpu { "enabled" } else { "disabled" }
+    );
+    println!();
+
+    println!("[4/11] Transcription Model Selection");
+    println!();
+    let whisper_manager = ModelManager::new(models_dir.clone());
+    let whisper_model = match &choices {
+        Some(c) => c.whisper_model,
+        None => ask_whisper_model(&whisper_manager),
+    };
+    match whisper_model {
+        Some(model) => setup_whisper_model(&whisper_manager, model)?,
+        None => println!("  Skipping model download"),
+    }
+    println!();
+
+    println!("[5/11] Speaker Diarization Model");
+    let diar_manager = DiarizationModelManager::new(models_dir.clone());
+    if diar_manager.model_exists(DiarizationModel::SortformerV2) {
+        println!("  Diarization model already installed");
+    } else {
+        let download = match &choices {
+            Some(c) => c.diarization,
+            None => confirm(
+                "Download speaker diarization model (sortformer-v2, ~127 MB)?",
+                true,
+            ),
+        };
+        if download {
+            setup_diarization_model(diar_manager).await?;
+        } else {
+            println!("  Skipping diarization model");
+            println!("  (You can download later with: muesli diarization download sortformer-v2)");
+        }
     }
-    Ok(())
-}
+    println!();
 
-async fn handle_audio(action: AudioCommands) -> Result<()> {
-    match action {
-        AudioCommands::ListDevices => {
-            println!("Input Devices (Microphones):");
-            println!("{}", "-".repeat(50));
+    println!("[6/11] Faster Processing on Stop");
+    println!("  muesli transcribes incrementally with Whisper while recording.");
+    println!("  This reduces wait time after stopping for long meetings.");
+    println!();
 
-            let host = cpal::default_host();
-            if let Ok(devices) = host.input_devices() {
-                for device in devices {
-                    if let Ok(name) = device.name() {
-                        if let Ok(config) = device.default_input_config() {
-                            let sample_rate = config.sample_rate().0;
-                            let channels = config.channels();
-                            println!("  {} ({}Hz, {} ch)", name, sample_rate, channels);
-                        }
-                    }
-                }
+    println!("[7/11] LLM for Meeting Notes");
+    println!("  An LLM generates meeting summaries and notes from transcripts.");
+    println!();
+    let asked;
+    let llm = match choices.as_ref().map(|c| &c.llm) {
+        Some(llm) => llm,
+        None => {
+            let mut provider_options = vec!["LM Studio (local, free)"];
+            provider_options.extend(CLOUD_PROVIDERS.iter().map(|p| p.menu));
+            provider_options.push("Skip LLM setup");
+
+            let provider_selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Select an LLM provider")
+                .items(&provider_options)
+                .default(0)
+                .interact()
+                .unwrap_or(provider_options.len() - 1);
+
+            asked = match provider_selection {
+                0 => ask_local_llm(),
+                n if n <= CLOUD_PROVIDERS.len() => ask_cloud_provider(&CLOUD_PROVIDERS[n - 1]),
+                _ => LlmSetup::Disabled,
+            };
+            &asked
+        }
+    };
+    setup_llm(llm)?;
+    if !matches!(llm, LlmSetup::Disabled) {
+        let asked;
+        let tuning = match choices.as_ref().map(|c| &c.llm_tuning) {
+            Some(tuning) => tuning,
+            None => {
+                asked = if confirm("Set advanced LLM options (temperature, max tokens)?", false) {
+                    ask_llm_tuning()
+                } else {
+                    LlmTuning::default()
+                };
+                &asked
             }
+        };
+        setup_llm_tuning(tuning)?;
+    }
+    println!();
 
-            println!("\nLoopback Devices (System Audio):");
-            println!("{}", "-".repeat(50));
+    println!("[8/11] Meeting Detection");
+    println!("  Auto-detection monitors your windows for meeting apps (Zoom, Meet, Teams, etc.)");
+    println!("  When detected, a notification prompt asks if you want to record.");
+    println!();
+    let auto_prompt = match &choices {
+        Some(c) => c.auto_prompt,
+        None => confirm(
+            "Enable automatic meeting detection and recording prompts?",
+            true,
+        ),
+    };
+    update_config_value("auto_prompt", if auto_prompt { "true" } else { "false" })?;
+    update_config_value("prompt_timeout_secs", "30")?;
+    println!(
+        "  Meeting auto-detection: {}",
+        if auto_prompt { "enabled" } else { "disabled" }
+    );
+    println!();
 
-            if let Ok(devices) = host.input_devices() {
-                for device in devices {
-                    if let Ok(name) = device.name() {
-                        let name_lower = name.to_lowercase();
-                        if name_lower.contains("monitor") || name_lower.contains("loopback") {
-                            if let Ok(config) = device.default_input_config() {
-                                let sample_rate = config.sample_rate().0;
-                                let channels = config.channels();
-                                println!("  {} ({}Hz, {} ch)", name, sample_rate, channels);
-                            }
-                        }
-                    }
-                }
-            }
+    println!("[9/11] Audio Cues");
+    println!("  Play a sound when recording starts and stops.");
+    println!();
+    let enable_audio_cues = match &choices {
+        Some(c) => c.audio_cues,
+        None => confirm("Enable audio cues for recording start/stop?", false),
+    };
+    update_audio_cues_config(enable_audio_cues)?;
+    println!(
+        "  Audio cues: {}",
+        if enable_audio_cues {
+            "enabled"
+        } else {
+            "disabled"
         }
-    }
-    Ok(())
-}
+    );
+    println!();
 
-async fn handle_setup() -> Result<()> {
-    use dialoguer::{theme::ColorfulTheme, Confirm, Select};
+    println!("[10/11] Systemd Service");
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+    let systemd_dir = std::path::PathBuf::from(&home).join(".config/systemd/user");
+    let service_path = systemd_dir.join("muesli.service");
+    if service_path.exists() {
+        println!("  Service already installed at {}", service_path.display());
+    } else {
+        let install_service = match &choices {
+            Some(c) => c.install_service,
+            None => confirm("Install systemd user service for auto-start?", true),
+        };
+        if install_service {
+            install_systemd_service(&systemd_dir, &service_path)?;
+        } else {
+            println!("  Skipping systemd service installation");
+        }
+    }
+    println!();
 
+    println!("[11/11] qmd Search Integration");
+    println!("  qmd enables AI-powered semantic search across all your meeting notes.");
+    println!();
+    let enable_qmd = match &choices {
+        Some(c) => c.qmd,
+        None => confirm("Enable qmd search for meeting notes?", true),
+    };
+    setup_qmd(enable_qmd)?;
     println!();
+
     println!("==========================================");
-    println!("  muesli Setup Wizard");
+    println!("  Setup Complete!");
     println!("==========================================");
     println!();
 
-    println!("[1/11] Creating directories...");
+    if choices.as_ref().map(|c| c.start_daemon).unwrap_or(true) {
+        start_daemon_process();
+    }
+
+    println!();
+    println!("Tips:");
+    println!();
+    println!("  - Auto-start on login:");
+    println!("    systemctl --user enable --now muesli.service");
+    println!();
+    println!("  - Test audio devices:");
+    println!("    muesli audio list-devices");
+    println!();
+    println!("  - Edit configuration:");
+    println!("    muesli config edit");
+    println!();
+
+    Ok(())
+}
+
+/// Setup step 1. Returns the models directory.
+fn setup_directories() -> Result<std::path::PathBuf> {
     config::loader::ensure_directories()?;
     let config_dir = config::loader::config_dir()?;
     let data_dir = config::loader::data_dir()?;
@@ -480,9 +2488,11 @@ async fn handle_setup() -> Result<()> {
     println!("  Config: {}", config_dir.display());
     println!("  Data:   {}", data_dir.display());
     println!("  Models: {}", models_dir.display());
-    println!();
+    Ok(models_dir)
+}
 
-    println!("[2/11] Initializing configuration...");
+/// Setup step 2: write the default config unless there is one
+fn setup_config_file() -> Result<()> {
     let config_path = config::loader::config_path()?;
     if config_path.exists() {
         println!(
@@ -496,45 +2506,26 @@ async fn handle_setup() -> Result<()> {
             config_path.display()
         );
     }
-    println!();
-
-    println!("[3/11] GPU Acceleration");
-    println!("  GPU acceleration provides faster transcription.");
-    println!();
-
-    let use_gpu = Confirm::with_theme(&ColorfulTheme::default())
-        .with_prompt("Enable GPU acceleration? (requires Vulkan/CUDA/Metal)")
-        .default(false)
-        .interact()
-        .unwrap_or(false);
-
-    update_config_value("use_gpu", if use_gpu { "true" } else { "false" })?;
-    println!(
-        "  GPU acceleration: {}",
-        if use_gpu { "enabled" } else { "disabled" }
-    );
-    println!();
-
-    println!("[4/11] Transcription Model Selection");
-    println!();
+    Ok(())
+}
 
-    let whisper_models = vec![
-        ("tiny", 75, "Fastest, lowest accuracy"),
-        ("base", 142, "Good balance (recommended)"),
-        ("small", 466, "Better accuracy"),
-        ("medium", 1500, "High accuracy"),
-        ("large", 2900, "Best accuracy"),
-        ("large-v3-turbo", 1620, "Fast + high quality"),
-    ];
+const SETUP_WHISPER_MODELS: &[(&str, u64, &str)] = &[
+    ("tiny", 75, "Fastest, lowest accuracy"),
+    ("base", 142, "Good balance (recommended)"),
+    ("small", 466, "Better accuracy"),
+    ("medium", 1500, "High accuracy"),
+    ("large", 2900, "Best accuracy"),
+    ("large-v3-turbo", 1620, "Fast + high quality"),
+];
 
-    let whisper_manager = ModelManager::new(models_dir.clone());
+fn ask_whisper_model(manager: &ModelManager) -> Option<WhisperModel> {
+    use dialoguer::{theme::ColorfulTheme, Select};
 
     let mut model_options: Vec<String> = vec![];
-
     model_options.push("--- Whisper Models (whisper.cpp) ---".to_string());
-    for (name, size, desc) in &whisper_models {
+    for (name, size, desc) in SETUP_WHISPER_MODELS {
         let model = WhisperModel::parse(name).unwrap();
-        let installed = if whisper_manager.model_exists(model) {
+        let installed = if manager.model_exists(model) {
             " [installed]"
         } else {
             ""
@@ -544,7 +2535,6 @@ async fn handle_setup() -> Result<()> {
             name, size, desc, installed
         ));
     }
-
     model_options.push("Skip model download".to_string());
 
     let selection = Select::with_theme(&ColorfulTheme::default())
@@ -554,299 +2544,255 @@ async fn handle_setup() -> Result<()> {
         .interact()
         .unwrap_or(model_options.len() - 1);
 
-    if selection == 0 || selection == 7 {
-        println!("  Skipping model download");
-    } else if (1..=6).contains(&selection) {
-        let model_name = whisper_models[selection - 1].0;
-        let model = WhisperModel::parse(model_name).unwrap();
-
-        if whisper_manager.model_exists(model) {
-            println!("  Model '{}' is already installed", model_name);
-        } else {
-            println!("  Downloading {} model...", model_name);
-            let path = whisper_manager.download_model(model, |downloaded, total| {
-                let percent = (downloaded as f64 / total as f64 * 100.0) as u32;
-                print!(
-                    "\r  Progress: {}% ({}/{} MB)    ",
-                    percent,
-                    downloaded / 1024 / 1024,
-                    total / 1024 / 1024
-                );
-                std::io::stdout().flush().ok();
-            })?;
-            println!("\n  Downloaded to: {}", path.display());
-        }
+    // 0 is the group header, the last entry is "Skip"
+    SETUP_WHISPER_MODELS
+        .get(selection.checked_sub(1)?)
+        .and_then(|(name, _, _)| WhisperModel::parse(name))
+}
 
-        update_transcription_config("whisper", model_name)?;
+/// Setup step 4: download `model` if needed and transcribe with it
+fn setup_whisper_model(manager: &ModelManager, model: WhisperModel) -> Result<()> {
+    if manager.model_exists(model) {
+        println!("  Model '{}' is already installed", model);
+    } else {
+        println!("  Downloading {} model...", model);
+        let path = manager.download_model(model, |downloaded, total| {
+            output::download_progress("  ", downloaded, total)
+        })?;
+        println!("\n  Downloaded to: {}", path.display());
     }
-    println!();
+    update_transcription_config("whisper", &model.to_string())
+}
 
-    println!("[5/11] Speaker Diarization Model");
-    let diar_manager = DiarizationModelManager::new(models_dir.clone());
-    let diar_model = DiarizationModel::SortformerV2;
+/// Setup step 5
+async fn setup_diarization_model(manager: DiarizationModelManager) -> Result<()> {
+    println!("  Downloading sortformer-v2...");
+    let path = tokio::task::spawn_blocking(move || {
+        manager.download_model(DiarizationModel::SortformerV2, |downloaded, total| {
+            output::download_progress("  ", downloaded, total)
+        })
+    })
+    .await
+    .map_err(|e| crate::error::MuesliError::Config(format!("Download failed: {}", e)))??;
+    println!("\n  Downloaded to: {}", path.display());
+    Ok(())
+}
 
-    if diar_manager.model_exists(diar_model) {
-        println!("  Diarization model already installed");
-    } else {
-        let download_diar = Confirm::with_theme(&ColorfulTheme::default())
-            .with_prompt("Download speaker diarization model (sortformer-v2, ~127 MB)?")
-            .default(true)
-            .interact()
-            .unwrap_or(false);
+fn ask_local_llm() -> LlmSetup {
+    use dialoguer::{theme::ColorfulTheme, Select};
 
-        if download_diar {
-            println!("  Downloading sortformer-v2...");
-            let path = tokio::task::spawn_blocking(move || {
-                diar_manager.download_model(diar_model, |downloaded, total| {
-                    let percent = (downloaded as f64 / total as f64 * 100.0) as u32;
-                    print!(
-                        "\r  Progress: {}% ({}/{} MB)    ",
-                        percent,
-                        downloaded / 1024 / 1024,
-                        total / 1024 / 1024
-                    );
-                    std::io::stdout().flush().ok();
-                })
-            })
-            .await
-            .map_err(|e| crate::error::MuesliError::Config(format!("Download failed: {}", e)))??;
-            println!("\n  Downloaded to: {}", path.display());
-        } else {
-            println!("  Skipping diarization model");
-            println!("  (You can download later with: muesli diarization download sortformer-v2)");
-        }
+    let Some(lms) = find_lms_binary() else {
+        return LlmSetup::Local { model: None };
+    };
+    println!("  Found LM Studio CLI at: {}", lms);
+    let models = discover_lms_models(&lms);
+    if models.is_empty() {
+        return LlmSetup::Local { model: None };
     }
+    println!("  Found {} LLM model(s)", models.len());
     println!();
 
-    println!("[6/11] Faster Processing on Stop");
-    println!("  muesli transcribes incrementally with Whisper while recording.");
-    println!("  This reduces wait time after stopping for long meetings.");
-    println!();
-
-    println!("[7/11] LLM for Meeting Notes");
-    println!("  An LLM generates meeting summaries and notes from transcripts.");
-    println!();
-
-    let provider_options = vec![
-        "LM Studio (local, free)",
-        "Anthropic (Claude)",
-        "OpenAI (GPT)",
-        "Moonshot (Kimi)",
-        "OpenRouter (multi-provider)",
-        "Skip LLM setup",
-    ];
+    let mut options: Vec<String> = models.clone();
+    options.push("Skip LLM setup".to_string());
 
-    let provider_selection = Select::with_theme(&ColorfulTheme::default())
-        .with_prompt("Select an LLM provider")
-        .items(&provider_options)
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Select a model")
+        .items(&options)
         .default(0)
         .interact()
-        .unwrap_or(provider_options.len() - 1);
+        .unwrap_or(options.len() - 1);
 
-    match provider_selection {
-        0 => {
-            let lms_path = find_lms_binary();
-            if let Some(ref lms) = lms_path {
-                println!("  Found LM Studio CLI at: {}", lms);
-
-                let models = discover_lms_models(lms);
-
-                if !models.is_empty() {
-                    println!("  Found {} LLM model(s)", models.len());
-                    println!();
-
-                    let mut options: Vec<String> = models.clone();
-                    options.push("Skip LLM setup".to_string());
-
-                    let selection = Select::with_theme(&ColorfulTheme::default())
-                        .with_prompt("Select a model")
-                        .items(&options)
-                        .default(0)
-                        .interact()
-                        .unwrap_or(options.len() - 1);
+    match models.get(selection) {
+        Some(model) => LlmSetup::Local {
+            model: Some(model.clone()),
+        },
+        None => LlmSetup::Disabled,
+    }
+}
 
-                    if selection < models.len() {
-                        update_llm_config("local", &models[selection], None)?;
-                        println!("  LLM configured: {} (via LM Studio)", models[selection]);
-                    } else {
-                        update_llm_config("none", "", None)?;
-                        println!("  LLM disabled");
-                    }
-                } else {
+/// Setup step 7: point `[llm]` at the chosen provider
+fn setup_llm(llm: &LlmSetup) -> Result<()> {
+    match llm {
+        LlmSetup::Disabled => {
+            update_llm_config("none", "", None)?;
+            println!("  LLM disabled");
+        }
+        LlmSetup::Local { model } => {
+            let Some(lms) = find_lms_binary() else {
+                println!("  LM Studio not found.");
+                println!("  Install from https://lmstudio.ai for local LLM support.");
+                update_llm_config("none", "", None)?;
+                return Ok(());
+            };
+            let model = match model {
+                Some(model) => Some(model.clone()),
+                None => discover_lms_models(&lms).into_iter().next(),
+            };
+            match model {
+                Some(model) => {
+                    update_llm_config("local", &model, None)?;
+                    println!("  LLM configured: {} (via LM Studio)", model);
+                }
+                None => {
                     println!("  No LLM models found in LM Studio.");
                     println!("  Download a model in LM Studio first, then run setup again.");
                     update_llm_config("none", "", None)?;
                 }
-            } else {
-                println!("  LM Studio not found.");
-                println!("  Install from https://lmstudio.ai for local LLM support.");
-                update_llm_config("none", "", None)?;
             }
         }
-        1 => setup_cloud_provider("anthropic", "Anthropic", "claude-sonnet-4-20250514")?,
-        2 => setup_cloud_provider("openai", "OpenAI", "gpt-4o")?,
-        3 => setup_cloud_provider("moonshot", "Moonshot (Kimi)", "kimi-k2.5")?,
-        4 => setup_cloud_provider("openrouter", "OpenRouter", "anthropic/claude-sonnet-4")?,
-        _ => {
-            update_llm_config("none", "", None)?;
-            println!("  LLM disabled");
-        }
-    }
-    println!();
-
-    println!("[8/11] Meeting Detection");
-    println!("  Auto-detection monitors your windows for meeting apps (Zoom, Meet, Teams, etc.)");
-    println!("  When detected, a notification prompt asks if you want to record.");
-    println!();
-
-    let auto_prompt = Confirm::with_theme(&ColorfulTheme::default())
-        .with_prompt("Enable automatic meeting detection and recording prompts?")
-        .default(true)
-        .interact()
-        .unwrap_or(true);
-
-    update_config_value("auto_prompt", if auto_prompt { "true" } else { "false" })?;
-    update_config_value("prompt_timeout_secs", "30")?;
-    println!(
-        "  Meeting auto-detection: {}",
-        if auto_prompt { "enabled" } else { "disabled" }
-    );
-    println!();
-
-    println!("[9/11] Audio Cues");
-    println!("  Play a sound when recording starts and stops.");
-    println!();
-
-    let enable_audio_cues = Confirm::with_theme(&ColorfulTheme::default())
-        .with_prompt("Enable audio cues for recording start/stop?")
-        .default(false)
-        .interact()
-        .unwrap_or(false);
-
-    update_audio_cues_config(enable_audio_cues)?;
-    println!(
-        "  Audio cues: {}",
-        if enable_audio_cues {
-            "enabled"
-        } else {
-            "disabled"
-        }
-    );
-    println!();
+        LlmSetup::Cloud {
+            provider,
+            model,
+            api_key,
+        } => {
+            let use_keyring = config::loader::load_config()?.llm.use_keyring;
+            let api_key =
+                keyring_or_plaintext(use_keyring, "llm.api_key", api_key.trim().to_string());
+            update_llm_config(provider.id, model, Some(&api_key))?;
+            println!("  LLM configured: {} (model: {})", provider.name, model);
+        }
+    }
+    Ok(())
+}
 
-    println!("[10/11] Systemd Service");
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-    let systemd_dir = std::path::PathBuf::from(&home).join(".config/systemd/user");
+/// Ask for the advanced `[llm]` options; blank answers keep the defaults
+fn ask_llm_tuning() -> LlmTuning {
+    use dialoguer::{theme::ColorfulTheme, Input};
+
+    let temperature: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Temperature, 0-2 (blank = 0.3)")
+        .allow_empty(true)
+        .validate_with(|input: &String| match input.trim() {
+            "" => Ok(()),
+            t => match t.parse::<f32>() {
+                Ok(t) if (0.0..=2.0).contains(&t) => Ok(()),
+                _ => Err("enter a number from 0 to 2"),
+            },
+        })
+        .interact_text()
+        .unwrap_or_default();
+    let max_tokens: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Max response tokens (blank = provider default)")
+        .allow_empty(true)
+        .validate_with(|input: &String| match input.trim() {
+            "" => Ok(()),
+            n => n
+                .parse::<u32>()
+                .map(|_| ())
+                .map_err(|_| "enter a whole number"),
+        })
+        .interact_text()
+        .unwrap_or_default();
 
-    let service_path = systemd_dir.join("muesli.service");
+    LlmTuning {
+        temperature: temperature.trim().parse().ok(),
+        max_tokens: max_tokens.trim().parse().ok(),
+    }
+}
 
-    if service_path.exists() {
-        println!("  Service already installed at {}", service_path.display());
-    } else {
-        let install_service = Confirm::with_theme(&ColorfulTheme::default())
-            .with_prompt("Install systemd user service for auto-start?")
-            .default(true)
-            .interact()
-            .unwrap_or(false);
+/// Setup step 7, continued: write the options that were given to `[llm]`
+fn setup_llm_tuning(tuning: &LlmTuning) -> Result<()> {
+    if let Some(temperature) = tuning.temperature {
+        update_llm_value("temperature", &temperature.to_string())?;
+        println!("  Temperature: {}", temperature);
+    }
+    if let Some(max_tokens) = tuning.max_tokens {
+        update_llm_value("max_tokens", &max_tokens.to_string())?;
+        println!("  Max response tokens: {}", max_tokens);
+    }
+    Ok(())
+}
 
-        if install_service {
-            std::fs::create_dir_all(&systemd_dir)?;
+/// Setup step 10
+fn install_systemd_service(
+    systemd_dir: &std::path::Path,
+    service_path: &std::path::Path,
+) -> Result<()> {
+    std::fs::create_dir_all(systemd_dir)?;
 
-            let binary_path =
-                std::env::current_exe().unwrap_or_else(|_| std::path::PathBuf::from("muesli"));
+    let binary_path =
+        std::env::current_exe().unwrap_or_else(|_| std::path::PathBuf::from("muesli"));
 
-            let service_content = format!(
-                r#"[Unit]
+    let service_content = format!(
+        r#"[Unit]
 Description=muesli - AI-powered meeting note-taker
 Documentation=https://github.com/itsameandrea/muesli
 After=graphical-session.target
 
 [Service]
-Type=simple
+Type=notify
 ExecStart={} daemon
 Restart=on-failure
 RestartSec=5
+WatchdogSec=120
 
 [Install]
 WantedBy=default.target
 "#,
-                binary_path.display()
-            );
+        binary_path.display()
+    );
+
+    std::fs::write(service_path, service_content)?;
+    println!("  Service installed at {}", service_path.display());
+
+    let _ = std::process::Command::new("systemctl")
+        .args(["--user", "daemon-reload"])
+        .status();
 
-            std::fs::write(&service_path, service_content)?;
-            println!("  Service installed at {}", service_path.display());
+    println!("  To enable auto-start: systemctl --user enable muesli.service");
+    println!("  To start now:         systemctl --user start muesli.service");
+    Ok(())
+}
 
-            let _ = std::process::Command::new("systemctl")
-                .args(["--user", "daemon-reload"])
-                .status();
+/// Setup step 11: install qmd if needed and index the notes, or turn it off
+fn setup_qmd(enable: bool) -> Result<()> {
+    if !enable {
+        update_qmd_config(false, false, "muesli-meetings")?;
+        println!("  qmd search disabled.");
+        return Ok(());
+    }
 
-            println!("  To enable auto-start: systemctl --user enable muesli.service");
-            println!("  To start now:         systemctl --user start muesli.service");
+    if !crate::qmd::indexer::is_qmd_installed() {
+        println!();
+        println!("  qmd not found. Installing...");
+        if install_qmd() {
+            println!("  qmd installed!");
         } else {
-            println!("  Skipping systemd service installation");
+            println!("  Failed to install qmd automatically.");
+            println!("  Install manually: bun install -g github:tobi/qmd");
+            println!("  Then re-run: muesli setup");
+            update_qmd_config(false, false, "muesli-meetings")?;
         }
+    } else {
+        println!("  qmd detected!");
     }
-    println!();
 
-    println!("[11/11] qmd Search Integration");
-    println!("  qmd enables AI-powered semantic search across all your meeting notes.");
-    println!();
+    if crate::qmd::indexer::is_qmd_installed() {
+        println!();
+        update_qmd_config(true, true, "muesli-meetings")?;
 
-    let enable_qmd = Confirm::with_theme(&ColorfulTheme::default())
-        .with_prompt("Enable qmd search for meeting notes?")
-        .default(true)
-        .interact()
-        .unwrap_or(false);
+        let notes_dir = config::loader::notes_dir()?;
+        println!("  Setting up qmd collection...");
 
-    if enable_qmd {
-        if !crate::qmd::indexer::is_qmd_installed() {
-            println!();
-            println!("  qmd not found. Installing...");
-            if install_qmd() {
-                println!("  qmd installed!");
-            } else {
-                println!("  Failed to install qmd automatically.");
-                println!("  Install manually: bun install -g github:tobi/qmd");
-                println!("  Then re-run: muesli setup");
-                update_qmd_config(false, false, "muesli-meetings")?;
-            }
+        if let Err(e) = crate::qmd::indexer::setup_collection(&notes_dir, "muesli-meetings") {
+            println!("  Warning: Collection setup failed: {}", e);
+            println!("  You can retry later with: muesli search reindex");
         } else {
-            println!("  qmd detected!");
-        }
-
-        if crate::qmd::indexer::is_qmd_installed() {
-            println!();
-            update_qmd_config(true, true, "muesli-meetings")?;
+            println!("  Collection created: muesli-meetings");
 
-            let notes_dir = config::loader::notes_dir()?;
-            println!("  Setting up qmd collection...");
-
-            if let Err(e) = crate::qmd::indexer::setup_collection(&notes_dir, "muesli-meetings") {
-                println!("  Warning: Collection setup failed: {}", e);
+            println!("  Running initial index (this may take a moment)...");
+            if let Err(e) = crate::qmd::indexer::update_index("muesli-meetings") {
+                println!("  Warning: Initial indexing failed: {}", e);
                 println!("  You can retry later with: muesli search reindex");
             } else {
-                println!("  Collection created: muesli-meetings");
-
-                println!("  Running initial index (this may take a moment)...");
-                if let Err(e) = crate::qmd::indexer::update_index("muesli-meetings") {
-                    println!("  Warning: Initial indexing failed: {}", e);
-                    println!("  You can retry later with: muesli search reindex");
-                } else {
-                    println!("  Initial indexing complete!");
-                }
+                println!("  Initial indexing complete!");
             }
         }
-    } else {
-        update_qmd_config(false, false, "muesli-meetings")?;
-        println!("  qmd search disabled.");
     }
-    println!();
-
-    println!("==========================================");
-    println!("  Setup Complete!");
-    println!("==========================================");
-    println!();
+    Ok(())
+}
 
+fn start_daemon_process() {
     println!("Starting daemon...");
     let daemon_bin = std::env::current_exe().unwrap_or_else(|_| "muesli".into());
     match std::process::Command::new(&daemon_bin)
@@ -864,21 +2810,6 @@ WantedBy=default.target
             println!("  Start manually with: muesli daemon");
         }
     }
-
-    println!();
-    println!("Tips:");
-    println!();
-    println!("  - Auto-start on login:");
-    println!("    systemctl --user enable --now muesli.service");
-    println!();
-    println!("  - Test audio devices:");
-    println!("    muesli audio list-devices");
-    println!();
-    println!("  - Edit configuration:");
-    println!("    muesli config edit");
-    println!();
-
-    Ok(())
 }
 
 async fn handle_update() -> Result<()> {
@@ -896,16 +2827,13 @@ async fn handle_update() -> Result<()> {
         eprintln!("Falling back to latest GitHub release binary...");
         println!();
 
-        let install_path = std::env::current_exe().ok();
-        if let Some(path) = install_path {
-            if let Err(e) = update_from_latest_release(current_version, &path) {
-                eprintln!("Release update failed: {}", e);
-            }
-        } else {
-            eprintln!("Could not determine installed binary path for update");
-        }
-
-        return Ok(());
+        let path = std::env::current_exe().map_err(|e| {
+            MuesliError::Config(format!(
+                "Could not determine installed binary path for update: {}",
+                e
+            ))
+        })?;
+        return update_from_latest_release(current_version, &path);
     }
 
     let install_dir = std::env::current_exe()
@@ -922,8 +2850,7 @@ async fn handle_update() -> Result<()> {
         .map_err(|e| crate::error::MuesliError::Config(format!("Failed to run cargo: {}", e)))?;
 
     if !status.success() {
-        eprintln!("Build failed.");
-        return Ok(());
+        return Err(MuesliError::Config("Build failed".to_string()));
     }
 
     let built_binary = repo_dir.join("target/release/muesli");
@@ -932,14 +2859,20 @@ async fn handle_update() -> Result<()> {
         let dest = dir.join("muesli");
         let tmp = dir.join(".muesli.update.tmp");
         if let Err(e) = std::fs::copy(&built_binary, &tmp) {
-            eprintln!("Failed to copy to {}: {}", tmp.display(), e);
-            return Ok(());
+            return Err(MuesliError::Config(format!(
+                "Failed to copy to {}: {}",
+                tmp.display(),
+                e
+            )));
         }
         let _ = std::fs::remove_file(&dest);
         if let Err(e) = std::fs::rename(&tmp, &dest) {
-            eprintln!("Failed to install to {}: {}", dest.display(), e);
             let _ = std::fs::remove_file(&tmp);
-            return Ok(());
+            return Err(MuesliError::Config(format!(
+                "Failed to install to {}: {}",
+                dest.display(),
+                e
+            )));
         }
     }
 
@@ -993,11 +2926,12 @@ fn update_from_latest_release(current_version: &str, install_path: &std::path::P
             }
 
             let url = asset.get("browser_download_url").and_then(|v| v.as_str())?;
-            Some((name.to_string(), url.to_string()))
+            let size = asset.get("size").and_then(|v| v.as_u64());
+            Some((name.to_string(), url.to_string(), size))
         })
     });
 
-    let (asset_name, asset_url) = match selected {
+    let (asset_name, asset_url, asset_size) = match selected {
         Some(v) => v,
         None => {
             let available: Vec<String> = assets
@@ -1014,17 +2948,48 @@ fn update_from_latest_release(current_version: &str, install_path: &std::path::P
         }
     };
 
+    let checksum_url = assets.iter().find_map(|asset| {
+        let name = asset.get("name").and_then(|v| v.as_str())?;
+        if name != format!("{}.sha256", asset_name) {
+            return None;
+        }
+        asset
+            .get("browser_download_url")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+    });
+
+    let tmp_path = install_path.with_extension("update.tmp");
+    ensure_install_writable(install_path, &tmp_path)?;
+
     println!("Updating to {} using {}...", tag, asset_name);
 
     let client = reqwest::blocking::Client::new();
-    let bytes = client
-        .get(&asset_url)
-        .header(reqwest::header::USER_AGENT, "muesli-updater")
-        .send()?
-        .error_for_status()?
-        .bytes()?;
+    let bytes = download_with_retry(&client, &asset_url, asset_size)?;
+
+    match checksum_url {
+        Some(url) => {
+            let published = download_with_retry(&client, &url, None)?;
+            let expected = String::from_utf8_lossy(&published)
+                .split_whitespace()
+                .next()
+                .and_then(crate::transcription::checksum::parse_sha256)
+                .ok_or_else(|| MuesliError::Config(format!("Malformed checksum in {}", url)))?;
+            let actual = crate::transcription::checksum::sha256_bytes(&bytes);
+            if actual != expected {
+                return Err(MuesliError::Config(format!(
+                    "Checksum mismatch for {} (expected {}, got {}); the installed binary was not changed",
+                    asset_name, expected, actual
+                )));
+            }
+            println!("Checksum verified.");
+        }
+        None => tracing::debug!(
+            "No {}.sha256 asset published, skipping checksum",
+            asset_name
+        ),
+    }
 
-    let tmp_path = install_path.with_extension("update.tmp");
     std::fs::write(&tmp_path, &bytes)?;
 
     #[cfg(unix)]
@@ -1035,8 +3000,15 @@ fn update_from_latest_release(current_version: &str, install_path: &std::path::P
         std::fs::set_permissions(&tmp_path, perms)?;
     }
 
-    let _ = std::fs::remove_file(install_path);
-    std::fs::rename(&tmp_path, install_path)?;
+    // rename() replaces the old binary atomically, so a failure here leaves it intact
+    if let Err(e) = std::fs::rename(&tmp_path, install_path) {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(MuesliError::Config(format!(
+            "Failed to install to {}: {}",
+            install_path.display(),
+            e
+        )));
+    }
 
     let new_version = std::process::Command::new(install_path)
         .arg("--version")
@@ -1050,6 +3022,81 @@ fn update_from_latest_release(current_version: &str, install_path: &std::path::P
     Ok(())
 }
 
+const UPDATE_DOWNLOAD_ATTEMPTS: u32 = 3;
+
+/// Download a release asset, retrying with exponential backoff. A download
+/// whose length doesn't match the asset metadata counts as a failed attempt.
+fn download_with_retry(
+    client: &reqwest::blocking::Client,
+    url: &str,
+    expected_size: Option<u64>,
+) -> Result<Vec<u8>> {
+    let mut attempt = 1;
+    loop {
+        let result = client
+            .get(url)
+            .header(reqwest::header::USER_AGENT, "muesli-updater")
+            .send()
+            .and_then(|r| r.error_for_status())
+            .and_then(|r| r.bytes())
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| match expected_size {
+                Some(size) if bytes.len() as u64 != size => Err(format!(
+                    "incomplete download ({} of {} bytes)",
+                    bytes.len(),
+                    size
+                )),
+                _ => Ok(bytes.to_vec()),
+            });
+
+        match result {
+            Ok(bytes) => return Ok(bytes),
+            Err(e) if attempt < UPDATE_DOWNLOAD_ATTEMPTS => {
+                let delay = 1u64 << attempt;
+                eprintln!(
+                    "Download failed (attempt {}/{}): {}. Retrying in {}s...",
+                    attempt, UPDATE_DOWNLOAD_ATTEMPTS, e, delay
+                );
+                std::thread::sleep(std::time::Duration::from_secs(delay));
+                attempt += 1;
+            }
+            Err(e) => {
+                return Err(MuesliError::Config(format!(
+                    "Download failed after {} attempts: {}; the installed binary was not changed",
+                    UPDATE_DOWNLOAD_ATTEMPTS, e
+                )))
+            }
+        }
+    }
+}
+
+/// Fail before downloading if the new binary couldn't be put in place
+fn ensure_install_writable(
+    install_path: &std::path::Path,
+    tmp_path: &std::path::Path,
+) -> Result<()> {
+    match std::fs::write(tmp_path, b"") {
+        Ok(()) => {
+            let _ = std::fs::remove_file(tmp_path);
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            let dir = install_path
+                .parent()
+                .unwrap_or_else(|| std::path::Path::new("/"));
+            Err(MuesliError::Config(format!(
+                "No permission to write to {}. Re-run with sudo (sudo muesli update), or reinstall muesli to a directory you own (e.g. ~/.local/bin)",
+                dir.display()
+            )))
+        }
+        Err(e) => Err(MuesliError::Config(format!(
+            "Cannot write to {}: {}",
+            tmp_path.display(),
+            e
+        ))),
+    }
+}
+
 fn fetch_latest_release() -> Result<serde_json::Value> {
     let client = reqwest::blocking::Client::new();
     let response = client
@@ -1064,13 +3111,7 @@ fn fetch_latest_release() -> Result<serde_json::Value> {
 fn detect_release_asset_candidates() -> Vec<&'static str> {
     match (std::env::consts::OS, std::env::consts::ARCH) {
         ("linux", "x86_64") => {
-            if std::process::Command::new("vulkaninfo")
-                .stdout(std::process::Stdio::null())
-                .stderr(std::process::Stdio::null())
-                .status()
-                .map(|s| s.success())
-                .unwrap_or(false)
-            {
+            if crate::transcription::gpu::vulkan_available() {
                 vec!["linux-x86_64-vulkan", "linux-x86_64-cpu"]
             } else {
                 vec!["linux-x86_64-cpu", "linux-x86_64-vulkan"]
@@ -1229,39 +3270,327 @@ async fn handle_uninstall() -> Result<()> {
         if data_kept {
             println!("  rm -rf {}", data_dir.display());
         }
-        println!();
+        println!();
+    }
+
+    Ok(())
+}
+
+async fn handle_waybar() -> Result<()> {
+    let status = match DaemonClient::connect().await {
+        Ok(mut client) => match client.send(DaemonRequest::GetStatus).await? {
+            DaemonResponse::Status(status) => Some(status),
+            _ => None,
+        },
+        Err(_) => None,
+    };
+
+    let waybar_config = config::loader::load_config()
+        .map(|c| c.waybar)
+        .unwrap_or_default();
+    let waybar_status = WaybarStatus::from_daemon_status(status.as_ref()).with_text(&waybar_config);
+    println!("{}", waybar_status.to_json());
+    Ok(())
+}
+
+async fn handle_prompt(action: PromptCommands) -> Result<()> {
+    match action {
+        PromptCommands::Show { id } => handle_prompt_show(id).await,
+    }
+}
+
+async fn handle_prompt_show(id: Option<String>) -> Result<()> {
+    let config = config::loader::load_config()?;
+    let db_path = config::loader::database_path()?;
+    let db = Database::open(&db_path)?;
+
+    let meeting_id = match id {
+        Some(id) => id,
+        None => select_meeting_interactive(&db)?,
+    };
+
+    let meeting = db
+        .get_meeting(&MeetingId::from_string(meeting_id.clone()))?
+        .ok_or_else(|| crate::error::MuesliError::MeetingNotFound(meeting_id))?;
+
+    let segments = db.get_transcript_segments(&meeting.id)?;
+    if segments.is_empty() {
+        return Err(MuesliError::NoResults(format!(
+            "Meeting '{}' has no transcript",
+            meeting.title
+        )));
+    }
+
+    let transcript = crate::transcription::Transcript::new(segments);
+    let preview = crate::llm::preview_prompts(
+        &crate::pipeline::summary_llm_config(&meeting, &config.llm),
+        &transcript,
+        crate::pipeline::summary_template(&meeting),
+    );
+
+    // Metadata goes to stderr so stdout can be piped or diffed as the raw prompt
+    if !output::is_quiet() {
+        eprintln!(
+            "Provider: {} / {}",
+            config.llm.provider,
+            config.llm.effective_model()
+        );
+        eprintln!("Context limit: {} tokens", preview.context_limit);
+        eprintln!(
+            "Transcript budget: {} chars ({} tokens reserved for prompt, {} chars/token)",
+            preview.budget.max_transcript_chars(),
+            preview.budget.overhead_tokens,
+            preview.budget.chars_per_token
+        );
+        if preview.chunked {
+            eprintln!(
+                "Chunking: yes ({} chunks, followed by a synthesis pass over the chunk notes)",
+                preview.prompts.len()
+            );
+        } else {
+            eprintln!("Chunking: no");
+        }
+        eprintln!();
+    }
+
+    println!("=== SYSTEM ===");
+    println!("{}", preview.system_prompt);
+    for (i, prompt) in preview.prompts.iter().enumerate() {
+        println!();
+        if preview.chunked {
+            println!("=== CHUNK {}/{} ===", i + 1, preview.prompts.len());
+        } else {
+            println!("=== PROMPT ===");
+        }
+        println!("{}", prompt);
+    }
+
+    Ok(())
+}
+
+async fn handle_repair(id: Option<String>, timestamps: bool) -> Result<()> {
+    if !timestamps {
+        return Err(MuesliError::Usage(
+            "Nothing to repair. Usage: muesli repair [meeting-id] --timestamps".to_string(),
+        ));
+    }
+
+    let db_path = config::loader::database_path()?;
+    let db = Database::open(&db_path)?;
+
+    let meeting_id = match id {
+        Some(id) => id,
+        None => select_meeting_interactive(&db)?,
+    };
+
+    let meeting = db
+        .get_meeting(&MeetingId::from_string(meeting_id.clone()))?
+        .ok_or_else(|| crate::error::MuesliError::MeetingNotFound(meeting_id))?;
+
+    let segments = db.get_transcript_segments(&meeting.id)?;
+    let normalized = crate::transcription::normalize_timestamps(segments.clone());
+    let changed = segments
+        .iter()
+        .zip(&normalized)
+        .filter(|(a, b)| a.start_ms != b.start_ms || a.end_ms != b.end_ms)
+        .count();
+
+    if changed == 0 {
+        println!("Timestamps for '{}' are already consistent.", meeting.title);
+        return Ok(());
+    }
+
+    db.delete_transcript_segments(&meeting.id)?;
+    // Only the timestamps are repaired; segments were merged when first stored
+    db.insert_transcript_segments(&meeting.id, &normalized, 0)?;
+    println!(
+        "Fixed timestamps on {} of {} segments for '{}'.",
+        changed,
+        segments.len(),
+        meeting.title
+    );
+    println!("Run 'muesli redo {}' to regenerate the notes.", meeting.id);
+
+    Ok(())
+}
+
+async fn handle_doctor() -> Result<()> {
+    use crate::transcription::gpu;
+
+    let config = config::loader::load_config()?;
+    let check = |ok: bool| if ok { "✓" } else { "-" };
+
+    println!("muesli {}", env!("CARGO_PKG_VERSION"));
+    println!();
+
+    println!("Transcription");
+    println!("  Engine:           {}", config.transcription.engine);
+    println!(
+        "  Model:            {}",
+        config.transcription.effective_model()
+    );
+    println!("  use_gpu:          {}", config.transcription.use_gpu);
+    println!("  Built with:       {}", gpu::compiled_backend());
+    println!("  Vulkan device:    {}", check(gpu::vulkan_available()));
+    println!("  CUDA device:      {}", check(gpu::cuda_available()));
+    let backend = gpu::effective_backend(config.transcription.use_gpu);
+    println!("  Active backend:   {}", backend);
+    if config.transcription.use_gpu && !backend.is_gpu() {
+        println!("  Warning: GPU is enabled in config but unavailable; running on CPU");
+    }
+    println!();
+
+    println!("Daemon");
+    println!("  Running:          {}", check(DaemonClient::ping().await?));
+    println!("  LLM provider:     {}", config.llm.provider);
+    println!(
+        "  qmd:              {}",
+        if config.qmd.enabled {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+
+    Ok(())
+}
+
+async fn handle_backup(file: std::path::PathBuf) -> Result<()> {
+    if file.exists() {
+        return Err(MuesliError::Usage(format!(
+            "{} already exists; choose a new file",
+            file.display()
+        )));
+    }
+
+    let db_path = config::loader::database_path()?;
+    let db = Database::open(&db_path)?;
+    db.backup_to(&file)?;
+
+    println!("Database backed up to {}", file.display());
+    println!("Audio recordings and notes files are not included.");
+    Ok(())
+}
+
+fn handle_delete(id: Option<String>, keep_audio: bool, yes: bool) -> Result<()> {
+    use dialoguer::{theme::ColorfulTheme, Confirm};
+
+    let db = Database::open(config::loader::database_path()?)?;
+    let meeting_id = match id {
+        Some(id) => id,
+        None => select_meeting_interactive(&db)?,
+    };
+    let meeting = db
+        .get_meeting(&MeetingId::from_string(meeting_id.clone()))?
+        .ok_or_else(|| MuesliError::MeetingNotFound(meeting_id.clone()))?;
+    if meeting.status == crate::storage::MeetingStatus::Recording {
+        return Err(MuesliError::Usage(format!(
+            "Meeting {} is still recording; stop it first with: muesli stop",
+            meeting_id
+        )));
+    }
+
+    if !yes {
+        let what = if keep_audio {
+            "its notes and transcript"
+        } else {
+            "its recording, notes and transcript"
+        };
+        let proceed = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Delete '{}' ({}) with {}?",
+                meeting.title,
+                meeting.started_at.format("%Y-%m-%d %H:%M"),
+                what
+            ))
+            .default(false)
+            .interact()
+            .unwrap_or(false);
+
+        if !proceed {
+            println!("Delete cancelled.");
+            return Ok(());
+        }
+    }
+
+    let Some(_lock) = crate::pipeline::ProcessingLock::try_acquire(&meeting_id)? else {
+        return Err(MuesliError::Storage(format!(
+            "Meeting {} is being processed; try again when it finishes",
+            meeting_id
+        )));
+    };
+
+    let removed = crate::pipeline::delete_meeting(&db, &meeting, keep_audio)?;
+    println!("Deleted '{}' ({})", meeting.title, meeting.id);
+    for path in &removed {
+        status!("  Removed {}", path.display());
+    }
+    if let Some(audio) = meeting.audio_path.filter(|p| keep_audio && p.exists()) {
+        status!("  Kept {}", audio.display());
     }
 
+    // Drop the deleted notes from the search index too
+    let config = config::loader::load_config()?;
+    if config.qmd.enabled && meeting.notes_path.is_some_and(|p| removed.contains(&p)) {
+        if let Err(e) = crate::qmd::indexer::update_index(&config.qmd.collection_name) {
+            eprintln!(
+                "Warning: search index update failed ({}); retry with: muesli search reindex",
+                e
+            );
+        }
+    }
     Ok(())
 }
 
-async fn handle_waybar() -> Result<()> {
-    let mut client = match DaemonClient::connect().await {
-        Ok(c) => c,
-        Err(_) => {
-            println!("{}", WaybarStatus::idle().to_json());
-            return Ok(());
-        }
-    };
+async fn handle_restore(file: std::path::PathBuf, yes: bool) -> Result<()> {
+    use dialoguer::{theme::ColorfulTheme, Confirm};
 
-    match client.send(DaemonRequest::GetStatus).await? {
-        DaemonResponse::Status(status) => {
-            let waybar_status = if status.recording {
-                let title = status.current_meeting.as_deref().unwrap_or("Recording");
-                WaybarStatus::recording(title, status.uptime_seconds)
-            } else {
-                WaybarStatus::idle()
-            };
-            println!("{}", waybar_status.to_json());
-        }
-        _ => {
-            println!("{}", WaybarStatus::idle().to_json());
+    if !file.exists() {
+        return Err(MuesliError::Usage(format!(
+            "Backup file not found: {}",
+            file.display()
+        )));
+    }
+
+    let db_path = config::loader::database_path()?;
+
+    if !yes {
+        let proceed = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Replace the current database with {}?",
+                file.display()
+            ))
+            .default(false)
+            .interact()
+            .unwrap_or(false);
+
+        if !proceed {
+            println!("Restore cancelled.");
+            return Ok(());
         }
     }
+
+    let mut db = Database::open(&db_path)?;
+
+    let safety_copy = db_path.with_file_name(format!(
+        "muesli.pre-restore-{}.db",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    db.backup_to(&safety_copy)?;
+    println!("Current database saved to {}", safety_copy.display());
+
+    db.restore_from(&file)?;
+    println!("Database restored from {}", file.display());
     Ok(())
 }
 
-async fn handle_redo(id: Option<String>, clean: bool) -> Result<()> {
+async fn handle_redo(
+    id: Option<String>,
+    clean: bool,
+    template: Option<SummaryTemplate>,
+    lang: Option<String>,
+    retitle: bool,
+) -> Result<()> {
     let db_path = config::loader::database_path()?;
     let db = Database::open(&db_path)?;
 
@@ -1270,25 +3599,45 @@ async fn handle_redo(id: Option<String>, clean: bool) -> Result<()> {
         None => select_meeting_with_audio(&db)?,
     };
 
-    let meeting = db
+    let mut meeting = db
         .get_meeting(&MeetingId::from_string(meeting_id.clone()))?
         .ok_or_else(|| crate::error::MuesliError::MeetingNotFound(meeting_id.clone()))?;
 
+    if template.is_some() || lang.is_some() {
+        if let Some(template) = template {
+            meeting.template = Some(template.name().to_string());
+        }
+        if let Some(lang) = lang {
+            meeting.output_language = Some(lang);
+        }
+        db.update_meeting(&meeting)?;
+    }
+
     let audio_path = meeting
         .audio_path
         .as_ref()
         .ok_or_else(|| crate::error::MuesliError::Audio("No audio file for this meeting".into()))?;
 
     if !audio_path.exists() {
-        eprintln!("Error: Audio file not found: {:?}", audio_path);
-        return Ok(());
+        return Err(MuesliError::Audio(format!(
+            "Audio file not found: {:?}",
+            audio_path
+        )));
     }
 
-    println!("Re-processing: {}", meeting.title);
-    println!("Audio file: {:?}", audio_path);
+    let Some(_lock) = crate::pipeline::ProcessingLock::try_acquire(&meeting_id)? else {
+        return Err(MuesliError::Storage(format!(
+            "Meeting {} is being processed by another muesli process; try again when it finishes",
+            meeting_id
+        )));
+    };
+
+    status!("Re-processing: {}", meeting.title);
+    status!("Audio file: {:?}", audio_path);
 
     let config = config::loader::load_config()?;
     let models_dir = config::loader::models_dir()?;
+    crate::interrupt::install();
 
     let existing_segments = db.get_transcript_segments(&meeting.id)?;
     let needs_transcription = clean || existing_segments.is_empty();
@@ -1296,94 +3645,159 @@ async fn handle_redo(id: Option<String>, clean: bool) -> Result<()> {
     if needs_transcription {
         let step_count = if config.llm.provider != "none" { 3 } else { 2 };
 
-        println!("\n[1/{}] Transcribing...", step_count);
-        let transcript = run_transcription(&config, &models_dir, audio_path)?;
-        println!("  {} segments transcribed", transcript.segments.len());
+        status!("\n[1/{}] Transcribing...", step_count);
+        let transcript = crate::pipeline::transcribe(audio_path, &config)?;
+        status!("  {} segments transcribed", transcript.segments.len());
 
         db.delete_transcript_segments(&meeting.id)?;
-        db.insert_transcript_segments(&meeting.id, &transcript.segments)?;
+        db.insert_transcript_segments(&meeting.id, &transcript.segments, 0)?;
+        crate::interrupt::check(|| {
+            format!(
+                "transcript saved; speakers and summary not updated. \
+                 Run `muesli redo {}` to summarize it, or add --clean to start over",
+                meeting_id
+            )
+        })?;
 
-        println!(
+        status!(
             "\n[2/{}] Diarization (speaker identification)...",
             step_count
         );
-        let diarization_manager = DiarizationModelManager::new(models_dir.clone());
+        let diarization_manager = DiarizationModelManager::new(models_dir);
         if diarization_manager.model_exists(DiarizationModel::SortformerV2) {
-            let model_path = diarization_manager.model_path(DiarizationModel::SortformerV2);
-            match run_diarization(audio_path, &model_path) {
-                Ok(speaker_segments) => {
-                    let mut segments = db.get_transcript_segments(&meeting.id)?;
-                    for seg in segments.iter_mut() {
-                        if let Some(speaker) = speaker_segments
-                            .iter()
-                            .find(|s| {
-                                let mid = (seg.start_ms + seg.end_ms) / 2;
-                                mid >= s.start_ms && mid <= s.end_ms
-                            })
-                            .map(|s| format!("SPEAKER_{}", s.speaker_id + 1))
-                        {
-                            seg.speaker = Some(speaker);
-                        }
-                    }
+            match crate::pipeline::diarize_and_label(&transcript.segments, audio_path, &config) {
+                Ok(segments) => {
                     db.delete_transcript_segments(&meeting.id)?;
-                    db.insert_transcript_segments(&meeting.id, &segments)?;
-                    println!("  Speakers identified");
+                    db.insert_transcript_segments(
+                        &meeting.id,
+                        &segments,
+                        config.transcription.merge_gap_ms,
+                    )?;
+                    status!("  Speakers identified");
                 }
-                Err(e) => println!("  Skipped: {}", e),
+                Err(e) => eprintln!("  Skipped: {}", e),
             }
         } else {
-            println!("  Skipped (model not installed)");
+            status!("  Skipped (model not installed)");
         }
+        crate::interrupt::check(|| {
+            format!(
+                "transcript and speakers saved; summary not updated. Run `muesli redo {}` to summarize",
+                meeting_id
+            )
+        })?;
 
         if config.llm.provider != "none" {
-            println!("\n[3/{}] Summarizing...", step_count);
+            status!("\n[3/{}] Summarizing...", step_count);
         }
     } else {
-        println!(
+        status!(
             "\n  Using existing transcript ({} segments)",
             existing_segments.len()
         );
         if clean {
-            println!("  (use --clean to re-transcribe from scratch)");
+            status!("  (use --clean to re-transcribe from scratch)");
         }
-        println!();
-        println!("Summarizing...");
+        status!();
+        status!("Summarizing...");
     }
 
     if config.llm.provider != "none" {
         let segments = db.get_transcript_segments(&meeting.id)?;
         let transcript = crate::transcription::Transcript::new(segments);
-        match crate::llm::summarize_transcript(&config.llm, &transcript).await {
-            Ok(summary) => {
-                db.insert_summary(&meeting.id, &summary)?;
-                println!("  Summary generated");
-
-                let mut updated_meeting = meeting.clone();
-                match crate::llm::generate_title(&config.llm, &summary.markdown).await {
-                    Ok(title) => {
-                        println!("  Title: {}", title);
-                        updated_meeting.title = title;
-                        let _ = db.update_meeting(&updated_meeting);
-                    }
-                    Err(e) => println!("  Title generation failed: {}", e),
-                }
-
-                let notes_dir = config::loader::notes_dir()?;
-                let generator = crate::notes::markdown::NoteGenerator::new(notes_dir);
-                if let Ok(path) = generator.generate(&updated_meeting, &transcript, &summary) {
-                    println!("  Notes saved: {:?}", path);
+        match crate::pipeline::summarize_and_generate_notes(
+            &db,
+            &meeting,
+            &transcript,
+            &config,
+            retitle,
+        )
+        .await
+        {
+            Ok(notes) => {
+                status!("  Summary generated");
+                if notes.meeting.title != meeting.title {
+                    status!("  Title: {}", notes.meeting.title);
                 }
+                status!("  Notes saved: {:?}", notes.notes_path);
+                meeting = notes.meeting;
+            }
+            Err(e) => {
+                eprintln!("  Failed: {}", e);
+                meeting.status = MeetingStatus::Failed;
+                db.update_meeting(&meeting)?;
+                return Err(e);
             }
-            Err(e) => println!("  Failed: {}", e),
         }
     } else {
-        println!("  Skipped (LLM not configured)");
+        status!("  Skipped (LLM not configured)");
     }
 
+    meeting.status = MeetingStatus::Complete;
+    db.update_meeting(&meeting)?;
+
     println!("\nDone! View with: muesli notes {}", meeting_id);
     Ok(())
 }
 
+/// `redo` every meeting with `status`, newest first, reporting how many made it
+async fn handle_redo_all(
+    status: MeetingStatus,
+    limit: usize,
+    clean: bool,
+    template: Option<SummaryTemplate>,
+    lang: Option<String>,
+    retitle: bool,
+) -> Result<()> {
+    let db = Database::open(config::loader::database_path()?)?;
+    let meetings = db.list_meetings_filtered(limit, Some(status), None, None)?;
+    if meetings.is_empty() {
+        println!("No {} meetings to re-process.", status);
+        return Ok(());
+    }
+
+    let total = meetings.len();
+    let mut failed: Vec<(String, MuesliError)> = Vec::new();
+    for (i, meeting) in meetings.iter().enumerate() {
+        status!(
+            "\n=== [{}/{}] {} ({}) ===",
+            i + 1,
+            total,
+            meeting.title,
+            meeting.id
+        );
+        match handle_redo(
+            Some(meeting.id.to_string()),
+            clean,
+            template,
+            lang.clone(),
+            retitle,
+        )
+        .await
+        {
+            Ok(()) => {}
+            // Ctrl-C stops the whole batch, not just this meeting
+            Err(e @ MuesliError::Interrupted(_)) => return Err(e),
+            Err(e) => {
+                eprintln!("  {} failed: {}", meeting.id, e);
+                failed.push((meeting.id.to_string(), e));
+            }
+        }
+    }
+
+    println!(
+        "\nRe-processed {} {} meetings: {} succeeded, {} failed",
+        total,
+        status,
+        total - failed.len(),
+        failed.len()
+    );
+    for (id, e) in &failed {
+        println!("  {}: {}", id, e);
+    }
+    Ok(())
+}
+
 async fn handle_search(
     query: Option<String>,
     limit: usize,
@@ -1391,57 +3805,220 @@ async fn handle_search(
     action: Option<SearchCommands>,
 ) -> Result<()> {
     match action {
-        Some(SearchCommands::Reindex) => {
+        Some(SearchCommands::Reindex { verify, fix }) => {
             let config = config::loader::load_config()?;
-            if !config.qmd.enabled {
-                eprintln!("qmd search is not enabled. Run 'muesli setup' to configure.");
-                return Ok(());
-            }
-            println!("Re-indexing meeting notes...");
-            match crate::qmd::reindex(&config.qmd.collection_name) {
-                Ok(()) => println!("Re-indexing complete."),
-                Err(e) => eprintln!("Error: {}", e),
+            ensure_qmd_enabled(&config)?;
+            if verify {
+                verify_search_index(&config, fix)?;
+            } else {
+                println!("Re-indexing meeting notes...");
+                crate::qmd::reindex(&config.qmd.collection_name)?;
+                println!("Re-indexing complete.");
             }
         }
-        Some(SearchCommands::Status) => match crate::qmd::status() {
-            Ok(output) => print!("{}", output),
-            Err(e) => eprintln!("Error: {}", e),
-        },
+        Some(SearchCommands::Status) => {
+            let output = crate::qmd::status()?;
+            print!("{}", output);
+        }
+        Some(SearchCommands::Ask {
+            question,
+            meeting: Some(meeting_id),
+        }) => handle_ask_meeting(&meeting_id, &question.join(" ")).await?,
+        Some(SearchCommands::Ask {
+            question,
+            meeting: None,
+        }) => handle_ask(question, None).await?,
         None => {
-            if let Some(q) = query {
-                let config = config::loader::load_config()?;
-                if !config.qmd.enabled {
-                    eprintln!("qmd search is not enabled. Run 'muesli setup' to configure.");
-                    return Ok(());
-                }
-                match crate::qmd::search(&q, &config.qmd.collection_name, limit, keyword) {
-                    Ok(output) => {
-                        if output.trim().is_empty() {
-                            println!("No results found for: {}", q);
-                        } else {
-                            print!("{}", output);
-                        }
-                    }
-                    Err(e) => eprintln!("Error: {}", e),
-                }
-            } else {
-                eprintln!("Usage: muesli search <query>");
-                eprintln!("       muesli search reindex");
-                eprintln!("       muesli search status");
+            let q = query.ok_or_else(|| {
+                MuesliError::Usage(
+                    "Usage: muesli search <query>\n       muesli search reindex\n       muesli search status"
+                        .to_string(),
+                )
+            })?;
+            let config = config::loader::load_config()?;
+            if !config.qmd.enabled {
+                return search_database(&q, limit);
             }
+            let output = crate::qmd::search(&q, &config.qmd.collection_name, limit, keyword)?;
+            if output.trim().is_empty() {
+                return Err(MuesliError::NoResults(q));
+            }
+            print!("{}", output);
         }
     }
     Ok(())
 }
 
-async fn handle_ask(question: Vec<String>) -> Result<()> {
-    if question.is_empty() {
-        eprintln!("Usage: muesli ask <your question>");
+/// Keyword search over the stored transcripts, for when qmd isn't set up
+fn search_database(query: &str, limit: usize) -> Result<()> {
+    let db = Database::open(config::loader::database_path()?)?;
+    let results = db.search_transcripts(query, limit)?;
+    if results.is_empty() {
+        return Err(MuesliError::NoResults(query.to_string()));
+    }
+
+    if output::is_json() {
+        let results: Vec<_> = results
+            .iter()
+            .map(|(id, title, snippet)| {
+                serde_json::json!({"id": id.0, "title": title, "snippet": snippet})
+            })
+            .collect();
+        return output::print_json(&results);
+    }
+    for (id, title, snippet) in results {
+        println!("{}  ({})", title, id);
+        println!("  {}\n", snippet);
+    }
+    Ok(())
+}
+
+fn verify_search_index(config: &crate::config::settings::MuesliConfig, fix: bool) -> Result<()> {
+    let notes_dir = config::loader::notes_dir()?;
+    let db = Database::open(config::loader::database_path()?)?;
+    let meeting_ids = db.list_meeting_ids()?.into_iter().map(|id| id.0).collect();
+
+    let report =
+        crate::qmd::indexer::verify_index(&notes_dir, &config.qmd.collection_name, &meeting_ids)?;
+
+    if report.is_clean() {
+        println!("Index is up to date.");
+        return Ok(());
+    }
+
+    let sections = [
+        ("Orphaned index entries (file missing)", &report.orphaned),
+        ("Unindexed notes", &report.unindexed),
+        ("Notes without a meeting in the database", &report.untracked),
+    ];
+    for (heading, paths) in sections {
+        if paths.is_empty() {
+            continue;
+        }
+        println!("{} ({}):", heading, paths.len());
+        for path in paths {
+            println!("  {}", path);
+        }
+    }
+
+    if !report.needs_update() {
         return Ok(());
     }
 
+    if fix {
+        println!("\nUpdating index...");
+        crate::qmd::indexer::update_index(&config.qmd.collection_name)?;
+        println!("Index updated.");
+    } else {
+        println!("\nRun with --fix to update the index.");
+    }
+    Ok(())
+}
+
+fn ensure_qmd_enabled(config: &crate::config::settings::MuesliConfig) -> Result<()> {
+    if !config.qmd.enabled {
+        return Err(MuesliError::NotConfigured(
+            "qmd search is not enabled. Run 'muesli setup' to configure.".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn unexpected_response() -> MuesliError {
+    MuesliError::Daemon("Unexpected response from daemon".to_string())
+}
+
+async fn handle_ask(question: Vec<String>, session: Option<String>) -> Result<()> {
+    if question.is_empty() {
+        return Err(MuesliError::Usage(
+            "Usage: muesli ask <your question>".to_string(),
+        ));
+    }
+
     let question_str = question.join(" ");
-    crate::qmd::ask(&question_str).await?;
+    // With --json the answer is printed whole once it's done, not streamed
+    let print_token = |token: &str| {
+        if !output::is_json() {
+            print!("{}", token);
+            let _ = std::io::stdout().flush();
+        }
+    };
+    let session = match session {
+        Some(session) => {
+            let db = Database::open(config::loader::database_path()?)?;
+            let history = db.get_ask_turns(&session)?;
+            Some((db, session, history))
+        }
+        None => None,
+    };
+    let history = session
+        .as_ref()
+        .map_or(&[][..], |(_, _, history)| history.as_slice());
+
+    match crate::qmd::ask(&question_str, history, print_token).await? {
+        crate::qmd::AskReply::Answer(answer) => {
+            if output::is_json() {
+                output::print_json(
+                    &serde_json::json!({"question": question_str, "answer": answer}),
+                )?;
+            } else {
+                println!();
+            }
+            if let Some((db, session, _)) = &session {
+                db.insert_ask_turn(session, &question_str, &answer)?;
+            }
+        }
+        // Without an LLM the matching notes are the best answer there is
+        crate::qmd::AskReply::Notes(notes) => {
+            if output::is_json() {
+                return output::print_json(
+                    &serde_json::json!({"question": question_str, "notes": notes}),
+                );
+            }
+            println!("Relevant meeting notes:\n");
+            println!("{}", notes);
+            println!("\n---");
+            status!(
+                "No LLM provider configured for AI-powered answers. Run: muesli setup (step 7)"
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn handle_ask_meeting(meeting_id: &str, question: &str) -> Result<()> {
+    let config = config::loader::load_config()?;
+    if config.llm.provider == "none" {
+        return Err(MuesliError::NotConfigured(
+            "No LLM provider configured. Run: muesli setup (step 7)".to_string(),
+        ));
+    }
+
+    let db = Database::open(config::loader::database_path()?)?;
+    let meeting = db
+        .get_meeting(&MeetingId::from_string(meeting_id.to_string()))?
+        .ok_or_else(|| MuesliError::MeetingNotFound(meeting_id.to_string()))?;
+
+    let segments = db.get_transcript_segments(&meeting.id)?;
+    if segments.is_empty() {
+        return Err(MuesliError::NoResults(format!(
+            "no transcript for meeting {}",
+            meeting.id
+        )));
+    }
+
+    status!("Asking about \"{}\"...\n", meeting.title);
+    let transcript = crate::transcription::Transcript::new(segments);
+    let answer = crate::llm::ask_meeting(&config.llm, &transcript, question)
+        .await
+        .map_err(|e| MuesliError::Api(format!("LLM error: {}", e)))?;
+
+    if output::is_json() {
+        return output::print_json(
+            &serde_json::json!({"meeting_id": meeting.id.0, "question": question, "answer": answer}),
+        );
+    }
+    println!("{}", answer);
     Ok(())
 }
 
@@ -1482,69 +4059,6 @@ fn select_meeting_with_audio(db: &Database) -> Result<String> {
     Ok(meetings[selection].id.0.clone())
 }
 
-fn run_transcription(
-    config: &crate::config::settings::MuesliConfig,
-    models_dir: &std::path::Path,
-    audio_path: &std::path::Path,
-) -> Result<crate::transcription::Transcript> {
-    let manager = ModelManager::new(models_dir.to_path_buf());
-    let model =
-        WhisperModel::parse(config.transcription.effective_model()).unwrap_or(WhisperModel::Base);
-
-    if !manager.model_exists(model) {
-        return Err(crate::error::MuesliError::Config(format!(
-            "Whisper model {:?} not found. Run: muesli models whisper download {}",
-            model,
-            config.transcription.effective_model()
-        )));
-    }
-
-    let model_path = manager.model_path(model);
-    let engine = crate::transcription::whisper::WhisperEngine::new(
-        &model_path,
-        config.transcription.use_gpu,
-    )?;
-    crate::transcription::whisper::transcribe_wav_file(&engine, audio_path)
-}
-
-fn run_diarization(
-    audio_path: &std::path::Path,
-    model_path: &std::path::Path,
-) -> Result<Vec<crate::transcription::diarization::SpeakerSegment>> {
-    let samples = load_wav_samples_for_diarization(audio_path)?;
-    let mut diarizer = crate::transcription::diarization::Diarizer::new(model_path)?;
-    diarizer.diarize(samples, 16000)
-}
-
-fn load_wav_samples_for_diarization(path: &std::path::Path) -> Result<Vec<f32>> {
-    use hound::WavReader;
-
-    let mut reader = WavReader::open(path)
-        .map_err(|e| crate::error::MuesliError::Audio(format!("Failed to open WAV: {}", e)))?;
-
-    let spec = reader.spec();
-    let samples: Vec<f32> = match spec.sample_format {
-        hound::SampleFormat::Int => {
-            let max_val = (1 << (spec.bits_per_sample - 1)) as f32;
-            reader
-                .samples::<i32>()
-                .filter_map(|s| s.ok())
-                .map(|s| s as f32 / max_val)
-                .collect()
-        }
-        hound::SampleFormat::Float => reader.samples::<f32>().filter_map(|s| s.ok()).collect(),
-    };
-
-    if spec.channels > 1 {
-        Ok(samples
-            .chunks(spec.channels as usize)
-            .map(|chunk| chunk.iter().sum::<f32>() / chunk.len() as f32)
-            .collect())
-    } else {
-        Ok(samples)
-    }
-}
-
 fn update_llm_config(provider: &str, model: &str, api_key: Option<&str>) -> Result<()> {
     let config_path = config::loader::config_path()?;
     let content = std::fs::read_to_string(&config_path)?;
@@ -1632,24 +4146,93 @@ fn update_llm_config(provider: &str, model: &str, api_key: Option<&str>) -> Resu
     Ok(())
 }
 
-fn setup_cloud_provider(provider: &str, display_name: &str, default_model: &str) -> Result<()> {
+/// Set `key = value` in the `[llm]` section, adding the section if needed.
+/// `value` is written as-is, so strings must come quoted.
+fn update_llm_value(key: &str, value: &str) -> Result<()> {
+    update_section_value("llm", key, value)
+}
+
+/// Set `key = value` in the `[section]` table of the config file, adding the
+/// key or section if needed and leaving every other line (comments included)
+/// alone. `value` is written as-is, so strings must come quoted.
+fn update_section_value(section: &str, key: &str, value: &str) -> Result<()> {
+    let config_path = config::loader::config_path()?;
+    let content = match std::fs::read_to_string(&config_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            if let Some(parent) = config_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            String::new()
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let line = format!("{} = {}", key, value);
+    let header = format!("[{}]", section);
+
+    match lines.iter().position(|l| l.trim() == header) {
+        Some(section) => {
+            let is_header = |l: &String| {
+                let trimmed = l.trim();
+                trimmed.starts_with('[') && trimmed.ends_with(']')
+            };
+            let mut end = lines[section + 1..]
+                .iter()
+                .position(is_header)
+                .map_or(lines.len(), |i| section + 1 + i);
+            let key_pattern = format!("{} =", key);
+            match lines[section + 1..end]
+                .iter()
+                .position(|l| l.trim().starts_with(&key_pattern))
+            {
+                Some(i) => lines[section + 1 + i] = line,
+                None => {
+                    // After the section's last setting, not the blank line before the next
+                    while end > section + 1 && lines[end - 1].trim().is_empty() {
+                        end -= 1;
+                    }
+                    lines.insert(end, line);
+                }
+            }
+        }
+        None => lines.extend([String::new(), header, line]),
+    }
+
+    std::fs::write(&config_path, lines.join("\n") + "\n")?;
+    Ok(())
+}
+
+/// Ask for a cloud provider's API key (or take it from the environment) and model
+fn ask_cloud_provider(provider: &'static CloudProvider) -> LlmSetup {
     use dialoguer::{theme::ColorfulTheme, Input, Select};
 
-    println!("  {} selected. An API key is required.", display_name);
+    println!("  {} selected. An API key is required.", provider.name);
     println!();
 
-    let api_key: String = Input::with_theme(&ColorfulTheme::default())
-        .with_prompt(format!("Enter your {} API key", display_name))
-        .interact_text()
-        .unwrap_or_default();
+    // Lets scripted setups pass the key without typing it or putting it in history
+    let env_var = format!("MUESLI_{}_API_KEY", provider.id.to_uppercase());
+    let api_key: String = match std::env::var(&env_var) {
+        Ok(key) if !key.trim().is_empty() => {
+            println!("  Using the API key from {}", env_var);
+            key
+        }
+        _ => Input::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Enter your {} API key (or set {})",
+                provider.name, env_var
+            ))
+            .interact_text()
+            .unwrap_or_default(),
+    };
 
     if api_key.trim().is_empty() {
-        println!("  No API key provided. LLM disabled.");
-        update_llm_config("none", "", None)?;
-        return Ok(());
+        println!("  No API key provided.");
+        return LlmSetup::Disabled;
     }
 
-    let catalog_models = crate::llm::catalog::models_for_provider(provider);
+    let default_model = provider.default_model;
+    let catalog_models = crate::llm::catalog::models_for_provider(provider.id);
     let model = if catalog_models.is_empty() {
         default_model.to_string()
     } else {
@@ -1678,9 +4261,11 @@ fn setup_cloud_provider(provider: &str, display_name: &str, default_model: &str)
         }
     };
 
-    update_llm_config(provider, &model, Some(api_key.trim()))?;
-    println!("  LLM configured: {} (model: {})", display_name, model);
-    Ok(())
+    LlmSetup::Cloud {
+        provider,
+        model,
+        api_key,
+    }
 }
 
 fn discover_lms_models(lms: &str) -> Vec<String> {
@@ -1737,35 +4322,21 @@ fn discover_lms_models(lms: &str) -> Vec<String> {
     models
 }
 
+/// Point `[transcription]` at `engine`/`model`, keeping the rest of the file.
+/// A legacy `whisper_model` line would take precedence, so it follows along.
 fn update_transcription_config(engine: &str, model: &str) -> Result<()> {
+    update_section_value("transcription", "engine", &format!("\"{}\"", engine))?;
+    update_section_value("transcription", "model", &format!("\"{}\"", model))?;
+
     let config_path = config::loader::config_path()?;
     let content = std::fs::read_to_string(&config_path)?;
-
-    let mut in_transcription = false;
-    let updated = content
-        .lines()
-        .map(|line| {
-            let trimmed = line.trim();
-            if trimmed == "[transcription]" {
-                in_transcription = true;
-            } else if trimmed.starts_with("[") && trimmed.ends_with("]") {
-                in_transcription = false;
-            }
-
-            if in_transcription && trimmed.starts_with("engine =") {
-                format!("engine = \"{}\"", engine)
-            } else if in_transcription && trimmed.starts_with("model =") {
-                format!("model = \"{}\"", model)
-            } else if engine == "whisper" && trimmed.starts_with("whisper_model =") {
-                format!("whisper_model = \"{}\"", model)
-            } else {
-                line.to_string()
-            }
-        })
-        .collect::<Vec<_>>()
-        .join("\n");
-
-    std::fs::write(&config_path, updated)?;
+    if engine == "whisper"
+        && content
+            .lines()
+            .any(|line| line.trim().starts_with("whisper_model ="))
+    {
+        update_section_value("transcription", "whisper_model", &format!("\"{}\"", model))?;
+    }
     Ok(())
 }
 
@@ -2002,13 +4573,145 @@ fn truncate(s: &str, max_len: usize) -> String {
 }
 
 fn print_segment(segment: &crate::transcription::TranscriptSegment) {
-    match &segment.speaker {
-        Some(speaker) => println!(
-            "[{}] [{}] {}",
-            segment.format_timestamp(),
-            speaker,
-            segment.text
-        ),
-        None => println!("[{}] {}", segment.format_timestamp(), segment.text),
+    print_segment_at(segment, Some(&segment.format_timestamp()));
+}
+
+/// Script-like layout: the speaker and start time once per turn, then the
+/// turn's combined text
+fn print_dialogue(
+    segments: &[crate::transcription::TranscriptSegment],
+    timestamp: impl Fn(&crate::transcription::TranscriptSegment) -> Option<String>,
+) {
+    let turns = crate::transcription::merge_segments(segments, u64::MAX);
+    let mut previous: Option<&Option<String>> = None;
+    for turn in &turns {
+        // merge_segments also splits on a language change; keep that one turn
+        if previous != Some(&turn.speaker) {
+            if previous.is_some() {
+                println!();
+            }
+            let speaker = turn.speaker.as_deref().unwrap_or("Unknown speaker");
+            match timestamp(turn) {
+                Some(timestamp) => println!("{} [{}]", speaker, timestamp),
+                None => println!("{}", speaker),
+            }
+        }
+        println!("  {}", turn.text);
+        previous = Some(&turn.speaker);
+    }
+}
+
+/// One transcript line; without a timestamp it reads as plain dialogue
+fn print_segment_at(segment: &crate::transcription::TranscriptSegment, timestamp: Option<&str>) {
+    match (&segment.speaker, timestamp) {
+        (Some(speaker), Some(timestamp)) => {
+            println!("[{}] [{}] {}", timestamp, speaker, segment.text)
+        }
+        (None, Some(timestamp)) => println!("[{}] {}", timestamp, segment.text),
+        (Some(speaker), None) => println!("{}: {}", speaker, segment.text),
+        (None, None) => println!("{}", segment.text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_choices(args: &[&str]) -> Result<SetupChoices> {
+        let cli = <Cli as clap::Parser>::try_parse_from(
+            ["muesli", "setup", "--noninteractive"].iter().chain(args),
+        )
+        .unwrap();
+        let Some(Commands::Setup(args)) = cli.command else {
+            panic!("not a setup command");
+        };
+        SetupChoices::from_args(&args)
+    }
+
+    #[test]
+    fn test_setup_choices_defaults_and_env() {
+        let choices = setup_choices(&[]).unwrap();
+        assert_eq!(choices.whisper_model, Some(WhisperModel::Base));
+        assert!(matches!(choices.llm, LlmSetup::Disabled));
+        assert!(choices.diarization && choices.auto_prompt && choices.start_daemon);
+        assert!(!choices.use_gpu && !choices.audio_cues && !choices.install_service);
+        assert!(!choices.qmd);
+        assert!(choices.llm_tuning.temperature.is_none());
+
+        // Every choice can come from the environment instead of a flag
+        let vars = [
+            ("MUESLI_SETUP_MODEL", "tiny"),
+            ("MUESLI_SETUP_GPU", "true"),
+            ("MUESLI_SETUP_LLM_PROVIDER", "openrouter"),
+            ("MUESLI_SETUP_LLM_MAX_TOKENS", "2048"),
+            ("MUESLI_OPENROUTER_API_KEY", "sk-test"),
+        ];
+        for (name, value) in vars {
+            std::env::set_var(name, value);
+        }
+        let choices = setup_choices(&[]);
+        for (name, _) in vars {
+            std::env::remove_var(name);
+        }
+        let choices = choices.unwrap();
+
+        assert_eq!(choices.whisper_model, Some(WhisperModel::Tiny));
+        assert!(choices.use_gpu);
+        assert_eq!(choices.llm_tuning.max_tokens, Some(2048));
+        let LlmSetup::Cloud {
+            provider,
+            model,
+            api_key,
+        } = choices.llm
+        else {
+            panic!("expected a cloud provider");
+        };
+        assert_eq!(provider.id, "openrouter");
+        assert_eq!(model, provider.default_model);
+        assert_eq!(api_key, "sk-test");
+    }
+
+    #[test]
+    fn test_setup_choices_flags() {
+        let choices = setup_choices(&[
+            "--model",
+            "none",
+            "--no-diarization",
+            "--llm-provider",
+            "local",
+            "--llm-model",
+            "qwen3-8b",
+            "--llm-temperature",
+            "0.2",
+            "--no-auto-prompt",
+            "--qmd",
+            "--no-start",
+        ])
+        .unwrap();
+
+        assert!(choices.whisper_model.is_none());
+        assert!(!choices.diarization && !choices.auto_prompt && !choices.start_daemon);
+        assert!(choices.qmd);
+        assert_eq!(choices.llm_tuning.temperature, Some(0.2));
+        assert!(matches!(
+            choices.llm,
+            LlmSetup::Local { model: Some(ref m) } if m == "qwen3-8b"
+        ));
+    }
+
+    #[test]
+    fn test_setup_choices_rejects_bad_values() {
+        for args in [
+            &["--model", "huge"][..],
+            &["--llm-provider", "gemini"][..],
+            &["--llm-provider", "moonshot"][..],
+        ] {
+            std::env::remove_var("MUESLI_MOONSHOT_API_KEY");
+            assert!(
+                matches!(setup_choices(args), Err(MuesliError::Usage(_))),
+                "{:?}",
+                args
+            );
+        }
     }
 }
@@ -1,5 +1,6 @@
 pub mod commands;
 pub mod handlers;
+pub mod output;
 
 pub use commands::Cli;
 pub use handlers::handle_command;
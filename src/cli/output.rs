@@ -0,0 +1,82 @@
+//! Output verbosity shared by the CLI handlers.
+
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+static JSON: AtomicBool = AtomicBool::new(false);
+
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// `--json`: results are printed as JSON (progress output is suppressed as with `--quiet`)
+pub fn set_json(json: bool) {
+    JSON.store(json, Ordering::Relaxed);
+}
+
+pub fn is_json() -> bool {
+    JSON.load(Ordering::Relaxed)
+}
+
+/// Print a `--json` result payload on stdout
+pub fn print_json<T: serde::Serialize>(value: &T) -> crate::error::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Default tracing filter for the given `-v` count / `--quiet` flag.
+/// `RUST_LOG` still takes precedence.
+pub fn log_filter(verbose: u8, quiet: bool) -> &'static str {
+    if quiet {
+        return "warn";
+    }
+    match verbose {
+        0 => "info",
+        1 => "debug",
+        _ => "trace",
+    }
+}
+
+/// Progress/informational line, suppressed by `--quiet`
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if !$crate::cli::output::is_quiet() {
+            println!($($arg)*);
+        }
+    };
+}
+pub(crate) use status;
+
+/// Download progress callback output (`\rProgress: 42% (10/24 MB)`)
+pub fn download_progress(indent: &str, downloaded: u64, total: u64) {
+    if is_quiet() {
+        return;
+    }
+    let percent = (downloaded as f64 / total.max(1) as f64 * 100.0) as u32;
+    print!(
+        "\r{}Progress: {}% ({}/{} MB)    ",
+        indent,
+        percent,
+        downloaded / 1024 / 1024,
+        total / 1024 / 1024
+    );
+    std::io::stdout().flush().ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_filter() {
+        assert_eq!(log_filter(0, false), "info");
+        assert_eq!(log_filter(1, false), "debug");
+        assert_eq!(log_filter(3, false), "trace");
+        assert_eq!(log_filter(2, true), "warn");
+    }
+}
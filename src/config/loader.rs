@@ -2,7 +2,10 @@ use crate::config::settings::MuesliConfig;
 use crate::error::{MuesliError, Result};
 use directories::ProjectDirs;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+static SOCKET_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
 
 /// Get XDG-compliant config directory
 pub fn config_dir() -> Result<PathBuf> {
@@ -23,6 +26,16 @@ pub fn config_path() -> Result<PathBuf> {
     Ok(config_dir()?.join("config.toml"))
 }
 
+/// Last-known-good copy of the config, kept by `config edit`
+pub fn config_backup_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join("config.toml.bak"))
+}
+
+/// Custom prompt templates (see `muesli config prompts init`)
+pub fn prompts_dir() -> Result<PathBuf> {
+    Ok(config_dir()?.join("prompts"))
+}
+
 /// Get database path
 pub fn database_path() -> Result<PathBuf> {
     Ok(data_dir()?.join("muesli.db"))
@@ -43,9 +56,38 @@ pub fn models_dir() -> Result<PathBuf> {
     Ok(data_dir()?.join("models"))
 }
 
-/// Get socket path
+/// Use `path` as the daemon socket for the rest of the process (`--socket`)
+pub fn set_socket_path_override(path: PathBuf) {
+    let _ = SOCKET_PATH_OVERRIDE.set(path);
+}
+
+/// Get socket path: `--socket` override, then `[daemon] socket_path`, then the
+/// default in the data directory. Used by both the daemon and its clients.
 pub fn socket_path() -> Result<PathBuf> {
-    Ok(data_dir()?.join("muesli.sock"))
+    let configured = configured_socket_path();
+    Ok(resolve_socket_path(
+        SOCKET_PATH_OVERRIDE.get().map(PathBuf::as_path),
+        configured.as_deref(),
+        data_dir()?.join("muesli.sock"),
+    ))
+}
+
+fn resolve_socket_path(
+    override_path: Option<&Path>,
+    configured: Option<&Path>,
+    default: PathBuf,
+) -> PathBuf {
+    override_path
+        .or(configured)
+        .map(Path::to_path_buf)
+        .unwrap_or(default)
+}
+
+/// `[daemon] socket_path` from the config file, without creating a default config
+fn configured_socket_path() -> Option<PathBuf> {
+    let content = fs::read_to_string(config_path().ok()?).ok()?;
+    let config: MuesliConfig = toml::from_str(&content).ok()?;
+    config.daemon.socket_path
 }
 
 /// Load config from file, creating default if not exists
@@ -63,6 +105,14 @@ pub fn load_config() -> Result<MuesliConfig> {
     Ok(config)
 }
 
+/// Parse and validate the config file at `path`
+pub fn check_config_file(path: &Path) -> Result<MuesliConfig> {
+    let content = fs::read_to_string(path)?;
+    let config: MuesliConfig = toml::from_str(&content)?;
+    config.validate()?;
+    Ok(config)
+}
+
 /// Save config to file
 pub fn save_config(config: &MuesliConfig) -> Result<()> {
     let path = config_path()?;
@@ -143,4 +193,42 @@ mod tests {
         let _ = config_path();
         let _ = database_path();
     }
+
+    #[test]
+    fn test_resolve_socket_path_precedence() {
+        let default = PathBuf::from("/run/default.sock");
+        let configured = Path::new("/tmp/configured.sock");
+        let cli = Path::new("/tmp/cli.sock");
+
+        assert_eq!(resolve_socket_path(None, None, default.clone()), default);
+        assert_eq!(
+            resolve_socket_path(None, Some(configured), default.clone()),
+            configured
+        );
+        assert_eq!(
+            resolve_socket_path(Some(cli), Some(configured), default),
+            cli
+        );
+    }
+
+    #[test]
+    fn test_check_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        std::fs::write(&path, "[llm]\nprovider = \"openai\"\n").unwrap();
+        assert!(check_config_file(&path).is_ok());
+
+        std::fs::write(&path, "[llm\nprovider = \"openai\"\n").unwrap();
+        assert!(matches!(
+            check_config_file(&path),
+            Err(MuesliError::TomlParse(_))
+        ));
+
+        std::fs::write(&path, "[llm]\nprovider = \"gpt\"\n").unwrap();
+        assert!(matches!(
+            check_config_file(&path),
+            Err(MuesliError::InvalidConfig(_))
+        ));
+    }
 }
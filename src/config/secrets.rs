@@ -0,0 +1,46 @@
+//! API keys kept in the system keyring (Secret Service) instead of the config file.
+//!
+//! With `[llm] use_keyring = true` the config stores a reference such as
+//! `keyring:llm.api_key` in place of the key, resolved when the LLM is called.
+
+use crate::error::{MuesliError, Result};
+
+const SERVICE: &str = "muesli";
+const REFERENCE_PREFIX: &str = "keyring:";
+
+/// Store `secret` under `name` (the setting, e.g. `llm.api_key`) and return the
+/// reference to put in the config instead
+pub fn store(name: &str, secret: &str) -> Result<String> {
+    entry(name)?
+        .set_password(secret)
+        .map_err(|e| MuesliError::Config(format!("keyring: {}", e)))?;
+    Ok(format!("{}{}", REFERENCE_PREFIX, name))
+}
+
+/// The secret a config value stands for: looked up in the keyring for
+/// `keyring:` references, the value itself otherwise
+pub fn resolve(value: &str) -> Result<String> {
+    match value.strip_prefix(REFERENCE_PREFIX) {
+        Some(name) => entry(name)?.get_password().map_err(|e| {
+            MuesliError::Config(format!(
+                "keyring entry {} unavailable ({}). Set it again with: muesli config set {} -",
+                name, e, name
+            ))
+        }),
+        None => Ok(value.to_string()),
+    }
+}
+
+fn entry(name: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(SERVICE, name).map_err(|e| MuesliError::Config(format!("keyring: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_plaintext() {
+        assert_eq!(resolve("sk-plain").unwrap(), "sk-plain");
+    }
+}
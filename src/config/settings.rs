@@ -1,9 +1,13 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// Main configuration struct
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MuesliConfig {
+    #[serde(default)]
+    pub general: GeneralConfig,
+
     #[serde(default)]
     pub audio: AudioConfig,
 
@@ -30,6 +34,174 @@ pub struct MuesliConfig {
 
     #[serde(default)]
     pub qmd: QmdConfig,
+
+    #[serde(default)]
+    pub calendar: CalendarConfig,
+
+    #[serde(default)]
+    pub smtp: SmtpConfig,
+}
+
+const LLM_PROVIDERS: &[&str] = &[
+    "none",
+    "local",
+    "anthropic",
+    "openai",
+    "moonshot",
+    "openrouter",
+];
+/// Engines the daemon can record with (the hosted APIs aren't wired up yet)
+const TRANSCRIPTION_ENGINES: &[&str] = &["whisper"];
+const ASK_OVERFLOW_POLICIES: &[&str] = &["truncate", "summarize"];
+const BIT_DEPTHS: &[&str] = &["i16", "f32"];
+const NOTES_TRANSCRIPT_MODES: &[&str] = &["full", "collapsed", "none"];
+
+impl MuesliConfig {
+    /// Check values that parse fine but would break recording or summarization later
+    pub fn validate(&self) -> crate::error::Result<()> {
+        let mut problems = Vec::new();
+
+        if !LLM_PROVIDERS.contains(&self.llm.provider.as_str()) {
+            problems.push(format!(
+                "llm.provider = \"{}\" (expected one of: {})",
+                self.llm.provider,
+                LLM_PROVIDERS.join(", ")
+            ));
+        }
+        if !TRANSCRIPTION_ENGINES.contains(&self.transcription.engine.as_str()) {
+            problems.push(format!(
+                "transcription.engine = \"{}\" (expected one of: {})",
+                self.transcription.engine,
+                TRANSCRIPTION_ENGINES.join(", ")
+            ));
+        }
+        if !ASK_OVERFLOW_POLICIES.contains(&self.llm.ask_overflow.as_str()) {
+            problems.push(format!(
+                "llm.ask_overflow = \"{}\" (expected one of: {})",
+                self.llm.ask_overflow,
+                ASK_OVERFLOW_POLICIES.join(", ")
+            ));
+        }
+        if self.llm.chars_per_token <= 0.0 {
+            problems.push("llm.chars_per_token must be greater than 0".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.transcription.speaker_ambiguity_threshold) {
+            problems.push(
+                "transcription.speaker_ambiguity_threshold must be between 0 and 1".to_string(),
+            );
+        }
+        if !(0.0..=1.0).contains(&self.transcription.speaker_match_threshold) {
+            problems
+                .push("transcription.speaker_match_threshold must be between 0 and 1".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.audio_cues.volume) {
+            problems.push("audio_cues.volume must be between 0 and 1".to_string());
+        }
+        if self.audio.sample_rate == 0 {
+            problems.push("audio.sample_rate must be greater than 0".to_string());
+        }
+        if self.detection.ipc_retries > crate::detection::hyprland::MAX_IPC_RETRIES {
+            problems.push(format!(
+                "detection.ipc_retries must be at most {}",
+                crate::detection::hyprland::MAX_IPC_RETRIES
+            ));
+        }
+        for (app, template) in &self.detection.app_templates {
+            if crate::llm::prompts::SummaryTemplate::parse(template).is_none() {
+                problems.push(format!(
+                    "detection.app_templates.{} = \"{}\" (expected one of: {})",
+                    app,
+                    template,
+                    crate::llm::prompts::SummaryTemplate::ALL
+                        .iter()
+                        .map(|t| t.name())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+        }
+        if !BIT_DEPTHS.contains(&self.audio.bit_depth.as_str()) {
+            problems.push(format!(
+                "audio.bit_depth = \"{}\" (expected one of: {})",
+                self.audio.bit_depth,
+                BIT_DEPTHS.join(", ")
+            ));
+        }
+        if !NOTES_TRANSCRIPT_MODES.contains(&self.storage.notes_transcript.as_str()) {
+            problems.push(format!(
+                "storage.notes_transcript = \"{}\" (expected one of: {})",
+                self.storage.notes_transcript,
+                NOTES_TRANSCRIPT_MODES.join(", ")
+            ));
+        }
+        if self.smtp.on_complete
+            && (self.smtp.host.is_empty() || self.smtp.from.is_empty() || self.smtp.to.is_empty())
+        {
+            problems.push("smtp.on_complete needs smtp.host, smtp.from and smtp.to".to_string());
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(crate::error::MuesliError::InvalidConfig(
+                problems.join("; "),
+            ))
+        }
+    }
+
+    /// A validated copy with the dotted `key` (e.g. `llm.api_key`) set to `raw`,
+    /// parsed as the type the setting already has
+    pub fn with_value(&self, key: &str, raw: &str) -> crate::error::Result<MuesliConfig> {
+        use crate::error::MuesliError;
+        use toml::Value;
+
+        let unknown = || MuesliError::Config(format!("Unknown setting: {}", key));
+        let (section, leaf) = key.rsplit_once('.').ok_or_else(unknown)?;
+
+        let mut root = Value::try_from(self)?;
+        let mut table = root.as_table_mut().ok_or_else(unknown)?;
+        for part in section.split('.') {
+            table = table
+                .get_mut(part)
+                .and_then(Value::as_table_mut)
+                .ok_or_else(unknown)?;
+        }
+
+        let invalid = |expected: &str| {
+            MuesliError::Config(format!("{} expects {}, got '{}'", key, expected, raw))
+        };
+        let value = match table.get(leaf) {
+            Some(Value::Boolean(_)) => {
+                Value::Boolean(raw.parse().map_err(|_| invalid("true or false"))?)
+            }
+            Some(Value::Integer(_)) => {
+                Value::Integer(raw.parse().map_err(|_| invalid("an integer"))?)
+            }
+            Some(Value::Float(_)) => Value::Float(raw.parse().map_err(|_| invalid("a number"))?),
+            // Unset optional settings aren't serialized, so take those as strings
+            Some(Value::String(_)) | None => Value::String(raw.to_string()),
+            Some(_) => {
+                return Err(MuesliError::Config(format!(
+                    "{} is a list or table; change it with: muesli config edit",
+                    key
+                )))
+            }
+        };
+        table.insert(leaf.to_string(), value);
+
+        let config: MuesliConfig = root.try_into()?;
+        // Keys serde doesn't know are dropped on the way back; don't report those as set
+        let stored = Value::try_from(&config)?;
+        if key
+            .split('.')
+            .try_fold(&stored, |v, part| v.get(part))
+            .is_none()
+        {
+            return Err(unknown());
+        }
+        config.validate()?;
+        Ok(config)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +216,21 @@ pub struct AudioConfig {
     /// Sample rate for recording (default: 16000 for Whisper)
     #[serde(default = "default_sample_rate")]
     pub sample_rate: u32,
+    /// Even out loudness before transcribing (helps when one party is much quieter)
+    #[serde(default)]
+    pub auto_normalize: bool,
+    /// Recording sample format: "i16" (16-bit PCM, half the size) or "f32" (lossless)
+    #[serde(default = "default_bit_depth")]
+    pub bit_depth: String,
+    /// Weight of the microphone when mixed with system audio (0.0 to 2.0)
+    #[serde(default = "default_gain")]
+    pub mic_gain: f32,
+    /// Weight of system audio when mixed with the microphone (0.0 to 2.0)
+    #[serde(default = "default_gain")]
+    pub loopback_gain: f32,
+    /// Re-encode recordings to Opus once processed, deleting the WAV
+    #[serde(default)]
+    pub compress_after_recording: bool,
 }
 
 impl Default for AudioConfig {
@@ -53,10 +240,23 @@ impl Default for AudioConfig {
             device_loopback: None,
             capture_system_audio: true,
             sample_rate: 16000,
+            auto_normalize: false,
+            bit_depth: default_bit_depth(),
+            mic_gain: default_gain(),
+            loopback_gain: default_gain(),
+            compress_after_recording: false,
         }
     }
 }
 
+fn default_bit_depth() -> String {
+    "i16".to_string()
+}
+
+fn default_gain() -> f32 {
+    0.5
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscriptionConfig {
     /// Transcription engine (currently: "whisper")
@@ -75,6 +275,21 @@ pub struct TranscriptionConfig {
     pub openai_api_key: Option<String>,
     #[serde(default = "default_true")]
     pub fallback_to_local: bool,
+    /// Merge same-speaker segments separated by less than this many ms (0 = off)
+    #[serde(default = "default_merge_gap_ms")]
+    pub merge_gap_ms: u64,
+    /// Label a segment SPEAKER_? when the top two speakers' overlap differs by
+    /// less than this fraction of its length and confidence can't decide (0 = off)
+    #[serde(default = "default_speaker_ambiguity_threshold")]
+    pub speaker_ambiguity_threshold: f32,
+    /// Feed the streaming transcriber audio in windows of this many ms,
+    /// whatever the capture buffer size (0 = feed buffers as captured)
+    #[serde(default = "default_stream_chunk_ms")]
+    pub stream_chunk_ms: u64,
+    /// How closely a diarized speaker must sound like an enrolled profile
+    /// (`muesli speakers enroll`) to be given its name (1 = never)
+    #[serde(default = "default_speaker_match_threshold")]
+    pub speaker_match_threshold: f32,
 }
 
 impl Default for TranscriptionConfig {
@@ -88,18 +303,37 @@ impl Default for TranscriptionConfig {
             deepgram_api_key: None,
             openai_api_key: None,
             fallback_to_local: true,
+            merge_gap_ms: 800,
+            speaker_ambiguity_threshold: 0.1,
+            stream_chunk_ms: 500,
+            speaker_match_threshold: 0.95,
         }
     }
 }
 
 impl TranscriptionConfig {
+    /// The Whisper model to use: `model`, then the legacy `whisper_model`, then
+    /// "base" when neither is set
     #[allow(dead_code)]
     pub fn effective_model(&self) -> &str {
-        if !self.model.is_empty() && self.model != "base" {
-            return &self.model;
+        let model = self.model.trim();
+        if !model.is_empty() && model != "base" {
+            return model;
         }
 
-        self.whisper_model.as_deref().unwrap_or(&self.model)
+        match self.whisper_model.as_deref().map(str::trim) {
+            Some(legacy) if !legacy.is_empty() => legacy,
+            _ => "base",
+        }
+    }
+
+    /// Whether any model is set, as opposed to falling back to "base"
+    pub fn has_model(&self) -> bool {
+        !self.model.trim().is_empty()
+            || self
+                .whisper_model
+                .as_deref()
+                .is_some_and(|m| !m.trim().is_empty())
     }
 }
 
@@ -111,14 +345,53 @@ pub struct LlmConfig {
     /// Model name for the selected provider
     #[serde(default)]
     pub model: String,
-    /// API key for the selected provider
+    /// API key for the selected provider (or a `keyring:` reference, see `use_keyring`)
     pub api_key: Option<String>,
+    /// Keep API keys set via `setup`/`config set` in the system keyring; the
+    /// config then only holds a `keyring:` reference
+    #[serde(default)]
+    pub use_keyring: bool,
     /// Path to LM Studio CLI binary (auto-detect if empty, only used for "local" provider)
     #[serde(default)]
     pub local_lms_path: String,
     /// Override context window size in tokens (0 = auto-detect from models.dev)
     #[serde(default)]
     pub context_limit: usize,
+    /// System message / persona sent with every request (empty = built-in default)
+    #[serde(default)]
+    pub system_prompt: String,
+    /// Language to write notes and titles in, e.g. "German" (empty = the transcript's language)
+    #[serde(default)]
+    pub output_language: String,
+    /// Characters per token used to estimate prompt size (lower for code-heavy or non-English)
+    #[serde(default = "default_chars_per_token")]
+    pub chars_per_token: f32,
+    /// Tokens reserved for the prompt template (0 = measure from the template)
+    #[serde(default)]
+    pub prompt_overhead_tokens: usize,
+    /// Refresh the notes file with an interim summary every N minutes while recording (0 = off)
+    #[serde(default)]
+    pub interim_summary_mins: u64,
+    /// Provider to retry with when the primary fails with a quota/auth/server error
+    /// (empty or "none" = no fallback)
+    #[serde(default)]
+    pub fallback_provider: String,
+    /// Model for the fallback provider (empty = that provider's default)
+    #[serde(default)]
+    pub fallback_model: String,
+    /// API key for the fallback provider
+    pub fallback_api_key: Option<String>,
+    /// What `ask` does when a prompt exceeds the model's context: "truncate"
+    /// (drop the middle of the context) or "summarize" (condense it in chunks first)
+    #[serde(default = "default_ask_overflow")]
+    pub ask_overflow: String,
+    /// Times to retry a request the provider rate-limited (429) or failed (5xx)
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Sampling temperature (unset = 0.3)
+    pub temperature: Option<f32>,
+    /// Longest response to ask for (unset = 8192 for Anthropic, the model's limit elsewhere)
+    pub max_tokens: Option<u32>,
 }
 
 impl Default for LlmConfig {
@@ -127,29 +400,85 @@ impl Default for LlmConfig {
             provider: "none".to_string(),
             model: String::new(),
             api_key: None,
+            use_keyring: false,
             local_lms_path: String::new(),
             context_limit: 0,
+            system_prompt: String::new(),
+            output_language: String::new(),
+            chars_per_token: 4.0,
+            prompt_overhead_tokens: 0,
+            interim_summary_mins: 0,
+            fallback_provider: String::new(),
+            fallback_model: String::new(),
+            fallback_api_key: None,
+            ask_overflow: default_ask_overflow(),
+            max_retries: default_max_retries(),
+            temperature: None,
+            max_tokens: None,
         }
     }
 }
 
 impl LlmConfig {
-    /// Returns the default model for the configured provider
+    /// Returns the concrete model id: the configured model with aliases
+    /// ("fast", "smart", "sonnet", ...) resolved, or the provider default
     pub fn effective_model(&self) -> &str {
-        if !self.model.is_empty() {
-            return &self.model;
+        let model = self.model.trim();
+        let name = if model.is_empty() { "default" } else { model };
+        match crate::llm::catalog::resolve_alias(&self.provider, name) {
+            Some(id) => id,
+            None => model,
         }
-        match self.provider.as_str() {
-            "anthropic" => "claude-sonnet-4-20250514",
-            "openai" => "gpt-4o",
-            "moonshot" => "kimi-k2.5",
-            "openrouter" => "anthropic/claude-sonnet-4",
-            _ => "",
+    }
+
+    /// The API key with any keyring reference resolved
+    pub fn resolved_api_key(&self) -> crate::error::Result<Option<String>> {
+        self.api_key
+            .as_deref()
+            .map(crate::config::secrets::resolve)
+            .transpose()
+    }
+
+    /// The config to fail over to, or `None` when no usable fallback is set
+    pub fn fallback(&self) -> Option<LlmConfig> {
+        let provider = self.fallback_provider.trim();
+        if provider.is_empty() || provider == "none" {
+            return None;
+        }
+
+        let fallback = LlmConfig {
+            provider: provider.to_string(),
+            model: self.fallback_model.clone(),
+            api_key: self.fallback_api_key.clone(),
+            fallback_provider: String::new(),
+            fallback_model: String::new(),
+            fallback_api_key: None,
+            ..self.clone()
+        };
+        if fallback.provider == self.provider
+            && fallback.effective_model() == self.effective_model()
+        {
+            return None;
+        }
+        Some(fallback)
+    }
+
+    /// Returns the configured system prompt, or the built-in default
+    pub fn effective_system_prompt(&self) -> &str {
+        if self.system_prompt.trim().is_empty() {
+            crate::llm::prompts::DEFAULT_SYSTEM_PROMPT
+        } else {
+            &self.system_prompt
         }
     }
+
+    /// The language notes should be written in, if one is set
+    pub fn output_language(&self) -> Option<&str> {
+        Some(self.output_language.trim()).filter(|l| !l.is_empty())
+    }
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageConfig {
     /// Directory for meeting notes
     pub notes_dir: Option<PathBuf>,
@@ -157,6 +486,34 @@ pub struct StorageConfig {
     pub database_path: Option<PathBuf>,
     /// Directory for audio recordings
     pub recordings_dir: Option<PathBuf>,
+    /// Show wall-clock times (14:42:30) instead of meeting offsets in notes transcripts
+    #[serde(default)]
+    pub clock_timestamps: bool,
+    /// Prefix notes transcript lines with their time (false = plain dialogue)
+    #[serde(default = "default_true")]
+    pub notes_timestamps: bool,
+    /// Transcript in notes files: "full", "collapsed" (in a `<details>` block)
+    /// or "none" (summary only; the transcript stays in the database)
+    #[serde(default = "default_notes_transcript")]
+    pub notes_transcript: String,
+    /// Stop recording when free space on the recordings volume drops below
+    /// this many MB (0 = no check)
+    #[serde(default = "default_min_free_mb")]
+    pub min_free_mb: u64,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            notes_dir: None,
+            database_path: None,
+            recordings_dir: None,
+            clock_timestamps: false,
+            notes_timestamps: true,
+            notes_transcript: default_notes_transcript(),
+            min_free_mb: default_min_free_mb(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -166,6 +523,19 @@ pub struct DaemonConfig {
     /// Log level
     #[serde(default = "default_log_level")]
     pub log_level: String,
+    /// Stop any recording after this many seconds (0 = no limit)
+    #[serde(default)]
+    pub max_recording_secs: u64,
+    /// While recording, show the latest transcribed line in a notification and
+    /// the Waybar tooltip, at most once every N seconds (0 = off; needs streaming)
+    #[serde(default)]
+    pub live_preview_secs: u64,
+    /// Notify when post-recording processing starts and when it finishes
+    #[serde(default = "default_true")]
+    pub notify_processing: bool,
+    /// Also notify as processing moves between stages (transcribe, speakers, summary)
+    #[serde(default)]
+    pub notify_processing_stages: bool,
 }
 
 impl Default for DaemonConfig {
@@ -173,6 +543,10 @@ impl Default for DaemonConfig {
         Self {
             socket_path: None,
             log_level: "info".to_string(),
+            max_recording_secs: 0,
+            live_preview_secs: 0,
+            notify_processing: true,
+            notify_processing_stages: false,
         }
     }
 }
@@ -188,12 +562,29 @@ pub struct DetectionConfig {
     /// Timeout for the recording prompt in seconds (0 = no timeout)
     #[serde(default = "default_prompt_timeout")]
     pub prompt_timeout_secs: u64,
-    /// Debounce time for window switches (ms)
+    /// How long focus must rest on a window before detection looks at it (ms),
+    /// so rapid focus flips are evaluated once
     #[serde(default = "default_debounce")]
     pub debounce_ms: u64,
     /// Fallback polling interval (seconds)
     #[serde(default = "default_poll_interval")]
     pub poll_interval_secs: u64,
+    /// Title templates for auto-recorded meetings, keyed by app (e.g. `zoom = "{app} - {date}"`)
+    #[serde(default)]
+    pub app_titles: HashMap<String, String>,
+    /// Summary templates for meetings recorded from an app (e.g. `zoom = "standup"`)
+    #[serde(default)]
+    pub app_templates: HashMap<String, String>,
+    /// Stop recording when the meeting window closes (false = only stop manually)
+    #[serde(default = "default_true")]
+    pub stop_on_window_close: bool,
+    /// Extra attempts for a failed Hyprland IPC call (compositor reloads drop a few)
+    #[serde(default = "default_ipc_retries")]
+    pub ipc_retries: u32,
+    /// Only prompt once the meeting app has a running audio stream (checked with
+    /// pw-dump), so an idle client sitting in the tray doesn't trigger a prompt
+    #[serde(default)]
+    pub require_audio: bool,
 }
 
 impl Default for DetectionConfig {
@@ -204,6 +595,11 @@ impl Default for DetectionConfig {
             prompt_timeout_secs: 30,
             debounce_ms: 500,
             poll_interval_secs: 30,
+            app_titles: HashMap::new(),
+            app_templates: HashMap::new(),
+            stop_on_window_close: true,
+            ipc_retries: 3,
+            require_audio: false,
         }
     }
 }
@@ -228,6 +624,42 @@ fn default_llm_provider() -> String {
     "none".to_string()
 }
 
+fn default_merge_gap_ms() -> u64 {
+    800
+}
+
+fn default_speaker_ambiguity_threshold() -> f32 {
+    0.1
+}
+
+fn default_speaker_match_threshold() -> f32 {
+    0.95
+}
+
+fn default_stream_chunk_ms() -> u64 {
+    500
+}
+
+fn default_chars_per_token() -> f32 {
+    4.0
+}
+
+fn default_ask_overflow() -> String {
+    "truncate".to_string()
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_notes_transcript() -> String {
+    "collapsed".to_string()
+}
+
+fn default_min_free_mb() -> u64 {
+    500
+}
+
 fn default_log_level() -> String {
     "info".to_string()
 }
@@ -240,6 +672,10 @@ fn default_poll_interval() -> u64 {
     30
 }
 
+fn default_ipc_retries() -> u32 {
+    3
+}
+
 fn default_prompt_timeout() -> u64 {
     30
 }
@@ -274,6 +710,9 @@ pub struct WaybarConfig {
     #[serde(default)]
     pub enabled: bool,
     pub status_file: Option<std::path::PathBuf>,
+    /// Show the elapsed recording time as the module text, not just in the tooltip
+    #[serde(default)]
+    pub show_text: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -303,6 +742,67 @@ fn default_qmd_collection() -> String {
     "muesli-meetings".to_string()
 }
 
+/// Settings for the CLI as a whole
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GeneralConfig {
+    /// Command line run by a bare `muesli`, e.g. "status" or "list --limit 5"
+    /// (empty = print help)
+    #[serde(default)]
+    pub default_command: String,
+}
+
+/// Where `muesli start --calendar` looks up the event happening now
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CalendarConfig {
+    /// iCalendar file to read events from
+    pub ics_path: Option<PathBuf>,
+    /// Shell command printing the current event(s) instead, either as iCalendar
+    /// or one per line as `TITLE[<tab>ATTENDEE, ATTENDEE...]` (takes precedence over ics_path)
+    #[serde(default)]
+    pub command: String,
+}
+
+/// Outgoing mail server for `muesli email`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmtpConfig {
+    /// SMTP server host (empty = email not configured)
+    #[serde(default)]
+    pub host: String,
+    /// Server port: 465 uses implicit TLS, anything else STARTTLS
+    #[serde(default = "default_smtp_port")]
+    pub port: u16,
+    /// Sender address, e.g. "muesli <me@example.com>"
+    #[serde(default)]
+    pub from: String,
+    /// Login user (None = send without authenticating)
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Default recipients when `--to` isn't given
+    #[serde(default)]
+    pub to: Vec<String>,
+    /// Email the notes to `to` whenever a meeting finishes processing
+    #[serde(default)]
+    pub on_complete: bool,
+}
+
+impl Default for SmtpConfig {
+    fn default() -> Self {
+        Self {
+            host: String::new(),
+            port: default_smtp_port(),
+            from: String::new(),
+            username: None,
+            password: None,
+            to: Vec::new(),
+            on_complete: false,
+        }
+    }
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -321,6 +821,61 @@ mod tests {
         assert!(audio.capture_system_audio);
         assert_eq!(audio.sample_rate, 16000);
         assert!(audio.device_mic.is_none());
+        assert!(!audio.auto_normalize);
+        assert_eq!(audio.mic_gain, 0.5);
+        assert_eq!(audio.loopback_gain, 0.5);
+        assert!(!audio.compress_after_recording);
+        assert_eq!(audio.bit_depth, "i16");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(MuesliConfig::default().validate().is_ok());
+
+        let mut config = MuesliConfig::default();
+        config.llm.provider = "antropic".to_string();
+        config.audio_cues.volume = 2.0;
+        config.llm.ask_overflow = "drop".to_string();
+        config.smtp.on_complete = true;
+        config.audio.bit_depth = "i24".to_string();
+        config.storage.notes_transcript = "summary".to_string();
+        config.transcription.speaker_match_threshold = 1.5;
+        config.transcription.engine = "deepgram".to_string();
+        config.detection.ipc_retries = 1_000;
+        config
+            .detection
+            .app_templates
+            .insert("zoom".to_string(), "retro".to_string());
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("llm.provider = \"antropic\""));
+        assert!(err.contains("audio_cues.volume"));
+        assert!(err.contains("llm.ask_overflow"));
+        assert!(err.contains("smtp.on_complete"));
+        assert!(err.contains("audio.bit_depth = \"i24\""));
+        assert!(err.contains("transcription.engine = \"deepgram\""));
+        assert!(err.contains("detection.ipc_retries"));
+        assert!(err.contains("detection.app_templates.zoom"));
+        assert!(err.contains("storage.notes_transcript"));
+        assert!(err.contains("transcription.speaker_match_threshold"));
+    }
+
+    #[test]
+    fn test_with_value() {
+        let config = MuesliConfig::default();
+
+        let updated = config.with_value("llm.api_key", "sk-test").unwrap();
+        assert_eq!(updated.llm.api_key.as_deref(), Some("sk-test"));
+        let updated = config.with_value("audio_cues.volume", "0.25").unwrap();
+        assert_eq!(updated.audio_cues.volume, 0.25);
+        let updated = config.with_value("audio.auto_normalize", "true").unwrap();
+        assert!(updated.audio.auto_normalize);
+
+        assert!(config.with_value("audio.auto_normalize", "yes").is_err());
+        assert!(config.with_value("llm.api_kee", "sk-test").is_err());
+        assert!(config.with_value("nosuch.key", "1").is_err());
+        assert!(config.with_value("llm", "x").is_err());
+        // Values that fail validation are rejected too
+        assert!(config.with_value("llm.provider", "antropic").is_err());
     }
 
     #[test]
@@ -329,6 +884,9 @@ mod tests {
         assert_eq!(trans.engine, "whisper");
         assert_eq!(trans.model, "base");
         assert!(trans.fallback_to_local);
+        assert_eq!(trans.merge_gap_ms, 800);
+        assert_eq!(trans.speaker_ambiguity_threshold, 0.1);
+        assert_eq!(trans.stream_chunk_ms, 500);
     }
 
     #[test]
@@ -337,7 +895,15 @@ mod tests {
         assert_eq!(llm.provider, "none");
         assert!(llm.model.is_empty());
         assert!(llm.api_key.is_none());
+        assert!(!llm.use_keyring);
         assert!(llm.local_lms_path.is_empty());
+        assert_eq!(
+            llm.effective_system_prompt(),
+            crate::llm::prompts::DEFAULT_SYSTEM_PROMPT
+        );
+        assert_eq!(llm.chars_per_token, 4.0);
+        assert_eq!(llm.prompt_overhead_tokens, 0);
+        assert_eq!(llm.interim_summary_mins, 0);
     }
 
     #[test]
@@ -350,6 +916,81 @@ mod tests {
         assert_eq!(llm.effective_model(), "claude-opus-4-20250514");
     }
 
+    #[test]
+    fn test_llm_fallback() {
+        let mut llm = LlmConfig {
+            provider: "anthropic".to_string(),
+            api_key: Some("primary".to_string()),
+            ..Default::default()
+        };
+        assert!(llm.fallback().is_none());
+
+        llm.fallback_provider = "none".to_string();
+        assert!(llm.fallback().is_none());
+
+        // Same provider and model is not a fallback
+        llm.fallback_provider = "anthropic".to_string();
+        llm.fallback_model = "sonnet".to_string();
+        assert!(llm.fallback().is_none());
+
+        llm.fallback_provider = "local".to_string();
+        llm.fallback_model = "qwen2.5-7b-instruct-1m".to_string();
+        let fallback = llm.fallback().unwrap();
+        assert_eq!(fallback.provider, "local");
+        assert_eq!(fallback.effective_model(), "qwen2.5-7b-instruct-1m");
+        assert!(fallback.api_key.is_none());
+        assert!(fallback.fallback().is_none());
+    }
+
+    #[test]
+    fn test_llm_effective_model_aliases() {
+        let mut llm = LlmConfig {
+            model: "fast".to_string(),
+            ..Default::default()
+        };
+        for (provider, expected) in [
+            ("anthropic", "claude-3-5-haiku-20241022"),
+            ("openai", "gpt-4o-mini"),
+            ("openrouter", "anthropic/claude-3.5-haiku"),
+        ] {
+            llm.provider = provider.to_string();
+            assert_eq!(llm.effective_model(), expected);
+        }
+
+        // Local models have no aliases; unknown names pass through as literal ids
+        llm.provider = "local".to_string();
+        assert_eq!(llm.effective_model(), "fast");
+        llm.provider = "openai".to_string();
+        llm.model = "o3-mini".to_string();
+        assert_eq!(llm.effective_model(), "o3-mini");
+        llm.model = String::new();
+        llm.provider = "none".to_string();
+        assert_eq!(llm.effective_model(), "");
+
+        // A blank model is no model: the provider default, or nothing for local
+        llm.model = "  ".to_string();
+        llm.provider = "moonshot".to_string();
+        assert_eq!(llm.effective_model(), "kimi-k2.5");
+        llm.provider = "local".to_string();
+        assert_eq!(llm.effective_model(), "");
+    }
+
+    #[test]
+    fn test_transcription_effective_model() {
+        let mut transcription = TranscriptionConfig::default();
+        assert_eq!(transcription.effective_model(), "base");
+
+        transcription.whisper_model = Some("small".to_string());
+        assert_eq!(transcription.effective_model(), "small");
+        transcription.model = "large-v3".to_string();
+        assert_eq!(transcription.effective_model(), "large-v3");
+
+        transcription.model = " ".to_string();
+        transcription.whisper_model = Some(String::new());
+        assert_eq!(transcription.effective_model(), "base");
+        assert!(!transcription.has_model());
+    }
+
     #[test]
     fn test_detection_config_defaults() {
         let detection = DetectionConfig::default();
@@ -358,5 +999,23 @@ mod tests {
         assert_eq!(detection.prompt_timeout_secs, 30);
         assert_eq!(detection.debounce_ms, 500);
         assert_eq!(detection.poll_interval_secs, 30);
+        assert!(detection.app_titles.is_empty());
+        assert!(detection.stop_on_window_close);
+    }
+
+    #[test]
+    fn test_detection_app_titles_parse() {
+        let config: MuesliConfig = toml::from_str(
+            r#"
+            [detection.app_titles]
+            zoom = "{app} - {date}"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.detection.app_titles.get("zoom").map(String::as_str),
+            Some("{app} - {date}")
+        );
+        assert!(config.detection.auto_detect);
     }
 }
@@ -1,6 +1,7 @@
 use crate::config::loader::socket_path;
 use crate::daemon::protocol::{DaemonRequest, DaemonResponse};
 use crate::error::{MuesliError, Result};
+use std::path::Path;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::UnixStream;
 
@@ -10,9 +11,11 @@ pub struct DaemonClient {
 
 impl DaemonClient {
     pub async fn connect() -> Result<Self> {
-        let socket = socket_path()?;
+        Self::connect_to(&socket_path()?).await
+    }
 
-        let stream = UnixStream::connect(&socket)
+    pub async fn connect_to(socket: &Path) -> Result<Self> {
+        let stream = UnixStream::connect(socket)
             .await
             .map_err(|_| MuesliError::DaemonNotRunning)?;
 
@@ -84,6 +87,29 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_custom_socket_path_end_to_end() {
+        let dir = tempdir().unwrap();
+        let socket = dir.path().join("custom.sock");
+        let listener = setup_test_server(&socket).await;
+
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            crate::daemon::server::handle_connection(stream, state, shutdown)
+                .await
+                .unwrap();
+        });
+
+        let mut client = DaemonClient::connect_to(&socket).await.unwrap();
+        let response = client.send(DaemonRequest::GetStatus).await.unwrap();
+        match response {
+            DaemonResponse::Status(status) => assert!(status.running),
+            _ => panic!("Expected Status response"),
+        }
+    }
+
     #[tokio::test]
     async fn test_ping_returns_false_when_daemon_not_running() {
         let result = DaemonClient::ping().await.unwrap();
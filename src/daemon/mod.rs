@@ -1,6 +1,7 @@
 pub mod client;
 pub mod protocol;
 pub mod server;
+pub mod watchdog;
 
 pub use client::DaemonClient;
 pub use protocol::{DaemonRequest, DaemonResponse};
@@ -1,9 +1,24 @@
+use crate::error::MuesliError;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DaemonRequest {
-    StartRecording { title: Option<String> },
+    StartRecording {
+        title: Option<String>,
+        /// Stop automatically after this many seconds
+        #[serde(default)]
+        auto_stop_secs: Option<u64>,
+        /// Attendees to record on the meeting (e.g. from a calendar event)
+        #[serde(default)]
+        participants: Vec<String>,
+        /// Record the microphone only, whatever `[audio] capture_system_audio` says
+        #[serde(default)]
+        mic_only: bool,
+    },
     StopRecording,
+    /// Stop capturing audio until resumed, keeping the recording open
+    PauseRecording,
+    ResumeRecording,
     GetStatus,
     Shutdown,
     Ping,
@@ -12,11 +27,78 @@ pub enum DaemonRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DaemonResponse {
     Ok,
-    Error { message: String },
+    Error {
+        message: String,
+        /// What went wrong, for clients that react to specific failures
+        /// (missing from daemons predating it)
+        #[serde(default)]
+        error_kind: DaemonErrorKind,
+    },
     Status(DaemonStatus),
     Pong,
-    RecordingStarted { meeting_id: String },
-    RecordingStopped { meeting_id: String },
+    RecordingStarted {
+        meeting_id: String,
+    },
+    RecordingStopped {
+        meeting_id: String,
+    },
+}
+
+/// Machine-readable cause of a [`DaemonResponse::Error`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DaemonErrorKind {
+    AlreadyRecording,
+    NotRecording,
+    AudioDeviceNotFound,
+    Audio,
+    ModelNotFound,
+    NotConfigured,
+    Config,
+    Storage,
+    Transcription,
+    /// Anything else, including kinds added by a newer daemon
+    #[default]
+    #[serde(other)]
+    Internal,
+}
+
+impl DaemonErrorKind {
+    /// The kind reported for an error raised while handling a request
+    pub fn from_error(error: &MuesliError) -> Self {
+        match error {
+            MuesliError::AlreadyRecording => Self::AlreadyRecording,
+            MuesliError::NotRecording => Self::NotRecording,
+            MuesliError::AudioDeviceNotFound(_) => Self::AudioDeviceNotFound,
+            MuesliError::Audio(_) | MuesliError::AudioStream(_) => Self::Audio,
+            MuesliError::WhisperModelNotFound(_) => Self::ModelNotFound,
+            MuesliError::NotConfigured(_) => Self::NotConfigured,
+            MuesliError::Config(_)
+            | MuesliError::InvalidConfig(_)
+            | MuesliError::ConfigNotFound(_)
+            | MuesliError::TomlParse(_)
+            | MuesliError::TomlSerialize(_) => Self::Config,
+            MuesliError::Storage(_) | MuesliError::Database(_) => Self::Storage,
+            MuesliError::Transcription(_) => Self::Transcription,
+            _ => Self::Internal,
+        }
+    }
+
+    /// Stable name, as serialized and reported by `--json`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::AlreadyRecording => "already_recording",
+            Self::NotRecording => "not_recording",
+            Self::AudioDeviceNotFound => "audio_device_not_found",
+            Self::Audio => "audio",
+            Self::ModelNotFound => "model_not_found",
+            Self::NotConfigured => "not_configured",
+            Self::Config => "config",
+            Self::Storage => "storage",
+            Self::Transcription => "transcription",
+            Self::Internal => "daemon",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +109,15 @@ pub struct DaemonStatus {
     pub current_meeting_id: Option<String>,
     pub meeting_detected: Option<String>,
     pub uptime_seconds: u64,
+    /// How long the current recording has been running
+    #[serde(default)]
+    pub recording_seconds: Option<u64>,
+    /// The current recording is paused (not capturing audio)
+    #[serde(default)]
+    pub paused: bool,
+    /// Finished recordings the daemon is still transcribing or summarizing
+    #[serde(default)]
+    pub background_jobs: usize,
 }
 
 #[cfg(test)]
@@ -37,18 +128,38 @@ mod tests {
     fn test_request_serialization() {
         let request = DaemonRequest::StartRecording {
             title: Some("Test Meeting".to_string()),
+            auto_stop_secs: Some(1800),
+            participants: vec!["Alice".to_string()],
+            mic_only: true,
         };
         let json = serde_json::to_string(&request).unwrap();
         let parsed: DaemonRequest = serde_json::from_str(&json).unwrap();
 
         match parsed {
-            DaemonRequest::StartRecording { title } => {
+            DaemonRequest::StartRecording {
+                title,
+                auto_stop_secs,
+                participants,
+                mic_only,
+            } => {
                 assert_eq!(title, Some("Test Meeting".to_string()));
+                assert_eq!(auto_stop_secs, Some(1800));
+                assert_eq!(participants, vec!["Alice"]);
+                assert!(mic_only);
             }
             _ => panic!("Wrong request type"),
         }
     }
 
+    #[test]
+    fn test_start_request_from_older_client() {
+        let json = r#"{"StartRecording":{"title":null}}"#;
+        match serde_json::from_str(json).unwrap() {
+            DaemonRequest::StartRecording { mic_only, .. } => assert!(!mic_only),
+            _ => panic!("Wrong request type"),
+        }
+    }
+
     #[test]
     fn test_response_serialization() {
         let response = DaemonResponse::RecordingStarted {
@@ -74,6 +185,9 @@ mod tests {
             current_meeting_id: None,
             meeting_detected: Some("Zoom".to_string()),
             uptime_seconds: 3600,
+            recording_seconds: None,
+            paused: false,
+            background_jobs: 1,
         };
         let json = serde_json::to_string(&status).unwrap();
         let parsed: DaemonStatus = serde_json::from_str(&json).unwrap();
@@ -82,13 +196,28 @@ mod tests {
         assert!(!parsed.recording);
         assert_eq!(parsed.meeting_detected, Some("Zoom".to_string()));
         assert_eq!(parsed.uptime_seconds, 3600);
+        assert_eq!(parsed.background_jobs, 1);
+
+        // Daemons predating pause support don't send it
+        let json = r#"{"running":true,"recording":true,"current_meeting":null,
+            "current_meeting_id":null,"meeting_detected":null,"uptime_seconds":5}"#;
+        let parsed: DaemonStatus = serde_json::from_str(json).unwrap();
+        assert!(!parsed.paused);
+        assert_eq!(parsed.background_jobs, 0);
     }
 
     #[test]
     fn test_all_request_variants() {
         let requests = vec![
-            DaemonRequest::StartRecording { title: None },
+            DaemonRequest::StartRecording {
+                title: None,
+                auto_stop_secs: None,
+                participants: Vec::new(),
+                mic_only: false,
+            },
             DaemonRequest::StopRecording,
+            DaemonRequest::PauseRecording,
+            DaemonRequest::ResumeRecording,
             DaemonRequest::GetStatus,
             DaemonRequest::Shutdown,
             DaemonRequest::Ping,
@@ -106,6 +235,7 @@ mod tests {
             DaemonResponse::Ok,
             DaemonResponse::Error {
                 message: "test".to_string(),
+                error_kind: DaemonErrorKind::NotRecording,
             },
             DaemonResponse::Status(DaemonStatus {
                 running: true,
@@ -114,6 +244,9 @@ mod tests {
                 current_meeting_id: None,
                 meeting_detected: None,
                 uptime_seconds: 0,
+                recording_seconds: Some(0),
+                paused: true,
+                background_jobs: 0,
             }),
             DaemonResponse::Pong,
             DaemonResponse::RecordingStarted {
@@ -129,4 +262,36 @@ mod tests {
             let _: DaemonResponse = serde_json::from_str(&json).unwrap();
         }
     }
+
+    #[test]
+    fn test_error_kind_serialization() {
+        let response = DaemonResponse::Error {
+            message: "Already recording".to_string(),
+            error_kind: DaemonErrorKind::AlreadyRecording,
+        };
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["Error"]["error_kind"], "already_recording");
+
+        // Older daemons send no kind, newer ones may send kinds this client doesn't know
+        for json in [
+            r#"{"Error":{"message":"x"}}"#,
+            r#"{"Error":{"message":"x","error_kind":"disk_full"}}"#,
+        ] {
+            match serde_json::from_str(json).unwrap() {
+                DaemonResponse::Error { error_kind, .. } => {
+                    assert_eq!(error_kind, DaemonErrorKind::Internal)
+                }
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+
+        assert_eq!(
+            DaemonErrorKind::from_error(&MuesliError::AudioDeviceNotFound("mic".to_string())),
+            DaemonErrorKind::AudioDeviceNotFound
+        );
+        assert_eq!(
+            DaemonErrorKind::from_error(&MuesliError::Api("x".to_string())),
+            DaemonErrorKind::Internal
+        );
+    }
 }
@@ -1,37 +1,45 @@
 use crate::audio::capture::MicCapture;
 use crate::audio::loopback::LoopbackCapture;
 use crate::audio::mixer::mix_streams;
-use crate::audio::recorder::WavRecorder;
+use crate::audio::recorder::{BitDepth, WavRecorder};
 use crate::audio::AudioChunk;
 use crate::config::loader::{database_path, load_config, models_dir, recordings_dir, socket_path};
-use crate::daemon::protocol::{DaemonRequest, DaemonResponse, DaemonStatus};
-use crate::detection::hyprland::{is_hyprland_running, HyprlandMonitor};
-use crate::detection::{DetectionEvent, MeetingApp};
+use crate::config::settings::AudioConfig;
+use crate::daemon::protocol::{DaemonErrorKind, DaemonRequest, DaemonResponse, DaemonStatus};
+use crate::detection::detector::{DetectorEvent, MeetingDetector};
+use crate::detection::hyprland::{is_hyprland_running, HyprlandMonitor, WindowPresence};
+use crate::detection::{DetectionEvent, MeetingApp, WindowInfo};
 use crate::error::{MuesliError, Result};
 use crate::notification;
 use crate::storage::database::Database;
 use crate::storage::Meeting;
 use crate::transcription::models::{ModelManager, WhisperModel};
 use crate::transcription::streaming::{StreamingTranscriber, WhisperStreamingConfig};
-use crate::transcription::whisper::WhisperEngine;
 use crate::transcription::TranscriptSegment;
 use crate::waybar::{update_waybar_status, WaybarStatus};
 use cpal::Stream;
 
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::{UnixListener, UnixStream};
 use tokio::sync::{broadcast, mpsc, Mutex};
 
+/// Everything the daemon tracks. It records one meeting at a time: `recording`,
+/// `current_meeting` and the audio, segment and timer fields belong to that one
+/// recording. They are set by `start_recording_internal` and cleared together
+/// by `stop_recording_internal`, which hands the finished meeting to a
+/// background thread that owns its own copies.
 #[allow(dead_code)]
 pub struct DaemonState {
     pub recording: bool,
     pub current_meeting: Option<Meeting>,
     pub meeting_detected: Option<MeetingApp>,
     pub prompt_active: bool,
+    /// A meeting window was found but `require_audio` is waiting for call audio
+    pub awaiting_audio: bool,
     pub start_time: Instant,
     pub audio_running: Option<Arc<AtomicBool>>,
     pub audio_path: Option<PathBuf>,
@@ -40,6 +48,24 @@ pub struct DaemonState {
     pub segment_rx: Option<std::sync::mpsc::Receiver<TranscriptSegment>>,
     pub meeting_monitor_running: Option<Arc<AtomicBool>>,
     pub detection_tx: Option<mpsc::Sender<DetectionEvent>>,
+    pub recording_deadline: Option<Instant>,
+    pub auto_stop_running: Option<Arc<AtomicBool>>,
+    pub interim_summary_running: Option<Arc<AtomicBool>>,
+    pub waybar_tick_running: Option<Arc<AtomicBool>>,
+    pub disk_guard_running: Option<Arc<AtomicBool>>,
+    /// Last live-preview line of the current recording, kept in the Waybar tooltip
+    pub latest_preview: Option<Arc<std::sync::Mutex<String>>>,
+    /// The current recording is paused: audio is dropped instead of recorded
+    pub paused: bool,
+    /// When the ongoing pause began
+    pub paused_since: Option<Instant>,
+    /// Time spent in earlier pauses of the current recording
+    pub paused_total: std::time::Duration,
+    /// Shared with the capture thread, which drops audio while it is set
+    pub audio_paused: Option<Arc<AtomicBool>>,
+    /// Finished recordings still being transcribed, summarized or compressed.
+    /// Outlives the recording: each background thread holds a [`BackgroundJob`].
+    pub background_jobs: Arc<AtomicUsize>,
 }
 
 impl Default for DaemonState {
@@ -49,6 +75,7 @@ impl Default for DaemonState {
             current_meeting: None,
             meeting_detected: None,
             prompt_active: false,
+            awaiting_audio: false,
             start_time: Instant::now(),
             audio_running: None,
             audio_path: None,
@@ -57,14 +84,124 @@ impl Default for DaemonState {
             segment_rx: None,
             meeting_monitor_running: None,
             detection_tx: None,
+            recording_deadline: None,
+            auto_stop_running: None,
+            interim_summary_running: None,
+            waybar_tick_running: None,
+            disk_guard_running: None,
+            latest_preview: None,
+            paused: false,
+            paused_since: None,
+            paused_total: std::time::Duration::ZERO,
+            audio_paused: None,
+            background_jobs: Arc::new(AtomicUsize::new(0)),
         }
     }
 }
 
+impl DaemonState {
+    /// Whether any recording state is held: a running recording, or one not
+    /// fully torn down. A new recording is only started when this is false, so
+    /// two can never share the per-recording fields.
+    pub fn has_recording(&self) -> bool {
+        self.recording
+            || self.current_meeting.is_some()
+            || self.audio_running.is_some()
+            || self.segment_rx.is_some()
+    }
+
+    /// Reset every per-recording field, stopping any timer still attached
+    fn clear_recording(&mut self) {
+        for running in [
+            self.audio_running.take(),
+            self.meeting_monitor_running.take(),
+            self.auto_stop_running.take(),
+            self.interim_summary_running.take(),
+            self.waybar_tick_running.take(),
+            self.disk_guard_running.take(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            running.store(false, Ordering::Relaxed);
+        }
+        self.recording = false;
+        self.current_meeting = None;
+        self.audio_path = None;
+        self.transcript_segments.clear();
+        self.streaming_enabled = false;
+        self.segment_rx = None;
+        self.recording_deadline = None;
+        self.latest_preview = None;
+        self.paused = false;
+        self.paused_since = None;
+        self.paused_total = std::time::Duration::ZERO;
+        self.audio_paused = None;
+    }
+
+    /// Pause or resume the current recording; doing either twice is a no-op
+    fn set_paused(&mut self, paused: bool) -> Result<()> {
+        if !self.recording {
+            return Err(MuesliError::NotRecording);
+        }
+        if paused == self.paused {
+            return Ok(());
+        }
+
+        if let Some(flag) = &self.audio_paused {
+            flag.store(paused, Ordering::Relaxed);
+        }
+        self.paused = paused;
+        if paused {
+            self.paused_since = Some(Instant::now());
+        } else if let Some(since) = self.paused_since.take() {
+            self.paused_total += since.elapsed();
+        }
+        Ok(())
+    }
+
+    /// Time the current recording has spent paused, the ongoing pause included
+    fn paused_duration(&self) -> std::time::Duration {
+        self.paused_total
+            + self
+                .paused_since
+                .map_or(std::time::Duration::ZERO, |since| since.elapsed())
+    }
+
+    /// How much audio the current recording has captured so far, in seconds
+    fn recorded_secs(&self) -> Option<u64> {
+        self.current_meeting
+            .as_ref()
+            .filter(|_| self.recording)
+            .map(|meeting| {
+                recording_elapsed_secs(meeting).saturating_sub(self.paused_duration().as_secs())
+            })
+    }
+
+    /// Waybar status of the current recording, without the live preview
+    fn recording_waybar_status(&self) -> Option<WaybarStatus> {
+        let meeting = self.current_meeting.as_ref()?;
+        let elapsed = self.recorded_secs()?;
+        Some(if self.paused {
+            WaybarStatus::paused(&meeting.title, elapsed)
+        } else {
+            WaybarStatus::recording(&meeting.title, elapsed)
+        })
+    }
+}
+
 pub async fn run_daemon() -> Result<()> {
     let socket = socket_path()?;
 
     if socket.exists() {
+        // Only a socket nobody answers on is stale; rebinding over a live one
+        // would silently cut off the running daemon
+        if crate::daemon::DaemonClient::ping().await? {
+            return Err(MuesliError::Daemon(format!(
+                "daemon already running on {}. Stop it first (e.g. systemctl --user stop muesli)",
+                socket.display()
+            )));
+        }
         std::fs::remove_file(&socket)?;
     }
 
@@ -75,6 +212,9 @@ pub async fn run_daemon() -> Result<()> {
     let listener = UnixListener::bind(&socket).map_err(MuesliError::Io)?;
 
     tracing::info!("Daemon listening on {:?}", socket);
+    crate::daemon::watchdog::notify_ready();
+    crate::daemon::watchdog::start_watchdog();
+    warn_if_transcription_unavailable();
 
     let state = Arc::new(Mutex::new(DaemonState::default()));
     let shutdown = Arc::new(AtomicBool::new(false));
@@ -89,77 +229,137 @@ pub async fn run_daemon() -> Result<()> {
     let state_for_detection = state.clone();
     let detection_tx_for_handler = detection_tx.clone();
 
+    // Window changes go through the detector, which waits for focus to settle
+    // and reports meeting windows back as MeetingDetected
+    let debounce_ms = load_config()
+        .map(|c| c.detection.debounce_ms)
+        .unwrap_or(500);
+    let (window_tx, window_rx) = mpsc::channel::<WindowInfo>(100);
+    let (detector_tx, mut detector_rx) = mpsc::channel::<DetectorEvent>(100);
+    tokio::spawn(MeetingDetector::new(detector_tx, debounce_ms).run(window_rx));
+    let detection_tx_for_detector = detection_tx.clone();
+    tokio::spawn(async move {
+        while let Some(event) = detector_rx.recv().await {
+            // Focus leaving a meeting window doesn't end the meeting; the window
+            // monitor reports when it closes
+            if let DetectorEvent::MeetingDetected { app, window } = event {
+                let _ = detection_tx_for_detector
+                    .send(DetectionEvent::MeetingDetected { app, window })
+                    .await;
+            }
+        }
+    });
+
     tokio::spawn(async move {
         while let Some(event) = detection_rx.recv().await {
             match event {
                 DetectionEvent::MeetingDetected { app, window } => {
-                    {
-                        let mut state = state_for_detection.lock().await;
-                        state.meeting_detected = Some(app);
+                    tracing::debug!("Meeting app detected: {}", app);
+                    let (current_detected, is_recording, prompt_active) = {
+                        let state = state_for_detection.lock().await;
+                        (state.meeting_detected, state.recording, state.prompt_active)
+                    };
+
+                    if is_recording || prompt_active {
+                        continue;
                     }
 
-                    let config = crate::config::loader::load_config();
-                    let auto_prompt = config
-                        .as_ref()
-                        .map(|c| c.detection.auto_prompt)
-                        .unwrap_or(false);
+                    if current_detected != Some(app) {
+                        if !call_audio_ready(
+                            app,
+                            &window,
+                            &state_for_detection,
+                            &detection_tx_for_handler,
+                        )
+                        .await
+                        {
+                            continue;
+                        }
+                        tracing::info!("New meeting detected ({}), showing prompt", app);
+                        {
+                            let mut state = state_for_detection.lock().await;
+                            state.meeting_detected = Some(app);
+                            state.prompt_active = true;
+                        }
 
-                    if auto_prompt {
-                        let state_clone = state_for_detection.clone();
-                        let window_title = window.title.clone();
-                        let timeout = config
-                            .map(|c| c.detection.prompt_timeout_secs)
-                            .unwrap_or(30);
-                        let tx_for_monitor = detection_tx_for_handler.clone();
+                        let config = crate::config::loader::load_config();
+                        let auto_prompt = config
+                            .as_ref()
+                            .map(|c| c.detection.auto_prompt)
+                            .unwrap_or(false);
+
+                        if auto_prompt {
+                            let state_clone = state_for_detection.clone();
+                            let window_title = window.title.clone();
+                            let timeout = config
+                                .map(|c| c.detection.prompt_timeout_secs)
+                                .unwrap_or(30);
+                            let tx_for_monitor = detection_tx_for_handler.clone();
+
+                            tokio::spawn(async move {
+                                let title_for_prompt = window_title.clone();
+                                let response = tokio::task::spawn_blocking(move || {
+                                    notification::prompt_meeting_detected(
+                                        app,
+                                        &title_for_prompt,
+                                        timeout,
+                                    )
+                                })
+                                .await;
+
+                                let mut state = state_clone.lock().await;
+                                state.prompt_active = false;
+
+                                match response {
+                                    Ok(notification::PromptResponse::Record) => {
+                                        tracing::info!(
+                                            "User clicked Record, starting recording..."
+                                        );
+                                    }
+                                    Ok(notification::PromptResponse::Skip) => {
+                                        tracing::info!("User clicked Skip");
+                                        return;
+                                    }
+                                    Ok(notification::PromptResponse::Closed) => {
+                                        tracing::debug!("Notification was closed/timed out");
+                                        return;
+                                    }
+                                    Err(e) => {
+                                        tracing::error!("Prompt task failed: {:?}", e);
+                                        return;
+                                    }
+                                }
 
-                        tokio::spawn(async move {
-                            let title_for_prompt = window_title.clone();
-                            let response = tokio::task::spawn_blocking(move || {
-                                notification::prompt_meeting_detected(
-                                    app,
-                                    &title_for_prompt,
-                                    timeout,
-                                )
-                            })
-                            .await;
-
-                            match response {
-                                Ok(notification::PromptResponse::Record) => {
-                                    tracing::info!("User clicked Record, starting recording...");
-                                    let mut state = state_clone.lock().await;
-                                    if !state.recording {
-                                        let title = "Untitled Meeting".to_string();
-                                        match start_recording_internal(&mut state, title).await {
-                                            Ok(id) => {
-                                                tracing::info!("Recording started: {}", id);
-                                                state.meeting_monitor_running =
-                                                    Some(start_meeting_window_monitor(
-                                                        app,
-                                                        tx_for_monitor,
-                                                    ));
-                                            }
-                                            Err(e) => tracing::error!(
-                                                "Failed to auto-start recording: {}",
-                                                e
-                                            ),
+                                if !state.has_recording() {
+                                    let title = auto_record_title(app, &window_title);
+                                    match start_recording_internal(
+                                        &mut state,
+                                        title,
+                                        Vec::new(),
+                                        false,
+                                    )
+                                    .await
+                                    {
+                                        Ok(id) => {
+                                            tracing::info!("Recording started: {}", id);
+                                            state.meeting_monitor_running =
+                                                start_meeting_window_monitor(app, tx_for_monitor);
+                                            arm_auto_stop(&state_clone, &mut state, None);
+                                            arm_interim_summaries(&state_clone, &mut state);
+                                            arm_waybar_ticker(&state_clone, &mut state);
+                                            arm_disk_space_guard(&state_clone, &mut state);
+                                        }
+                                        Err(e) => {
+                                            tracing::error!("Failed to auto-start recording: {}", e)
                                         }
-                                    } else {
-                                        tracing::info!("Already recording, skipping");
                                     }
+                                } else {
+                                    tracing::info!("Already recording, skipping");
                                 }
-                                Ok(notification::PromptResponse::Skip) => {
-                                    tracing::info!("User clicked Skip");
-                                }
-                                Ok(notification::PromptResponse::Closed) => {
-                                    tracing::debug!("Notification was closed/timed out");
-                                }
-                                Err(e) => {
-                                    tracing::error!("Prompt task failed: {:?}", e);
-                                }
-                            }
-                        });
-                    } else {
-                        let _ = notification::notify_meeting_detected(app, &window.title);
+                            });
+                        } else {
+                            let _ = notification::notify_meeting_detected(app, &window.title);
+                        }
                     }
                 }
                 DetectionEvent::MeetingEnded { app } => {
@@ -174,84 +374,7 @@ pub async fn run_daemon() -> Result<()> {
                         window.class,
                         window.title
                     );
-                    let detected_app = crate::detection::patterns::detect_meeting_app(
-                        &window.class,
-                        &window.title,
-                    );
-
-                    let (current_detected, is_recording, prompt_active) = {
-                        let state = state_for_detection.lock().await;
-                        (state.meeting_detected, state.recording, state.prompt_active)
-                    };
-
-                    if let Some(app) = detected_app {
-                        tracing::debug!("Meeting app detected: {}", app);
-
-                        if is_recording || prompt_active {
-                            continue;
-                        }
-
-                        if current_detected != Some(app) {
-                            tracing::info!("New meeting detected ({}), showing prompt", app);
-                            {
-                                let mut state = state_for_detection.lock().await;
-                                state.meeting_detected = Some(app);
-                                state.prompt_active = true;
-                            }
-
-                            let config = crate::config::loader::load_config();
-                            let auto_prompt = config
-                                .as_ref()
-                                .map(|c| c.detection.auto_prompt)
-                                .unwrap_or(false);
-
-                            if auto_prompt {
-                                let state_clone = state_for_detection.clone();
-                                let window_title = window.title.clone();
-                                let timeout = config
-                                    .map(|c| c.detection.prompt_timeout_secs)
-                                    .unwrap_or(30);
-                                let tx_for_monitor = detection_tx_for_handler.clone();
-
-                                tokio::spawn(async move {
-                                    let title_for_prompt = window_title.clone();
-                                    let response = tokio::task::spawn_blocking(move || {
-                                        notification::prompt_meeting_detected(
-                                            app,
-                                            &title_for_prompt,
-                                            timeout,
-                                        )
-                                    })
-                                    .await;
-
-                                    let mut state = state_clone.lock().await;
-                                    state.prompt_active = false;
-
-                                    if let Ok(notification::PromptResponse::Record) = response {
-                                        if !state.recording {
-                                            let title = "Untitled Meeting".to_string();
-                                            match start_recording_internal(&mut state, title).await
-                                            {
-                                                Ok(_) => {
-                                                    state.meeting_monitor_running =
-                                                        Some(start_meeting_window_monitor(
-                                                            app,
-                                                            tx_for_monitor,
-                                                        ));
-                                                }
-                                                Err(e) => tracing::error!(
-                                                    "Failed to auto-start recording: {}",
-                                                    e
-                                                ),
-                                            }
-                                        }
-                                    }
-                                });
-                            } else {
-                                let _ = notification::notify_meeting_detected(app, &window.title);
-                            }
-                        }
-                    }
+                    let _ = window_tx.send(window).await;
                 }
                 DetectionEvent::MeetingWindowClosed { app } => {
                     tracing::info!(
@@ -261,84 +384,10 @@ pub async fn run_daemon() -> Result<()> {
                     let mut state = state_for_detection.lock().await;
                     if state.recording && state.meeting_detected == Some(app) {
                         tracing::info!("Auto-stopping recording due to meeting window closure");
-                        if let Some(running) = state.meeting_monitor_running.take() {
-                            running.store(false, Ordering::Relaxed);
-                        }
-                        let meeting_id = state
-                            .current_meeting
-                            .as_ref()
-                            .map(|m| m.id.to_string())
-                            .unwrap_or_default();
-
-                        let audio_path = state.audio_path.clone();
-                        let audio_running = state.audio_running.take();
-                        let segment_rx = state.segment_rx.take();
-                        let streaming_enabled = state.streaming_enabled;
-
-                        if let Some(running) = audio_running {
-                            running.store(false, Ordering::Relaxed);
-                        }
-
-                        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-
-                        let segments = collect_streaming_segments(segment_rx);
-
-                        if let Some(meeting) = &mut state.current_meeting {
-                            let ended = chrono::Utc::now();
-                            meeting.ended_at = Some(ended);
-                            let duration_secs =
-                                (ended.timestamp() - meeting.started_at.timestamp()) as u64;
-                            meeting.duration_seconds = Some(duration_secs);
-                            meeting.status = crate::storage::MeetingStatus::Processing;
-
-                            if let Some(ref path) = audio_path {
-                                meeting.audio_path = Some(path.clone());
-                            }
-
-                            if let Ok(db_path) = database_path() {
-                                if let Ok(db) = Database::open(&db_path) {
-                                    let _ = db.update_meeting(meeting);
-                                    if !segments.is_empty() {
-                                        let _ =
-                                            db.insert_transcript_segments(&meeting.id, &segments);
-                                    }
-                                }
-                            }
-
-                            let duration_mins = meeting.duration_seconds.unwrap_or(0) / 60;
-                            let meeting_title = meeting.title.clone();
-                            let meeting_id_clone = meeting_id.clone();
-
-                            let _ = notification::notify_recording_stopped(
-                                &meeting_title,
-                                duration_mins,
-                            );
-
-                            if let Ok(cfg) = load_config() {
-                                notification::play_recording_stop(&cfg.audio_cues);
-                                update_waybar_status(&cfg.waybar, &WaybarStatus::idle());
-                            }
-
-                            if let Some(path) = audio_path {
-                                if streaming_enabled && !segments.is_empty() {
-                                    std::thread::spawn(move || {
-                                        run_background_diarization(meeting_id_clone, path, true);
-                                    });
-                                } else {
-                                    std::thread::spawn(move || {
-                                        run_background_diarization_and_summarization(
-                                            meeting_id_clone,
-                                            path,
-                                        );
-                                    });
-                                }
-                            }
+                        match stop_recording_internal(&mut state).await {
+                            Ok(meeting_id) => tracing::info!("Stopped recording {}", meeting_id),
+                            Err(e) => tracing::error!("Failed to stop recording: {}", e),
                         }
-
-                        state.recording = false;
-                        state.current_meeting = None;
-                        state.audio_path = None;
-                        state.streaming_enabled = false;
                         state.meeting_detected = None;
                     }
                 }
@@ -348,9 +397,10 @@ pub async fn run_daemon() -> Result<()> {
 
     if is_hyprland_running() {
         tracing::info!("Hyprland detected, starting window monitor");
-        let poll_interval = crate::config::loader::load_config()
-            .map(|c| c.detection.poll_interval_secs)
-            .unwrap_or(5);
+        let (poll_interval, ipc_retries) = crate::config::loader::load_config()
+            .map(|c| (c.detection.poll_interval_secs, c.detection.ipc_retries))
+            .unwrap_or((5, 3));
+        crate::detection::hyprland::set_ipc_retries(ipc_retries);
         let monitor = HyprlandMonitor::with_poll_interval(detection_tx, poll_interval);
         tokio::spawn(async move {
             tracing::info!("Hyprland monitor task started");
@@ -384,13 +434,14 @@ pub async fn run_daemon() -> Result<()> {
         }
     }
 
+    crate::daemon::watchdog::notify_stopping();
     let _ = std::fs::remove_file(&socket);
     tracing::info!("Daemon shutdown complete");
 
     Ok(())
 }
 
-async fn handle_connection(
+pub(crate) async fn handle_connection(
     stream: UnixStream,
     state: Arc<Mutex<DaemonState>>,
     shutdown: Arc<AtomicBool>,
@@ -433,15 +484,29 @@ async fn handle_request(
                 current_meeting_id: state.current_meeting.as_ref().map(|m| m.id.to_string()),
                 meeting_detected: state.meeting_detected.map(|app| app.to_string()),
                 uptime_seconds: state.start_time.elapsed().as_secs(),
+                recording_seconds: state.recorded_secs(),
+                paused: state.paused,
+                background_jobs: state.background_jobs.load(Ordering::SeqCst),
             })
         }
 
-        DaemonRequest::StartRecording { title } => {
+        DaemonRequest::StartRecording {
+            title,
+            auto_stop_secs,
+            participants,
+            mic_only,
+        } => {
+            let state_arc = state;
             let mut state = state.lock().await;
             let title = title.unwrap_or_else(|| "Untitled Meeting".to_string());
 
-            match start_recording_internal(&mut state, title).await {
+            match start_recording_internal(&mut state, title, participants, mic_only).await {
                 Ok(meeting_id) => {
+                    arm_auto_stop(state_arc, &mut state, auto_stop_secs);
+                    arm_interim_summaries(state_arc, &mut state);
+                    arm_waybar_ticker(state_arc, &mut state);
+                    arm_disk_space_guard(state_arc, &mut state);
+
                     tracing::info!(
                         "Recording started, checking for meeting windows. meeting_detected={:?}",
                         state.meeting_detected
@@ -452,8 +517,7 @@ async fn handle_request(
                         state.meeting_detected = Some(app);
                         if let Some(tx) = state.detection_tx.clone() {
                             tracing::info!("Starting meeting window monitor for manual recording (detected: {})", app);
-                            state.meeting_monitor_running =
-                                Some(start_meeting_window_monitor(app, tx));
+                            state.meeting_monitor_running = start_meeting_window_monitor(app, tx);
                         } else {
                             tracing::warn!("No detection_tx available, cannot start monitor");
                         }
@@ -464,104 +528,33 @@ async fn handle_request(
                 }
                 Err(MuesliError::AlreadyRecording) => DaemonResponse::Error {
                     message: "Already recording".to_string(),
+                    error_kind: DaemonErrorKind::AlreadyRecording,
                 },
                 Err(e) => DaemonResponse::Error {
                     message: format!("Failed to start recording: {}", e),
+                    error_kind: DaemonErrorKind::from_error(&e),
                 },
             }
         }
 
         DaemonRequest::StopRecording => {
             let mut state = state.lock().await;
-            if !state.recording {
-                return DaemonResponse::Error {
+            match stop_recording_internal(&mut state).await {
+                Ok(meeting_id) => DaemonResponse::RecordingStopped { meeting_id },
+                Err(MuesliError::NotRecording) => DaemonResponse::Error {
                     message: "Not recording".to_string(),
-                };
-            }
-
-            let meeting_id = state
-                .current_meeting
-                .as_ref()
-                .map(|m| m.id.to_string())
-                .unwrap_or_default();
-
-            let meeting_id_clone = meeting_id.clone();
-
-            let audio_path = state.audio_path.clone();
-            let audio_running = state.audio_running.take();
-            let segment_rx = state.segment_rx.take();
-            let streaming_enabled = state.streaming_enabled;
-
-            if let Some(running) = state.meeting_monitor_running.take() {
-                running.store(false, Ordering::Relaxed);
-            }
-
-            if let Some(running) = audio_running {
-                running.store(false, Ordering::Relaxed);
-            }
-
-            tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-
-            let segments = collect_streaming_segments(segment_rx);
-
-            if let Some(meeting) = &mut state.current_meeting {
-                let ended = chrono::Utc::now();
-                meeting.ended_at = Some(ended);
-                let duration_secs = (ended.timestamp() - meeting.started_at.timestamp()) as u64;
-                meeting.duration_seconds = Some(duration_secs);
-                meeting.status = crate::storage::MeetingStatus::Processing;
-
-                if let Some(ref path) = audio_path {
-                    meeting.audio_path = Some(path.clone());
-                }
-
-                if let Ok(db_path) = database_path() {
-                    if let Ok(db) = Database::open(&db_path) {
-                        if let Err(e) = db.update_meeting(meeting) {
-                            tracing::error!("Failed to update meeting in database: {}", e);
-                        }
-
-                        if !segments.is_empty() {
-                            if let Err(e) = db.insert_transcript_segments(&meeting.id, &segments) {
-                                tracing::error!("Failed to save transcript segments: {}", e);
-                            } else {
-                                tracing::info!("Saved {} transcript segments", segments.len());
-                            }
-                        }
-                    }
-                }
-
-                let duration_mins = meeting.duration_seconds.unwrap_or(0) / 60;
-                let meeting_title = meeting.title.clone();
-
-                let _ = notification::notify_recording_stopped(&meeting_title, duration_mins);
-
-                if let Ok(cfg) = load_config() {
-                    notification::play_recording_stop(&cfg.audio_cues);
-                    update_waybar_status(&cfg.waybar, &WaybarStatus::idle());
-                }
-
-                if streaming_enabled && !segments.is_empty() {
-                    if let Some(path) = audio_path {
-                        std::thread::spawn(move || {
-                            run_background_diarization(meeting_id_clone, path, true);
-                        });
-                    }
-                } else if let Some(path) = audio_path {
-                    std::thread::spawn(move || {
-                        run_background_diarization_and_summarization(meeting_id_clone, path);
-                    });
-                }
+                    error_kind: DaemonErrorKind::NotRecording,
+                },
+                Err(e) => DaemonResponse::Error {
+                    message: format!("Failed to stop recording: {}", e),
+                    error_kind: DaemonErrorKind::from_error(&e),
+                },
             }
-
-            state.recording = false;
-            state.current_meeting = None;
-            state.audio_path = None;
-            state.streaming_enabled = false;
-
-            DaemonResponse::RecordingStopped { meeting_id }
         }
 
+        DaemonRequest::PauseRecording => set_recording_paused(state, true).await,
+        DaemonRequest::ResumeRecording => set_recording_paused(state, false).await,
+
         DaemonRequest::Shutdown => {
             shutdown.store(true, Ordering::Relaxed);
             DaemonResponse::Ok
@@ -569,6 +562,31 @@ async fn handle_request(
     }
 }
 
+async fn set_recording_paused(state: &Arc<Mutex<DaemonState>>, paused: bool) -> DaemonResponse {
+    let mut state = state.lock().await;
+    match state.set_paused(paused) {
+        Ok(()) => {
+            tracing::info!("Recording {}", if paused { "paused" } else { "resumed" });
+            if let (Ok(cfg), Some(status)) = (load_config(), state.recording_waybar_status()) {
+                update_waybar_status(&cfg.waybar, &status);
+            }
+            DaemonResponse::Ok
+        }
+        Err(MuesliError::NotRecording) => DaemonResponse::Error {
+            message: "Not recording".to_string(),
+            error_kind: DaemonErrorKind::NotRecording,
+        },
+        Err(e) => DaemonResponse::Error {
+            message: format!(
+                "Failed to {} recording: {}",
+                if paused { "pause" } else { "resume" },
+                e
+            ),
+            error_kind: DaemonErrorKind::from_error(&e),
+        },
+    }
+}
+
 /// Collect transcript segments by blocking until the recording thread finishes.
 ///
 /// WARNING: Do NOT add a timeout here. Large models on CPU (e.g. large-v3-turbo)
@@ -591,18 +609,153 @@ fn collect_streaming_segments(
     segments
 }
 
-async fn start_recording_internal(state: &mut DaemonState, title: String) -> Result<String> {
-    if state.recording {
+async fn stop_recording_internal(state: &mut DaemonState) -> Result<String> {
+    if !state.recording {
+        return Err(MuesliError::NotRecording);
+    }
+
+    state.recording_deadline = None;
+    if let Some(running) = state.auto_stop_running.take() {
+        running.store(false, Ordering::Relaxed);
+    }
+
+    let meeting_id = state
+        .current_meeting
+        .as_ref()
+        .map(|m| m.id.to_string())
+        .unwrap_or_default();
+
+    let meeting_id_clone = meeting_id.clone();
+
+    let audio_path = state.audio_path.clone();
+    let paused_secs = state.paused_duration().as_secs();
+    let audio_running = state.audio_running.take();
+    let segment_rx = state.segment_rx.take();
+    let streaming_enabled = state.streaming_enabled;
+
+    if let Some(running) = state.interim_summary_running.take() {
+        running.store(false, Ordering::Relaxed);
+    }
+
+    if let Some(running) = state.waybar_tick_running.take() {
+        running.store(false, Ordering::Relaxed);
+    }
+
+    if let Some(running) = state.disk_guard_running.take() {
+        running.store(false, Ordering::Relaxed);
+    }
+
+    if let Some(running) = state.meeting_monitor_running.take() {
+        running.store(false, Ordering::Relaxed);
+    }
+
+    if let Some(running) = audio_running {
+        running.store(false, Ordering::Relaxed);
+    }
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+    let mut segments = std::mem::take(&mut state.transcript_segments);
+    segments.extend(collect_streaming_segments(segment_rx));
+
+    if let Some(meeting) = &mut state.current_meeting {
+        let ended = chrono::Utc::now();
+        meeting.ended_at = Some(ended);
+        let duration_secs = (ended.timestamp() - meeting.started_at.timestamp()) as u64;
+        // Paused time isn't in the recording
+        meeting.duration_seconds = Some(duration_secs.saturating_sub(paused_secs));
+        meeting.status = crate::storage::MeetingStatus::Processing;
+        // A manual recording may only have found its meeting window after starting
+        if let Some(app) = state.meeting_detected {
+            meeting.detected_app = Some(app.to_string());
+        }
+
+        if let Some(ref path) = audio_path {
+            meeting.audio_path = Some(path.clone());
+        }
+
+        if let Ok(db_path) = database_path() {
+            if let Ok(db) = Database::open(&db_path) {
+                if let Err(e) = db.update_meeting(meeting) {
+                    tracing::error!("Failed to update meeting in database: {}", e);
+                }
+
+                if !segments.is_empty() {
+                    // Not diarized yet; merged once speakers are labeled
+                    if let Err(e) = db.insert_transcript_segments(&meeting.id, &segments, 0) {
+                        tracing::error!("Failed to save transcript segments: {}", e);
+                    } else {
+                        tracing::info!("Saved {} transcript segments", segments.len());
+                    }
+                }
+            }
+        }
+
+        let duration_mins = meeting.duration_seconds.unwrap_or(0) / 60;
+        let meeting_title = meeting.title.clone();
+
+        let _ = notification::notify_recording_stopped(&meeting_title, duration_mins);
+
+        if let Ok(cfg) = load_config() {
+            notification::play_recording_stop(&cfg.audio_cues);
+            update_waybar_status(&cfg.waybar, &WaybarStatus::idle());
+        }
+
+        if streaming_enabled && !segments.is_empty() {
+            if let Some(path) = audio_path {
+                let job = BackgroundJob::start(&state.background_jobs);
+                std::thread::spawn(move || {
+                    run_background_diarization(meeting_id_clone.clone(), path, true);
+                    compress_finished_recording(&meeting_id_clone);
+                    drop(job);
+                });
+            }
+        } else if let Some(path) = audio_path {
+            let job = BackgroundJob::start(&state.background_jobs);
+            std::thread::spawn(move || {
+                run_background_diarization_and_summarization(meeting_id_clone.clone(), path);
+                compress_finished_recording(&meeting_id_clone);
+                drop(job);
+            });
+        }
+    }
+
+    state.clear_recording();
+
+    Ok(meeting_id)
+}
+
+async fn start_recording_internal(
+    state: &mut DaemonState,
+    title: String,
+    participants: Vec<String>,
+    mic_only: bool,
+) -> Result<String> {
+    if state.has_recording() {
         return Err(MuesliError::AlreadyRecording);
     }
 
     let mut meeting = Meeting::new(title.clone());
+    meeting.participants = participants;
+    meeting.detected_app = state.meeting_detected.map(|app| app.to_string());
+    meeting.template = state.meeting_detected.and_then(|app| {
+        load_config()
+            .ok()?
+            .detection
+            .app_templates
+            .get(app.config_key())
+            .cloned()
+    });
     let meeting_id = meeting.id.to_string();
 
     let audio_path = setup_recording_path(&meeting_id).await?;
     meeting.audio_path = Some(audio_path.clone());
 
-    start_audio_recording(state, audio_path.clone()).await?;
+    let mut audio_config = load_config().map(|c| c.audio).unwrap_or_default();
+    if mic_only {
+        audio_config.capture_system_audio = false;
+    }
+    start_audio_recording(state, audio_path.clone(), &title, audio_config).await?;
     tracing::info!("Audio recording started for meeting {}", meeting_id);
 
     if let Ok(db_path) = database_path() {
@@ -632,9 +785,16 @@ async fn setup_recording_path(meeting_id: &str) -> Result<PathBuf> {
     Ok(recordings_dir.join(format!("{}.wav", meeting_id)))
 }
 
-async fn start_audio_recording(state: &mut DaemonState, audio_path: PathBuf) -> Result<()> {
+async fn start_audio_recording(
+    state: &mut DaemonState,
+    audio_path: PathBuf,
+    title: &str,
+    audio_config: AudioConfig,
+) -> Result<()> {
     let audio_running = Arc::new(AtomicBool::new(true));
     let audio_running_task = audio_running.clone();
+    let audio_paused = Arc::new(AtomicBool::new(false));
+    let audio_paused_task = audio_paused.clone();
     let audio_path_task = audio_path.clone();
 
     let streaming_backend = select_streaming_backend();
@@ -651,6 +811,19 @@ async fn start_audio_recording(state: &mut DaemonState, audio_path: PathBuf) ->
         }
     }
 
+    let latest_preview = Arc::new(std::sync::Mutex::new(String::new()));
+    state.latest_preview = Some(latest_preview.clone());
+    let preview = load_config().ok().and_then(|cfg| {
+        (streaming_enabled && cfg.daemon.live_preview_secs > 0).then(|| {
+            LivePreview::new(
+                title.to_string(),
+                cfg.daemon.live_preview_secs,
+                cfg.waybar.clone(),
+                latest_preview,
+            )
+        })
+    });
+
     let (segment_tx, segment_rx) = std::sync::mpsc::channel::<TranscriptSegment>();
 
     std::thread::spawn(move || {
@@ -659,14 +832,18 @@ async fn start_audio_recording(state: &mut DaemonState, audio_path: PathBuf) ->
             run_recording_task(
                 audio_path_task,
                 audio_running_task,
+                audio_paused_task,
+                audio_config,
                 streaming_backend,
                 segment_tx,
+                preview,
             )
             .await;
         });
     });
 
     state.audio_running = Some(audio_running);
+    state.audio_paused = Some(audio_paused);
     state.audio_path = Some(audio_path);
     state.streaming_enabled = streaming_enabled;
     state.segment_rx = Some(segment_rx);
@@ -674,6 +851,65 @@ async fn start_audio_recording(state: &mut DaemonState, audio_path: PathBuf) ->
     Ok(())
 }
 
+/// Record to `audio_path` on a dedicated thread until `is_running` is cleared,
+/// without the socket server or live transcription (`muesli record`)
+pub fn record_in_process(
+    audio_path: PathBuf,
+    is_running: Arc<AtomicBool>,
+) -> std::thread::JoinHandle<()> {
+    let audio_config = load_config().map(|c| c.audio).unwrap_or_default();
+    std::thread::spawn(move || {
+        let (segment_tx, _segment_rx) = std::sync::mpsc::channel::<TranscriptSegment>();
+        let rt = tokio::runtime::Runtime::new().expect("Failed to create runtime");
+        rt.block_on(run_recording_task(
+            audio_path,
+            is_running,
+            Arc::new(AtomicBool::new(false)),
+            audio_config,
+            None,
+            segment_tx,
+            None,
+        ));
+    })
+}
+
+/// Startup check: recordings still work without a transcription model, but
+/// every one of them would end up without a transcript, so say so up front.
+fn warn_if_transcription_unavailable() {
+    let (Ok(config), Ok(dir)) = (load_config(), models_dir()) else {
+        return;
+    };
+    if let Some(problem) = transcription_setup_problem(&config, &ModelManager::new(dir)) {
+        tracing::warn!(
+            "!!! {} Recordings will be saved but not transcribed.",
+            problem
+        );
+        let _ = notification::notify_error("Muesli: transcription unavailable", &problem);
+    }
+}
+
+fn transcription_setup_problem(
+    config: &crate::config::settings::MuesliConfig,
+    manager: &ModelManager,
+) -> Option<String> {
+    if config.transcription.engine != "whisper" {
+        return Some(format!(
+            "Transcription engine '{}' is not supported by the daemon (expected \"whisper\").",
+            config.transcription.engine
+        ));
+    }
+
+    let name = config.transcription.effective_model();
+    let model = WhisperModel::parse(name).unwrap_or(WhisperModel::Base);
+    if manager.model_exists(model) {
+        return None;
+    }
+    Some(format!(
+        "Whisper model '{}' is not downloaded. Run: muesli models whisper download {}",
+        name, model
+    ))
+}
+
 fn select_streaming_backend() -> Option<WhisperStreamingConfig> {
     let models_dir = models_dir().ok()?;
 
@@ -690,19 +926,112 @@ fn select_streaming_backend() -> Option<WhisperStreamingConfig> {
         Some(WhisperStreamingConfig {
             model_path: whisper_manager.model_path(whisper_model),
             use_gpu: config.transcription.use_gpu,
+            feed_chunk_ms: config.transcription.stream_chunk_ms,
         })
     } else {
         None
     }
 }
 
+/// Longest transcript line shown in a live preview
+const PREVIEW_MAX_CHARS: usize = 120;
+
+/// Throttled "last transcribed line" feedback while recording
+struct LivePreview {
+    title: String,
+    interval: std::time::Duration,
+    started: Instant,
+    last_shown: Option<Instant>,
+    waybar: crate::config::settings::WaybarConfig,
+    /// Shared with the Waybar ticker so its refreshes keep the line
+    latest: Arc<std::sync::Mutex<String>>,
+}
+
+impl LivePreview {
+    fn new(
+        title: String,
+        interval_secs: u64,
+        waybar: crate::config::settings::WaybarConfig,
+        latest: Arc<std::sync::Mutex<String>>,
+    ) -> Self {
+        Self {
+            title,
+            interval: std::time::Duration::from_secs(interval_secs),
+            started: Instant::now(),
+            last_shown: None,
+            waybar,
+            latest,
+        }
+    }
+
+    fn due(&self, now: Instant) -> bool {
+        match self.last_shown {
+            Some(last) => now.duration_since(last) >= self.interval,
+            None => true,
+        }
+    }
+
+    /// Leave audio dropped while paused out of the elapsed time
+    fn skip(&mut self, chunk: &AudioChunk) {
+        self.started += std::time::Duration::from_millis(chunk.duration_ms());
+    }
+
+    fn offer(&mut self, segment: &TranscriptSegment) {
+        let now = Instant::now();
+        let line = preview_line(&segment.text, PREVIEW_MAX_CHARS);
+        if line.is_empty() || !self.due(now) {
+            return;
+        }
+        self.last_shown = Some(now);
+        if let Ok(mut latest) = self.latest.lock() {
+            latest.clone_from(&line);
+        }
+
+        let _ = notification::notify_transcript_preview(&self.title, &line);
+        let elapsed = now.duration_since(self.started).as_secs();
+        update_waybar_status(
+            &self.waybar,
+            &WaybarStatus::recording(&self.title, elapsed).with_preview(&line),
+        );
+    }
+}
+
+/// Single-line, length-capped form of a transcript segment for previews
+fn preview_line(text: &str, max_chars: usize) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() <= max_chars {
+        return line;
+    }
+    let mut truncated: String = line.chars().take(max_chars.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Open the system audio source with `open` (given `[audio] device_loopback`),
+/// or `None` without trying when system audio capture is off
+fn system_audio_source<T>(
+    audio_config: &AudioConfig,
+    open: impl FnOnce(Option<&str>) -> Result<T>,
+) -> Option<Result<T>> {
+    audio_config
+        .capture_system_audio
+        .then(|| open(audio_config.device_loopback.as_deref()))
+}
+
+/// Capture into `audio_path` (and the streaming transcriber) until
+/// `is_running` is cleared. Audio arriving while `is_paused` is set is dropped,
+/// so the file and transcript timestamps run on without a gap for the pause.
 async fn run_recording_task(
     audio_path: PathBuf,
     is_running: Arc<AtomicBool>,
+    is_paused: Arc<AtomicBool>,
+    audio_config: AudioConfig,
     streaming_backend: Option<WhisperStreamingConfig>,
     segment_tx: std::sync::mpsc::Sender<TranscriptSegment>,
+    mut preview: Option<LivePreview>,
 ) {
-    let mut recorder = match WavRecorder::new(&audio_path) {
+    let bit_depth = BitDepth::parse(&audio_config.bit_depth).unwrap_or_default();
+    let mut recorder = match WavRecorder::with_bit_depth(&audio_path, bit_depth) {
         Ok(rec) => rec,
         Err(e) => {
             tracing::error!("Failed to create WAV recorder: {}", e);
@@ -710,7 +1039,7 @@ async fn run_recording_task(
         }
     };
 
-    let transcriber =
+    let mut transcriber =
         streaming_backend.and_then(|backend| match StreamingTranscriber::new(backend) {
             Ok(t) => {
                 tracing::info!("Streaming transcriber initialized");
@@ -725,13 +1054,16 @@ async fn run_recording_task(
             }
         });
 
-    let mic_capture = match MicCapture::from_default() {
+    let mic_capture = match MicCapture::from_config(audio_config.device_mic.as_deref()) {
         Ok(capture) => capture,
         Err(e) => {
             tracing::error!("Failed to initialize microphone: {}", e);
             return;
         }
     };
+    if let Ok(device) = mic_capture.device_info() {
+        tracing::info!("Recording microphone: {}", device.name);
+    }
 
     let (mic_stream, mic_rx) = match mic_capture.start(is_running.clone()) {
         Ok(result) => result,
@@ -741,12 +1073,18 @@ async fn run_recording_task(
         }
     };
 
-    let loopback_capture_result = LoopbackCapture::find_monitor();
+    let loopback_capture_result = system_audio_source(&audio_config, LoopbackCapture::from_config);
+    if let Some(Ok(Ok(device))) = loopback_capture_result
+        .as_ref()
+        .map(|result| result.as_ref().map(|capture| capture.device_info()))
+    {
+        tracing::info!("Recording system audio from: {}", device.name);
+    }
 
     let (loopback_stream_opt, loopback_rx_opt): (
         Option<Stream>,
         Option<broadcast::Receiver<AudioChunk>>,
-    ) = if let Ok(loopback_capture) = loopback_capture_result {
+    ) = if let Some(Ok(loopback_capture)) = loopback_capture_result {
         match loopback_capture.start(is_running.clone()) {
             Ok((stream, rx)) => {
                 tracing::info!("Loopback capture started successfully");
@@ -760,17 +1098,30 @@ async fn run_recording_task(
                 (None, None)
             }
         }
-    } else {
+    } else if audio_config.capture_system_audio {
         tracing::info!("No loopback device available. Recording microphone only.");
         (None, None)
+    } else {
+        tracing::info!("System audio capture is off. Recording microphone only.");
+        (None, None)
     };
 
     let (mixed_tx, mut mixed_rx) = broadcast::channel::<AudioChunk>(100);
     let mut forwarded_count: usize = 0;
 
     if let Some(loopback_rx) = loopback_rx_opt {
+        let (mic_gain, loopback_gain) = (audio_config.mic_gain, audio_config.loopback_gain);
         let _mixer_handle = tokio::spawn(async move {
-            mix_streams(mic_rx, loopback_rx, mixed_tx, 16000, 1).await;
+            mix_streams(
+                mic_rx,
+                loopback_rx,
+                mixed_tx,
+                16000,
+                1,
+                mic_gain,
+                loopback_gain,
+            )
+            .await;
         });
 
         loop {
@@ -782,12 +1133,21 @@ async fn run_recording_task(
                 .await
             {
                 Ok(Ok(chunk)) => {
+                    if is_paused.load(Ordering::Relaxed) {
+                        if let Some(p) = preview.as_mut() {
+                            p.skip(&chunk);
+                        }
+                        continue;
+                    }
                     if let Err(e) = recorder.write_chunk(&chunk) {
                         tracing::error!("Failed to write audio chunk: {}", e);
                     }
-                    if let Some(ref t) = transcriber {
+                    if let Some(ref mut t) = transcriber {
                         let _ = t.feed_samples(&chunk.samples);
                         for seg in t.drain_segments() {
+                            if let Some(p) = preview.as_mut() {
+                                p.offer(&seg);
+                            }
                             let _ = segment_tx.send(seg);
                             forwarded_count += 1;
                         }
@@ -812,12 +1172,21 @@ async fn run_recording_task(
                 .await
             {
                 Ok(Ok(chunk)) => {
+                    if is_paused.load(Ordering::Relaxed) {
+                        if let Some(p) = preview.as_mut() {
+                            p.skip(&chunk);
+                        }
+                        continue;
+                    }
                     if let Err(e) = recorder.write_chunk(&chunk) {
                         tracing::error!("Failed to write audio chunk: {}", e);
                     }
-                    if let Some(ref t) = transcriber {
+                    if let Some(ref mut t) = transcriber {
                         let _ = t.feed_samples(&chunk.samples);
                         for seg in t.drain_segments() {
+                            if let Some(p) = preview.as_mut() {
+                                p.offer(&seg);
+                            }
                             let _ = segment_tx.send(seg);
                             forwarded_count += 1;
                         }
@@ -841,10 +1210,13 @@ async fn run_recording_task(
     tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 
     while let Ok(chunk) = mixed_rx.recv().await {
+        if is_paused.load(Ordering::Relaxed) {
+            continue;
+        }
         if let Err(e) = recorder.write_chunk(&chunk) {
             tracing::error!("Failed to write audio chunk: {}", e);
         }
-        if let Some(ref t) = transcriber {
+        if let Some(ref mut t) = transcriber {
             let _ = t.feed_samples(&chunk.samples);
             for seg in t.drain_segments() {
                 let _ = segment_tx.send(seg);
@@ -862,7 +1234,7 @@ async fn run_recording_task(
         }
     }
 
-    if let Some(t) = transcriber {
+    if let Some(mut t) = transcriber {
         let _ = t.flush();
         match t.stop() {
             Ok(final_segments) => {
@@ -900,14 +1272,43 @@ async fn mic_only_task(
 
 fn run_background_diarization(meeting_id: String, audio_path: PathBuf, refine_transcript: bool) {
     tracing::info!("Starting background diarization for meeting {}", meeting_id);
+    let _lock = match crate::pipeline::ProcessingLock::try_acquire(&meeting_id) {
+        Ok(Some(lock)) => Some(lock),
+        Ok(None) => {
+            tracing::warn!(
+                "Meeting {} is being processed elsewhere (muesli redo?), skipping diarization",
+                meeting_id
+            );
+            return;
+        }
+        Err(e) => {
+            tracing::warn!("Could not take the processing lock: {}", e);
+            None
+        }
+    };
 
-    if let Err(e) = refresh_transcript_from_audio(&meeting_id, &audio_path, refine_transcript) {
+    let progress = ProcessingProgress::new(&meeting_id);
+    progress.started();
+
+    if refine_transcript {
+        progress.stage("Transcribing the full recording");
+    }
+    if let Err(e) = refresh_transcript_from_audio(&meeting_id, &audio_path, refine_transcript) {
         tracing::warn!(
             "Transcript refinement failed, using existing segments: {}",
             e
         );
     }
 
+    let cfg = match load_config() {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Failed to load config for diarization: {}", e);
+            mark_meeting_complete(&meeting_id);
+            return;
+        }
+    };
+
     let models_dir = match models_dir() {
         Ok(dir) => dir,
         Err(e) => {
@@ -945,67 +1346,94 @@ fn run_background_diarization(meeting_id: String, audio_path: PathBuf, refine_tr
         }
     }
 
-    let samples = match load_wav_samples(&audio_path) {
-        Ok(s) => s,
-        Err(e) => {
-            tracing::error!("Failed to load audio for diarization: {}", e);
-            mark_meeting_complete(&meeting_id);
-            return;
-        }
-    };
-
-    let model_path = diar_manager.model_path(diar_model);
-    let mut diarizer = match crate::transcription::diarization::Diarizer::new(&model_path) {
-        Ok(d) => d,
-        Err(e) => {
-            tracing::error!("Failed to create diarizer: {}", e);
-            mark_meeting_complete(&meeting_id);
-            return;
-        }
-    };
-
-    let speaker_segments = match diarizer.diarize(samples, 16000) {
-        Ok(segs) => segs,
-        Err(e) => {
-            tracing::error!("Diarization failed: {}", e);
-            mark_meeting_complete(&meeting_id);
-            return;
-        }
-    };
-
-    tracing::info!(
-        "Diarization complete: {} speaker segments",
-        speaker_segments.len()
-    );
-
     if let Ok(db_path) = database_path() {
         if let Ok(db) = Database::open(&db_path) {
             let meeting_id_obj = crate::storage::MeetingId::from_string(meeting_id.clone());
-            if let Ok(mut segments) = db.get_transcript_segments(&meeting_id_obj) {
-                for seg in segments.iter_mut() {
-                    if let Some(speaker) = speaker_segments
-                        .iter()
-                        .find(|s| {
-                            let mid = (seg.start_ms + seg.end_ms) / 2;
-                            mid >= s.start_ms && mid <= s.end_ms
-                        })
-                        .map(|s| format!("SPEAKER_{}", s.speaker_id + 1))
-                    {
-                        seg.speaker = Some(speaker);
+            if let Ok(segments) = db.get_transcript_segments(&meeting_id_obj) {
+                progress.stage("Identifying speakers");
+                match crate::pipeline::diarize_and_label(&segments, &audio_path, &cfg) {
+                    Ok(segments) => {
+                        let _ = db.delete_transcript_segments(&meeting_id_obj);
+                        let _ = db.insert_transcript_segments(
+                            &meeting_id_obj,
+                            &segments,
+                            cfg.transcription.merge_gap_ms,
+                        );
+                        tracing::info!("Updated {} segments with speaker labels", segments.len());
+                    }
+                    Err(e) => {
+                        tracing::error!("Diarization failed: {}", e);
+                        mark_meeting_complete(&meeting_id);
+                        return;
                     }
                 }
-
-                let _ = db.delete_transcript_segments(&meeting_id_obj);
-                let _ = db.insert_transcript_segments(&meeting_id_obj, &segments);
-                tracing::info!("Updated {} segments with speaker labels", segments.len());
             }
         }
     }
 
+    if cfg.llm.provider != "none" {
+        progress.stage("Summarizing");
+    }
     run_background_summarization(meeting_id.clone());
 
     mark_meeting_complete(&meeting_id);
-    let _ = notification::notify_status("Processing complete for meeting");
+    progress.finished();
+}
+
+/// Notifications for the gap between a recording stopping and its notes being
+/// ready, per `[daemon] notify_processing` / `notify_processing_stages`
+struct ProcessingProgress {
+    title: String,
+    notify: bool,
+    stages: bool,
+}
+
+impl ProcessingProgress {
+    fn new(meeting_id: &str) -> Self {
+        let (notify, stages) = load_config()
+            .map(|c| {
+                (
+                    c.daemon.notify_processing,
+                    c.daemon.notify_processing_stages,
+                )
+            })
+            .unwrap_or((true, false));
+        let title = database_path()
+            .and_then(Database::open)
+            .and_then(|db| {
+                db.get_meeting(&crate::storage::MeetingId::from_string(
+                    meeting_id.to_string(),
+                ))
+            })
+            .ok()
+            .flatten()
+            .map(|m| m.title)
+            .unwrap_or_else(|| "Meeting".to_string());
+        Self {
+            title,
+            notify,
+            stages,
+        }
+    }
+
+    fn started(&self) {
+        if self.notify {
+            let _ = notification::notify_processing_started(&self.title);
+        }
+    }
+
+    fn stage(&self, stage: &str) {
+        tracing::info!("Processing {}: {}", self.title, stage);
+        if self.notify && self.stages {
+            let _ = notification::notify_processing_stage(&self.title, stage);
+        }
+    }
+
+    fn finished(&self) {
+        if self.notify {
+            let _ = notification::notify_status(&format!("Processing complete: {}", self.title));
+        }
+    }
 }
 
 fn run_background_summarization(meeting_id: String) {
@@ -1044,6 +1472,14 @@ fn run_background_summarization(meeting_id: String) {
     };
 
     let meeting_id_obj = crate::storage::MeetingId::from_string(meeting_id.clone());
+    let meeting = match db.get_meeting(&meeting_id_obj) {
+        Ok(Some(m)) => m,
+        _ => {
+            tracing::error!("Failed to get meeting for summarization");
+            return;
+        }
+    };
+
     let segments = match db.get_transcript_segments(&meeting_id_obj) {
         Ok(s) => s,
         Err(e) => {
@@ -1071,33 +1507,40 @@ fn run_background_summarization(meeting_id: String) {
         }
     };
 
-    let result = rt.block_on(crate::llm::summarize_transcript(&cfg.llm, &transcript));
-
-    match result {
-        Ok(summary) => {
-            tracing::info!("Summarization complete: {} chars", summary.markdown.len());
-
-            if let Err(e) = db.insert_summary(&meeting_id_obj, &summary) {
-                tracing::error!("Failed to save summary: {}", e);
-            } else {
-                tracing::info!("Summary saved for meeting {}", meeting_id);
+    match rt.block_on(crate::pipeline::summarize_and_generate_notes(
+        &db,
+        &meeting,
+        &transcript,
+        &cfg,
+        false,
+    )) {
+        Ok(notes) => {
+            tracing::info!(
+                "Summarization complete: {} chars",
+                notes.summary.markdown.len()
+            );
+            if cfg.smtp.on_complete {
+                match crate::notes::email::send_notes(
+                    &cfg.smtp,
+                    &notes.meeting,
+                    &notes.summary,
+                    &cfg.smtp.to,
+                ) {
+                    Ok(()) => tracing::info!("Emailed notes to {}", cfg.smtp.to.join(", ")),
+                    Err(e) => tracing::warn!("Failed to email notes: {}", e),
+                }
             }
-
-            if let Ok(Some(meeting)) = db.get_meeting(&meeting_id_obj) {
-                if meeting.title == "Untitled Meeting" {
-                    tracing::info!("Generating title for untitled meeting");
-                    let title_result =
-                        rt.block_on(crate::llm::generate_title(&cfg.llm, &summary.markdown));
-                    if let Ok(title) = title_result {
-                        tracing::info!("Generated title: {}", title);
-                        let mut updated = meeting;
-                        updated.title = title;
-                        let _ = db.update_meeting(&updated);
+            if cfg.qmd.enabled && cfg.qmd.auto_index {
+                let collection_name = cfg.qmd.collection_name.clone();
+                std::thread::spawn(move || {
+                    tracing::info!("Auto-indexing meeting notes with qmd...");
+                    if let Err(e) = crate::qmd::indexer::update_index(&collection_name) {
+                        tracing::warn!("qmd auto-index failed: {}", e);
+                    } else {
+                        tracing::info!("qmd auto-index complete");
                     }
-                }
+                });
             }
-
-            generate_meeting_notes(&db, &meeting_id_obj, &transcript, &summary);
         }
         Err(e) => {
             tracing::error!("Summarization failed: {}", e);
@@ -1105,58 +1548,6 @@ fn run_background_summarization(meeting_id: String) {
     }
 }
 
-fn generate_meeting_notes(
-    db: &Database,
-    meeting_id: &crate::storage::MeetingId,
-    transcript: &crate::transcription::Transcript,
-    summary: &crate::llm::SummaryResult,
-) {
-    let meeting = match db.get_meeting(meeting_id) {
-        Ok(Some(m)) => m,
-        _ => {
-            tracing::error!("Failed to get meeting for notes generation");
-            return;
-        }
-    };
-
-    let notes_dir = match crate::config::loader::notes_dir() {
-        Ok(d) => d,
-        Err(e) => {
-            tracing::error!("Failed to get notes dir: {}", e);
-            return;
-        }
-    };
-
-    let generator = crate::notes::markdown::NoteGenerator::new(notes_dir);
-    match generator.generate(&meeting, transcript, summary) {
-        Ok(path) => {
-            tracing::info!("Generated notes: {}", path.display());
-            let mut updated_meeting = meeting;
-            updated_meeting.notes_path = Some(path);
-            if let Err(e) = db.update_meeting(&updated_meeting) {
-                tracing::error!("Failed to update meeting with notes path: {}", e);
-            }
-
-            if let Ok(config) = crate::config::loader::load_config() {
-                if config.qmd.enabled && config.qmd.auto_index {
-                    let collection_name = config.qmd.collection_name.clone();
-                    std::thread::spawn(move || {
-                        tracing::info!("Auto-indexing meeting notes with qmd...");
-                        if let Err(e) = crate::qmd::indexer::update_index(&collection_name) {
-                            tracing::warn!("qmd auto-index failed: {}", e);
-                        } else {
-                            tracing::info!("qmd auto-index complete");
-                        }
-                    });
-                }
-            }
-        }
-        Err(e) => {
-            tracing::error!("Failed to generate notes: {}", e);
-        }
-    }
-}
-
 fn run_background_diarization_and_summarization(meeting_id: String, audio_path: PathBuf) {
     tracing::info!(
         "Starting background processing for meeting {} (diarization + summarization)",
@@ -1184,19 +1575,7 @@ fn refresh_transcript_from_audio(
         return Ok(());
     }
 
-    let manager = ModelManager::new(models_dir()?);
-    let model =
-        WhisperModel::parse(cfg.transcription.effective_model()).unwrap_or(WhisperModel::Base);
-    if !manager.model_exists(model) {
-        tracing::warn!(
-            "Skipping final transcription refinement: Whisper model {:?} not found",
-            model
-        );
-        return Ok(());
-    }
-
-    let engine = WhisperEngine::new(manager.model_path(model), cfg.transcription.use_gpu)?;
-    let transcript = crate::transcription::whisper::transcribe_wav_file(&engine, audio_path)?;
+    let transcript = crate::pipeline::transcribe(audio_path, &cfg)?;
 
     if transcript.segments.is_empty() {
         tracing::warn!("Final transcription returned no segments");
@@ -1204,7 +1583,7 @@ fn refresh_transcript_from_audio(
     }
 
     db.delete_transcript_segments(&meeting_id_obj)?;
-    db.insert_transcript_segments(&meeting_id_obj, &transcript.segments)?;
+    db.insert_transcript_segments(&meeting_id_obj, &transcript.segments, 0)?;
     tracing::info!(
         "Stored refined transcript from full audio pass: {} segments",
         transcript.segments.len()
@@ -1213,6 +1592,56 @@ fn refresh_transcript_from_audio(
     Ok(())
 }
 
+/// One finished recording being processed in the background, counted in
+/// `DaemonState::background_jobs` until dropped (also when the thread panics)
+struct BackgroundJob(Arc<AtomicUsize>);
+
+impl BackgroundJob {
+    fn start(jobs: &Arc<AtomicUsize>) -> Self {
+        jobs.fetch_add(1, Ordering::SeqCst);
+        Self(jobs.clone())
+    }
+}
+
+impl Drop for BackgroundJob {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// With `[audio] compress_after_recording`, swap the processed recording's WAV
+/// for Opus. Skipped while another process (`muesli redo`) is reading it.
+fn compress_finished_recording(meeting_id: &str) {
+    if !load_config().is_ok_and(|cfg| cfg.audio.compress_after_recording) {
+        return;
+    }
+    let _lock = match crate::pipeline::ProcessingLock::try_acquire(meeting_id) {
+        Ok(Some(lock)) => lock,
+        Ok(None) => {
+            tracing::warn!(
+                "Meeting {} is being processed elsewhere, leaving its recording uncompressed",
+                meeting_id
+            );
+            return;
+        }
+        Err(e) => {
+            tracing::warn!("Could not take the processing lock: {}", e);
+            return;
+        }
+    };
+
+    let result = database_path().and_then(Database::open).and_then(|db| {
+        let id = crate::storage::MeetingId::from_string(meeting_id.to_string());
+        match db.get_meeting(&id)? {
+            Some(mut meeting) => crate::pipeline::compress_recording(&db, &mut meeting),
+            None => Ok(None),
+        }
+    });
+    if let Err(e) = result {
+        tracing::warn!("Failed to compress recording of {}: {}", meeting_id, e);
+    }
+}
+
 fn mark_meeting_complete(meeting_id: &str) {
     if let Ok(db_path) = database_path() {
         if let Ok(db) = Database::open(&db_path) {
@@ -1225,35 +1654,6 @@ fn mark_meeting_complete(meeting_id: &str) {
     }
 }
 
-fn load_wav_samples(path: &PathBuf) -> Result<Vec<f32>> {
-    use hound::WavReader;
-
-    let mut reader = WavReader::open(path)
-        .map_err(|e| MuesliError::Audio(format!("Failed to open WAV: {}", e)))?;
-
-    let spec = reader.spec();
-    let samples: Vec<f32> = match spec.sample_format {
-        hound::SampleFormat::Int => {
-            let max_val = (1 << (spec.bits_per_sample - 1)) as f32;
-            reader
-                .samples::<i32>()
-                .filter_map(|s| s.ok())
-                .map(|s| s as f32 / max_val)
-                .collect()
-        }
-        hound::SampleFormat::Float => reader.samples::<f32>().filter_map(|s| s.ok()).collect(),
-    };
-
-    if spec.channels > 1 {
-        Ok(samples
-            .chunks(spec.channels as usize)
-            .map(|chunk| chunk.iter().sum::<f32>() / chunk.len() as f32)
-            .collect())
-    } else {
-        Ok(samples)
-    }
-}
-
 fn find_any_meeting_window() -> Option<MeetingApp> {
     tracing::info!("Searching for meeting windows...");
     let windows = match crate::detection::hyprland::list_all_windows() {
@@ -1288,10 +1688,551 @@ fn find_any_meeting_window() -> Option<MeetingApp> {
     None
 }
 
+/// Initial title for a recording started from a detection prompt. Apps with a
+/// `[detection.app_titles]` template use the window title when it's meaningful and
+/// the rendered template otherwise; everything else is left for LLM title generation.
+fn auto_record_title(app: MeetingApp, window_title: &str) -> String {
+    let template = load_config()
+        .ok()
+        .and_then(|c| c.detection.app_titles.get(app.config_key()).cloned());
+
+    match template {
+        Some(template) => crate::detection::patterns::meeting_title_from_window(app, window_title)
+            .unwrap_or_else(|| {
+                crate::detection::patterns::render_title_template(
+                    &template,
+                    app,
+                    &chrono::Local::now(),
+                )
+            }),
+        None => "Untitled Meeting".to_string(),
+    }
+}
+
+/// Combines a per-recording auto-stop request with the configured safety cap
+/// (`0` = no cap). The earlier of the two wins.
+fn recording_limit_secs(requested: Option<u64>, max_recording_secs: u64) -> Option<u64> {
+    let cap = (max_recording_secs > 0).then_some(max_recording_secs);
+    match (requested, cap) {
+        (Some(r), Some(c)) => Some(r.min(c)),
+        (r, c) => r.or(c),
+    }
+}
+
+fn arm_auto_stop(
+    state_arc: &Arc<Mutex<DaemonState>>,
+    state: &mut DaemonState,
+    requested_secs: Option<u64>,
+) {
+    let max_recording_secs = load_config()
+        .map(|c| c.daemon.max_recording_secs)
+        .unwrap_or(0);
+
+    let Some(limit) = recording_limit_secs(requested_secs, max_recording_secs) else {
+        return;
+    };
+
+    let deadline = Instant::now() + std::time::Duration::from_secs(limit);
+    state.recording_deadline = Some(deadline);
+    state.auto_stop_running = Some(start_auto_stop_timer(state_arc.clone(), deadline));
+    tracing::info!("Recording will auto-stop in {}s", limit);
+}
+
+fn start_auto_stop_timer(state: Arc<Mutex<DaemonState>>, deadline: Instant) -> Arc<AtomicBool> {
+    let timer_running = Arc::new(AtomicBool::new(true));
+    let timer_running_clone = timer_running.clone();
+
+    tokio::spawn(async move {
+        let check_interval = std::time::Duration::from_secs(1);
+
+        while timer_running_clone.load(Ordering::Relaxed) {
+            if Instant::now() < deadline {
+                tokio::time::sleep(check_interval).await;
+                continue;
+            }
+
+            let mut state = state.lock().await;
+            if !timer_running_clone.load(Ordering::Relaxed)
+                || state.recording_deadline != Some(deadline)
+            {
+                break;
+            }
+
+            tracing::info!("Recording deadline reached, auto-stopping");
+            // Detach the flag first so the stop path doesn't cancel this task.
+            state.auto_stop_running = None;
+            match stop_recording_internal(&mut state).await {
+                Ok(meeting_id) => tracing::info!("Auto-stopped recording {}", meeting_id),
+                Err(e) => tracing::error!("Failed to auto-stop recording: {}", e),
+            }
+            break;
+        }
+
+        tracing::debug!("Auto-stop timer ended");
+    });
+
+    timer_running
+}
+
+fn recording_elapsed_secs(meeting: &Meeting) -> u64 {
+    (chrono::Utc::now() - meeting.started_at)
+        .num_seconds()
+        .max(0) as u64
+}
+
+/// Refresh the Waybar status every second while recording, so the elapsed
+/// time in the tooltip keeps counting between daemon events.
+fn arm_waybar_ticker(state_arc: &Arc<Mutex<DaemonState>>, state: &mut DaemonState) {
+    let Ok(cfg) = load_config() else {
+        return;
+    };
+    if !cfg.waybar.enabled {
+        return;
+    }
+    if let Some(running) = state.waybar_tick_running.take() {
+        running.store(false, Ordering::Relaxed);
+    }
+    state.waybar_tick_running = Some(start_waybar_ticker(state_arc.clone(), cfg.waybar));
+}
+
+fn start_waybar_ticker(
+    state: Arc<Mutex<DaemonState>>,
+    waybar: crate::config::settings::WaybarConfig,
+) -> Arc<AtomicBool> {
+    let ticker_running = Arc::new(AtomicBool::new(true));
+    let ticker_running_clone = ticker_running.clone();
+
+    tokio::spawn(async move {
+        let tick = std::time::Duration::from_secs(1);
+
+        loop {
+            tokio::time::sleep(tick).await;
+
+            let status = {
+                let state = state.lock().await;
+                if !ticker_running_clone.load(Ordering::Relaxed) || !state.recording {
+                    break;
+                }
+                let Some(status) = state.recording_waybar_status() else {
+                    break;
+                };
+                let latest = state
+                    .latest_preview
+                    .as_ref()
+                    .and_then(|latest| latest.lock().ok().map(|line| line.clone()))
+                    .unwrap_or_default();
+                if latest.is_empty() {
+                    status
+                } else {
+                    status.with_preview(&latest)
+                }
+            };
+
+            // The file write and pkill block, so run them off the runtime and
+            // without the state lock. The stop path clears `ticker_running`
+            // before writing idle, which this write then can't overwrite.
+            let waybar = waybar.clone();
+            let active = ticker_running_clone.clone();
+            let _ = tokio::task::spawn_blocking(move || {
+                crate::waybar::update_waybar_status_while(&waybar, &status, &active)
+            })
+            .await;
+        }
+
+        tracing::debug!("Waybar ticker ended");
+    });
+
+    ticker_running
+}
+
+/// Stop the recording once free space on its volume drops below `[storage]
+/// min_free_mb`, so a full disk ends it cleanly with the audio so far saved
+/// instead of failing mid-write.
+fn arm_disk_space_guard(state_arc: &Arc<Mutex<DaemonState>>, state: &mut DaemonState) {
+    let Ok(cfg) = load_config() else {
+        return;
+    };
+    if cfg.storage.min_free_mb == 0 {
+        return;
+    }
+    let Some(dir) = state
+        .audio_path
+        .as_ref()
+        .and_then(|p| p.parent())
+        .map(PathBuf::from)
+    else {
+        return;
+    };
+    if let Some(running) = state.disk_guard_running.take() {
+        running.store(false, Ordering::Relaxed);
+    }
+    state.disk_guard_running = Some(start_disk_space_guard(
+        state_arc.clone(),
+        dir,
+        cfg.storage.min_free_mb,
+    ));
+}
+
+fn start_disk_space_guard(
+    state: Arc<Mutex<DaemonState>>,
+    dir: PathBuf,
+    min_free_mb: u64,
+) -> Arc<AtomicBool> {
+    let guard_running = Arc::new(AtomicBool::new(true));
+    let guard_running_clone = guard_running.clone();
+
+    tokio::spawn(async move {
+        let check_interval = std::time::Duration::from_secs(5);
+        let floor = min_free_mb * 1024 * 1024;
+
+        while guard_running_clone.load(Ordering::Relaxed) {
+            tokio::time::sleep(check_interval).await;
+
+            let free = match fs2::available_space(&dir) {
+                Ok(free) => free,
+                Err(e) => {
+                    tracing::debug!("Free space check failed for {:?}: {}", dir, e);
+                    continue;
+                }
+            };
+            if free >= floor {
+                continue;
+            }
+
+            let mut state = state.lock().await;
+            if !guard_running_clone.load(Ordering::Relaxed) || !state.recording {
+                break;
+            }
+
+            let free_mb = free / (1024 * 1024);
+            tracing::warn!(
+                "Only {} MB free on {:?} (min_free_mb = {}), stopping recording",
+                free_mb,
+                dir,
+                min_free_mb
+            );
+            let _ = notification::notify_error(
+                "Recording stopped: disk almost full",
+                &format!(
+                    "Only {} MB free. The recording so far was saved and will be processed.",
+                    free_mb
+                ),
+            );
+            // Detach the flag first so the stop path doesn't cancel this task.
+            state.disk_guard_running = None;
+            match stop_recording_internal(&mut state).await {
+                Ok(meeting_id) => {
+                    tracing::info!("Stopped recording {} (low disk space)", meeting_id)
+                }
+                Err(e) => tracing::error!("Failed to stop recording on low disk space: {}", e),
+            }
+            break;
+        }
+
+        tracing::debug!("Disk space guard ended");
+    });
+
+    guard_running
+}
+
+/// Start periodic interim summaries if `[llm] interim_summary_mins` is set.
+/// Needs streaming transcription, since that is the only source of segments
+/// while the recording is still running.
+fn arm_interim_summaries(state_arc: &Arc<Mutex<DaemonState>>, state: &mut DaemonState) {
+    let Ok(cfg) = load_config() else {
+        return;
+    };
+    if cfg.llm.interim_summary_mins == 0 || cfg.llm.provider == "none" {
+        return;
+    }
+    if !state.streaming_enabled {
+        tracing::info!("Interim summaries need streaming transcription, skipping");
+        return;
+    }
+
+    let interval = std::time::Duration::from_secs(cfg.llm.interim_summary_mins * 60);
+    state.interim_summary_running = Some(start_interim_summary_timer(state_arc.clone(), interval));
+    tracing::info!(
+        "Interim summaries every {} min while recording",
+        cfg.llm.interim_summary_mins
+    );
+}
+
+fn start_interim_summary_timer(
+    state: Arc<Mutex<DaemonState>>,
+    interval: std::time::Duration,
+) -> Arc<AtomicBool> {
+    let timer_running = Arc::new(AtomicBool::new(true));
+    let timer_running_clone = timer_running.clone();
+    let in_flight = Arc::new(AtomicBool::new(false));
+
+    tokio::spawn(async move {
+        let check_interval = std::time::Duration::from_secs(1);
+        let mut next_run = Instant::now() + interval;
+
+        while timer_running_clone.load(Ordering::Relaxed) {
+            if Instant::now() < next_run {
+                tokio::time::sleep(check_interval).await;
+                continue;
+            }
+            next_run = Instant::now() + interval;
+
+            let (meeting, segments) = {
+                let mut state = state.lock().await;
+                if !timer_running_clone.load(Ordering::Relaxed) || !state.recording {
+                    break;
+                }
+                let DaemonState {
+                    segment_rx,
+                    transcript_segments,
+                    ..
+                } = &mut *state;
+                if let Some(rx) = segment_rx {
+                    drain_pending_segments(rx, transcript_segments);
+                }
+                (
+                    state.current_meeting.clone(),
+                    state.transcript_segments.clone(),
+                )
+            };
+
+            let Some(meeting) = meeting else {
+                continue;
+            };
+            if segments.is_empty() {
+                tracing::debug!("No segments yet, skipping interim summary");
+                continue;
+            }
+            if in_flight.swap(true, Ordering::SeqCst) {
+                tracing::info!("Previous interim summary still running, skipping");
+                continue;
+            }
+
+            let in_flight = in_flight.clone();
+            let recording = timer_running_clone.clone();
+            let state = state.clone();
+            std::thread::spawn(move || {
+                run_interim_summary(meeting, segments, &recording, &state);
+                in_flight.store(false, Ordering::SeqCst);
+            });
+        }
+
+        tracing::debug!("Interim summary timer ended");
+    });
+
+    timer_running
+}
+
+/// Move segments the recording thread has already produced into `into`
+/// without waiting for more.
+fn drain_pending_segments(
+    rx: &std::sync::mpsc::Receiver<TranscriptSegment>,
+    into: &mut Vec<TranscriptSegment>,
+) -> usize {
+    let before = into.len();
+    into.extend(rx.try_iter());
+    into.len() - before
+}
+
+/// Summarize the transcript so far and write it to the meeting's notes file.
+/// The summary is not stored in the database; the final one replaces it at stop.
+/// `recording` is the timer's running flag: if the recording stopped while the
+/// LLM was working, nothing is written, so the final notes aren't overwritten.
+/// The flag is checked and the notes written under the `state` lock, which the
+/// stop path holds while clearing the flag, so a stop can't slip in between.
+fn run_interim_summary(
+    meeting: Meeting,
+    segments: Vec<TranscriptSegment>,
+    recording: &AtomicBool,
+    state: &Arc<Mutex<DaemonState>>,
+) {
+    let Ok(cfg) = load_config() else {
+        return;
+    };
+    let Ok(db) = database_path().and_then(|p| Database::open(&p)) else {
+        tracing::error!("Interim summary: failed to open database");
+        return;
+    };
+    let rt = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(rt) => rt,
+        Err(e) => {
+            tracing::error!("Failed to create tokio runtime: {}", e);
+            return;
+        }
+    };
+
+    tracing::info!("Generating interim summary ({} segments)", segments.len());
+    let transcript = crate::transcription::Transcript::new(segments);
+    // The chunk cache means earlier parts of a long meeting are only summarized once
+    let summary = match rt.block_on(crate::llm::summarize_transcript(
+        &crate::pipeline::summary_llm_config(&meeting, &cfg.llm),
+        &transcript,
+        crate::pipeline::summary_template(&meeting),
+        Some(&db),
+    )) {
+        Ok(summary) => summary,
+        Err(e) => {
+            tracing::error!("Interim summary failed: {}", e);
+            return;
+        }
+    };
+
+    let summary = crate::llm::SummaryResult {
+        markdown: format!(
+            "> **Interim notes** (recording in progress, updated {}). Final notes are generated when the recording stops.\n\n{}",
+            chrono::Local::now().format("%H:%M"),
+            summary.markdown
+        ),
+    };
+
+    let notes_dir = match crate::config::loader::notes_dir() {
+        Ok(d) => d,
+        Err(e) => {
+            tracing::error!("Failed to get notes dir: {}", e);
+            return;
+        }
+    };
+
+    let _state = rt.block_on(state.lock());
+    let still_recording = recording.load(Ordering::Relaxed)
+        && matches!(
+            db.get_meeting(&meeting.id),
+            Ok(Some(m)) if m.status == crate::storage::MeetingStatus::Recording
+        );
+    if !still_recording {
+        tracing::info!("Recording stopped during interim summary, discarding it");
+        return;
+    }
+
+    match crate::notes::markdown::NoteGenerator::from_config(notes_dir, &cfg.storage).generate(
+        &meeting,
+        &transcript,
+        &summary,
+    ) {
+        Ok(path) => tracing::info!("Interim notes updated: {}", path.display()),
+        Err(e) => tracing::error!("Failed to write interim notes: {}", e),
+    }
+}
+
+/// With `[detection] require_audio`, whether `app` is in a call yet. If not, a
+/// watcher re-sends the detection once call audio starts (or gives up when
+/// the window closes), so the prompt arrives as the call begins.
+async fn call_audio_ready(
+    app: MeetingApp,
+    window: &WindowInfo,
+    state: &Arc<Mutex<DaemonState>>,
+    detection_tx: &mpsc::Sender<DetectionEvent>,
+) -> bool {
+    let require_audio = load_config()
+        .map(|c| c.detection.require_audio)
+        .unwrap_or(false);
+    if !require_audio {
+        return true;
+    }
+
+    let check_window = window.clone();
+    let active = tokio::task::spawn_blocking(move || app_audio_active(app, &check_window))
+        .await
+        .unwrap_or(true);
+    if active {
+        return true;
+    }
+
+    {
+        let mut s = state.lock().await;
+        if s.awaiting_audio {
+            return false;
+        }
+        s.awaiting_audio = true;
+    }
+    tracing::info!(
+        "{} window found but no call audio yet, waiting before prompting",
+        app
+    );
+
+    let state = state.clone();
+    let detection_tx = detection_tx.clone();
+    let window = window.clone();
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+            if state.lock().await.recording {
+                break;
+            }
+
+            let check_window = window.clone();
+            let (presence, active) = tokio::task::spawn_blocking(move || {
+                (
+                    crate::detection::hyprland::meeting_window_presence(app),
+                    app_audio_active(app, &check_window),
+                )
+            })
+            .await
+            .unwrap_or((WindowPresence::Unknown, false));
+
+            if presence == WindowPresence::Gone {
+                tracing::info!("{} window closed before any call audio", app);
+                break;
+            }
+            if active {
+                tracing::info!("Call audio started in {}", app);
+                state.lock().await.awaiting_audio = false;
+                let _ = detection_tx
+                    .send(DetectionEvent::MeetingDetected { app, window })
+                    .await;
+                return;
+            }
+        }
+        state.lock().await.awaiting_audio = false;
+    });
+    false
+}
+
+/// Whether any of `app`'s windows has a running audio stream. Counts as active
+/// when PipeWire can't be asked, so detection still works without pw-dump.
+fn app_audio_active(app: MeetingApp, window: &WindowInfo) -> bool {
+    let mut pids: Vec<i32> = window.pid.into_iter().collect();
+    if let Ok(windows) = crate::detection::hyprland::list_all_windows() {
+        pids.extend(
+            windows
+                .iter()
+                .filter(|w| {
+                    crate::detection::patterns::detect_meeting_app(&w.class, &w.title) == Some(app)
+                })
+                .filter_map(|w| w.pid),
+        );
+    }
+
+    match crate::detection::audio_activity::has_active_stream(&pids) {
+        Ok(active) => active,
+        Err(e) => {
+            tracing::warn!("Can't check call audio, prompting anyway: {}", e);
+            true
+        }
+    }
+}
+
+/// Watch for the meeting window to close so the recording can be stopped.
+/// Returns `None` when `[detection] stop_on_window_close` is off, leaving the
+/// recording to be stopped manually.
 fn start_meeting_window_monitor(
     app: MeetingApp,
     detection_tx: mpsc::Sender<DetectionEvent>,
-) -> Arc<AtomicBool> {
+) -> Option<Arc<AtomicBool>> {
+    let stop_on_window_close = crate::config::loader::load_config()
+        .map(|c| c.detection.stop_on_window_close)
+        .unwrap_or(true);
+    if !stop_on_window_close {
+        tracing::info!(
+            "stop_on_window_close is disabled, recording for {} will only stop manually",
+            app
+        );
+        return None;
+    }
+
     let monitor_running = Arc::new(AtomicBool::new(true));
     let monitor_running_clone = monitor_running.clone();
 
@@ -1299,6 +2240,10 @@ fn start_meeting_window_monitor(
 
     tokio::spawn(async move {
         let check_interval = std::time::Duration::from_secs(3);
+        // Give up on an unreachable compositor after about a minute: the window
+        // is as good as gone, and waiting forever would keep recording forever
+        let max_unreachable_checks = 20;
+        let mut unreachable_checks = 0;
 
         tokio::time::sleep(std::time::Duration::from_secs(5)).await;
         tracing::debug!("Meeting window monitor active for {}", app);
@@ -1311,14 +2256,47 @@ fn start_meeting_window_monitor(
                 break;
             }
 
-            let window_exists = crate::detection::hyprland::meeting_window_exists(app);
-            tracing::trace!("Meeting window check for {}: exists={}", app, window_exists);
+            let presence = tokio::task::spawn_blocking(move || {
+                crate::detection::hyprland::meeting_window_presence(app)
+            })
+            .await
+            .unwrap_or(WindowPresence::Unknown);
+            tracing::trace!("Meeting window check for {}: {:?}", app, presence);
+
+            let closed = match presence {
+                WindowPresence::Open => {
+                    unreachable_checks = 0;
+                    false
+                }
+                WindowPresence::Gone => {
+                    tracing::info!(
+                        "Meeting window for {} no longer exists, triggering auto-stop",
+                        app
+                    );
+                    true
+                }
+                WindowPresence::Unknown => {
+                    unreachable_checks += 1;
+                    if unreachable_checks < max_unreachable_checks {
+                        tracing::warn!(
+                            "Compositor unavailable, keeping {} recording ({}/{})",
+                            app,
+                            unreachable_checks,
+                            max_unreachable_checks
+                        );
+                        false
+                    } else {
+                        tracing::warn!(
+                            "Compositor unreachable for {} checks, treating {} window as closed",
+                            unreachable_checks,
+                            app
+                        );
+                        true
+                    }
+                }
+            };
 
-            if !window_exists {
-                tracing::info!(
-                    "Meeting window for {} no longer exists, triggering auto-stop",
-                    app
-                );
+            if closed {
                 let _ = detection_tx
                     .send(DetectionEvent::MeetingWindowClosed { app })
                     .await;
@@ -1329,7 +2307,7 @@ fn start_meeting_window_monitor(
         tracing::debug!("Meeting window monitor stopped for {}", app);
     });
 
-    monitor_running
+    Some(monitor_running)
 }
 
 #[cfg(test)]
@@ -1345,6 +2323,53 @@ mod tests {
         assert!(state.meeting_detected.is_none());
     }
 
+    #[test]
+    fn test_drain_pending_segments() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut segments = vec![TranscriptSegment::new(0, 1000, "a".to_string())];
+
+        tx.send(TranscriptSegment::new(1000, 2000, "b".to_string()))
+            .unwrap();
+        tx.send(TranscriptSegment::new(2000, 3000, "c".to_string()))
+            .unwrap();
+
+        assert_eq!(drain_pending_segments(&rx, &mut segments), 2);
+        assert_eq!(segments.len(), 3);
+        assert_eq!(drain_pending_segments(&rx, &mut segments), 0);
+
+        drop(tx);
+        assert_eq!(drain_pending_segments(&rx, &mut segments), 0);
+    }
+
+    #[test]
+    fn test_mic_only_skips_system_audio() {
+        let mic_only = AudioConfig {
+            capture_system_audio: false,
+            device_loopback: Some("monitor".to_string()),
+            ..AudioConfig::default()
+        };
+        let opened = system_audio_source(&mic_only, |_| -> Result<()> {
+            panic!("loopback attempted in mic-only mode")
+        });
+        assert!(opened.is_none());
+
+        let configured = AudioConfig {
+            capture_system_audio: true,
+            ..mic_only
+        };
+        let opened = system_audio_source(&configured, |name| Ok(name.map(str::to_string)));
+        assert_eq!(opened.unwrap().unwrap().as_deref(), Some("monitor"));
+    }
+
+    #[test]
+    fn test_recording_limit_secs() {
+        assert_eq!(recording_limit_secs(None, 0), None);
+        assert_eq!(recording_limit_secs(Some(600), 0), Some(600));
+        assert_eq!(recording_limit_secs(None, 7200), Some(7200));
+        assert_eq!(recording_limit_secs(Some(600), 7200), Some(600));
+        assert_eq!(recording_limit_secs(Some(9000), 7200), Some(7200));
+    }
+
     #[tokio::test]
     async fn test_handle_ping() {
         let state = Arc::new(Mutex::new(DaemonState::default()));
@@ -1378,6 +2403,9 @@ mod tests {
         let response = handle_request(
             DaemonRequest::StartRecording {
                 title: Some("Test Meeting".to_string()),
+                auto_stop_secs: None,
+                participants: Vec::new(),
+                mic_only: false,
             },
             &state,
             &shutdown,
@@ -1399,28 +2427,75 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_start_recording_records_detected_app() {
+        let state = Arc::new(Mutex::new(DaemonState {
+            meeting_detected: Some(MeetingApp::Zoom),
+            ..DaemonState::default()
+        }));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let response = handle_request(
+            DaemonRequest::StartRecording {
+                title: Some("Detected".to_string()),
+                auto_stop_secs: None,
+                participants: Vec::new(),
+                mic_only: false,
+            },
+            &state,
+            &shutdown,
+        )
+        .await;
+        let DaemonResponse::RecordingStarted { meeting_id } = response else {
+            panic!("Expected RecordingStarted response");
+        };
+
+        let db = Database::open(database_path().unwrap()).unwrap();
+        let id = crate::storage::MeetingId::from_string(meeting_id);
+        let stored = db
+            .get_meeting(&id)
+            .unwrap()
+            .expect("meeting was not stored");
+        db.delete_meeting(&id).unwrap();
+        assert_eq!(stored.detected_app.as_deref(), Some("Zoom"));
+    }
+
     #[tokio::test]
     async fn test_handle_start_recording_already_recording() {
         let state = Arc::new(Mutex::new(DaemonState::default()));
         let shutdown = Arc::new(AtomicBool::new(false));
 
         let _ = handle_request(
-            DaemonRequest::StartRecording { title: None },
+            DaemonRequest::StartRecording {
+                title: None,
+                auto_stop_secs: None,
+                participants: Vec::new(),
+                mic_only: false,
+            },
             &state,
             &shutdown,
         )
         .await;
 
         let response = handle_request(
-            DaemonRequest::StartRecording { title: None },
+            DaemonRequest::StartRecording {
+                title: None,
+                auto_stop_secs: None,
+                participants: Vec::new(),
+                mic_only: false,
+            },
             &state,
             &shutdown,
         )
         .await;
 
         match response {
-            DaemonResponse::Error { message } => {
+            DaemonResponse::Error {
+                message,
+                error_kind,
+            } => {
                 assert_eq!(message, "Already recording");
+                assert_eq!(error_kind, DaemonErrorKind::AlreadyRecording);
             }
             _ => panic!("Expected Error response"),
         }
@@ -1432,7 +2507,12 @@ mod tests {
         let shutdown = Arc::new(AtomicBool::new(false));
 
         let _ = handle_request(
-            DaemonRequest::StartRecording { title: None },
+            DaemonRequest::StartRecording {
+                title: None,
+                auto_stop_secs: None,
+                participants: Vec::new(),
+                mic_only: false,
+            },
             &state,
             &shutdown,
         )
@@ -1452,6 +2532,161 @@ mod tests {
         assert!(state.current_meeting.is_none());
     }
 
+    #[tokio::test]
+    async fn test_pause_and_resume_recording() {
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let response = handle_request(DaemonRequest::PauseRecording, &state, &shutdown).await;
+        assert!(matches!(
+            response,
+            DaemonResponse::Error {
+                error_kind: DaemonErrorKind::NotRecording,
+                ..
+            }
+        ));
+
+        let _ = handle_request(
+            DaemonRequest::StartRecording {
+                title: Some("With a break".to_string()),
+                auto_stop_secs: None,
+                participants: Vec::new(),
+                mic_only: false,
+            },
+            &state,
+            &shutdown,
+        )
+        .await;
+
+        let response = handle_request(DaemonRequest::PauseRecording, &state, &shutdown).await;
+        assert!(matches!(response, DaemonResponse::Ok));
+        {
+            let state = state.lock().await;
+            assert!(state.paused);
+            assert!(state.paused_since.is_some());
+            let flag = state.audio_paused.as_ref().unwrap();
+            assert!(flag.load(Ordering::Relaxed));
+        }
+        match handle_request(DaemonRequest::GetStatus, &state, &shutdown).await {
+            DaemonResponse::Status(status) => {
+                assert!(status.recording);
+                assert!(status.paused);
+            }
+            _ => panic!("Expected Status response"),
+        }
+
+        // Pausing again keeps the original pause start
+        let since = state.lock().await.paused_since;
+        let response = handle_request(DaemonRequest::PauseRecording, &state, &shutdown).await;
+        assert!(matches!(response, DaemonResponse::Ok));
+        assert_eq!(state.lock().await.paused_since, since);
+
+        let response = handle_request(DaemonRequest::ResumeRecording, &state, &shutdown).await;
+        assert!(matches!(response, DaemonResponse::Ok));
+        {
+            let state = state.lock().await;
+            assert!(!state.paused);
+            assert!(state.paused_since.is_none());
+            let flag = state.audio_paused.as_ref().unwrap();
+            assert!(!flag.load(Ordering::Relaxed));
+        }
+
+        let _ = handle_request(DaemonRequest::PauseRecording, &state, &shutdown).await;
+        let _ = handle_request(DaemonRequest::StopRecording, &state, &shutdown).await;
+        let state = state.lock().await;
+        assert!(!state.paused);
+        assert!(state.audio_paused.is_none());
+        assert_eq!(state.paused_total, std::time::Duration::ZERO);
+    }
+
+    #[test]
+    fn test_recorded_secs_excludes_pauses() {
+        let mut meeting = Meeting::new("Long one".to_string());
+        meeting.started_at = chrono::Utc::now() - chrono::Duration::seconds(600);
+        let mut state = DaemonState {
+            recording: true,
+            current_meeting: Some(meeting),
+            paused_total: std::time::Duration::from_secs(120),
+            ..DaemonState::default()
+        };
+        let recorded = state.recorded_secs().unwrap();
+        assert!((479..=480).contains(&recorded));
+
+        state.recording = false;
+        assert_eq!(state.recorded_secs(), None);
+    }
+
+    #[tokio::test]
+    async fn test_rapid_start_stop_start() {
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let start = || DaemonRequest::StartRecording {
+            title: Some("Back to back".to_string()),
+            auto_stop_secs: None,
+            participants: Vec::new(),
+            mic_only: false,
+        };
+
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            match handle_request(start(), &state, &shutdown).await {
+                DaemonResponse::RecordingStarted { meeting_id } => ids.push(meeting_id),
+                other => panic!("Expected RecordingStarted, got {:?}", other),
+            }
+            assert!(matches!(
+                handle_request(start(), &state, &shutdown).await,
+                DaemonResponse::Error {
+                    error_kind: DaemonErrorKind::AlreadyRecording,
+                    ..
+                }
+            ));
+            match handle_request(DaemonRequest::StopRecording, &state, &shutdown).await {
+                DaemonResponse::RecordingStopped { meeting_id } => {
+                    assert_eq!(&meeting_id, ids.last().unwrap())
+                }
+                other => panic!("Expected RecordingStopped, got {:?}", other),
+            }
+
+            let state = state.lock().await;
+            assert!(!state.has_recording());
+            assert!(state.audio_path.is_none());
+            assert!(state.transcript_segments.is_empty());
+        }
+        assert_ne!(ids[0], ids[1]);
+    }
+
+    #[tokio::test]
+    async fn test_start_rejected_while_recording_state_held() {
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        // Not flagged as recording, but a meeting is still attached (paused or
+        // mid-teardown): starting another would clobber it
+        state.lock().await.current_meeting = Some(Meeting::new("Held".to_string()));
+
+        let response = handle_request(
+            DaemonRequest::StartRecording {
+                title: None,
+                auto_stop_secs: None,
+                participants: Vec::new(),
+                mic_only: false,
+            },
+            &state,
+            &shutdown,
+        )
+        .await;
+        assert!(matches!(
+            response,
+            DaemonResponse::Error {
+                error_kind: DaemonErrorKind::AlreadyRecording,
+                ..
+            }
+        ));
+        assert_eq!(
+            state.lock().await.current_meeting.as_ref().unwrap().title,
+            "Held"
+        );
+    }
+
     #[tokio::test]
     async fn test_handle_stop_recording_not_recording() {
         let state = Arc::new(Mutex::new(DaemonState::default()));
@@ -1460,8 +2695,12 @@ mod tests {
         let response = handle_request(DaemonRequest::StopRecording, &state, &shutdown).await;
 
         match response {
-            DaemonResponse::Error { message } => {
+            DaemonResponse::Error {
+                message,
+                error_kind,
+            } => {
                 assert_eq!(message, "Not recording");
+                assert_eq!(error_kind, DaemonErrorKind::NotRecording);
             }
             _ => panic!("Expected Error response"),
         }
@@ -1477,4 +2716,45 @@ mod tests {
         assert!(matches!(response, DaemonResponse::Ok));
         assert!(shutdown.load(Ordering::Relaxed));
     }
+
+    #[test]
+    fn test_transcription_setup_problem() {
+        let dir = tempdir().unwrap();
+        let manager = ModelManager::new(dir.path().to_path_buf());
+        let mut config = crate::config::settings::MuesliConfig::default();
+        config.transcription.model = "tiny".to_string();
+
+        let problem = transcription_setup_problem(&config, &manager).unwrap();
+        assert!(problem.contains("muesli models whisper download tiny"));
+
+        std::fs::write(manager.model_path(WhisperModel::Tiny), b"model").unwrap();
+        assert!(transcription_setup_problem(&config, &manager).is_none());
+
+        config.transcription.engine = "deepgram".to_string();
+        assert!(transcription_setup_problem(&config, &manager)
+            .unwrap()
+            .contains("'deepgram'"));
+    }
+
+    #[test]
+    fn test_preview_line() {
+        assert_eq!(preview_line("  hello\n  world ", 120), "hello world");
+        assert_eq!(preview_line("abcdefgh", 5), "abcd…");
+    }
+
+    #[test]
+    fn test_live_preview_throttle() {
+        let mut preview = LivePreview::new(
+            "Standup".to_string(),
+            10,
+            crate::config::settings::WaybarConfig::default(),
+            Arc::default(),
+        );
+        let now = Instant::now();
+        assert!(preview.due(now));
+
+        preview.last_shown = Some(now);
+        assert!(!preview.due(now + std::time::Duration::from_secs(9)));
+        assert!(preview.due(now + std::time::Duration::from_secs(10)));
+    }
 }
@@ -0,0 +1,50 @@
+//! systemd integration: readiness notification and watchdog keep-alives.
+//!
+//! All functions are no-ops when the daemon isn't started by systemd
+//! (`NOTIFY_SOCKET` unset), so running `muesli daemon` by hand is unaffected.
+
+use sd_notify::NotifyState;
+use std::time::Duration;
+
+/// Tell systemd the daemon has finished starting up (`Type=notify`).
+pub fn notify_ready() {
+    if let Err(e) = sd_notify::notify(false, &[NotifyState::Ready]) {
+        tracing::debug!("sd_notify READY failed: {}", e);
+    }
+}
+
+/// Tell systemd the daemon is shutting down on purpose.
+pub fn notify_stopping() {
+    let _ = sd_notify::notify(false, &[NotifyState::Stopping]);
+}
+
+/// Interval at which to send keep-alives, or None if `WatchdogSec=` isn't set.
+/// Pings at half the configured timeout, as recommended by sd_watchdog_enabled(3).
+fn keepalive_interval() -> Option<Duration> {
+    let mut usec = 0u64;
+    if sd_notify::watchdog_enabled(false, &mut usec) && usec > 0 {
+        Some(Duration::from_micros(usec / 2))
+    } else {
+        None
+    }
+}
+
+/// Spawn a task that sends `WATCHDOG=1` while the runtime keeps scheduling it.
+/// If the daemon's event loop wedges, keep-alives stop and systemd restarts it.
+pub fn start_watchdog() {
+    let Some(interval) = keepalive_interval() else {
+        return;
+    };
+
+    tracing::info!("systemd watchdog enabled, pinging every {:?}", interval);
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = sd_notify::notify(false, &[NotifyState::Watchdog]) {
+                tracing::warn!("sd_notify WATCHDOG failed: {}", e);
+            }
+        }
+    });
+}
@@ -0,0 +1,105 @@
+//! Whether a meeting app is actually in a call, judged by its PipeWire streams.
+//!
+//! A Zoom window idling in the tray looks the same as one in a call; only a
+//! running audio stream (microphone or playback) tells them apart. Browsers
+//! play audio from a child process, so a stream counts for a window when its
+//! process is the window's process or one of its descendants.
+
+use crate::error::{MuesliError, Result};
+use std::process::Command;
+
+/// Whether any process in `pids` (or a child of one) has a running audio stream
+pub fn has_active_stream(pids: &[i32]) -> Result<bool> {
+    let output = Command::new("pw-dump")
+        .output()
+        .map_err(|e| MuesliError::Audio(format!("pw-dump unavailable: {}", e)))?;
+    if !output.status.success() {
+        return Err(MuesliError::Audio(format!(
+            "pw-dump failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let dump: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    Ok(running_stream_pids(&dump)
+        .into_iter()
+        .any(|stream_pid| pids.iter().any(|&pid| descends_from(stream_pid, pid))))
+}
+
+/// Process ids owning the running audio streams in a `pw-dump` document
+fn running_stream_pids(dump: &serde_json::Value) -> Vec<i32> {
+    let Some(objects) = dump.as_array() else {
+        return Vec::new();
+    };
+
+    objects
+        .iter()
+        .filter(|o| o["type"] == "PipeWire:Interface:Node")
+        .filter(|o| o["info"]["state"] == "running")
+        .filter(|o| {
+            o["info"]["props"]["media.class"]
+                .as_str()
+                .is_some_and(|class| class.starts_with("Stream/") && class.ends_with("/Audio"))
+        })
+        .filter_map(|o| {
+            let pid = &o["info"]["props"]["application.process.id"];
+            // Older PipeWire versions dump props as strings
+            pid.as_i64()
+                .or_else(|| pid.as_str().and_then(|s| s.parse().ok()))
+                .map(|pid| pid as i32)
+        })
+        .collect()
+}
+
+/// Whether `pid` is `ancestor` or one of its descendants, walking /proc parents
+fn descends_from(mut pid: i32, ancestor: i32) -> bool {
+    loop {
+        if pid == ancestor {
+            return true;
+        }
+        if pid <= 1 {
+            return false;
+        }
+        match parent_pid(pid) {
+            Some(parent) => pid = parent,
+            None => return false,
+        }
+    }
+}
+
+fn parent_pid(pid: i32) -> Option<i32> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // The command name is parenthesized and may contain spaces
+    let (_, rest) = stat.rsplit_once(')')?;
+    rest.split_whitespace().nth(1)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_running_stream_pids() {
+        let dump = serde_json::json!([
+            {"type": "PipeWire:Interface:Node", "info": {"state": "running", "props": {
+                "media.class": "Stream/Input/Audio", "application.process.id": 4242}}},
+            {"type": "PipeWire:Interface:Node", "info": {"state": "idle", "props": {
+                "media.class": "Stream/Output/Audio", "application.process.id": 5000}}},
+            {"type": "PipeWire:Interface:Node", "info": {"state": "running", "props": {
+                "media.class": "Audio/Sink", "application.process.id": 6000}}},
+            {"type": "PipeWire:Interface:Node", "info": {"state": "running", "props": {
+                "media.class": "Stream/Output/Audio", "application.process.id": "7000"}}},
+            {"type": "PipeWire:Interface:Client", "info": {"props": {}}}
+        ]);
+        assert_eq!(running_stream_pids(&dump), vec![4242, 7000]);
+        assert!(running_stream_pids(&serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_descends_from() {
+        let me = std::process::id() as i32;
+        assert!(descends_from(me, me));
+        assert!(descends_from(me, parent_pid(me).unwrap()));
+        assert!(!descends_from(me, i32::MAX));
+    }
+}
@@ -0,0 +1,251 @@
+use crate::config::settings::CalendarConfig;
+use crate::error::{MuesliError, Result};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use std::process::Command;
+
+/// The calendar event a recording is started from
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEvent {
+    pub summary: String,
+    pub attendees: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct IcsEvent {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    summary: String,
+    attendees: Vec<String>,
+}
+
+/// Look up the event in progress right now from the configured source
+pub fn current_event(config: &CalendarConfig) -> Result<Option<CalendarEvent>> {
+    let now = Utc::now();
+
+    if !config.command.trim().is_empty() {
+        let output = run_command(&config.command)?;
+        return Ok(parse_command_output(&output, now));
+    }
+
+    if let Some(path) = &config.ics_path {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            MuesliError::Config(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        return Ok(event_at(&parse_ics(&content), now));
+    }
+
+    Err(MuesliError::NotConfigured(
+        "No calendar source. Set [calendar] ics_path or command in the config".to_string(),
+    ))
+}
+
+fn run_command(command: &str) -> Result<String> {
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .output()
+        .map_err(|e| MuesliError::Config(format!("Failed to run calendar command: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(MuesliError::Config(format!(
+            "Calendar command failed ({}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Command output is either iCalendar, or events already filtered to "now"
+/// (e.g. `khal list now 1m`) printed one per line
+fn parse_command_output(output: &str, now: DateTime<Utc>) -> Option<CalendarEvent> {
+    if output.contains("BEGIN:VEVENT") {
+        return event_at(&parse_ics(output), now);
+    }
+
+    let line = output.lines().map(str::trim).find(|l| !l.is_empty())?;
+    let mut fields = line.split('\t');
+    let summary = fields.next()?.trim().to_string();
+    let attendees = fields
+        .flat_map(|f| f.split(','))
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    Some(CalendarEvent { summary, attendees })
+}
+
+/// The event overlapping `now`; the most recently started one if several do
+fn event_at(events: &[IcsEvent], now: DateTime<Utc>) -> Option<CalendarEvent> {
+    events
+        .iter()
+        .filter(|e| e.start <= now && now < e.end)
+        .max_by_key(|e| e.start)
+        .map(|e| CalendarEvent {
+            summary: e.summary.clone(),
+            attendees: e.attendees.clone(),
+        })
+}
+
+/// Minimal VEVENT reader: timed events only (all-day events would match the
+/// whole day), no recurrence expansion, TZID times taken as local time.
+fn parse_ics(content: &str) -> Vec<IcsEvent> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in content.lines() {
+        let raw = raw.trim_end_matches('\r');
+        match (
+            raw.strip_prefix(' ').or_else(|| raw.strip_prefix('\t')),
+            lines.last_mut(),
+        ) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(raw.to_string()),
+        }
+    }
+
+    let mut events = Vec::new();
+    let mut in_event = false;
+    let mut start = None;
+    let mut end = None;
+    let mut summary = None;
+    let mut attendees = Vec::new();
+
+    for line in &lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (property, params) = name.split_once(';').unwrap_or((name, ""));
+
+        match (property.to_ascii_uppercase().as_str(), value) {
+            ("BEGIN", "VEVENT") => {
+                in_event = true;
+                start = None;
+                end = None;
+                summary = None;
+                attendees.clear();
+            }
+            ("END", "VEVENT") if in_event => {
+                in_event = false;
+                if let (Some(start), Some(end), Some(summary)) = (start, end, summary.take()) {
+                    events.push(IcsEvent {
+                        start,
+                        end,
+                        summary,
+                        attendees: std::mem::take(&mut attendees),
+                    });
+                }
+            }
+            _ if !in_event => {}
+            ("DTSTART", value) => start = parse_ics_time(value),
+            ("DTEND", value) => end = parse_ics_time(value),
+            ("SUMMARY", value) => summary = Some(unescape(value)),
+            ("ATTENDEE", value) => {
+                let name = ics_param(params, "CN")
+                    .map(unescape)
+                    .unwrap_or_else(|| value.trim_start_matches("mailto:").to_string());
+                if !name.is_empty() {
+                    attendees.push(name);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    events
+}
+
+fn parse_ics_time(value: &str) -> Option<DateTime<Utc>> {
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some(Utc.from_utc_datetime(&naive));
+    }
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+fn ics_param<'a>(params: &'a str, key: &str) -> Option<&'a str> {
+    params.split(';').find_map(|p| {
+        let (k, v) = p.split_once('=')?;
+        k.eq_ignore_ascii_case(key).then(|| v.trim_matches('"'))
+    })
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("\\n", " ")
+        .replace("\\N", " ")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ICS: &str = "BEGIN:VCALENDAR\r
+BEGIN:VEVENT\r
+DTSTART:20240301T140000Z\r
+DTEND:20240301T150000Z\r
+SUMMARY:Roadmap\\, Q2 planning with a very long title that the\r
+  server folded\r
+ATTENDEE;CN=\"Alice Smith\";ROLE=REQ-PARTICIPANT:mailto:alice@example.com\r
+ATTENDEE:mailto:bob@example.com\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+DTSTART;VALUE=DATE:20240301\r
+DTEND;VALUE=DATE:20240302\r
+SUMMARY:Company holiday\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+DTSTART:20240301T143000Z\r
+DTEND:20240301T144500Z\r
+SUMMARY:Quick sync\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+    fn at(h: u32, m: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 1, h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_ics() {
+        let events = parse_ics(ICS);
+        assert_eq!(events.len(), 2, "all-day event is skipped");
+        assert_eq!(
+            events[0].summary,
+            "Roadmap, Q2 planning with a very long title that the server folded"
+        );
+        assert_eq!(events[0].attendees, vec!["Alice Smith", "bob@example.com"]);
+        assert_eq!(events[0].start, at(14, 0));
+    }
+
+    #[test]
+    fn test_event_at() {
+        let events = parse_ics(ICS);
+        assert!(event_at(&events, at(14, 10))
+            .unwrap()
+            .summary
+            .starts_with("Roadmap"));
+        assert_eq!(event_at(&events, at(14, 40)).unwrap().summary, "Quick sync");
+        assert!(event_at(&events, at(15, 0)).is_none());
+    }
+
+    #[test]
+    fn test_parse_command_output() {
+        let event = parse_command_output("\nStandup\tAlice, Bob\nLater event\n", at(9, 0)).unwrap();
+        assert_eq!(event.summary, "Standup");
+        assert_eq!(event.attendees, vec!["Alice", "Bob"]);
+
+        assert!(parse_command_output("  \n", at(9, 0)).is_none());
+        assert_eq!(
+            parse_command_output(ICS, at(14, 40)).unwrap().summary,
+            "Quick sync"
+        );
+    }
+}
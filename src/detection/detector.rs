@@ -40,6 +40,20 @@ impl MeetingDetector {
         }
     }
 
+    /// Feed windows from `windows` through the detector until the channel
+    /// closes. Rapid focus changes are coalesced: a window is only evaluated
+    /// once focus has rested on it for the debounce period, so flipping through
+    /// windows (alt-tab past a meeting) costs one evaluation, not one per window.
+    pub async fn run(mut self, mut windows: mpsc::Receiver<WindowInfo>) {
+        let settle = Duration::from_millis(self.debounce_ms);
+        while let Some(mut window) = windows.recv().await {
+            while let Ok(Some(newer)) = tokio::time::timeout(settle, windows.recv()).await {
+                window = newer;
+            }
+            self.on_window_change(window).await;
+        }
+    }
+
     /// Process a window change event
     pub async fn on_window_change(&mut self, window: WindowInfo) {
         let now = Instant::now();
@@ -69,6 +83,21 @@ impl MeetingDetector {
                     .await;
             }
 
+            // Detected → Detected (another meeting app)
+            (DetectionState::Detected { app, .. }, Some(new_app)) if *app != new_app => {
+                self.state = DetectionState::Detected {
+                    app: new_app,
+                    since: now,
+                };
+                let _ = self
+                    .event_tx
+                    .send(DetectorEvent::MeetingDetected {
+                        app: new_app,
+                        window,
+                    })
+                    .await;
+            }
+
             // Detected → Idle (switched away)
             (DetectionState::Detected { app, .. }, None) => {
                 let app = *app;
@@ -291,4 +320,42 @@ mod tests {
         // Should not receive another event
         assert!(rx.try_recv().is_err());
     }
+
+    #[tokio::test]
+    async fn test_run_coalesces_focus_flips() {
+        let (tx, mut rx) = mpsc::channel(10);
+        let (window_tx, window_rx) = mpsc::channel(10);
+        tokio::spawn(MeetingDetector::new(tx, 50).run(window_rx));
+
+        let zoom = WindowInfo {
+            class: "zoom".to_string(),
+            title: "Meeting".to_string(),
+            pid: None,
+        };
+        let terminal = WindowInfo {
+            class: "alacritty".to_string(),
+            title: "Terminal".to_string(),
+            pid: None,
+        };
+
+        // Alt-tabbing past the meeting window: only the window focus rests on counts
+        window_tx.send(terminal.clone()).await.unwrap();
+        window_tx.send(zoom.clone()).await.unwrap();
+        window_tx.send(terminal.clone()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(rx.try_recv().is_err());
+
+        window_tx.send(zoom).await.unwrap();
+        let event = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            event,
+            DetectorEvent::MeetingDetected {
+                app: MeetingApp::Zoom,
+                ..
+            }
+        ));
+    }
 }
@@ -5,10 +5,67 @@ use crate::error::{MuesliError, Result};
 use hyprland::data::{Client, Clients};
 use hyprland::event_listener::AsyncEventListener;
 use hyprland::prelude::*;
+use std::sync::atomic::{AtomicU32, Ordering};
 use tokio::sync::mpsc;
 
 const DEFAULT_POLL_INTERVAL_SECS: u64 = 30;
 
+/// Delay before the first retry of a failed IPC call; doubled for each one after
+const IPC_RETRY_BACKOFF_MS: u64 = 50;
+
+/// Longest wait between IPC retries, however many there are
+const IPC_RETRY_MAX_BACKOFF_MS: u64 = 2_000;
+
+/// Most `[detection] ipc_retries` the config accepts
+pub const MAX_IPC_RETRIES: u32 = 10;
+
+static IPC_RETRIES: AtomicU32 = AtomicU32::new(3);
+
+/// How many extra attempts a failed IPC call gets (`[detection] ipc_retries`)
+pub fn set_ipc_retries(retries: u32) {
+    IPC_RETRIES.store(retries, Ordering::Relaxed);
+}
+
+/// Run an IPC call, retrying with backoff while the compositor doesn't answer
+/// (it drops requests for a moment while reloading).
+fn with_retry<T, E: std::fmt::Display>(
+    what: &str,
+    retries: u32,
+    mut call: impl FnMut() -> std::result::Result<T, E>,
+) -> Result<T> {
+    let mut attempt = 0;
+    loop {
+        match call() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < retries => {
+                tracing::debug!(
+                    "Hyprland IPC {} failed (attempt {}): {}",
+                    what,
+                    attempt + 1,
+                    e
+                );
+                std::thread::sleep(std::time::Duration::from_millis(retry_backoff_ms(attempt)));
+                attempt += 1;
+            }
+            Err(e) => {
+                return Err(MuesliError::HyprlandIpc(format!(
+                    "Failed to {} after {} attempts (compositor unavailable?): {}",
+                    what,
+                    attempt + 1,
+                    e
+                )))
+            }
+        }
+    }
+}
+
+/// Delay before retry number `attempt + 1`, capped at [`IPC_RETRY_MAX_BACKOFF_MS`]
+fn retry_backoff_ms(attempt: u32) -> u64 {
+    IPC_RETRY_BACKOFF_MS
+        .saturating_mul(1 << attempt.min(16))
+        .min(IPC_RETRY_MAX_BACKOFF_MS)
+}
+
 pub struct HyprlandMonitor {
     event_tx: mpsc::Sender<DetectionEvent>,
     poll_interval_secs: u64,
@@ -33,8 +90,11 @@ impl HyprlandMonitor {
     }
 
     pub fn get_active_window() -> Result<Option<WindowInfo>> {
-        let client = Client::get_active()
-            .map_err(|e| MuesliError::HyprlandIpc(format!("Failed to get active window: {}", e)))?;
+        let client = with_retry(
+            "get active window",
+            IPC_RETRIES.load(Ordering::Relaxed),
+            Client::get_active,
+        )?;
 
         Ok(client.map(|c| WindowInfo {
             class: c.class,
@@ -244,8 +304,11 @@ fn find_active_hyprland_session() -> Option<String> {
 }
 
 pub fn list_all_windows() -> Result<Vec<WindowInfo>> {
-    let clients = Clients::get()
-        .map_err(|e| MuesliError::HyprlandIpc(format!("Failed to get clients: {}", e)))?;
+    let clients = with_retry(
+        "list windows",
+        IPC_RETRIES.load(Ordering::Relaxed),
+        Clients::get,
+    )?;
 
     Ok(clients
         .iter()
@@ -257,12 +320,21 @@ pub fn list_all_windows() -> Result<Vec<WindowInfo>> {
         .collect())
 }
 
-pub fn meeting_window_exists(app: crate::detection::MeetingApp) -> bool {
+/// Whether a meeting's window is still open, as far as the compositor can tell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowPresence {
+    Open,
+    Gone,
+    /// Hyprland didn't answer even after retrying
+    Unknown,
+}
+
+pub fn meeting_window_presence(app: crate::detection::MeetingApp) -> WindowPresence {
     let windows = match list_all_windows() {
         Ok(w) => w,
         Err(e) => {
             tracing::warn!("Failed to list windows for meeting check: {}", e);
-            return true;
+            return WindowPresence::Unknown;
         }
     };
 
@@ -276,7 +348,11 @@ pub fn meeting_window_exists(app: crate::detection::MeetingApp) -> bool {
         windows.len(),
         exists
     );
-    exists
+    if exists {
+        WindowPresence::Open
+    } else {
+        WindowPresence::Gone
+    }
 }
 
 #[cfg(test)]
@@ -317,6 +393,36 @@ mod tests {
         assert!(result.is_ok(), "Failed to list windows: {:?}", result.err());
     }
 
+    #[test]
+    fn test_with_retry() {
+        let mut calls = 0;
+        let result = with_retry("list windows", 2, || {
+            calls += 1;
+            if calls < 3 {
+                Err("socket closed")
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+
+        let mut calls = 0;
+        let result: Result<()> = with_retry("list windows", 1, || {
+            calls += 1;
+            Err("socket closed")
+        });
+        assert_eq!(calls, 2);
+        assert!(matches!(result, Err(MuesliError::HyprlandIpc(m)) if m.contains("2 attempts")));
+    }
+
+    #[test]
+    fn test_retry_backoff_is_capped() {
+        assert_eq!(retry_backoff_ms(0), IPC_RETRY_BACKOFF_MS);
+        assert_eq!(retry_backoff_ms(1), 2 * IPC_RETRY_BACKOFF_MS);
+        assert_eq!(retry_backoff_ms(10), IPC_RETRY_MAX_BACKOFF_MS);
+        assert_eq!(retry_backoff_ms(u32::MAX), IPC_RETRY_MAX_BACKOFF_MS);
+    }
+
     #[tokio::test]
     async fn test_monitor_creation() {
         let (tx, _rx) = mpsc::channel(10);
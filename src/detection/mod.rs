@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+pub mod audio_activity;
+pub mod calendar;
 pub mod detector;
 pub mod hyprland;
 pub mod patterns;
@@ -32,6 +34,21 @@ impl std::fmt::Display for MeetingApp {
     }
 }
 
+impl MeetingApp {
+    /// Key used for this app in config tables such as `[detection.app_titles]`
+    pub fn config_key(&self) -> &'static str {
+        match self {
+            MeetingApp::Zoom => "zoom",
+            MeetingApp::GoogleMeet => "google_meet",
+            MeetingApp::MicrosoftTeams => "teams",
+            MeetingApp::Slack => "slack",
+            MeetingApp::Discord => "discord",
+            MeetingApp::WebEx => "webex",
+            MeetingApp::Unknown => "unknown",
+        }
+    }
+}
+
 /// Window information from Hyprland
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WindowInfo {
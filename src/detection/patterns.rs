@@ -4,9 +4,12 @@ use crate::detection::MeetingApp;
 pub fn detect_meeting_app(class: &str, title: &str) -> Option<MeetingApp> {
     let class_lower = class.to_lowercase();
     let title_lower = title.to_lowercase();
+    // Browser tabs and installed PWAs: the class only names the browser, so the
+    // title has to identify the app
+    let web = is_browser(class) || is_pwa(class);
 
-    // Zoom
-    if class_lower.contains("zoom") {
+    // Zoom (native, or the web client)
+    if class_lower.contains("zoom") || (web && title_lower.contains("zoom meeting")) {
         return Some(MeetingApp::Zoom);
     }
 
@@ -14,8 +17,7 @@ pub fn detect_meeting_app(class: &str, title: &str) -> Option<MeetingApp> {
     // Matches: "meet.google.com", "Google Meet", or active call "Meet – abc-xyz" in browser
     if title_lower.contains("meet.google.com")
         || (title_lower.contains("google meet") && !title_lower.contains("calendar"))
-        || (is_browser(class)
-            && (title_lower.starts_with("meet –") || title_lower.starts_with("meet -")))
+        || (web && (title_lower.starts_with("meet –") || title_lower.starts_with("meet -")))
     {
         return Some(MeetingApp::GoogleMeet);
     }
@@ -28,15 +30,17 @@ pub fn detect_meeting_app(class: &str, title: &str) -> Option<MeetingApp> {
         return Some(MeetingApp::MicrosoftTeams);
     }
 
-    // Slack (huddles/calls)
-    if class_lower.contains("slack")
-        && (title_lower.contains("huddle") || title_lower.contains("call"))
+    // Slack (huddles/calls). In a browser only huddles count: "call" is too
+    // common in ordinary page titles
+    if (class_lower.contains("slack")
+        && (title_lower.contains("huddle") || title_lower.contains("call")))
+        || (web && title_lower.contains("slack") && title_lower.contains("huddle"))
     {
         return Some(MeetingApp::Slack);
     }
 
     // Discord (voice channels)
-    if class_lower.contains("discord")
+    if (class_lower.contains("discord") || (web && title_lower.contains("discord")))
         && (title_lower.contains("voice") || title_lower.contains("stage"))
     {
         return Some(MeetingApp::Discord);
@@ -62,6 +66,114 @@ pub fn is_browser(class: &str) -> bool {
         || class_lower.contains("zen")
 }
 
+/// Installed web apps, whose class is derived from the app ID rather than the
+/// app name: `crx_<id>` (Chromium on Wayland) or `<browser>-<id>-<profile>`
+/// such as `chrome-abcdef-Default` or `brave-abcdef-Profile_1` (XWayland)
+pub fn is_pwa(class: &str) -> bool {
+    let class_lower = class.to_lowercase();
+    if class_lower.starts_with("crx_") {
+        return true;
+    }
+    let mut parts = class_lower.splitn(3, '-');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(browser), Some(id), Some(_profile)) => {
+            ["chrome", "chromium", "brave", "msedge", "vivaldi"].contains(&browser)
+                && id.starts_with(|c: char| c == '_' || c.is_ascii_lowercase())
+        }
+        _ => false,
+    }
+}
+
+const BROWSER_SUFFIXES: &[&str] = &[
+    "google chrome",
+    "chromium",
+    "mozilla firefox",
+    "firefox",
+    "brave",
+    "microsoft edge",
+    "vivaldi",
+    "zen browser",
+];
+
+const GENERIC_TITLES: &[&str] = &[
+    "zoom",
+    "zoom meeting",
+    "zoom workplace",
+    "meet",
+    "google meet",
+    "microsoft teams",
+    "teams",
+    "slack",
+    "huddle",
+    "discord",
+    "webex",
+    "meeting",
+    "call",
+];
+
+/// Extract a meeting title from a window title, e.g. "Meet - Daily Standup - Google Chrome"
+/// becomes "Daily Standup". Returns None when the title is generic ("Zoom Meeting", a
+/// Meet room code, ...).
+pub fn meeting_title_from_window(app: MeetingApp, title: &str) -> Option<String> {
+    let normalized = title
+        .replace(" – ", " - ")
+        .replace(" — ", " - ")
+        .replace(" | ", " - ");
+    let mut parts: Vec<&str> = normalized
+        .split(" - ")
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect();
+
+    while let Some(last) = parts.last() {
+        let lower = last.to_lowercase();
+        if BROWSER_SUFFIXES.contains(&lower.as_str()) || GENERIC_TITLES.contains(&lower.as_str()) {
+            parts.pop();
+        } else {
+            break;
+        }
+    }
+
+    while let Some(first) = parts.first() {
+        if GENERIC_TITLES.contains(&first.to_lowercase().as_str()) {
+            parts.remove(0);
+        } else {
+            break;
+        }
+    }
+
+    let candidate = parts.join(" - ");
+    if candidate.is_empty() || (app == MeetingApp::GoogleMeet && is_meet_code(&candidate)) {
+        return None;
+    }
+
+    Some(candidate)
+}
+
+/// Google Meet room codes look like "abc-defg-hij"
+fn is_meet_code(s: &str) -> bool {
+    let groups: Vec<&str> = s.split('-').collect();
+    groups.len() == 3
+        && groups
+            .iter()
+            .zip([3, 4, 3])
+            .all(|(g, len)| g.len() == len && g.chars().all(|c| c.is_ascii_lowercase()))
+}
+
+/// Render a title template. Supported placeholders: `{app}`, `{date}` (YYYY-MM-DD),
+/// `{time}` (HH:MM) and `{weekday}`.
+pub fn render_title_template(
+    template: &str,
+    app: MeetingApp,
+    now: &chrono::DateTime<chrono::Local>,
+) -> String {
+    template
+        .replace("{app}", &app.to_string())
+        .replace("{date}", &now.format("%Y-%m-%d").to_string())
+        .replace("{time}", &now.format("%H:%M").to_string())
+        .replace("{weekday}", &now.format("%A").to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,9 +233,112 @@ mod tests {
         assert_eq!(detect_meeting_app("discord", "Text Channel"), None);
     }
 
+    #[test]
+    fn test_detect_pwa() {
+        assert!(is_pwa("crx_cifhbcnohmdccbgoicgdjpfamggdegmo"));
+        assert!(is_pwa("chrome-cifhbcnohmdccbgoicgdjpfamggdegmo-Default"));
+        assert!(is_pwa("brave-ompifgpmddkgmclendfeacglnodjjndh-Profile_1"));
+        assert!(is_pwa("msedge-_cifhbcnohmdccbgoicgdjpfamggdegmo-Default"));
+        assert!(!is_pwa("google-chrome"));
+        assert!(!is_pwa("Brave-browser"));
+
+        assert_eq!(
+            detect_meeting_app(
+                "chrome-cifhbcnohmdccbgoicgdjpfamggdegmo-Default",
+                "Meeting with Dana | Microsoft Teams"
+            ),
+            Some(MeetingApp::MicrosoftTeams)
+        );
+        assert_eq!(
+            detect_meeting_app(
+                "crx_cifhbcnohmdccbgoicgdjpfamggdegmo",
+                "Microsoft Teams (work or school)"
+            ),
+            Some(MeetingApp::MicrosoftTeams)
+        );
+        assert_eq!(
+            detect_meeting_app(
+                "brave-ompifgpmddkgmclendfeacglnodjjndh-Default",
+                "Huddle: #eng-platform - Acme - Slack"
+            ),
+            Some(MeetingApp::Slack)
+        );
+        assert_eq!(
+            detect_meeting_app("Brave-browser", "Slack | huddle | Acme"),
+            Some(MeetingApp::Slack)
+        );
+        assert_eq!(
+            detect_meeting_app("crx_kjgfgldnnfoeklkmfkjfagphfepbbdan", "Meet - Weekly Sync"),
+            Some(MeetingApp::GoogleMeet)
+        );
+        assert_eq!(
+            detect_meeting_app("google-chrome", "Zoom Meeting - Google Chrome"),
+            Some(MeetingApp::Zoom)
+        );
+        assert_eq!(
+            detect_meeting_app("firefox", "General Voice - Discord - Mozilla Firefox"),
+            Some(MeetingApp::Discord)
+        );
+
+        // Browsing Slack or a page about calls is not a meeting
+        assert_eq!(
+            detect_meeting_app(
+                "brave-ompifgpmddkgmclendfeacglnodjjndh-Default",
+                "#general - Acme - Slack"
+            ),
+            None
+        );
+        assert_eq!(
+            detect_meeting_app("Brave-browser", "Slack call etiquette - Brave"),
+            None
+        );
+    }
+
     #[test]
     fn test_no_meeting() {
         assert_eq!(detect_meeting_app("alacritty", "Terminal"), None);
         assert_eq!(detect_meeting_app("code", "VS Code"), None);
     }
+
+    #[test]
+    fn test_meeting_title_from_window() {
+        assert_eq!(
+            meeting_title_from_window(
+                MeetingApp::GoogleMeet,
+                "Meet – Daily Standup - Google Chrome"
+            ),
+            Some("Daily Standup".to_string())
+        );
+        assert_eq!(
+            meeting_title_from_window(
+                MeetingApp::MicrosoftTeams,
+                "Sprint Review | Microsoft Teams"
+            ),
+            Some("Sprint Review".to_string())
+        );
+        assert_eq!(
+            meeting_title_from_window(MeetingApp::GoogleMeet, "Meet - abc-defg-hij - Firefox"),
+            None
+        );
+        assert_eq!(
+            meeting_title_from_window(MeetingApp::Zoom, "Zoom Meeting"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_render_title_template() {
+        use chrono::TimeZone;
+        let now = chrono::Local
+            .with_ymd_and_hms(2024, 3, 4, 9, 30, 0)
+            .unwrap();
+        assert_eq!(
+            render_title_template("{app} - {date}", MeetingApp::Zoom, &now),
+            "Zoom - 2024-03-04"
+        );
+        assert_eq!(
+            render_title_template("Standup {weekday} {time}", MeetingApp::Slack, &now),
+            "Standup Monday 09:30"
+        );
+    }
 }
@@ -1,3 +1,4 @@
+use crate::daemon::protocol::DaemonErrorKind;
 use std::path::PathBuf;
 use thiserror::Error;
 
@@ -18,7 +19,6 @@ pub enum MuesliError {
     #[error("Whisper model not found: {0}")]
     WhisperModelNotFound(PathBuf),
 
-    #[allow(dead_code)]
     #[error("Storage error: {0}")]
     Storage(String),
 
@@ -32,7 +32,6 @@ pub enum MuesliError {
     #[error("Config file not found: {0}")]
     ConfigNotFound(PathBuf),
 
-    #[allow(dead_code)]
     #[error("Invalid config: {0}")]
     InvalidConfig(String),
 
@@ -60,22 +59,196 @@ pub enum MuesliError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
-    #[error("Daemon not running")]
+    #[error("Daemon is not running. Start it with: muesli daemon")]
     DaemonNotRunning,
 
+    #[error("Daemon error: {0}")]
+    Daemon(String),
+
+    /// A request the daemon answered with an error
+    #[error("{message}")]
+    DaemonRequest {
+        kind: crate::daemon::protocol::DaemonErrorKind,
+        message: String,
+    },
+
     #[error("Meeting not found: {0}")]
     MeetingNotFound(String),
 
-    #[allow(dead_code)]
     #[error("Already recording")]
     AlreadyRecording,
 
-    #[allow(dead_code)]
     #[error("Not recording")]
     NotRecording,
 
     #[error("Qmd error: {0}")]
     Qmd(String),
+
+    #[error("Export error: {0}")]
+    Export(String),
+
+    #[error("Email error: {0}")]
+    Email(String),
+
+    #[error("{0}")]
+    Usage(String),
+
+    #[error("No results found for: {0}")]
+    NoResults(String),
+
+    #[error("Not configured: {0}")]
+    NotConfigured(String),
+
+    #[error("Timed out: {0}")]
+    Timeout(String),
+
+    #[error("Interrupted: {0}")]
+    Interrupted(String),
+}
+
+/// Process exit codes reported by the CLI
+pub mod exit_code {
+    /// Command completed successfully
+    #[allow(dead_code)]
+    pub const SUCCESS: i32 = 0;
+    /// Generic failure (I/O, transcription, API, ...)
+    pub const FAILURE: i32 = 1;
+    /// Invalid arguments or usage (matches clap's own usage errors)
+    pub const USAGE: i32 = 2;
+    /// The daemon is not running or could not be reached
+    pub const DAEMON_NOT_RUNNING: i32 = 3;
+    /// The requested meeting, model, or search result does not exist
+    pub const NOT_FOUND: i32 = 4;
+    /// A required feature (LLM, qmd, ...) is not configured
+    pub const NOT_CONFIGURED: i32 = 5;
+    /// A wait (e.g. `status --wait-for`) did not complete in time
+    pub const TIMEOUT: i32 = 6;
+    /// Stopped by Ctrl-C (128 + SIGINT, as shells report it)
+    pub const INTERRUPTED: i32 = 130;
+}
+
+impl MuesliError {
+    /// Exit code the CLI should terminate with for this error
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            MuesliError::Usage(_) => exit_code::USAGE,
+            MuesliError::DaemonNotRunning => exit_code::DAEMON_NOT_RUNNING,
+            MuesliError::MeetingNotFound(_)
+            | MuesliError::NoResults(_)
+            | MuesliError::WhisperModelNotFound(_)
+            | MuesliError::ConfigNotFound(_) => exit_code::NOT_FOUND,
+            MuesliError::NotConfigured(_) => exit_code::NOT_CONFIGURED,
+            MuesliError::Timeout(_) => exit_code::TIMEOUT,
+            MuesliError::Interrupted(_) => exit_code::INTERRUPTED,
+            MuesliError::DaemonRequest { kind, .. } => match kind {
+                DaemonErrorKind::ModelNotFound => exit_code::NOT_FOUND,
+                DaemonErrorKind::NotConfigured => exit_code::NOT_CONFIGURED,
+                _ => exit_code::FAILURE,
+            },
+            _ => exit_code::FAILURE,
+        }
+    }
+
+    /// Stable machine-readable name for this error, reported by `--json`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            MuesliError::Usage(_) => "usage",
+            MuesliError::DaemonNotRunning => "daemon_not_running",
+            MuesliError::MeetingNotFound(_) => "meeting_not_found",
+            MuesliError::NoResults(_) => "no_results",
+            MuesliError::WhisperModelNotFound(_) => "model_not_found",
+            MuesliError::ConfigNotFound(_) => "config_not_found",
+            MuesliError::NotConfigured(_) => "not_configured",
+            MuesliError::Timeout(_) => "timeout",
+            MuesliError::Interrupted(_) => "interrupted",
+            MuesliError::Config(_)
+            | MuesliError::InvalidConfig(_)
+            | MuesliError::TomlParse(_)
+            | MuesliError::TomlSerialize(_) => "config",
+            MuesliError::Audio(_)
+            | MuesliError::AudioDeviceNotFound(_)
+            | MuesliError::AudioStream(_) => "audio",
+            MuesliError::Transcription(_) => "transcription",
+            MuesliError::Storage(_) | MuesliError::Database(_) => "storage",
+            MuesliError::Api(_) | MuesliError::Http(_) | MuesliError::Json(_) => "api",
+            MuesliError::Daemon(_) => "daemon",
+            MuesliError::DaemonRequest { kind, .. } => kind.as_str(),
+            MuesliError::AlreadyRecording => "already_recording",
+            MuesliError::NotRecording => "not_recording",
+            MuesliError::HyprlandIpc(_) => "hyprland",
+            MuesliError::Notification(_) => "notification",
+            MuesliError::Qmd(_) => "qmd",
+            MuesliError::Export(_) => "export",
+            MuesliError::Email(_) => "email",
+            MuesliError::Io(_) => "io",
+        }
+    }
+
+    /// `{"error": {"kind": ..., "message": ...}}` as printed on stderr with `--json`
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "error": {
+                "kind": self.kind(),
+                "message": self.to_string(),
+            }
+        })
+    }
 }
 
 pub type Result<T> = std::result::Result<T, MuesliError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes() {
+        assert_eq!(
+            MuesliError::DaemonNotRunning.exit_code(),
+            exit_code::DAEMON_NOT_RUNNING
+        );
+        assert_eq!(
+            MuesliError::MeetingNotFound("abc".to_string()).exit_code(),
+            exit_code::NOT_FOUND
+        );
+        assert_eq!(
+            MuesliError::NotConfigured("llm".to_string()).exit_code(),
+            exit_code::NOT_CONFIGURED
+        );
+        assert_eq!(
+            MuesliError::Usage("bad".to_string()).exit_code(),
+            exit_code::USAGE
+        );
+        assert_eq!(
+            MuesliError::Timeout("idle".to_string()).exit_code(),
+            exit_code::TIMEOUT
+        );
+        assert_eq!(
+            MuesliError::Interrupted("step".to_string()).exit_code(),
+            exit_code::INTERRUPTED
+        );
+        assert_eq!(
+            MuesliError::Audio("x".to_string()).exit_code(),
+            exit_code::FAILURE
+        );
+    }
+
+    #[test]
+    fn test_error_json() {
+        let json = MuesliError::MeetingNotFound("abc".to_string()).to_json();
+        assert_eq!(json["error"]["kind"], "meeting_not_found");
+        assert_eq!(json["error"]["message"], "Meeting not found: abc");
+        assert_eq!(MuesliError::DaemonNotRunning.kind(), "daemon_not_running");
+
+        let refused = MuesliError::DaemonRequest {
+            kind: DaemonErrorKind::NotConfigured,
+            message: "Failed to start recording: no microphone configured".to_string(),
+        };
+        assert_eq!(refused.kind(), "not_configured");
+        assert_eq!(refused.exit_code(), exit_code::NOT_CONFIGURED);
+        assert_eq!(
+            refused.to_string(),
+            "Failed to start recording: no microphone configured"
+        );
+    }
+}
@@ -0,0 +1,42 @@
+//! Graceful Ctrl-C for long CLI operations (reprocessing, model downloads).
+//!
+//! Once [`install`] has run, the first Ctrl-C only sets a flag that those
+//! operations poll between atomic steps, so they stop with consistent state
+//! and can be resumed by running the same command again. A second Ctrl-C
+//! exits immediately.
+
+use crate::error::{exit_code, MuesliError, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Take over Ctrl-C for the rest of the process. Must be called from within
+/// the tokio runtime; later calls do nothing.
+pub fn install() {
+    if INSTALLED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tokio::spawn(async {
+        while tokio::signal::ctrl_c().await.is_ok() {
+            if REQUESTED.swap(true, Ordering::SeqCst) {
+                std::process::exit(exit_code::INTERRUPTED);
+            }
+            eprintln!("\nInterrupted: finishing the current step (Ctrl-C again to quit now)");
+        }
+    });
+}
+
+/// Whether Ctrl-C has been pressed since [`install`]
+pub fn requested() -> bool {
+    REQUESTED.load(Ordering::SeqCst)
+}
+
+/// `Err(Interrupted)` describing what was saved, if Ctrl-C has been pressed
+pub fn check(progress: impl FnOnce() -> String) -> Result<()> {
+    if requested() {
+        Err(MuesliError::Interrupted(progress()))
+    } else {
+        Ok(())
+    }
+}
@@ -4,9 +4,11 @@ pub mod config;
 pub mod daemon;
 pub mod detection;
 pub mod error;
+pub mod interrupt;
 pub mod llm;
 pub mod notes;
 pub mod notification;
+pub mod pipeline;
 pub mod qmd;
 pub mod storage;
 pub mod transcription;
@@ -25,3 +25,82 @@ pub fn context_limit_for_model(provider: &str, model: &str) -> Option<u64> {
         .find(|e| e.id == model)
         .map(|e| e.context)
 }
+
+/// Provider-independent model names, so `model = "fast"` or `model = "sonnet"`
+/// keeps working when the provider changes. `default` is used when no model is set.
+const MODEL_ALIASES: &[(&str, &[(&str, &str)])] = &[
+    (
+        "anthropic",
+        &[
+            ("default", "claude-sonnet-4-20250514"),
+            ("fast", "claude-3-5-haiku-20241022"),
+            ("smart", "claude-opus-4-20250514"),
+            ("haiku", "claude-3-5-haiku-20241022"),
+            ("sonnet", "claude-sonnet-4-20250514"),
+            ("opus", "claude-opus-4-20250514"),
+        ],
+    ),
+    (
+        "openai",
+        &[
+            ("default", "gpt-4o"),
+            ("fast", "gpt-4o-mini"),
+            ("smart", "gpt-4.1"),
+        ],
+    ),
+    (
+        "moonshot",
+        &[
+            ("default", "kimi-k2.5"),
+            ("fast", "kimi-k2-turbo-preview"),
+            ("smart", "kimi-k2.5"),
+        ],
+    ),
+    (
+        "openrouter",
+        &[
+            ("default", "anthropic/claude-sonnet-4"),
+            ("fast", "anthropic/claude-3.5-haiku"),
+            ("smart", "anthropic/claude-opus-4"),
+            ("haiku", "anthropic/claude-3.5-haiku"),
+            ("sonnet", "anthropic/claude-sonnet-4"),
+            ("opus", "anthropic/claude-opus-4"),
+        ],
+    ),
+];
+
+/// Concrete model id for an alias (case-insensitive), if the provider defines it
+pub fn resolve_alias(provider: &str, alias: &str) -> Option<&'static str> {
+    MODEL_ALIASES
+        .iter()
+        .find(|(p, _)| *p == provider)?
+        .1
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(alias.trim()))
+        .map(|(_, id)| *id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_alias_per_provider() {
+        assert_eq!(
+            resolve_alias("anthropic", "fast"),
+            Some("claude-3-5-haiku-20241022")
+        );
+        assert_eq!(resolve_alias("openai", "fast"), Some("gpt-4o-mini"));
+        assert_eq!(
+            resolve_alias("openrouter", "Sonnet"),
+            Some("anthropic/claude-sonnet-4")
+        );
+    }
+
+    #[test]
+    fn test_resolve_alias_unknown() {
+        assert_eq!(resolve_alias("openai", "sonnet"), None);
+        assert_eq!(resolve_alias("anthropic", "claude-opus-4-20250514"), None);
+        assert_eq!(resolve_alias("local", "fast"), None);
+    }
+}
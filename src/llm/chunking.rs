@@ -1,10 +1,61 @@
 use crate::llm::catalog;
 use crate::transcription::TranscriptSegment;
 
-const CHARS_PER_TOKEN: usize = 4;
+pub const DEFAULT_CHARS_PER_TOKEN: f32 = 4.0;
 const LOCAL_MODEL_CONTEXT_TOKENS: usize = 12_000;
 const CLOUD_FALLBACK_CONTEXT_TOKENS: usize = 128_000;
 const PROMPT_OVERHEAD_TOKENS: usize = 2_000;
+/// Extra headroom on top of a measured prompt template, for estimate error
+const PROMPT_SAFETY_MARGIN_TOKENS: usize = 500;
+
+/// How much of a model's context window is available for transcript text
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContextBudget {
+    pub context_tokens: usize,
+    pub chars_per_token: f32,
+    pub overhead_tokens: usize,
+}
+
+impl ContextBudget {
+    pub fn new(context_tokens: usize) -> Self {
+        Self {
+            context_tokens,
+            chars_per_token: DEFAULT_CHARS_PER_TOKEN,
+            overhead_tokens: PROMPT_OVERHEAD_TOKENS,
+        }
+    }
+
+    /// Override the chars/token estimate (ignored unless positive)
+    pub fn with_chars_per_token(mut self, chars_per_token: f32) -> Self {
+        if chars_per_token > 0.0 {
+            self.chars_per_token = chars_per_token;
+        }
+        self
+    }
+
+    pub fn with_overhead_tokens(mut self, overhead_tokens: usize) -> Self {
+        self.overhead_tokens = overhead_tokens;
+        self
+    }
+
+    /// Derive the overhead from the fixed parts of the request: the prompt
+    /// template rendered with an empty transcript, plus the system prompt.
+    pub fn with_prompt_overhead(self, template: &str, system_prompt: &str) -> Self {
+        let overhead = estimate_tokens(template, self.chars_per_token)
+            + estimate_tokens(system_prompt, self.chars_per_token)
+            + PROMPT_SAFETY_MARGIN_TOKENS;
+        self.with_overhead_tokens(overhead)
+    }
+
+    pub fn max_transcript_chars(&self) -> usize {
+        let available = self.context_tokens.saturating_sub(self.overhead_tokens);
+        (available as f32 * self.chars_per_token) as usize
+    }
+}
+
+pub fn estimate_tokens(text: &str, chars_per_token: f32) -> usize {
+    (text.len() as f32 / chars_per_token).ceil() as usize
+}
 
 pub struct TranscriptChunk {
     pub segments: Vec<TranscriptSegment>,
@@ -38,6 +89,18 @@ impl TranscriptChunk {
     }
 }
 
+/// Characters a segment occupies in the prompt, including the timestamp and
+/// speaker label added by `format_for_prompt`.
+fn segment_prompt_chars(segment: &TranscriptSegment) -> usize {
+    // "[00:00:00] " + optional "SPEAKER: " + text + newline
+    let label = segment.speaker.as_ref().map(|s| s.len() + 2).unwrap_or(0);
+    11 + label + segment.text.len() + 1
+}
+
+fn total_prompt_chars(segments: &[TranscriptSegment]) -> usize {
+    segments.iter().map(segment_prompt_chars).sum()
+}
+
 pub fn resolve_context_limit(provider: &str, model: &str, config_override: usize) -> usize {
     if config_override > 0 {
         return config_override;
@@ -60,23 +123,17 @@ pub fn resolve_context_limit(provider: &str, model: &str, config_override: usize
         })
 }
 
-pub fn max_transcript_chars(context_tokens: usize) -> usize {
-    let available = context_tokens.saturating_sub(PROMPT_OVERHEAD_TOKENS);
-    available * CHARS_PER_TOKEN
-}
-
-pub fn needs_chunking(segments: &[TranscriptSegment], context_tokens: usize) -> bool {
-    let total_chars: usize = segments.iter().map(|s| s.text.len()).sum();
-    total_chars > max_transcript_chars(context_tokens)
+pub fn needs_chunking(segments: &[TranscriptSegment], budget: &ContextBudget) -> bool {
+    total_prompt_chars(segments) > budget.max_transcript_chars()
 }
 
 pub fn chunk_transcript(
     segments: &[TranscriptSegment],
-    context_tokens: usize,
+    budget: &ContextBudget,
 ) -> Vec<TranscriptChunk> {
-    let max_chars = max_transcript_chars(context_tokens);
+    let max_chars = budget.max_transcript_chars();
 
-    if segments.iter().map(|s| s.text.len()).sum::<usize>() <= max_chars {
+    if total_prompt_chars(segments) <= max_chars {
         return vec![TranscriptChunk {
             segments: segments.to_vec(),
             chunk_index: 0,
@@ -91,7 +148,7 @@ pub fn chunk_transcript(
     let mut current_chars = 0;
 
     for segment in segments {
-        let segment_chars = segment.text.len();
+        let segment_chars = segment_prompt_chars(segment);
 
         if current_chars + segment_chars > max_chars && !current_segments.is_empty() {
             chunks.push(current_segments);
@@ -122,6 +179,60 @@ pub fn chunk_transcript(
         .collect()
 }
 
+/// Cut the middle out of `text` so it fits in `max_chars`, keeping the start
+/// (where prompts put the question) and the end (closing instructions)
+pub fn truncate_middle(text: &str, max_chars: usize) -> String {
+    if text.len() <= max_chars {
+        return text.to_string();
+    }
+
+    let marker = "\n\n[... truncated to fit the model's context window ...]\n\n";
+    let keep = max_chars.saturating_sub(marker.len());
+    let head = floor_char_boundary(text, keep * 3 / 4);
+    let tail = ceil_char_boundary(text, text.len() - (keep - keep * 3 / 4));
+
+    format!("{}{}{}", &text[..head], marker, &text[tail..])
+}
+
+/// Split `text` into pieces of at most `max_chars`, breaking at line ends
+/// where possible
+pub fn split_text(text: &str, max_chars: usize) -> Vec<&str> {
+    let max_chars = max_chars.max(1);
+    let mut pieces = Vec::new();
+    let mut rest = text;
+
+    while rest.len() > max_chars {
+        let limit = floor_char_boundary(rest, max_chars);
+        let cut = match rest[..limit].rfind('\n') {
+            Some(pos) if pos > 0 => pos + 1,
+            _ if limit > 0 => limit,
+            _ => ceil_char_boundary(rest, 1),
+        };
+        pieces.push(&rest[..cut]);
+        rest = &rest[cut..];
+    }
+    if !rest.is_empty() {
+        pieces.push(rest);
+    }
+    pieces
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn ceil_char_boundary(text: &str, mut index: usize) -> usize {
+    index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
 fn format_timestamp(ms: u64) -> String {
     let total_seconds = ms / 1000;
     let hours = total_seconds / 3600;
@@ -146,6 +257,7 @@ mod tests {
             text: text.to_string(),
             speaker: speaker.map(|s| s.to_string()),
             confidence: None,
+            language: None,
         }
     }
 
@@ -156,7 +268,7 @@ mod tests {
             make_segment(5000, "How are you", Some("SPEAKER_1")),
         ];
 
-        let chunks = chunk_transcript(&segments, 200_000);
+        let chunks = chunk_transcript(&segments, &ContextBudget::new(200_000));
         assert_eq!(chunks.len(), 1);
         assert_eq!(chunks[0].chunk_index, 0);
         assert_eq!(chunks[0].total_chunks, 1);
@@ -165,7 +277,7 @@ mod tests {
     #[test]
     fn test_needs_chunking_false_for_small() {
         let segments = vec![make_segment(0, "Short text", None)];
-        assert!(!needs_chunking(&segments, 200_000));
+        assert!(!needs_chunking(&segments, &ContextBudget::new(200_000)));
     }
 
     #[test]
@@ -173,8 +285,8 @@ mod tests {
         let long_text = "x".repeat(60_000);
         let segments = vec![make_segment(0, &long_text, None)];
 
-        assert!(needs_chunking(&segments, 12_000));
-        assert!(!needs_chunking(&segments, 200_000));
+        assert!(needs_chunking(&segments, &ContextBudget::new(12_000)));
+        assert!(!needs_chunking(&segments, &ContextBudget::new(200_000)));
     }
 
     #[test]
@@ -186,7 +298,7 @@ mod tests {
             make_segment(120000, &long_text, Some("SPEAKER_0")),
         ];
 
-        let chunks = chunk_transcript(&segments, 12_000);
+        let chunks = chunk_transcript(&segments, &ContextBudget::new(12_000));
         assert!(chunks.len() > 1);
 
         for (i, chunk) in chunks.iter().enumerate() {
@@ -204,7 +316,7 @@ mod tests {
             make_segment(120000, &long_text, Some("SPEAKER_0")),
         ];
 
-        let chunks = chunk_transcript(&segments, 200_000);
+        let chunks = chunk_transcript(&segments, &ContextBudget::new(200_000));
         assert_eq!(chunks.len(), 1);
     }
 
@@ -215,7 +327,7 @@ mod tests {
             make_segment(5000, "Hi there", Some("SPEAKER_1")),
         ];
 
-        let chunks = chunk_transcript(&segments, 200_000);
+        let chunks = chunk_transcript(&segments, &ContextBudget::new(200_000));
         let formatted = chunks[0].format_for_prompt();
 
         assert!(formatted.contains("[00:00] SPEAKER_0: Hello"));
@@ -231,8 +343,57 @@ mod tests {
 
     #[test]
     fn test_max_transcript_chars() {
-        assert_eq!(max_transcript_chars(12_000), 40_000);
-        assert_eq!(max_transcript_chars(200_000), 792_000);
+        assert_eq!(ContextBudget::new(12_000).max_transcript_chars(), 40_000);
+        assert_eq!(ContextBudget::new(200_000).max_transcript_chars(), 792_000);
+    }
+
+    #[test]
+    fn test_chars_per_token_override() {
+        let budget = ContextBudget::new(12_000).with_chars_per_token(2.5);
+        assert_eq!(budget.max_transcript_chars(), 25_000);
+
+        let ignored = ContextBudget::new(12_000).with_chars_per_token(0.0);
+        assert_eq!(ignored.chars_per_token, DEFAULT_CHARS_PER_TOKEN);
+    }
+
+    #[test]
+    fn test_dynamic_prompt_overhead() {
+        let template = "t".repeat(4_000);
+        let system = "s".repeat(400);
+        let budget = ContextBudget::new(12_000).with_prompt_overhead(&template, &system);
+
+        assert_eq!(
+            budget.overhead_tokens,
+            1_000 + 100 + PROMPT_SAFETY_MARGIN_TOKENS
+        );
+        assert_eq!(budget.max_transcript_chars(), (12_000 - 1_600) * 4);
+
+        let denser = ContextBudget::new(12_000)
+            .with_chars_per_token(2.0)
+            .with_prompt_overhead(&template, &system);
+        assert_eq!(
+            denser.overhead_tokens,
+            2_000 + 200 + PROMPT_SAFETY_MARGIN_TOKENS
+        );
+    }
+
+    #[test]
+    fn test_speaker_labels_count_toward_budget() {
+        let text = "x".repeat(100);
+        let plain = vec![make_segment(0, &text, None)];
+        let labeled = vec![make_segment(0, &text, Some("SPEAKER_1"))];
+
+        assert!(total_prompt_chars(&labeled) > total_prompt_chars(&plain));
+        assert_eq!(
+            total_prompt_chars(&labeled) - total_prompt_chars(&plain),
+            "SPEAKER_1: ".len()
+        );
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens("", 4.0), 0);
+        assert_eq!(estimate_tokens("abcde", 4.0), 2);
     }
 
     #[test]
@@ -243,6 +404,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_truncate_middle() {
+        assert_eq!(truncate_middle("short", 100), "short");
+
+        let text = format!("QUESTION\n{}\nEND", "é".repeat(5_000));
+        let truncated = truncate_middle(&text, 1_000);
+        assert!(truncated.len() <= 1_000);
+        assert!(truncated.starts_with("QUESTION\n"));
+        assert!(truncated.ends_with("\nEND"));
+        assert!(truncated.contains("[... truncated"));
+    }
+
+    #[test]
+    fn test_split_text() {
+        let text = "line one\nline two\nline three\n";
+        let pieces = split_text(text, 12);
+        assert_eq!(pieces, vec!["line one\n", "line two\n", "line three\n"]);
+        assert_eq!(pieces.concat(), text);
+
+        let unbroken = "é".repeat(10);
+        let pieces = split_text(&unbroken, 5);
+        assert!(pieces.iter().all(|p| p.len() <= 5));
+        assert_eq!(pieces.concat(), unbroken);
+    }
+
     #[test]
     fn test_resolve_context_limit_local() {
         assert_eq!(
@@ -1,13 +1,24 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
+use super::RequestOptions;
+
 const CLAUDE_API_URL: &str = "https://api.anthropic.com/v1/messages";
 
+/// The Messages API requires `max_tokens`; this fits long meeting notes
+const DEFAULT_MAX_TOKENS: u32 = 8192;
+
 #[derive(Debug, Serialize)]
 struct ClaudeRequest {
     model: String,
     max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
     messages: Vec<ClaudeMessage>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -26,33 +37,14 @@ struct ClaudeContent {
     text: String,
 }
 
-pub async fn summarize_with_claude(api_key: &str, model: &str, prompt: &str) -> Result<String> {
-    let client = reqwest::Client::new();
-
-    let request = ClaudeRequest {
-        model: model.to_string(),
-        max_tokens: 4096,
-        messages: vec![ClaudeMessage {
-            role: "user".to_string(),
-            content: prompt.to_string(),
-        }],
-    };
-
-    let response = client
-        .post(CLAUDE_API_URL)
-        .header("x-api-key", api_key)
-        .header("anthropic-version", "2023-06-01")
-        .header("content-type", "application/json")
-        .json(&request)
-        .send()
-        .await
-        .context("Failed to send request to Claude API")?;
-
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response.text().await.unwrap_or_default();
-        anyhow::bail!("Claude API error {}: {}", status, error_text);
-    }
+pub async fn summarize_with_claude(
+    api_key: &str,
+    model: &str,
+    system_prompt: &str,
+    prompt: &str,
+    options: &RequestOptions,
+) -> Result<String> {
+    let response = send_request(api_key, model, system_prompt, prompt, options, false).await?;
 
     let claude_response: ClaudeResponse = response
         .json()
@@ -66,6 +58,77 @@ pub async fn summarize_with_claude(api_key: &str, model: &str, prompt: &str) ->
         .context("No content in Claude response")
 }
 
+/// [`summarize_with_claude`], passing the text to `on_token` as it streams in
+pub async fn stream_with_claude(
+    api_key: &str,
+    model: &str,
+    system_prompt: &str,
+    prompt: &str,
+    options: &RequestOptions,
+    on_token: &mut dyn FnMut(&str),
+) -> Result<String> {
+    let response = send_request(api_key, model, system_prompt, prompt, options, true).await?;
+    super::streaming::read_text_stream(response, delta_text, on_token).await
+}
+
+/// Text carried by one streamed Messages API event
+fn delta_text(data: &str) -> Result<Option<String>> {
+    let event: serde_json::Value =
+        serde_json::from_str(data).context("Failed to parse Claude stream event")?;
+    match event["type"].as_str() {
+        Some("content_block_delta") => Ok(event["delta"]["text"].as_str().map(str::to_string)),
+        Some("error") => anyhow::bail!(
+            "Claude API stream error: {}",
+            event["error"]["message"].as_str().unwrap_or("unknown")
+        ),
+        _ => Ok(None),
+    }
+}
+
+async fn send_request(
+    api_key: &str,
+    model: &str,
+    system_prompt: &str,
+    prompt: &str,
+    options: &RequestOptions,
+    stream: bool,
+) -> Result<reqwest::Response> {
+    let client = reqwest::Client::new();
+
+    let request = ClaudeRequest {
+        model: model.to_string(),
+        max_tokens: options.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
+        temperature: options.temperature,
+        system: (!system_prompt.is_empty()).then(|| system_prompt.to_string()),
+        messages: vec![ClaudeMessage {
+            role: "user".to_string(),
+            content: prompt.to_string(),
+        }],
+        stream,
+    };
+
+    let (client, request) = (&client, &request);
+    super::with_retries(options.max_retries, move || async move {
+        let response = client
+            .post(CLAUDE_API_URL)
+            .header("x-api-key", api_key)
+            .header("anthropic-version", "2023-06-01")
+            .header("content-type", "application/json")
+            .json(request)
+            .send()
+            .await
+            .context("Failed to send request to Claude API")?;
+
+        if !response.status().is_success() {
+            return Err(super::ApiStatusError::from_response("Claude API", response)
+                .await
+                .into());
+        }
+        Ok(response)
+    })
+    .await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,14 +138,47 @@ mod tests {
         let request = ClaudeRequest {
             model: "claude-sonnet-4-20250514".to_string(),
             max_tokens: 4096,
+            temperature: None,
+            system: None,
             messages: vec![ClaudeMessage {
                 role: "user".to_string(),
                 content: "test".to_string(),
             }],
+            stream: false,
         };
 
         let json = serde_json::to_string(&request).unwrap();
         assert!(json.contains("claude-sonnet-4-20250514"));
         assert!(json.contains("test"));
+        assert!(!json.contains("\"system\""));
+        assert!(!json.contains("\"stream\""));
+        assert!(!json.contains("\"temperature\""));
+    }
+
+    #[test]
+    fn test_request_with_system_prompt() {
+        let request = ClaudeRequest {
+            model: "claude-sonnet-4-20250514".to_string(),
+            max_tokens: 8192,
+            temperature: Some(0.5),
+            system: Some("Be concise".to_string()),
+            messages: vec![],
+            stream: true,
+        };
+
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(json.contains("\"system\":\"Be concise\""));
+        assert!(json.contains("\"stream\":true"));
+        assert!(json.contains("\"temperature\":0.5"));
+        assert!(json.contains("\"max_tokens\":8192"));
+    }
+
+    #[test]
+    fn test_delta_text() {
+        let delta =
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#;
+        assert_eq!(delta_text(delta).unwrap().as_deref(), Some("Hi"));
+        assert!(delta_text(r#"{"type":"message_stop"}"#).unwrap().is_none());
+        assert!(delta_text(r#"{"type":"error","error":{"message":"Overloaded"}}"#).is_err());
     }
 }
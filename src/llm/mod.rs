@@ -4,11 +4,17 @@ pub mod claude;
 pub mod local;
 pub mod openai_compat;
 pub mod prompts;
+pub mod redaction;
+pub mod streaming;
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::config::settings::LlmConfig;
+use crate::llm::prompts::SummaryTemplate;
+use crate::storage::database::Database;
+use crate::storage::ActionItem;
 use crate::transcription::Transcript;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -42,14 +48,184 @@ impl LlmProvider {
     }
 }
 
+/// Non-success HTTP response from a hosted provider
+#[derive(Debug, thiserror::Error)]
+#[error("API error {status} from {provider}: {body}")]
+pub struct ApiStatusError {
+    pub provider: String,
+    pub status: u16,
+    pub body: String,
+    /// How long the provider asked us to wait (`Retry-After`), if it did
+    pub retry_after: Option<Duration>,
+}
+
+impl ApiStatusError {
+    /// The error for a non-success `response`, reading its body
+    pub async fn from_response(provider: &str, response: reqwest::Response) -> Self {
+        let status = response.status().as_u16();
+        // Only the delay-seconds form; HTTP dates fall back to our own backoff
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok())
+            .map(Duration::from_secs);
+        Self {
+            provider: provider.to_string(),
+            status,
+            body: response.text().await.unwrap_or_default(),
+            retry_after,
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        self.status == 429 || (500..=599).contains(&self.status)
+    }
+}
+
+/// Longest `Retry-After` worth waiting for; a provider asking for more (an
+/// exhausted daily quota, say) fails the request instead of hanging it
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Cached chunk summaries older than this are pruned before summarizing
+const CHUNK_CACHE_MAX_AGE_DAYS: i64 = 30;
+
+/// Run `send` until it succeeds, retrying rate limits and server errors (429,
+/// 5xx) up to `max_retries` times: after the provider's `Retry-After` when it
+/// gives one (up to [`MAX_RETRY_AFTER`]), otherwise after 1s, 2s, 4s, ... plus
+/// jitter. Any other error is returned at once.
+pub async fn with_retries<T, F, Fut>(max_retries: u32, mut send: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let mut retry = 0;
+    loop {
+        let error = match send().await {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        };
+        let Some(status) = error.downcast_ref::<ApiStatusError>() else {
+            return Err(error);
+        };
+        if retry >= max_retries || !status.is_retryable() {
+            return Err(error);
+        }
+
+        if let Some(wait) = status.retry_after.filter(|wait| *wait > MAX_RETRY_AFTER) {
+            tracing::warn!(
+                "{} returned {} and asked to retry in {}s; giving up",
+                status.provider,
+                status.status,
+                wait.as_secs()
+            );
+            return Err(error);
+        }
+        let delay = status.retry_after.unwrap_or_else(|| backoff_delay(retry));
+        tracing::warn!(
+            "{} returned {}; retrying in {:.1}s ({}/{})",
+            status.provider,
+            status.status,
+            delay.as_secs_f32(),
+            retry + 1,
+            max_retries
+        );
+        tokio::time::sleep(delay).await;
+        retry += 1;
+    }
+}
+
+/// 2^retry seconds plus up to half a second, so clients rate-limited together
+/// don't all come back at once
+fn backoff_delay(retry: u32) -> Duration {
+    let jitter = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_millis() % 500)
+        .unwrap_or(0);
+    Duration::from_secs(1 << retry.min(6)) + Duration::from_millis(jitter as u64)
+}
+
+/// Sampling temperature when `[llm] temperature` is unset; low, so notes come
+/// out much the same on a rerun
+pub const DEFAULT_TEMPERATURE: f32 = 0.3;
+
+/// Request settings the hosted providers share, from `[llm]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RequestOptions {
+    pub temperature: Option<f32>,
+    /// Output limit (`None` = the provider's own default)
+    pub max_tokens: Option<u32>,
+    pub max_retries: u32,
+}
+
+impl RequestOptions {
+    pub fn from_config(config: &LlmConfig) -> Self {
+        Self {
+            temperature: Some(config.temperature.unwrap_or(DEFAULT_TEMPERATURE)),
+            max_tokens: config.max_tokens,
+            max_retries: config.max_retries,
+        }
+    }
+}
+
+/// Whether an LLM error is the provider's fault (quota, auth, outage,
+/// unreachable) rather than the request's, i.e. worth retrying elsewhere
+pub fn is_provider_failure(error: &anyhow::Error) -> bool {
+    match error.downcast_ref::<ApiStatusError>() {
+        Some(e) => matches!(e.status, 401 | 402 | 403 | 408 | 429) || e.status >= 500,
+        None => true,
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SummaryResult {
     pub markdown: String,
 }
 
+/// Summarizes a transcript. When `chunk_cache` is given, per-chunk summaries of
+/// long transcripts are reused from the database if the chunk is unchanged.
+/// If the provider fails and `fallback_provider` is set, the whole summary is
+/// retried against the fallback.
 pub async fn summarize_transcript(
     config: &LlmConfig,
     transcript: &Transcript,
+    template: SummaryTemplate,
+    chunk_cache: Option<&Database>,
+) -> Result<SummaryResult> {
+    let error = match summarize_with(config, transcript, template, chunk_cache).await {
+        Ok(summary) => return Ok(summary),
+        Err(e) => e,
+    };
+    let Some(fallback) = failover_config(config, &error) else {
+        return Err(error);
+    };
+    summarize_with(&fallback, transcript, template, chunk_cache)
+        .await
+        .with_context(|| format!("Fallback provider also failed (primary: {:#})", error))
+}
+
+/// The fallback to switch to after `error`, logging the failover
+fn failover_config(config: &LlmConfig, error: &anyhow::Error) -> Option<LlmConfig> {
+    if !is_provider_failure(error) {
+        return None;
+    }
+    let fallback = config.fallback()?;
+    tracing::warn!(
+        "LLM provider {}/{} failed ({:#}); failing over to {}/{}",
+        config.provider,
+        config.effective_model(),
+        error,
+        fallback.provider,
+        fallback.effective_model()
+    );
+    Some(fallback)
+}
+
+async fn summarize_with(
+    config: &LlmConfig,
+    transcript: &Transcript,
+    template: SummaryTemplate,
+    chunk_cache: Option<&Database>,
 ) -> Result<SummaryResult> {
     let provider =
         LlmProvider::from_provider(&config.provider).context("Invalid LLM provider specified")?;
@@ -64,19 +240,22 @@ pub async fn summarize_transcript(
         context_limit
     );
 
-    if chunking::needs_chunking(&transcript.segments, context_limit) {
+    let budget = context_budget(config, context_limit);
+
+    if chunking::needs_chunking(&transcript.segments, &budget) {
         tracing::info!("Transcript is large, using chunked summarization");
-        return summarize_chunked(config, provider, &transcript.segments, context_limit).await;
+        return summarize_chunked(
+            config,
+            provider,
+            &transcript.segments,
+            template,
+            &budget,
+            chunk_cache,
+        )
+        .await;
     }
 
-    let has_speakers = transcript.segments.iter().any(|s| s.speaker.is_some());
-    let prompt = if has_speakers {
-        prompts::meeting_summary_prompt_with_speakers(&transcript.segments)
-    } else {
-        let transcript_text = transcript.full_text();
-        prompts::meeting_summary_prompt(&transcript_text)
-    };
-
+    let prompt = single_prompt(transcript, template, config.output_language());
     let response_text = call_llm(config, provider, &prompt).await?;
 
     Ok(SummaryResult {
@@ -85,10 +264,24 @@ pub async fn summarize_transcript(
 }
 
 pub async fn generate_title(config: &LlmConfig, meeting_notes: &str) -> Result<String> {
+    let error = match title_with(config, meeting_notes).await {
+        Ok(title) => return Ok(title),
+        Err(e) => e,
+    };
+    match failover_config(config, &error) {
+        Some(fallback) => title_with(&fallback, meeting_notes).await,
+        None => Err(error),
+    }
+}
+
+async fn title_with(config: &LlmConfig, meeting_notes: &str) -> Result<String> {
     let provider =
         LlmProvider::from_provider(&config.provider).context("Invalid LLM provider specified")?;
 
-    let prompt = prompts::title_generation_prompt(meeting_notes);
+    let mut prompt = prompts::title_generation_prompt(meeting_notes);
+    if let Some(language) = config.output_language() {
+        prompt.push_str(&prompts::output_language_note(language));
+    }
     let title = call_llm(config, provider, &prompt).await?;
 
     let cleaned = title
@@ -103,15 +296,77 @@ pub async fn generate_title(config: &LlmConfig, meeting_notes: &str) -> Result<S
     Ok(cleaned)
 }
 
+/// The action items in finished meeting notes, asked for as JSON in a
+/// separate request. `meeting_date` resolves relative due dates.
+pub async fn extract_action_items(
+    config: &LlmConfig,
+    meeting_notes: &str,
+    meeting_date: chrono::NaiveDate,
+) -> Result<Vec<ActionItem>> {
+    let provider =
+        LlmProvider::from_provider(&config.provider).context("Invalid LLM provider specified")?;
+
+    let mut prompt = prompts::action_items_prompt(
+        meeting_notes,
+        &meeting_date.format("%Y-%m-%d (%A)").to_string(),
+    );
+    if let Some(language) = config.output_language() {
+        prompt.push_str(&prompts::output_language_note(language));
+    }
+    let response = call_llm(config, provider, &prompt).await?;
+    parse_action_items(&response)
+}
+
+/// The JSON array in a model's answer, which may come in a code fence or with
+/// a sentence around it. Blank owners and dates that aren't YYYY-MM-DD are dropped.
+fn parse_action_items(response: &str) -> Result<Vec<ActionItem>> {
+    let start = response
+        .find('[')
+        .context("No JSON array in the action items response")?;
+    let end = response
+        .rfind(']')
+        .filter(|&end| end > start)
+        .context("No JSON array in the action items response")?;
+    let items: Vec<ActionItem> =
+        serde_json::from_str(&response[start..=end]).context("Failed to parse action items")?;
+
+    Ok(items
+        .into_iter()
+        .filter(|item| !item.text.trim().is_empty())
+        .map(|item| ActionItem {
+            text: item.text.trim().to_string(),
+            owner: item.owner.filter(|owner| !owner.trim().is_empty()),
+            due_date: item
+                .due_date
+                .filter(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok()),
+            done: false,
+        })
+        .collect())
+}
+
 async fn summarize_chunked(
     config: &LlmConfig,
     provider: LlmProvider,
     segments: &[crate::transcription::TranscriptSegment],
-    context_limit: usize,
+    template: SummaryTemplate,
+    budget: &chunking::ContextBudget,
+    chunk_cache: Option<&Database>,
 ) -> Result<SummaryResult> {
-    let chunks = chunking::chunk_transcript(segments, context_limit);
+    let chunks = chunking::chunk_transcript(segments, budget);
     tracing::info!("Split transcript into {} chunks", chunks.len());
 
+    let model = config.effective_model();
+    if let Some(db) = chunk_cache {
+        match db.prune_chunk_summary_cache(
+            prompts::CHUNK_PROMPT_VERSION,
+            chrono::Duration::days(CHUNK_CACHE_MAX_AGE_DAYS),
+        ) {
+            Ok(0) => {}
+            Ok(n) => tracing::info!("Invalidated {} cached chunk summaries", n),
+            Err(e) => tracing::warn!("Failed to prune chunk summary cache: {}", e),
+        }
+    }
+
     let mut chunk_summaries = Vec::new();
 
     for chunk in &chunks {
@@ -124,11 +379,43 @@ async fn summarize_chunked(
             format_time(chunk.end_time_ms)
         );
 
-        let chunk_text = chunk.format_for_prompt();
-        let prompt =
-            prompts::chunk_summary_prompt(&chunk_text, chunk.chunk_index, chunk.total_chunks);
+        let prompt = chunk_prompt(chunk, template, config.output_language());
+
+        let cache_key = chunk_cache_key(
+            &config.provider,
+            model,
+            &RequestOptions::from_config(config),
+            config.effective_system_prompt(),
+            &prompt,
+        );
+        let cached = chunk_cache.and_then(|db| match db.get_cached_chunk_summary(&cache_key) {
+            Ok(summary) => summary,
+            Err(e) => {
+                tracing::warn!("Failed to read chunk summary cache: {}", e);
+                None
+            }
+        });
 
-        let summary = call_llm(config, provider, &prompt).await?;
+        let summary = match cached {
+            Some(summary) => {
+                tracing::info!("Reusing cached summary for chunk {}", chunk.chunk_index + 1);
+                summary
+            }
+            None => {
+                let summary = call_llm(config, provider, &prompt).await?;
+                if let Some(db) = chunk_cache {
+                    if let Err(e) = db.insert_cached_chunk_summary(
+                        &cache_key,
+                        model,
+                        prompts::CHUNK_PROMPT_VERSION,
+                        &summary,
+                    ) {
+                        tracing::warn!("Failed to cache chunk summary: {}", e);
+                    }
+                }
+                summary
+            }
+        };
         chunk_summaries.push(summary);
     }
 
@@ -136,7 +423,13 @@ async fn summarize_chunked(
         "Synthesizing {} chunk summaries into final notes",
         chunk_summaries.len()
     );
-    let synthesis_prompt = prompts::synthesis_prompt(&chunk_summaries);
+    let mut synthesis_prompt = prompts::synthesis_prompt(&chunk_summaries);
+    if let Some(note) = template.summary_note() {
+        synthesis_prompt.push_str(&note);
+    }
+    if let Some(language) = config.output_language() {
+        synthesis_prompt.push_str(&prompts::output_language_note(language));
+    }
     let final_summary = call_llm(config, provider, &synthesis_prompt).await?;
 
     Ok(SummaryResult {
@@ -144,7 +437,247 @@ async fn summarize_chunked(
     })
 }
 
-pub async fn ask(config: &LlmConfig, prompt: &str) -> Result<String> {
+/// Prompt for summarizing a transcript that fits in one request. A chosen
+/// output `language` replaces the mixed-language note.
+fn single_prompt(
+    transcript: &Transcript,
+    template: SummaryTemplate,
+    language: Option<&str>,
+) -> String {
+    let has_speakers = transcript.segments.iter().any(|s| s.speaker.is_some());
+    let mut prompt = if has_speakers {
+        prompts::meeting_summary_prompt_with_speakers(&transcript.segments)
+    } else {
+        let transcript_text = transcript.full_text();
+        prompts::meeting_summary_prompt(&transcript_text)
+    };
+    if let Some(note) = template.summary_note() {
+        prompt.push_str(&note);
+    }
+    match language {
+        Some(language) => prompt.push_str(&prompts::output_language_note(language)),
+        None => {
+            if let Some(note) = prompts::mixed_language_note(&transcript.segment_languages()) {
+                prompt.push_str(&note);
+            }
+        }
+    }
+    prompt
+}
+
+fn chunk_prompt(
+    chunk: &chunking::TranscriptChunk,
+    template: SummaryTemplate,
+    language: Option<&str>,
+) -> String {
+    let chunk_text = chunk.format_for_prompt();
+    let mut prompt =
+        prompts::chunk_summary_prompt(&chunk_text, chunk.chunk_index, chunk.total_chunks);
+    if let Some(note) = template.chunk_note() {
+        prompt.push_str(&note);
+    }
+    match language {
+        Some(language) => prompt.push_str(&prompts::output_language_note(language)),
+        None => {
+            let languages = crate::transcription::segment_languages(&chunk.segments);
+            if let Some(note) = prompts::mixed_language_note(&languages) {
+                prompt.push_str(&note);
+            }
+        }
+    }
+    prompt
+}
+
+/// The requests `summarize_transcript` would send, built without calling the LLM
+pub struct PromptPreview {
+    pub context_limit: usize,
+    pub budget: chunking::ContextBudget,
+    pub system_prompt: String,
+    /// One prompt for a single-pass summary, or one per chunk. The final
+    /// synthesis prompt of a chunked run depends on the chunk outputs and is not included.
+    pub prompts: Vec<String>,
+    pub chunked: bool,
+}
+
+pub fn preview_prompts(
+    config: &LlmConfig,
+    transcript: &Transcript,
+    template: SummaryTemplate,
+) -> PromptPreview {
+    let model = config.effective_model();
+    let context_limit =
+        chunking::resolve_context_limit(&config.provider, model, config.context_limit);
+    let budget = context_budget(config, context_limit);
+    let chunked = chunking::needs_chunking(&transcript.segments, &budget);
+
+    let prompts = if chunked {
+        chunking::chunk_transcript(&transcript.segments, &budget)
+            .iter()
+            .map(|chunk| chunk_prompt(chunk, template, config.output_language()))
+            .collect()
+    } else {
+        vec![single_prompt(
+            transcript,
+            template,
+            config.output_language(),
+        )]
+    };
+
+    PromptPreview {
+        context_limit,
+        budget,
+        system_prompt: config.effective_system_prompt().to_string(),
+        prompts,
+        chunked,
+    }
+}
+
+/// Token budget for transcript text. Unless `prompt_overhead_tokens` is set, the
+/// overhead is measured from the longest prompt template plus the system prompt.
+fn context_budget(config: &LlmConfig, context_limit: usize) -> chunking::ContextBudget {
+    let budget =
+        chunking::ContextBudget::new(context_limit).with_chars_per_token(config.chars_per_token);
+
+    if config.prompt_overhead_tokens > 0 {
+        return budget.with_overhead_tokens(config.prompt_overhead_tokens);
+    }
+
+    let mut template = [
+        prompts::meeting_summary_prompt(""),
+        prompts::meeting_summary_prompt_with_speakers(&[]),
+        prompts::chunk_summary_prompt("", 0, 1),
+    ]
+    .into_iter()
+    .max_by_key(String::len)
+    .unwrap_or_default();
+    // Leave room for the longest meeting-type note too
+    if let Some(note) = SummaryTemplate::ALL
+        .iter()
+        .filter_map(|t| t.summary_note())
+        .max_by_key(String::len)
+    {
+        template.push_str(&note);
+    }
+
+    budget.with_prompt_overhead(&template, config.effective_system_prompt())
+}
+
+/// Answer a free-form prompt, passing the answer to `on_token` as it streams in
+/// (all at once for providers that can't stream)
+pub async fn ask(config: &LlmConfig, prompt: &str, on_token: impl FnMut(&str)) -> Result<String> {
+    if config.provider == "none" {
+        anyhow::bail!("LLM is not configured. Run 'muesli setup' to set up an LLM provider.");
+    }
+
+    let provider =
+        LlmProvider::from_provider(&config.provider).context("Invalid LLM provider specified")?;
+
+    let context_limit = chunking::resolve_context_limit(
+        &config.provider,
+        config.effective_model(),
+        config.context_limit,
+    );
+    let max_chars = chunking::ContextBudget::new(context_limit)
+        .with_chars_per_token(config.chars_per_token)
+        .with_prompt_overhead("", config.effective_system_prompt())
+        .max_transcript_chars();
+
+    if prompt.len() <= max_chars {
+        return call_llm_streaming(config, provider, prompt, on_token).await;
+    }
+
+    let prompt = if config.ask_overflow == "summarize" {
+        condense_for_context(config, provider, prompt, max_chars).await?
+    } else {
+        tracing::warn!(
+            "Prompt is ~{} tokens but the model's context holds ~{}; truncating it (set [llm] ask_overflow = \"summarize\" to condense instead)",
+            chunking::estimate_tokens(prompt, config.chars_per_token),
+            context_limit
+        );
+        chunking::truncate_middle(prompt, max_chars)
+    };
+
+    call_llm_streaming(config, provider, &prompt, on_token).await
+}
+
+/// The newest `ask --session` turns that fit in a quarter of the model's
+/// context, formatted for the prompt (empty when there are none). Older turns
+/// are dropped first so the notes being asked about keep most of the budget.
+pub fn bounded_history(config: &LlmConfig, turns: &[(String, String)]) -> String {
+    let context_limit = chunking::resolve_context_limit(
+        &config.provider,
+        config.effective_model(),
+        config.context_limit,
+    );
+    let max_chars = chunking::ContextBudget::new(context_limit)
+        .with_chars_per_token(config.chars_per_token)
+        .with_prompt_overhead("", config.effective_system_prompt())
+        .max_transcript_chars()
+        / 4;
+
+    let mut start = turns.len();
+    let mut used = 0;
+    while start > 0 {
+        let (question, answer) = &turns[start - 1];
+        used += question.len() + answer.len();
+        if used > max_chars {
+            break;
+        }
+        start -= 1;
+    }
+
+    if start == turns.len() {
+        return String::new();
+    }
+    if start > 0 {
+        tracing::info!(
+            "Dropping {} older turns of the session to fit the context",
+            start
+        );
+    }
+    prompts::conversation_history(&turns[start..])
+}
+
+/// Condense an oversized prompt piece by piece, so the pieces together fit in
+/// `max_chars`. Anything still too long afterwards is truncated.
+async fn condense_for_context(
+    config: &LlmConfig,
+    provider: LlmProvider,
+    prompt: &str,
+    max_chars: usize,
+) -> Result<String> {
+    let pieces = chunking::split_text(prompt, max_chars / 2);
+    tracing::warn!(
+        "Prompt exceeds the model's context; condensing it in {} parts first",
+        pieces.len()
+    );
+
+    let mut condensed = Vec::with_capacity(pieces.len());
+    for (i, piece) in pieces.iter().enumerate() {
+        let request = prompts::condense_prompt(piece, i, pieces.len());
+        condensed.push(
+            call_llm(config, provider, &request)
+                .await?
+                .trim()
+                .to_string(),
+        );
+    }
+
+    let joined = condensed.join("\n\n");
+    if joined.len() > max_chars {
+        tracing::warn!("Condensed prompt is still too long; truncating it");
+        return Ok(chunking::truncate_middle(&joined, max_chars));
+    }
+    Ok(joined)
+}
+
+/// Answer a question from one meeting's transcript, chunking it like
+/// summarization when it doesn't fit the model's context
+pub async fn ask_meeting(
+    config: &LlmConfig,
+    transcript: &Transcript,
+    question: &str,
+) -> Result<String> {
     if config.provider == "none" {
         anyhow::bail!("LLM is not configured. Run 'muesli setup' to set up an LLM provider.");
     }
@@ -152,30 +685,172 @@ pub async fn ask(config: &LlmConfig, prompt: &str) -> Result<String> {
     let provider =
         LlmProvider::from_provider(&config.provider).context("Invalid LLM provider specified")?;
 
-    call_llm(config, provider, prompt).await
+    let model = config.effective_model();
+    let context_limit =
+        chunking::resolve_context_limit(&config.provider, model, config.context_limit);
+    let budget = context_budget(config, context_limit);
+    let budget = budget.with_overhead_tokens(
+        budget.overhead_tokens + chunking::estimate_tokens(question, budget.chars_per_token),
+    );
+
+    let chunks = chunking::chunk_transcript(&transcript.segments, &budget);
+    if chunks.len() == 1 {
+        let prompt = prompts::meeting_question_prompt(&chunks[0].format_for_prompt(), question);
+        return Ok(call_llm(config, provider, &prompt)
+            .await?
+            .trim()
+            .to_string());
+    }
+
+    tracing::info!(
+        "Transcript is large, answering over {} chunks",
+        chunks.len()
+    );
+    let mut findings = Vec::new();
+    for chunk in &chunks {
+        let prompt = prompts::chunk_question_prompt(
+            &chunk.format_for_prompt(),
+            question,
+            chunk.chunk_index,
+            chunk.total_chunks,
+        );
+        findings.push(call_llm(config, provider, &prompt).await?);
+    }
+
+    let prompt = prompts::question_synthesis_prompt(question, &findings);
+    Ok(call_llm(config, provider, &prompt)
+        .await?
+        .trim()
+        .to_string())
 }
 
 async fn call_llm(config: &LlmConfig, provider: LlmProvider, prompt: &str) -> Result<String> {
     let model = config.effective_model();
+    if model.is_empty() {
+        anyhow::bail!(
+            "no model configured for provider {}; set llm.model",
+            config.provider
+        );
+    }
+    let system_prompt = config.effective_system_prompt();
+    let options = RequestOptions::from_config(config);
+
+    match provider {
+        LlmProvider::Anthropic => {
+            let api_key = config
+                .resolved_api_key()?
+                .context("Anthropic API key not configured")?;
+            claude::summarize_with_claude(&api_key, model, system_prompt, prompt, &options).await
+        }
+        LlmProvider::OpenAI | LlmProvider::Moonshot | LlmProvider::OpenRouter => {
+            let api_key = config
+                .resolved_api_key()?
+                .context("API key not configured")?;
+            openai_compat::summarize(
+                provider.base_url(),
+                &api_key,
+                model,
+                system_prompt,
+                prompt,
+                &options,
+            )
+            .await
+        }
+        LlmProvider::Local => {
+            // `lms chat -p` has no separate system role, so prepend it
+            let prompt = format!("{}\n\n{}", system_prompt, prompt);
+            local::summarize_with_local(&config.local_lms_path, model, &prompt).await
+        }
+    }
+}
+
+/// [`call_llm`], passing the completion to `on_token` piece by piece as the
+/// hosted providers stream it. The local `lms` CLI can't stream, so its whole
+/// answer arrives as one piece.
+pub async fn call_llm_streaming(
+    config: &LlmConfig,
+    provider: LlmProvider,
+    prompt: &str,
+    mut on_token: impl FnMut(&str),
+) -> Result<String> {
+    let model = config.effective_model();
+    if model.is_empty() {
+        anyhow::bail!(
+            "no model configured for provider {}; set llm.model",
+            config.provider
+        );
+    }
+    let system_prompt = config.effective_system_prompt();
+    let options = RequestOptions::from_config(config);
 
     match provider {
         LlmProvider::Anthropic => {
             let api_key = config
-                .api_key
-                .as_ref()
+                .resolved_api_key()?
                 .context("Anthropic API key not configured")?;
-            claude::summarize_with_claude(api_key, model, prompt).await
+            claude::stream_with_claude(
+                &api_key,
+                model,
+                system_prompt,
+                prompt,
+                &options,
+                &mut on_token,
+            )
+            .await
         }
         LlmProvider::OpenAI | LlmProvider::Moonshot | LlmProvider::OpenRouter => {
-            let api_key = config.api_key.as_ref().context("API key not configured")?;
-            openai_compat::summarize(provider.base_url(), api_key, model, prompt).await
+            let api_key = config
+                .resolved_api_key()?
+                .context("API key not configured")?;
+            openai_compat::stream(
+                provider.base_url(),
+                &api_key,
+                model,
+                system_prompt,
+                prompt,
+                &options,
+                &mut on_token,
+            )
+            .await
         }
         LlmProvider::Local => {
-            local::summarize_with_local(&config.local_lms_path, model, prompt).await
+            let answer = call_llm(config, provider, prompt).await?;
+            on_token(&answer);
+            Ok(answer)
         }
     }
 }
 
+/// Stable cache key for a chunk prompt. The prompt embeds both the chunk text
+/// and the template, so editing either produces a new key; so does changing
+/// the temperature or output limit the summary was sampled with.
+fn chunk_cache_key(
+    provider: &str,
+    model: &str,
+    options: &RequestOptions,
+    system_prompt: &str,
+    prompt: &str,
+) -> String {
+    // FNV-1a: std's DefaultHasher is not guaranteed stable across releases.
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let version = prompts::CHUNK_PROMPT_VERSION.to_string();
+    let sampling = format!("{:?}/{:?}", options.temperature, options.max_tokens);
+    for part in [
+        provider,
+        model,
+        version.as_str(),
+        sampling.as_str(),
+        system_prompt,
+        prompt,
+    ] {
+        for byte in part.bytes().chain(std::iter::once(0)) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
 fn format_time(ms: u64) -> String {
     let total_seconds = ms / 1000;
     let hours = total_seconds / 3600;
@@ -231,6 +906,217 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_is_provider_failure() {
+        let status = |status: u16| {
+            anyhow::Error::new(ApiStatusError {
+                provider: "Claude".to_string(),
+                status,
+                body: String::new(),
+                retry_after: None,
+            })
+        };
+        assert!(is_provider_failure(&status(429)));
+        assert!(is_provider_failure(&status(401)));
+        assert!(is_provider_failure(&status(503)));
+        assert!(!is_provider_failure(&status(400)));
+        assert!(!is_provider_failure(&status(404)));
+        // Connection errors, missing keys, a missing lms binary, ...
+        assert!(is_provider_failure(&anyhow::anyhow!("connection refused")));
+    }
+
+    #[tokio::test]
+    async fn test_with_retries() {
+        let status = |status: u16| {
+            anyhow::Error::new(ApiStatusError {
+                provider: "Claude".to_string(),
+                status,
+                body: String::new(),
+                retry_after: Some(Duration::ZERO),
+            })
+        };
+
+        let mut attempts = 0;
+        let result = with_retries(3, || {
+            attempts += 1;
+            let response = if attempts <= 2 {
+                Err(status(429))
+            } else {
+                Ok("summary")
+            };
+            async move { response }
+        })
+        .await;
+        assert_eq!(result.unwrap(), "summary");
+        assert_eq!(attempts, 3);
+
+        // Out of retries
+        let mut attempts = 0;
+        let result: Result<()> = with_retries(2, || {
+            attempts += 1;
+            let error = status(503);
+            async move { Err(error) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(attempts, 3);
+
+        // Client errors aren't retried
+        let mut attempts = 0;
+        let result: Result<()> = with_retries(3, || {
+            attempts += 1;
+            let error = status(400);
+            async move { Err(error) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+
+        // Nor is a rate limit that asks to come back tomorrow
+        let mut attempts = 0;
+        let result: Result<()> = with_retries(3, || {
+            attempts += 1;
+            let error = anyhow::Error::new(ApiStatusError {
+                provider: "Claude".to_string(),
+                status: 429,
+                body: String::new(),
+                retry_after: Some(Duration::from_secs(86400)),
+            });
+            async move { Err(error) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_parse_action_items() {
+        let response = "Here are the action items:\n```json\n[\n  {\"text\": \"Send the deck\", \"owner\": \"Bo\", \"due_date\": \"2024-05-10\"},\n  {\"task\": \"Book a room\", \"owner\": \"\", \"due_date\": \"Friday\"},\n  {\"text\": \"  \"}\n]\n```";
+        let items = parse_action_items(response).unwrap();
+        assert_eq!(
+            items,
+            vec![
+                ActionItem {
+                    text: "Send the deck".to_string(),
+                    owner: Some("Bo".to_string()),
+                    due_date: Some("2024-05-10".to_string()),
+                    done: false,
+                },
+                ActionItem {
+                    text: "Book a room".to_string(),
+                    owner: None,
+                    due_date: None,
+                    done: false,
+                },
+            ]
+        );
+
+        assert!(parse_action_items("[]").unwrap().is_empty());
+        assert!(parse_action_items("There are no action items.").is_err());
+    }
+
+    #[test]
+    fn test_backoff_delay() {
+        for retry in 0..4 {
+            let delay = backoff_delay(retry);
+            let base = Duration::from_secs(1 << retry);
+            assert!(delay >= base && delay < base + Duration::from_millis(500));
+        }
+    }
+
+    #[test]
+    fn test_chunk_cache_key() {
+        let options = RequestOptions::from_config(&LlmConfig::default());
+        let key = |model: &str, options: &RequestOptions, system: &str, prompt: &str| {
+            chunk_cache_key("anthropic", model, options, system, prompt)
+        };
+        let base = key("claude", &options, "", "prompt");
+        assert_eq!(base, key("claude", &options, "", "prompt"));
+        assert_eq!(base.len(), 16);
+        assert_ne!(base, key("other", &options, "", "prompt"));
+        assert_ne!(base, key("claude", &options, "", "prompt2"));
+        assert_ne!(base, key("claude", &options, "persona", "prompt"));
+
+        let warmer = RequestOptions {
+            temperature: Some(0.9),
+            ..RequestOptions::from_config(&LlmConfig::default())
+        };
+        assert_ne!(base, key("claude", &warmer, "", "prompt"));
+        let longer = RequestOptions {
+            max_tokens: Some(8000),
+            ..RequestOptions::from_config(&LlmConfig::default())
+        };
+        assert_ne!(base, key("claude", &longer, "", "prompt"));
+    }
+
+    #[test]
+    fn test_context_budget_override() {
+        let mut config = LlmConfig::default();
+        config.prompt_overhead_tokens = 3_000;
+        config.chars_per_token = 3.0;
+
+        let budget = context_budget(&config, 12_000);
+        assert_eq!(budget.overhead_tokens, 3_000);
+        assert_eq!(budget.max_transcript_chars(), 27_000);
+    }
+
+    #[test]
+    fn test_context_budget_measures_templates() {
+        let config = LlmConfig::default();
+        let budget = context_budget(&config, 12_000);
+
+        let template_tokens = chunking::estimate_tokens(&prompts::meeting_summary_prompt(""), 4.0);
+        assert!(budget.overhead_tokens > template_tokens);
+        let speakers_tokens =
+            chunking::estimate_tokens(&prompts::meeting_summary_prompt_with_speakers(&[]), 4.0);
+        assert!(budget.overhead_tokens > speakers_tokens);
+        assert!(budget.overhead_tokens < 12_000);
+    }
+
+    #[test]
+    fn test_preview_prompts_single_and_chunked() {
+        use crate::transcription::TranscriptSegment;
+
+        let mut config = LlmConfig::default();
+        config.provider = "local".to_string();
+        config.context_limit = 4_000;
+
+        let short = Transcript::new(vec![TranscriptSegment::new(0, 1000, "Hi".to_string())]);
+        let preview = preview_prompts(&config, &short, SummaryTemplate::General);
+        assert!(!preview.chunked);
+        assert_eq!(preview.context_limit, 4_000);
+        assert_eq!(preview.prompts.len(), 1);
+        assert!(preview.prompts[0].contains("Hi"));
+
+        let long = Transcript::new(
+            (0..10)
+                .map(|i| TranscriptSegment::new(i * 1000, (i + 1) * 1000, "x".repeat(2_000)))
+                .collect(),
+        );
+        let preview = preview_prompts(&config, &long, SummaryTemplate::Interview);
+        assert!(preview.chunked);
+        assert!(preview.prompts.len() > 1);
+        assert!(preview.prompts[0].contains("MEETING TYPE: This is a job interview"));
+    }
+
+    #[test]
+    fn test_bounded_history() {
+        let config = LlmConfig {
+            provider: "openai".to_string(),
+            context_limit: 2000,
+            ..LlmConfig::default()
+        };
+        assert!(bounded_history(&config, &[]).is_empty());
+
+        let turns = vec![
+            ("first?".to_string(), "x".repeat(3000)),
+            ("second?".to_string(), "short".to_string()),
+        ];
+        let history = bounded_history(&config, &turns);
+        assert!(history.contains("Q: second?"));
+        assert!(!history.contains("first?"));
+    }
+
     #[test]
     fn test_summary_result_structure() {
         let result = SummaryResult {
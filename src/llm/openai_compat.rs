@@ -1,12 +1,18 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
+use super::RequestOptions;
+
 #[derive(Debug, Serialize)]
 struct ChatRequest {
     model: String,
     messages: Vec<ChatMessage>,
-    max_completion_tokens: u32,
-    temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_completion_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -30,34 +36,24 @@ struct ChatResponseMessage {
     content: String,
 }
 
-pub async fn summarize(base_url: &str, api_key: &str, model: &str, prompt: &str) -> Result<String> {
-    let client = reqwest::Client::new();
-    let url = format!("{}/chat/completions", base_url.trim_end_matches('/'));
-
-    let request = ChatRequest {
-        model: model.to_string(),
-        messages: vec![ChatMessage {
-            role: "user".to_string(),
-            content: prompt.to_string(),
-        }],
-        max_completion_tokens: 4096,
-        temperature: 0.3,
-    };
-
-    let response = client
-        .post(&url)
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("content-type", "application/json")
-        .json(&request)
-        .send()
-        .await
-        .with_context(|| format!("Failed to send request to {}", url))?;
-
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response.text().await.unwrap_or_default();
-        anyhow::bail!("API error {} from {}: {}", status, base_url, error_text);
-    }
+pub async fn summarize(
+    base_url: &str,
+    api_key: &str,
+    model: &str,
+    system_prompt: &str,
+    prompt: &str,
+    options: &RequestOptions,
+) -> Result<String> {
+    let response = send_request(
+        base_url,
+        api_key,
+        model,
+        system_prompt,
+        prompt,
+        options,
+        false,
+    )
+    .await?;
 
     let chat_response: ChatResponse = response
         .json()
@@ -71,6 +67,100 @@ pub async fn summarize(base_url: &str, api_key: &str, model: &str, prompt: &str)
         .context("No choices in response")
 }
 
+/// [`summarize`], passing the text to `on_token` as it streams in
+pub async fn stream(
+    base_url: &str,
+    api_key: &str,
+    model: &str,
+    system_prompt: &str,
+    prompt: &str,
+    options: &RequestOptions,
+    on_token: &mut dyn FnMut(&str),
+) -> Result<String> {
+    let response = send_request(
+        base_url,
+        api_key,
+        model,
+        system_prompt,
+        prompt,
+        options,
+        true,
+    )
+    .await?;
+    super::streaming::read_text_stream(response, delta_text, on_token).await
+}
+
+/// Text carried by one streamed chat completion chunk
+fn delta_text(data: &str) -> Result<Option<String>> {
+    if data == "[DONE]" {
+        return Ok(None);
+    }
+    let chunk: serde_json::Value =
+        serde_json::from_str(data).context("Failed to parse chat completion chunk")?;
+    if let Some(message) = chunk["error"]["message"].as_str() {
+        anyhow::bail!("Stream error: {}", message);
+    }
+    Ok(chunk["choices"][0]["delta"]["content"]
+        .as_str()
+        .map(str::to_string))
+}
+
+async fn send_request(
+    base_url: &str,
+    api_key: &str,
+    model: &str,
+    system_prompt: &str,
+    prompt: &str,
+    options: &RequestOptions,
+    stream: bool,
+) -> Result<reqwest::Response> {
+    let client = reqwest::Client::new();
+    let url = format!("{}/chat/completions", base_url.trim_end_matches('/'));
+
+    let request = ChatRequest {
+        model: model.to_string(),
+        messages: build_messages(system_prompt, prompt),
+        max_completion_tokens: options.max_tokens,
+        temperature: options.temperature,
+        stream,
+    };
+
+    let (client, request, url) = (&client, &request, url.as_str());
+    super::with_retries(options.max_retries, move || async move {
+        let response = client
+            .post(url)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .header("content-type", "application/json")
+            .json(request)
+            .send()
+            .await
+            .with_context(|| format!("Failed to send request to {}", url))?;
+
+        if !response.status().is_success() {
+            return Err(super::ApiStatusError::from_response(base_url, response)
+                .await
+                .into());
+        }
+        Ok(response)
+    })
+    .await
+}
+
+fn build_messages(system_prompt: &str, prompt: &str) -> Vec<ChatMessage> {
+    let mut messages = Vec::with_capacity(2);
+    if !system_prompt.is_empty() {
+        messages.push(ChatMessage {
+            role: "system".to_string(),
+            content: system_prompt.to_string(),
+        });
+    }
+    messages.push(ChatMessage {
+        role: "user".to_string(),
+        content: prompt.to_string(),
+    });
+    messages
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,12 +173,63 @@ mod tests {
                 role: "user".to_string(),
                 content: "test".to_string(),
             }],
-            max_completion_tokens: 4096,
-            temperature: 0.3,
+            max_completion_tokens: None,
+            temperature: None,
+            stream: false,
         };
 
         let json = serde_json::to_string(&request).unwrap();
         assert!(json.contains("gpt-4o"));
         assert!(json.contains("test"));
+        assert!(!json.contains("max_completion_tokens"));
+        assert!(!json.contains("temperature"));
+    }
+
+    #[test]
+    fn test_request_with_options() {
+        let request = ChatRequest {
+            model: "gpt-4o".to_string(),
+            messages: Vec::new(),
+            max_completion_tokens: Some(16000),
+            temperature: Some(0.3),
+            stream: false,
+        };
+
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(json.contains("\"max_completion_tokens\":16000"));
+        assert!(json.contains("\"temperature\":0.3"));
+    }
+
+    #[test]
+    fn test_build_messages_with_system_prompt() {
+        let messages = build_messages("Be concise", "Summarize");
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].role, "system");
+        assert_eq!(messages[1].role, "user");
+
+        let messages = build_messages("", "Summarize");
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].role, "user");
+    }
+
+    #[test]
+    fn test_stream_tokens_in_order() {
+        let body = concat!(
+            "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"The launch \"}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"moved to \"}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"May.\"}}]}\n\n",
+            "data: [DONE]\n\n",
+        );
+        let mut decoder = crate::llm::streaming::SseDecoder::default();
+        let mut tokens = Vec::new();
+        for chunk in body.as_bytes().chunks(7) {
+            for data in decoder.feed(chunk) {
+                if let Some(token) = delta_text(&data).unwrap() {
+                    tokens.push(token);
+                }
+            }
+        }
+        assert_eq!(tokens, vec!["The launch ", "moved to ", "May."]);
     }
 }
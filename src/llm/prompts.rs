@@ -1,54 +1,107 @@
 use crate::transcription::TranscriptSegment;
+use std::path::Path;
+
+/// System message used when `[llm] system_prompt` is not set
+pub const DEFAULT_SYSTEM_PROMPT: &str = "You are an expert meeting note-taker. You write accurate, well-structured markdown notes and never invent details that are not supported by the transcript.";
+
+/// Version of `chunk_summary_prompt`. Bump when the template changes so cached
+/// chunk summaries produced by the old wording are discarded.
+pub const CHUNK_PROMPT_VERSION: u32 = 1;
+
+/// A prompt users can reword by putting a file of the same name in the prompts
+/// directory (`muesli config prompts init` writes the defaults there). Both the
+/// built-in text and overrides use `{name}` placeholders.
+pub struct PromptTemplate {
+    pub file: &'static str,
+    pub builtin: &'static str,
+    /// Placeholders an override must keep; without them the prompt would go
+    /// out missing its input
+    pub required: &'static [&'static str],
+}
 
-pub fn meeting_summary_prompt(transcript: &str) -> String {
-    let char_count = transcript.len();
-    let length_hint = length_guidance(char_count);
-
-    format!(
-        r#"Create comprehensive meeting notes from this transcript.
-
-TRANSCRIPT:
-{transcript}
-
-Output MARKDOWN with these sections:
-
-## TL;DR
-2-3 sentence summary of the meeting's purpose and outcome.
-
-## Topics Covered
-- Bullet list of every distinct topic discussed ({topic_range})
-
-## Discussion
-For each topic above, create a subsection:
-### [Topic Name]
-- Write detailed paragraphs and bullet points covering what was discussed
-- Include specific details: names, numbers, tools, dates, URLs, code references
-- Capture the reasoning and context behind statements, not just conclusions
-- Note disagreements, alternative viewpoints, or open questions
-- Include relevant quotes when they capture important nuance
-{length_instruction}
-
-## Decisions Made
-- List each decision with context for why it was made (ONLY if explicit decisions were made, otherwise omit this section entirely)
+/// Every overridable prompt
+pub const TEMPLATES: &[PromptTemplate] = &[
+    MEETING_SUMMARY,
+    MEETING_SUMMARY_SPEAKERS,
+    CHUNK_SUMMARY,
+    SYNTHESIS,
+    TITLE,
+];
+
+impl PromptTemplate {
+    fn render(&self, values: &[(&str, &str)]) -> String {
+        let dir = crate::config::loader::prompts_dir().ok();
+        self.render_from(dir.as_deref(), values)
+    }
 
-## Action Items
-- [ ] Task — Owner — Due date (ONLY if action items exist, otherwise omit this section entirely)
+    /// The override in `dir` if there is one, else the built-in text, with
+    /// `values` substituted. An override missing a required placeholder is
+    /// ignored with a warning.
+    fn render_from(&self, dir: Option<&Path>, values: &[(&str, &str)]) -> String {
+        let custom = dir
+            .and_then(|dir| std::fs::read_to_string(dir.join(self.file)).ok())
+            .filter(|custom| {
+                let missing: Vec<String> = self
+                    .required
+                    .iter()
+                    .map(|name| format!("{{{}}}", name))
+                    .filter(|placeholder| !custom.contains(placeholder.as_str()))
+                    .collect();
+                if !missing.is_empty() {
+                    tracing::warn!(
+                        "Custom prompt {} lacks {}; using the built-in prompt",
+                        self.file,
+                        missing.join(", ")
+                    );
+                }
+                missing.is_empty()
+            });
+        if custom.is_some() {
+            tracing::debug!("Using custom prompt {}", self.file);
+        }
+        fill(custom.as_deref().unwrap_or(self.builtin), values)
+    }
+}
 
-## Open Questions
-- Unresolved questions or topics that need follow-up (OMIT if none)
+/// Replace each `{name}` in `template` with its value. Unknown names are left
+/// as they are, and substituted text (a transcript mentioning `{notes}`, say)
+/// is never expanded again.
+fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        filled.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let value = after.find('}').and_then(|close| {
+            values
+                .iter()
+                .find(|(name, _)| *name == &after[..close])
+                .map(|(_, value)| (close, value))
+        });
+        match value {
+            Some((close, value)) => {
+                filled.push_str(value);
+                rest = &after[close + 1..];
+            }
+            None => {
+                filled.push('{');
+                rest = after;
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
 
-RULES:
-- Be thorough — these notes replace attending the meeting
-- Someone reading this should understand not just WHAT was discussed but WHY
-- Fix transcription errors from context (e.g., "get" → "git", "hey I" → "AI")
-- Omit Decisions/Action Items/Open Questions sections if none exist
-- No fluff, no filler, no corporate speak — but DO include all substantive detail
+pub fn meeting_summary_prompt(transcript: &str) -> String {
+    let char_count = transcript.len();
+    let length_hint = length_guidance(char_count);
 
-Output ONLY the markdown."#,
-        transcript = transcript,
-        topic_range = length_hint.topic_range,
-        length_instruction = length_hint.detail_instruction,
-    )
+    MEETING_SUMMARY.render(&[
+        ("transcript", transcript),
+        ("topic_range", length_hint.topic_range),
+        ("length_instruction", length_hint.detail_instruction),
+    ])
 }
 
 struct LengthGuidance {
@@ -91,10 +144,321 @@ pub fn meeting_summary_prompt_with_speakers(segments: &[TranscriptSegment]) -> S
     let char_count = transcript.len();
     let length_hint = length_guidance(char_count);
 
+    MEETING_SUMMARY_SPEAKERS.render(&[
+        ("transcript", &transcript),
+        ("topic_range", length_hint.topic_range),
+        ("length_instruction", length_hint.detail_instruction),
+    ])
+}
+
+fn format_timestamp(ms: u64) -> String {
+    let total_seconds = ms / 1000;
+    let minutes = total_seconds / 60;
+    let seconds = total_seconds % 60;
+    format!("{:02}:{:02}", minutes, seconds)
+}
+
+/// Built-in note layouts for different kinds of meetings, chosen with
+/// `--template` or `[detection.app_templates]` and stored with the meeting
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SummaryTemplate {
+    /// The standard layout (topics, discussion, decisions, action items)
+    #[default]
+    General,
+    Interview,
+    Standup,
+    OneOnOne,
+    DecisionReview,
+}
+
+impl SummaryTemplate {
+    pub const ALL: &'static [Self] = &[
+        Self::General,
+        Self::Interview,
+        Self::Standup,
+        Self::OneOnOne,
+        Self::DecisionReview,
+    ];
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "general" | "default" => Some(Self::General),
+            "interview" => Some(Self::Interview),
+            "standup" => Some(Self::Standup),
+            "one-on-one" | "1:1" | "1on1" => Some(Self::OneOnOne),
+            "decision-review" | "decision" => Some(Self::DecisionReview),
+            _ => None,
+        }
+    }
+
+    /// Name stored with the meeting and accepted by `--template`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::General => "general",
+            Self::Interview => "interview",
+            Self::Standup => "standup",
+            Self::OneOnOne => "one-on-one",
+            Self::DecisionReview => "decision-review",
+        }
+    }
+
+    /// What this meeting type's notes should emphasize (used for every prompt, including chunks)
+    fn focus(self) -> Option<&'static str> {
+        match self {
+            Self::General => None,
+            Self::Interview => Some("This is a job interview. Focus on the candidate: their experience, how they answered each question, strengths, concerns, and any signals the interviewers raised."),
+            Self::Standup => Some("This is a team standup. Focus on each person's progress, plans, and especially blockers; keep it brief."),
+            Self::OneOnOne => Some("This is a 1:1 meeting. Focus on feedback given in both directions, personal goals, concerns raised, and commitments each person made."),
+            Self::DecisionReview => Some("This is a decision review. Focus on the options considered, the arguments and evidence for and against each, the decision reached, and who owns it."),
+        }
+    }
+
+    /// Sections replacing the standard layout in the final notes
+    fn sections(self) -> Option<&'static str> {
+        match self {
+            Self::General => None,
+            Self::Interview => Some("## TL;DR\n## Candidate Background\n## Questions and Answers (one ### subsection per question)\n## Strengths\n## Concerns\n## Recommendation / Next Steps"),
+            Self::Standup => Some("## TL;DR\n## Updates (one ### subsection per person: done, next)\n## Blockers\n## Action Items"),
+            Self::OneOnOne => Some("## TL;DR\n## Discussion\n## Feedback\n## Goals and Growth\n## Action Items"),
+            Self::DecisionReview => Some("## TL;DR\n## Decision\n## Options Considered (pros and cons for each)\n## Rationale\n## Risks and Open Questions\n## Action Items"),
+        }
+    }
+
+    /// Note appended to chunk prompts
+    pub fn chunk_note(self) -> Option<String> {
+        self.focus()
+            .map(|focus| format!("\n\nMEETING TYPE: {}", focus))
+    }
+
+    /// Note appended to single-pass and synthesis prompts
+    pub fn summary_note(self) -> Option<String> {
+        Some(format!(
+            "\n\nMEETING TYPE: {}\nUse these sections INSTEAD of the ones listed above (omit any with nothing to report):\n{}",
+            self.focus()?,
+            self.sections()?
+        ))
+    }
+}
+
+impl std::fmt::Display for SummaryTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Note appended to summary prompts when segments carry more than one language
+pub fn mixed_language_note(languages: &[String]) -> Option<String> {
+    if languages.len() < 2 {
+        return None;
+    }
+
+    Some(format!(
+        "\n\nNOTE: This meeting switches between languages ({}). Understand every part of the transcript regardless of language, translate quotes where needed, and write the notes in the language used for most of the meeting.",
+        languages.join(", ")
+    ))
+}
+
+/// Note appended to every notes and title prompt when an output language is chosen
+pub fn output_language_note(language: &str) -> String {
+    format!(
+        "\n\nLANGUAGE: Write your entire response in {}, whatever language the transcript is in. Keep names and product terms as they are and translate quotes.",
+        language
+    )
+}
+
+pub fn chunk_summary_prompt(
+    chunk_transcript: &str,
+    chunk_index: usize,
+    total_chunks: usize,
+) -> String {
+    CHUNK_SUMMARY.render(&[
+        ("current", &(chunk_index + 1).to_string()),
+        ("total", &total_chunks.to_string()),
+        ("transcript", chunk_transcript),
+    ])
+}
+
+pub fn synthesis_prompt(chunk_summaries: &[String]) -> String {
+    let combined = chunk_summaries
+        .iter()
+        .enumerate()
+        .map(|(i, s)| format!("--- CHUNK {} NOTES ---\n{}\n", i + 1, s))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    SYNTHESIS.render(&[
+        ("chunk_count", &chunk_summaries.len().to_string()),
+        ("combined", &combined),
+    ])
+}
+
+pub fn title_generation_prompt(meeting_notes: &str) -> String {
+    TITLE.render(&[("notes", meeting_notes)])
+}
+
+/// Action items from finished notes as a JSON array. `meeting_date` resolves
+/// relative due dates like "Friday".
+pub fn action_items_prompt(meeting_notes: &str, meeting_date: &str) -> String {
+    format!(
+        r#"List the action items in these meeting notes. The meeting took place on {meeting_date}.
+
+MEETING NOTES:
+{notes}
+
+Output a JSON array with one object per action item:
+[{{"text": "what needs doing", "owner": "who (or null)", "due_date": "YYYY-MM-DD (or null)"}}]
+
+RULES:
+- Only tasks someone committed to or was assigned; not decisions or open questions
+- Resolve relative dates ("next Friday") against the meeting date; null if no date was given
+- Output [] if there are no action items
+
+Output ONLY the JSON."#,
+        meeting_date = meeting_date,
+        notes = meeting_notes
+    )
+}
+
+/// Condense one part of a request too long to send to the model in one go
+pub fn condense_prompt(part: &str, part_index: usize, total_parts: usize) -> String {
     format!(
-        r#"Create comprehensive meeting notes from this transcript.
+        r#"The text below is part {current} of {total} of a request that is too long to send at once. Condense it so the parts together fit: keep any question or instructions word for word, and keep every fact, name, number, date and decision that could matter for answering. Drop repetition and filler.
+
+TEXT:
+{part}
 
-Speakers are labeled SPEAKER_1, SPEAKER_2, etc. Try to identify them by name if mentioned in conversation, otherwise use Speaker 1, Speaker 2.
+Output ONLY the condensed text."#,
+        current = part_index + 1,
+        total = total_parts,
+        part = part
+    )
+}
+
+/// Answer a question from a single meeting's transcript
+pub fn meeting_question_prompt(transcript: &str, question: &str) -> String {
+    format!(
+        r#"Answer the question below using ONLY this meeting transcript. Do NOT use outside knowledge. If the transcript does not contain enough information to answer, say so clearly.
+
+QUESTION: {question}
+
+TRANSCRIPT:
+{transcript}
+
+Give a clear, direct answer. Cite the timestamps (and speakers, if identified) the answer is based on."#,
+        question = question,
+        transcript = transcript
+    )
+}
+
+/// Collect what one part of a long meeting says about a question
+pub fn chunk_question_prompt(
+    chunk_transcript: &str,
+    question: &str,
+    chunk_index: usize,
+    total_chunks: usize,
+) -> String {
+    format!(
+        r#"This is part {current} of {total} of a meeting transcript. Extract everything in it that helps answer the question below. Your notes will be combined with notes from the other parts.
+
+QUESTION: {question}
+
+TRANSCRIPT PART:
+{transcript}
+
+List the relevant facts, statements and decisions with their timestamps (and speakers, if identified). If nothing in this part is relevant, output exactly: NOTHING RELEVANT"#,
+        current = chunk_index + 1,
+        total = total_chunks,
+        question = question,
+        transcript = chunk_transcript
+    )
+}
+
+/// Earlier turns of an `ask --session` conversation, for follow-up questions
+pub fn conversation_history(turns: &[(String, String)]) -> String {
+    let mut history = String::from("EARLIER IN THIS CONVERSATION:\n");
+    for (question, answer) in turns {
+        history.push_str(&format!("\nQ: {}\nA: {}\n", question, answer));
+    }
+    history
+}
+
+/// Combine per-chunk findings into one answer
+pub fn question_synthesis_prompt(question: &str, chunk_findings: &[String]) -> String {
+    let combined = chunk_findings
+        .iter()
+        .enumerate()
+        .map(|(i, s)| format!("--- PART {} ---\n{}\n", i + 1, s))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        r#"These notes were extracted from consecutive parts of one meeting to answer a question.
+
+QUESTION: {question}
+
+{combined}
+
+Answer the question using ONLY these notes. Give a clear, direct answer and cite the timestamps it is based on. If the notes do not contain enough information, say so clearly."#,
+        question = question,
+        combined = combined
+    )
+}
+
+/// Notes for a transcript without speaker labels.
+/// Placeholders: `{transcript}`, `{topic_range}`, `{length_instruction}`
+pub const MEETING_SUMMARY: PromptTemplate = PromptTemplate {
+    file: "meeting_summary.md",
+    required: &["transcript"],
+    builtin: r#"Create comprehensive meeting notes from this transcript.
+
+TRANSCRIPT:
+{transcript}
+
+Output MARKDOWN with these sections:
+
+## TL;DR
+2-3 sentence summary of the meeting's purpose and outcome.
+
+## Topics Covered
+- Bullet list of every distinct topic discussed ({topic_range})
+
+## Discussion
+For each topic above, create a subsection:
+### [Topic Name]
+- Write detailed paragraphs and bullet points covering what was discussed
+- Include specific details: names, numbers, tools, dates, URLs, code references
+- Capture the reasoning and context behind statements, not just conclusions
+- Note disagreements, alternative viewpoints, or open questions
+- Include relevant quotes when they capture important nuance
+{length_instruction}
+
+## Decisions Made
+- List each decision with context for why it was made (ONLY if explicit decisions were made, otherwise omit this section entirely)
+
+## Action Items
+- [ ] Task — Owner — Due date (ONLY if action items exist, otherwise omit this section entirely)
+
+## Open Questions
+- Unresolved questions or topics that need follow-up (OMIT if none)
+
+RULES:
+- Be thorough — these notes replace attending the meeting
+- Someone reading this should understand not just WHAT was discussed but WHY
+- Fix transcription errors from context (e.g., "get" → "git", "hey I" → "AI")
+- Omit Decisions/Action Items/Open Questions sections if none exist
+- No fluff, no filler, no corporate speak — but DO include all substantive detail
+
+Output ONLY the markdown."#,
+};
+
+/// Notes for a diarized transcript.
+/// Placeholders: `{transcript}`, `{topic_range}`, `{length_instruction}`
+pub const MEETING_SUMMARY_SPEAKERS: PromptTemplate = PromptTemplate {
+    file: "meeting_summary_speakers.md",
+    required: &["transcript"],
+    builtin: r#"Create comprehensive meeting notes from this transcript.
+
+Speakers are labeled SPEAKER_1, SPEAKER_2, etc. Try to identify them by name if mentioned in conversation, otherwise use Speaker 1, Speaker 2. SPEAKER_? marks lines whose speaker is uncertain.
 
 TRANSCRIPT:
 {transcript}
@@ -140,26 +504,14 @@ RULES:
 - Attribute statements to speakers throughout
 
 Output ONLY the markdown."#,
-        transcript = transcript,
-        topic_range = length_hint.topic_range,
-        length_instruction = length_hint.detail_instruction,
-    )
-}
+};
 
-fn format_timestamp(ms: u64) -> String {
-    let total_seconds = ms / 1000;
-    let minutes = total_seconds / 60;
-    let seconds = total_seconds % 60;
-    format!("{:02}:{:02}", minutes, seconds)
-}
-
-pub fn chunk_summary_prompt(
-    chunk_transcript: &str,
-    chunk_index: usize,
-    total_chunks: usize,
-) -> String {
-    format!(
-        r#"Create detailed notes for this portion of a meeting transcript (chunk {current} of {total}).
+/// Notes for one chunk of a long transcript.
+/// Placeholders: `{current}`, `{total}`, `{transcript}`
+pub const CHUNK_SUMMARY: PromptTemplate = PromptTemplate {
+    file: "chunk_summary.md",
+    required: &["transcript"],
+    builtin: r#"Create detailed notes for this portion of a meeting transcript (chunk {current} of {total}).
 
 IMPORTANT: This is one section of a longer meeting. Write thorough notes — they will be merged with other chunks later. Do NOT compress or summarize aggressively. Preserve detail.
 
@@ -194,22 +546,14 @@ RULES:
 - Write as much detail as the transcript warrants
 
 Output ONLY the markdown."#,
-        current = chunk_index + 1,
-        total = total_chunks,
-        transcript = chunk_transcript
-    )
-}
-
-pub fn synthesis_prompt(chunk_summaries: &[String]) -> String {
-    let combined = chunk_summaries
-        .iter()
-        .enumerate()
-        .map(|(i, s)| format!("--- CHUNK {} NOTES ---\n{}\n", i + 1, s))
-        .collect::<Vec<_>>()
-        .join("\n");
+};
 
-    format!(
-        r#"Merge these chunk notes into unified, comprehensive meeting notes.
+/// Merges the chunk notes of a long meeting.
+/// Placeholders: `{chunk_count}`, `{combined}`
+pub const SYNTHESIS: PromptTemplate = PromptTemplate {
+    file: "synthesis.md",
+    required: &["combined"],
+    builtin: r#"Merge these chunk notes into unified, comprehensive meeting notes.
 
 This was a long meeting ({chunk_count} chunks). The final notes should be proportionally detailed.
 
@@ -253,14 +597,14 @@ RULES:
 - If chunks covered the same topic, merge the discussion — don't repeat
 
 Output ONLY the markdown."#,
-        chunk_count = chunk_summaries.len(),
-        combined = combined
-    )
-}
+};
 
-pub fn title_generation_prompt(meeting_notes: &str) -> String {
-    format!(
-        r#"Generate a concise, descriptive title for this meeting based on the notes below.
+/// A title from finished notes.
+/// Placeholders: `{notes}`
+pub const TITLE: PromptTemplate = PromptTemplate {
+    file: "title.md",
+    required: &["notes"],
+    builtin: r#"Generate a concise, descriptive title for this meeting based on the notes below.
 
 MEETING NOTES:
 {notes}
@@ -279,14 +623,29 @@ Examples of good titles:
 - Bug Triage and Sprint Planning
 
 Output ONLY the title, nothing else."#,
-        notes = meeting_notes
-    )
-}
+};
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_question_prompts() {
+        let prompt = meeting_question_prompt("[00:05] SPEAKER_0: Ship Friday", "When do we ship?");
+        assert!(prompt.contains("QUESTION: When do we ship?"));
+        assert!(prompt.contains("[00:05] SPEAKER_0: Ship Friday"));
+
+        let chunk = chunk_question_prompt("text", "When?", 1, 3);
+        assert!(chunk.contains("part 2 of 3"));
+
+        let synthesis = question_synthesis_prompt(
+            "When?",
+            &["Friday".to_string(), "NOTHING RELEVANT".to_string()],
+        );
+        assert!(synthesis.contains("--- PART 1 ---\nFriday"));
+        assert!(synthesis.contains("--- PART 2 ---"));
+    }
+
     #[test]
     fn test_prompt_generation() {
         let transcript = "We discussed the project timeline and agreed to finish by Friday.";
@@ -305,6 +664,7 @@ mod tests {
                 text: "Hello everyone".to_string(),
                 speaker: Some("SPEAKER_0".to_string()),
                 confidence: None,
+                language: None,
             },
             TranscriptSegment {
                 start_ms: 5000,
@@ -312,6 +672,7 @@ mod tests {
                 text: "Hi there".to_string(),
                 speaker: Some("SPEAKER_1".to_string()),
                 confidence: None,
+                language: None,
             },
         ];
         let prompt = meeting_summary_prompt_with_speakers(&segments);
@@ -319,4 +680,81 @@ mod tests {
         assert!(prompt.contains("[00:05] SPEAKER_1: Hi there"));
         assert!(prompt.contains("Attendees"));
     }
+
+    #[test]
+    fn test_summary_templates() {
+        for template in SummaryTemplate::ALL {
+            assert_eq!(SummaryTemplate::parse(template.name()), Some(*template));
+        }
+        assert_eq!(
+            SummaryTemplate::parse("1:1"),
+            Some(SummaryTemplate::OneOnOne)
+        );
+        assert_eq!(SummaryTemplate::parse("retro"), None);
+
+        assert!(SummaryTemplate::General.summary_note().is_none());
+        assert!(SummaryTemplate::General.chunk_note().is_none());
+        let note = SummaryTemplate::Standup.summary_note().unwrap();
+        assert!(note.contains("## Blockers"));
+        assert!(!SummaryTemplate::Standup
+            .chunk_note()
+            .unwrap()
+            .contains("INSTEAD"));
+    }
+
+    #[test]
+    fn test_mixed_language_note() {
+        assert!(mixed_language_note(&[]).is_none());
+        assert!(mixed_language_note(&["en".to_string()]).is_none());
+
+        let note = mixed_language_note(&["en".to_string(), "de".to_string()]).unwrap();
+        assert!(note.contains("en, de"));
+    }
+
+    #[test]
+    fn test_fill() {
+        assert_eq!(
+            fill("{a} and {b}, {unknown} {", &[("a", "{b}"), ("b", "2")]),
+            "{b} and 2, {unknown} {"
+        );
+    }
+
+    #[test]
+    fn test_template_override() {
+        let dir = tempfile::tempdir().unwrap();
+        let values = [("current", "2"), ("total", "3"), ("transcript", "Ship it")];
+
+        let builtin = CHUNK_SUMMARY.render_from(Some(dir.path()), &values);
+        assert!(builtin.contains("(chunk 2 of 3)"));
+        assert!(builtin.contains("Ship it"));
+
+        std::fs::write(
+            dir.path().join("chunk_summary.md"),
+            "Part {current}/{total}\n## Risks\n{transcript}",
+        )
+        .unwrap();
+        assert_eq!(
+            CHUNK_SUMMARY.render_from(Some(dir.path()), &values),
+            "Part 2/3\n## Risks\nShip it"
+        );
+        // Other prompts keep their built-in text
+        assert!(TITLE
+            .render_from(Some(dir.path()), &[("notes", "x")])
+            .starts_with("Generate a concise"));
+    }
+
+    #[test]
+    fn test_template_override_without_input_falls_back() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("title.md"), "Give this meeting a title.").unwrap();
+
+        let prompt = TITLE.render_from(Some(dir.path()), &[("notes", "Ship it")]);
+        assert!(prompt.starts_with("Generate a concise"));
+        assert!(prompt.contains("Ship it"));
+    }
+
+    #[test]
+    fn test_output_language_note() {
+        assert!(output_language_note("German").contains("response in German"));
+    }
 }
@@ -0,0 +1,115 @@
+//! Scrubbing personal data (emails, phone and card numbers, IP addresses) out
+//! of transcript text. Each match is replaced by a bracketed label such as
+//! `[EMAIL]`, so the text still reads naturally.
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+struct Rule {
+    label: &'static str,
+    pattern: Regex,
+    /// Extra check a match must pass, for patterns too loose on their own
+    accept: fn(&str) -> bool,
+}
+
+/// Applied in order: emails and card numbers first, so their digits are not
+/// taken for phone numbers
+fn rules() -> &'static [Rule] {
+    static RULES: OnceLock<Vec<Rule>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        let rule = |label: &'static str, pattern: &str, accept: fn(&str) -> bool| Rule {
+            label,
+            pattern: Regex::new(pattern).expect("valid redaction pattern"),
+            accept,
+        };
+        vec![
+            rule(
+                "[EMAIL]",
+                r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}",
+                |_| true,
+            ),
+            rule("[CARD]", r"\b\d(?:[ -]?\d){12,18}\b", passes_luhn),
+            rule("[IP]", r"\b(?:\d{1,3}\.){3}\d{1,3}\b", is_ipv4),
+            rule("[PHONE]", r"\+?\(?\d[\d ()./-]{5,}\d\b", is_phone_number),
+        ]
+    })
+}
+
+/// `text` with every personal detail the rules recognize replaced by its label
+pub fn redact(text: &str) -> String {
+    let mut redacted = text.to_string();
+    for rule in rules() {
+        redacted = rule
+            .pattern
+            .replace_all(&redacted, |caps: &regex::Captures| {
+                let found = &caps[0];
+                if (rule.accept)(found) {
+                    rule.label.to_string()
+                } else {
+                    found.to_string()
+                }
+            })
+            .into_owned();
+    }
+    redacted
+}
+
+fn digits(s: &str) -> Vec<u32> {
+    s.chars().filter_map(|c| c.to_digit(10)).collect()
+}
+
+fn passes_luhn(s: &str) -> bool {
+    let digits = digits(s);
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| match (i % 2, d * 2) {
+            (0, _) => d,
+            (_, doubled) if doubled > 9 => doubled - 9,
+            (_, doubled) => doubled,
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+fn is_ipv4(s: &str) -> bool {
+    s.split('.').all(|octet| octet.parse::<u8>().is_ok())
+}
+
+/// International numbers (`+49 ...`) from 7 digits, others from 9, so dates,
+/// times and amounts are left alone
+fn is_phone_number(s: &str) -> bool {
+    let count = digits(s).len();
+    let minimum = if s.starts_with('+') { 7 } else { 9 };
+    (minimum..=15).contains(&count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        assert_eq!(
+            redact("Mail jane.doe@example.co.uk or call +49 30 1234567."),
+            "Mail [EMAIL] or call [PHONE]."
+        );
+        assert_eq!(
+            redact("Card 4111 1111 1111 1111, server 10.0.12.7, US line (555) 123-4567"),
+            "Card [CARD], server [IP], US line [PHONE]"
+        );
+    }
+
+    #[test]
+    fn test_redact_keeps_ordinary_numbers() {
+        let text = "On 2024-05-01 at 14:30 we shipped 1.2.3 to 4500 users for $12,000.";
+        assert_eq!(redact(text), text);
+        // Not a valid card number (fails the checksum) and too long for a phone
+        assert_eq!(
+            redact("ticket 1234 5678 9012 3456"),
+            "ticket 1234 5678 9012 3456"
+        );
+        assert_eq!(redact("version 300.1.2.3"), "version 300.1.2.3");
+    }
+}
@@ -0,0 +1,83 @@
+//! Server-sent events, as the hosted providers stream completions.
+
+use anyhow::{Context, Result};
+
+/// Incremental decoder for a `text/event-stream` body, fed network chunks as
+/// they arrive. Chunks may split lines (or characters) anywhere.
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    pending: Vec<u8>,
+    data: Vec<String>,
+}
+
+impl SseDecoder {
+    /// The `data` of every event completed by `chunk`
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.pending.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+        // A newline byte never occurs inside a multi-byte UTF-8 character
+        while let Some(end) = self.pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    events.push(self.data.join("\n"));
+                    self.data.clear();
+                }
+            } else if let Some(value) = line.strip_prefix("data:") {
+                self.data
+                    .push(value.strip_prefix(' ').unwrap_or(value).to_string());
+            }
+        }
+        events
+    }
+}
+
+/// Read a streamed completion, passing each piece of text to `on_token` as it
+/// arrives. `text_of` pulls the text out of one event's data (`None` for
+/// events that carry none). Returns the whole text.
+pub async fn read_text_stream(
+    mut response: reqwest::Response,
+    text_of: fn(&str) -> Result<Option<String>>,
+    on_token: &mut dyn FnMut(&str),
+) -> Result<String> {
+    let mut decoder = SseDecoder::default();
+    let mut text = String::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .context("Failed to read streamed response")?
+    {
+        for data in decoder.feed(&chunk) {
+            if let Some(piece) = text_of(&data)? {
+                on_token(&piece);
+                text.push_str(&piece);
+            }
+        }
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decoder_reassembles_split_events() {
+        let body = "event: ping\ndata: {\"n\":1}\n\n: comment\r\ndata: caf\u{e9}\r\n\r\ndata: a\ndata: b\n\n";
+        let mut decoder = SseDecoder::default();
+        let mut events = Vec::new();
+        // Three bytes at a time splits lines, and the é, across chunks
+        for chunk in body.as_bytes().chunks(3) {
+            events.extend(decoder.feed(chunk));
+        }
+        assert_eq!(events, vec!["{\"n\":1}", "caf\u{e9}", "a\nb"]);
+
+        // An event without its blank line yet is held back
+        assert!(decoder.feed(b"data: partial\n").is_empty());
+        assert_eq!(decoder.feed(b"\n"), vec!["partial"]);
+    }
+}
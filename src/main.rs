@@ -7,9 +7,11 @@ mod config;
 mod daemon;
 mod detection;
 mod error;
+mod interrupt;
 mod llm;
 mod notes;
 mod notification;
+mod pipeline;
 mod qmd;
 mod storage;
 mod transcription;
@@ -17,16 +19,23 @@ mod waybar;
 
 #[tokio::main]
 async fn main() {
+    let cli = cli::Cli::parse();
+
+    let default_filter = cli::output::log_filter(cli.verbose, cli.quiet);
     tracing_subscriber::fmt()
         .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_filter)),
         )
         .init();
 
-    let cli = cli::Cli::parse();
-
+    let json = cli.json;
     if let Err(e) = cli::handle_command(cli).await {
-        eprintln!("Error: {}", e);
-        std::process::exit(1);
+        // `--json` may also come from `[general] default_command`
+        if json || cli::output::is_json() {
+            eprintln!("{}", e.to_json());
+        } else {
+            eprintln!("Error: {}", e);
+        }
+        std::process::exit(e.exit_code());
     }
 }
@@ -0,0 +1,98 @@
+//! Line diffs between two meetings' summaries.
+
+use similar::{ChangeTag, TextDiff};
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const CYAN: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// Lines of unchanged context shown around each change
+const CONTEXT_LINES: usize = 3;
+
+/// Render a unified-style diff of `old` against `new`, ANSI-colored when `color` is set.
+/// Returns an empty string when the two are identical.
+pub fn render_diff(old_label: &str, new_label: &str, old: &str, new: &str, color: bool) -> String {
+    let diff = TextDiff::from_lines(old, new);
+    let groups = diff.grouped_ops(CONTEXT_LINES);
+    if groups.is_empty() {
+        return String::new();
+    }
+
+    let paint = |code: &str, line: String| {
+        if color {
+            format!("{}{}{}", code, line, RESET)
+        } else {
+            line
+        }
+    };
+
+    let mut out = String::new();
+    out.push_str(&paint(RED, format!("--- {}", old_label)));
+    out.push('\n');
+    out.push_str(&paint(GREEN, format!("+++ {}", new_label)));
+    out.push('\n');
+
+    for group in &groups {
+        let (first, last) = (&group[0], &group[group.len() - 1]);
+        let old_range = first.old_range().start..last.old_range().end;
+        let new_range = first.new_range().start..last.new_range().end;
+        out.push_str(&paint(
+            CYAN,
+            format!(
+                "@@ -{},{} +{},{} @@",
+                old_range.start + 1,
+                old_range.len(),
+                new_range.start + 1,
+                new_range.len()
+            ),
+        ));
+        out.push('\n');
+
+        for op in group {
+            for change in diff.iter_changes(op) {
+                let text = change.value().trim_end_matches('\n');
+                let line = match change.tag() {
+                    ChangeTag::Delete => paint(RED, format!("-{}", text)),
+                    ChangeTag::Insert => paint(GREEN, format!("+{}", text)),
+                    ChangeTag::Equal => format!(" {}", text),
+                };
+                out.push_str(&line);
+                out.push('\n');
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_is_empty() {
+        assert_eq!(render_diff("a", "b", "same\n", "same\n", false), "");
+    }
+
+    #[test]
+    fn test_plain_diff_marks_changes() {
+        let old = "## Decisions\n- Ship v1\n- Hire\n";
+        let new = "## Decisions\n- Ship v2\n- Hire\n- Retro\n";
+        let out = render_diff("last week", "this week", old, new, false);
+
+        assert!(out.starts_with("--- last week\n+++ this week\n@@ -1,3 +1,4 @@\n"));
+        assert!(out.contains("\n-- Ship v1\n"));
+        assert!(out.contains("\n+- Ship v2\n"));
+        assert!(out.contains("\n+- Retro\n"));
+        assert!(out.contains("\n ## Decisions\n"));
+        assert!(!out.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_colored_diff() {
+        let out = render_diff("a", "b", "x\n", "y\n", true);
+        assert!(out.contains(&format!("{}-x{}", RED, RESET)));
+        assert!(out.contains(&format!("{}+y{}", GREEN, RESET)));
+    }
+}
@@ -0,0 +1,130 @@
+//! Email delivery of a meeting's notes over SMTP.
+//!
+//! The message carries the markdown export as the plain-text part and its
+//! HTML rendering as the alternative, so it reads well in any mail client.
+
+use crate::config::settings::SmtpConfig;
+use crate::error::{MuesliError, Result};
+use crate::llm::SummaryResult;
+use crate::notes::export::{render_html, render_markdown};
+use crate::storage::Meeting;
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+
+/// Send the meeting's summary to `to` through the configured SMTP server.
+pub fn send_notes(
+    config: &SmtpConfig,
+    meeting: &Meeting,
+    summary: &SummaryResult,
+    to: &[String],
+) -> Result<()> {
+    if config.host.is_empty() || config.from.is_empty() {
+        return Err(MuesliError::NotConfigured(
+            "SMTP is not configured. Set host and from under [smtp] in the config".to_string(),
+        ));
+    }
+
+    let message = build_message(config, meeting, summary, to)?;
+
+    let builder = if config.port == 465 {
+        SmtpTransport::relay(&config.host)
+    } else {
+        SmtpTransport::starttls_relay(&config.host)
+    }
+    .map_err(|e| MuesliError::Email(format!("{}: {}", config.host, e)))?
+    .port(config.port);
+
+    let builder = match &config.username {
+        Some(username) => builder.credentials(Credentials::new(
+            username.clone(),
+            config.password.clone().unwrap_or_default(),
+        )),
+        None => builder,
+    };
+
+    builder
+        .build()
+        .send(&message)
+        .map_err(|e| MuesliError::Email(format!("sending via {}: {}", config.host, e)))?;
+    Ok(())
+}
+
+fn build_message(
+    config: &SmtpConfig,
+    meeting: &Meeting,
+    summary: &SummaryResult,
+    to: &[String],
+) -> Result<Message> {
+    if to.is_empty() {
+        return Err(MuesliError::Usage(
+            "No recipients. Pass --to ADDR or set to = [...] under [smtp]".to_string(),
+        ));
+    }
+
+    let mut builder = Message::builder()
+        .from(parse_mailbox(&config.from)?)
+        .subject(format!("Meeting notes: {}", meeting.title));
+    for address in to {
+        builder = builder.to(parse_mailbox(address)?);
+    }
+
+    builder
+        .multipart(MultiPart::alternative_plain_html(
+            render_markdown(meeting, summary),
+            render_html(meeting, summary),
+        ))
+        .map_err(|e| MuesliError::Email(e.to_string()))
+}
+
+fn parse_mailbox(address: &str) -> Result<Mailbox> {
+    address
+        .trim()
+        .parse()
+        .map_err(|e| MuesliError::Email(format!("invalid address '{}': {}", address, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> SmtpConfig {
+        SmtpConfig {
+            host: "smtp.example.com".to_string(),
+            from: "muesli <notes@example.com>".to_string(),
+            ..SmtpConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_build_message() {
+        let meeting = Meeting::new("Weekly Sync".to_string());
+        let summary = SummaryResult {
+            markdown: "## TL;DR\nShipped.\n".to_string(),
+        };
+        let to = vec![
+            "a@example.com".to_string(),
+            "Bo <b@example.com>".to_string(),
+        ];
+
+        let message = build_message(&config(), &meeting, &summary, &to).unwrap();
+        let raw = String::from_utf8(message.formatted()).unwrap();
+        assert!(raw.contains("Subject: Meeting notes: Weekly Sync"));
+        assert!(raw.contains("b@example.com"));
+        assert!(raw.contains("text/html"));
+
+        assert!(matches!(
+            build_message(&config(), &meeting, &summary, &[]),
+            Err(MuesliError::Usage(_))
+        ));
+        assert!(matches!(
+            build_message(
+                &config(),
+                &meeting,
+                &summary,
+                &["not an address".to_string()]
+            ),
+            Err(MuesliError::Email(_))
+        ));
+    }
+}
@@ -0,0 +1,470 @@
+//! Standalone exports of a meeting's notes (markdown, HTML, PDF).
+//!
+//! PDF rendering is delegated to external tools: pandoc (with its default LaTeX
+//! engine, or wkhtmltopdf as the engine) or wkhtmltopdf alone, fed by a small
+//! built-in markdown-to-HTML pass.
+
+use crate::error::{MuesliError, Result};
+use crate::llm::SummaryResult;
+use crate::storage::Meeting;
+use crate::transcription::Transcript;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Summary markdown with a title and metadata header
+pub fn render_markdown(meeting: &Meeting, summary: &SummaryResult) -> String {
+    let mut content = format!("# {}\n\n", meeting.title);
+
+    let mut meta = vec![format!(
+        "**Date:** {}",
+        meeting.started_at.format("%Y-%m-%d %H:%M")
+    )];
+    if let Some(duration) = meeting.duration_seconds {
+        meta.push(format!(
+            "**Duration:** {}m {}s",
+            duration / 60,
+            duration % 60
+        ));
+    }
+    if let Some(app) = &meeting.detected_app {
+        meta.push(format!("**App:** {}", app));
+    }
+    if !meeting.participants.is_empty() {
+        meta.push(format!(
+            "**Attendees:** {}",
+            meeting.participants.join(", ")
+        ));
+    }
+    content.push_str(&meta.join(" | "));
+    content.push_str("\n\n");
+
+    content.push_str(summary.markdown.trim());
+    content.push('\n');
+    content
+}
+
+/// Optional parts of an export, on top of the summary. Unlike the notes file
+/// these don't follow `[storage] notes_transcript`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExportSections<'a> {
+    /// Start with the notes file's YAML frontmatter (markdown only)
+    pub frontmatter: bool,
+    /// Append the full transcript after the summary
+    pub transcript: Option<&'a Transcript>,
+    /// How transcript lines are stamped
+    pub times: crate::notes::markdown::TranscriptTimes,
+}
+
+/// [`render_markdown`] with the requested extra sections
+pub fn render_document(
+    meeting: &Meeting,
+    summary: &SummaryResult,
+    sections: ExportSections,
+) -> String {
+    let mut content = String::new();
+    if sections.frontmatter {
+        content.push_str(&crate::notes::markdown::render_frontmatter(meeting));
+    }
+    content.push_str(&render_markdown(meeting, summary));
+    if let Some(transcript) = sections.transcript {
+        content.push_str("\n---\n\n");
+        content.push_str(&crate::notes::markdown::render_transcript(
+            meeting,
+            transcript,
+            false,
+            sections.times,
+        ));
+    }
+    content
+}
+
+/// [`render_markdown`] as a standalone HTML page
+pub fn render_html(meeting: &Meeting, summary: &SummaryResult) -> String {
+    markdown_to_html(&meeting.title, &render_markdown(meeting, summary))
+}
+
+/// Write [`render_html`] to a temp file and open it in the default browser
+/// with `xdg-open`. The file is left in place for the browser to read; it gets
+/// a random name and is readable only by the user, in `$XDG_RUNTIME_DIR` when set.
+pub fn open_in_browser(meeting: &Meeting, summary: &SummaryResult) -> Result<PathBuf> {
+    let dir = std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(std::env::temp_dir);
+    let mut file = tempfile::Builder::new()
+        .prefix(&format!("muesli-notes-{}-", meeting.id))
+        .suffix(".html")
+        .tempfile_in(dir)?;
+    file.write_all(render_html(meeting, summary).as_bytes())?;
+    let (_, path) = file.keep().map_err(|e| e.error)?;
+
+    let status = Command::new("xdg-open")
+        .arg(&path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| {
+            MuesliError::NotConfigured(format!(
+                "xdg-open is not available ({}); the notes are at {}",
+                e,
+                path.display()
+            ))
+        })?;
+    if !status.success() {
+        return Err(MuesliError::Export(format!(
+            "xdg-open exited with {}; the notes are at {}",
+            status,
+            path.display()
+        )));
+    }
+    Ok(path)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PdfAttempt {
+    /// `pandoc` with its default PDF engine (LaTeX)
+    Pandoc,
+    /// `pandoc --pdf-engine=wkhtmltopdf`
+    PandocWkhtmltopdf,
+    /// `wkhtmltopdf` on HTML from [`markdown_to_html`]
+    Wkhtmltopdf,
+}
+
+impl std::fmt::Display for PdfAttempt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pandoc => write!(f, "pandoc"),
+            Self::PandocWkhtmltopdf => write!(f, "pandoc --pdf-engine=wkhtmltopdf"),
+            Self::Wkhtmltopdf => write!(f, "wkhtmltopdf"),
+        }
+    }
+}
+
+/// Renderers to try, best first, for the tools that are installed
+fn pdf_attempts(has_pandoc: bool, has_wkhtmltopdf: bool) -> Vec<PdfAttempt> {
+    let mut attempts = Vec::new();
+    if has_pandoc {
+        attempts.push(PdfAttempt::Pandoc);
+        if has_wkhtmltopdf {
+            attempts.push(PdfAttempt::PandocWkhtmltopdf);
+        }
+    }
+    if has_wkhtmltopdf {
+        attempts.push(PdfAttempt::Wkhtmltopdf);
+    }
+    attempts
+}
+
+fn tool_available(name: &str) -> bool {
+    Command::new("which")
+        .arg(name)
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Render `markdown` to a PDF at `output`, trying each available tool in turn
+pub fn write_pdf(title: &str, markdown: &str, output: &Path) -> Result<()> {
+    let attempts = pdf_attempts(tool_available("pandoc"), tool_available("wkhtmltopdf"));
+    if attempts.is_empty() {
+        return Err(MuesliError::NotConfigured(
+            "PDF export needs pandoc (with a LaTeX engine) or wkhtmltopdf. \
+             Install one, e.g.: sudo pacman -S pandoc texlive-latex  (or: wkhtmltopdf)"
+                .to_string(),
+        ));
+    }
+
+    let mut failures = Vec::new();
+    for attempt in attempts {
+        match run_attempt(attempt, title, markdown, output) {
+            Ok(()) => {
+                tracing::debug!("PDF rendered with {}", attempt);
+                return Ok(());
+            }
+            Err(e) => {
+                tracing::debug!("PDF export via {} failed: {}", attempt, e);
+                failures.push(format!("  {}: {}", attempt, e));
+            }
+        }
+    }
+
+    Err(MuesliError::Export(format!(
+        "PDF export failed with every available tool:\n{}",
+        failures.join("\n")
+    )))
+}
+
+fn run_attempt(attempt: PdfAttempt, title: &str, markdown: &str, output: &Path) -> Result<()> {
+    let (mut command, input) = match attempt {
+        PdfAttempt::Pandoc | PdfAttempt::PandocWkhtmltopdf => {
+            let mut command = Command::new("pandoc");
+            command
+                .args(["--from", "markdown", "--standalone"])
+                .arg("--metadata")
+                .arg(format!("pagetitle={}", title))
+                .arg("--output")
+                .arg(output);
+            if attempt == PdfAttempt::PandocWkhtmltopdf {
+                command.arg("--pdf-engine=wkhtmltopdf");
+            }
+            (command, markdown.to_string())
+        }
+        PdfAttempt::Wkhtmltopdf => {
+            let mut command = Command::new("wkhtmltopdf");
+            command
+                .args(["--quiet", "--encoding", "utf-8", "-"])
+                .arg(output);
+            (command, markdown_to_html(title, markdown))
+        }
+    };
+
+    // A PDF left over from an earlier export would otherwise pass the check below
+    match std::fs::remove_file(output) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+        _ => {}
+    }
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes())?;
+    }
+    let result = child.wait_with_output()?;
+
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        let reason = stderr.lines().last().unwrap_or("").trim();
+        return Err(MuesliError::Export(if reason.is_empty() {
+            format!("exited with {}", result.status)
+        } else {
+            reason.to_string()
+        }));
+    }
+    if !output.exists() {
+        return Err(MuesliError::Export(
+            "no output file was produced".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Minimal markdown-to-HTML for the summaries muesli generates: headings,
+/// bullet/numbered/task lists, rules, paragraphs, **bold**, *italic* and `code`.
+pub fn markdown_to_html(title: &str, markdown: &str) -> String {
+    let mut body = String::new();
+    let mut list: Option<&str> = None;
+    let mut paragraph: Vec<String> = Vec::new();
+
+    fn flush_paragraph(body: &mut String, paragraph: &mut Vec<String>) {
+        if !paragraph.is_empty() {
+            body.push_str(&format!("<p>{}</p>\n", paragraph.join(" ")));
+            paragraph.clear();
+        }
+    }
+
+    for raw in markdown.lines() {
+        let line = raw.trim();
+
+        let item = line
+            .strip_prefix("- ")
+            .or_else(|| line.strip_prefix("* "))
+            .map(|rest| ("ul", rest))
+            .or_else(|| {
+                let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+                (digits > 0)
+                    .then(|| line[digits..].strip_prefix(". "))
+                    .flatten()
+                    .map(|rest| ("ol", rest))
+            });
+
+        if item.map(|(tag, _)| tag) != list {
+            if let Some(tag) = list.take() {
+                body.push_str(&format!("</{}>\n", tag));
+            }
+        }
+
+        if let Some((tag, rest)) = item {
+            flush_paragraph(&mut body, &mut paragraph);
+            if list.is_none() {
+                body.push_str(&format!("<{}>\n", tag));
+                list = Some(tag);
+            }
+            let rest = if let Some(task) = rest.strip_prefix("[ ] ") {
+                format!("&#9744; {}", inline_html(task))
+            } else if let Some(task) = rest
+                .strip_prefix("[x] ")
+                .or_else(|| rest.strip_prefix("[X] "))
+            {
+                format!("&#9745; {}", inline_html(task))
+            } else {
+                inline_html(rest)
+            };
+            body.push_str(&format!("<li>{}</li>\n", rest));
+            continue;
+        }
+
+        if line.is_empty() {
+            flush_paragraph(&mut body, &mut paragraph);
+            continue;
+        }
+
+        let level = line.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&level) && line[level..].starts_with(' ') {
+            flush_paragraph(&mut body, &mut paragraph);
+            body.push_str(&format!(
+                "<h{level}>{}</h{level}>\n",
+                inline_html(line[level..].trim())
+            ));
+        } else if line == "---" || line == "***" {
+            flush_paragraph(&mut body, &mut paragraph);
+            body.push_str("<hr>\n");
+        } else {
+            paragraph.push(inline_html(line));
+        }
+    }
+    flush_paragraph(&mut body, &mut paragraph);
+    if let Some(tag) = list {
+        body.push_str(&format!("</{}>\n", tag));
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <style>body {{ font-family: sans-serif; max-width: 48em; margin: 2em auto; line-height: 1.4; }}</style>\n\
+         </head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        body
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Escape, then apply `**bold**`, `*italic*` and `` `code` `` spans
+fn inline_html(text: &str) -> String {
+    let escaped = escape_html(text);
+    let mut out = String::new();
+    let mut rest = escaped.as_str();
+
+    while !rest.is_empty() {
+        let (marker, open, close) = if rest.starts_with("**") {
+            ("**", "<strong>", "</strong>")
+        } else if rest.starts_with('`') {
+            ("`", "<code>", "</code>")
+        } else if rest.starts_with('*') {
+            ("*", "<em>", "</em>")
+        } else {
+            let ch = rest.chars().next().unwrap();
+            out.push(ch);
+            rest = &rest[ch.len_utf8()..];
+            continue;
+        };
+
+        let inner = &rest[marker.len()..];
+        match inner.find(marker) {
+            Some(end) if end > 0 => {
+                out.push_str(open);
+                out.push_str(&inner[..end]);
+                out.push_str(close);
+                rest = &inner[end + marker.len()..];
+            }
+            _ => {
+                out.push_str(marker);
+                rest = inner;
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pdf_attempt_order() {
+        assert!(pdf_attempts(false, false).is_empty());
+        assert_eq!(pdf_attempts(true, false), vec![PdfAttempt::Pandoc]);
+        assert_eq!(pdf_attempts(false, true), vec![PdfAttempt::Wkhtmltopdf]);
+        assert_eq!(
+            pdf_attempts(true, true),
+            vec![
+                PdfAttempt::Pandoc,
+                PdfAttempt::PandocWkhtmltopdf,
+                PdfAttempt::Wkhtmltopdf
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_markdown_header() {
+        let mut meeting = Meeting::new("Weekly Sync".to_string());
+        meeting.duration_seconds = Some(125);
+        let summary = SummaryResult {
+            markdown: "## TL;DR\nShipped.\n".to_string(),
+        };
+
+        let out = render_markdown(&meeting, &summary);
+        assert!(out.starts_with("# Weekly Sync\n\n**Date:** "));
+        assert!(out.contains("| **Duration:** 2m 5s\n\n## TL;DR\nShipped.\n"));
+    }
+
+    #[test]
+    fn test_render_document_sections() {
+        let meeting = Meeting::new("Weekly Sync".to_string());
+        let summary = SummaryResult {
+            markdown: "## TL;DR\nShipped.\n".to_string(),
+        };
+        let transcript = Transcript::new(vec![crate::transcription::TranscriptSegment::new(
+            0,
+            1000,
+            "Morning all".to_string(),
+        )]);
+
+        let plain = render_document(&meeting, &summary, ExportSections::default());
+        assert_eq!(plain, render_markdown(&meeting, &summary));
+
+        let full = render_document(
+            &meeting,
+            &summary,
+            ExportSections {
+                frontmatter: true,
+                transcript: Some(&transcript),
+                times: Default::default(),
+            },
+        );
+        assert!(full.starts_with("---\ntitle: \"Weekly Sync\"\n"));
+        assert!(full.contains("Shipped.\n\n---\n\n## Full Transcript\n\n**[00:00]** Morning all"));
+        assert!(!full.contains("<details>"));
+    }
+
+    #[test]
+    fn test_markdown_to_html() {
+        let html = markdown_to_html(
+            "Sync",
+            "## Action Items\n- [ ] **Alice**: send <notes>\n- Bob\n\n1. First\n2. Second\n\nSome `code` and *emphasis*\ncontinued\n",
+        );
+
+        assert!(html.contains("<title>Sync</title>"));
+        assert!(html.contains("<h2>Action Items</h2>\n<ul>\n"));
+        assert!(html.contains("<li>&#9744; <strong>Alice</strong>: send &lt;notes&gt;</li>"));
+        assert!(html.contains("<li>Bob</li>\n</ul>\n<ol>\n<li>First</li>"));
+        assert!(
+            html.contains("</ol>\n<p>Some <code>code</code> and <em>emphasis</em> continued</p>")
+        );
+    }
+
+    #[test]
+    fn test_inline_html_unmatched_markers() {
+        assert_eq!(inline_html("2 * 3 = 6"), "2 * 3 = 6");
+        assert_eq!(inline_html("a ** b"), "a ** b");
+    }
+}
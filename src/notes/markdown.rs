@@ -1,3 +1,4 @@
+use crate::config::settings::StorageConfig;
 use crate::error::Result;
 use crate::llm::SummaryResult;
 use crate::storage::Meeting;
@@ -5,13 +6,96 @@ use crate::transcription::Transcript;
 use std::fs;
 use std::path::PathBuf;
 
+/// How notes files include the transcript (`[storage] notes_transcript`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NotesTranscript {
+    /// Inline after the summary
+    Full,
+    /// Inside a `<details>` block
+    #[default]
+    Collapsed,
+    /// Left out; `muesli transcript` still shows it from the database
+    Omitted,
+}
+
+impl NotesTranscript {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "full" => Some(Self::Full),
+            "collapsed" => Some(Self::Collapsed),
+            "none" => Some(Self::Omitted),
+            _ => None,
+        }
+    }
+}
+
+/// How lines of a rendered transcript are stamped
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TranscriptTimes {
+    /// Offsets from the meeting start (`[12:30]`)
+    #[default]
+    Offset,
+    /// Wall-clock times (`[14:42:30]`)
+    Clock,
+    /// No times, just speaker-attributed dialogue
+    Hidden,
+}
+
+impl TranscriptTimes {
+    /// The `[storage]` transcript time options
+    pub fn from_config(config: &StorageConfig) -> Self {
+        Self::new(config.notes_timestamps, config.clock_timestamps)
+    }
+
+    pub fn new(timestamps: bool, clock: bool) -> Self {
+        match (timestamps, clock) {
+            (false, _) => Self::Hidden,
+            (true, true) => Self::Clock,
+            (true, false) => Self::Offset,
+        }
+    }
+}
+
 pub struct NoteGenerator {
     notes_dir: PathBuf,
+    clock_timestamps: bool,
+    timestamps: bool,
+    transcript: NotesTranscript,
 }
 
 impl NoteGenerator {
     pub fn new(notes_dir: PathBuf) -> Self {
-        Self { notes_dir }
+        Self {
+            notes_dir,
+            clock_timestamps: false,
+            timestamps: true,
+            transcript: NotesTranscript::default(),
+        }
+    }
+
+    /// A generator with the `[storage]` note options applied
+    pub fn from_config(notes_dir: PathBuf, config: &StorageConfig) -> Self {
+        Self::new(notes_dir)
+            .with_clock_timestamps(config.clock_timestamps)
+            .with_timestamps(config.notes_timestamps)
+            .with_transcript(NotesTranscript::parse(&config.notes_transcript).unwrap_or_default())
+    }
+
+    /// Show transcript times as wall-clock times instead of meeting offsets
+    pub fn with_clock_timestamps(mut self, clock_timestamps: bool) -> Self {
+        self.clock_timestamps = clock_timestamps;
+        self
+    }
+
+    /// Leave the times off transcript lines entirely
+    pub fn with_timestamps(mut self, timestamps: bool) -> Self {
+        self.timestamps = timestamps;
+        self
+    }
+
+    pub fn with_transcript(mut self, transcript: NotesTranscript) -> Self {
+        self.transcript = transcript;
+        self
     }
 
     pub fn generate(
@@ -22,55 +106,107 @@ impl NoteGenerator {
     ) -> Result<PathBuf> {
         fs::create_dir_all(&self.notes_dir)?;
 
-        let mut content = String::new();
+        let content = self.render(meeting, transcript, summary);
+        let notes_path = self.notes_dir.join(format!("{}.md", meeting.id));
+        // Write-then-rename so an interrupted run never leaves half a notes file
+        let temp_path = notes_path.with_extension("md.tmp");
+        fs::write(&temp_path, content)?;
+        fs::rename(&temp_path, &notes_path)?;
 
-        content.push_str("---\n");
-        content.push_str(&format!("title: \"{}\"\n", meeting.title));
-        content.push_str(&format!(
-            "date: {}\n",
-            meeting.started_at.format("%Y-%m-%d %H:%M")
-        ));
-        if let Some(duration) = meeting.duration_seconds {
-            content.push_str(&format!(
-                "duration: {}m {}s\n",
-                duration / 60,
-                duration % 60
-            ));
-        }
-        if let Some(app) = &meeting.detected_app {
-            content.push_str(&format!("app: \"{}\"\n", app));
-        }
-        content.push_str(&format!("id: \"{}\"\n", meeting.id));
-        content.push_str("---\n\n");
+        Ok(notes_path)
+    }
 
+    /// Render the markdown note without writing it
+    pub fn render(
+        &self,
+        meeting: &Meeting,
+        transcript: &Transcript,
+        summary: &SummaryResult,
+    ) -> String {
+        let mut content = render_frontmatter(meeting);
         content.push_str(&format!("# {}\n\n", meeting.title));
 
         content.push_str(&summary.markdown);
+        if self.transcript == NotesTranscript::Omitted {
+            content.push('\n');
+            return content;
+        }
         content.push_str("\n\n---\n\n");
+        content.push_str(&render_transcript(
+            meeting,
+            transcript,
+            self.transcript == NotesTranscript::Collapsed,
+            TranscriptTimes::new(self.timestamps, self.clock_timestamps),
+        ));
+        content
+    }
+}
+
+/// YAML frontmatter with the meeting's metadata, closing `---` and blank line included
+pub fn render_frontmatter(meeting: &Meeting) -> String {
+    let mut content = String::new();
 
-        content.push_str("## Full Transcript\n\n");
+    content.push_str("---\n");
+    content.push_str(&format!("title: \"{}\"\n", meeting.title));
+    content.push_str(&format!(
+        "date: {}\n",
+        meeting.started_at.format("%Y-%m-%d %H:%M")
+    ));
+    if let Some(duration) = meeting.duration_seconds {
+        content.push_str(&format!(
+            "duration: {}m {}s\n",
+            duration / 60,
+            duration % 60
+        ));
+    }
+    if let Some(app) = &meeting.detected_app {
+        content.push_str(&format!("app: \"{}\"\n", app));
+    }
+    if !meeting.participants.is_empty() {
+        content.push_str("attendees:\n");
+        for participant in &meeting.participants {
+            content.push_str(&format!("  - \"{}\"\n", participant));
+        }
+    }
+    content.push_str(&format!("id: \"{}\"\n", meeting.id));
+    content.push_str("---\n\n");
+    content
+}
+
+/// The `## Full Transcript` section, optionally inside a `<details>` block
+pub fn render_transcript(
+    meeting: &Meeting,
+    transcript: &Transcript,
+    collapsed: bool,
+    times: TranscriptTimes,
+) -> String {
+    let mut content = String::from("## Full Transcript\n\n");
+    if collapsed {
         content.push_str("<details>\n<summary>Click to expand transcript</summary>\n\n");
-        for segment in &transcript.segments {
-            let timestamp = format_timestamp(segment.start_ms);
-            match &segment.speaker {
-                Some(speaker) => {
-                    content.push_str(&format!(
-                        "**[{}] {}:** {}\n\n",
-                        timestamp, speaker, segment.text
-                    ));
-                }
-                None => {
-                    content.push_str(&format!("**[{}]** {}\n\n", timestamp, segment.text));
-                }
+    }
+    for segment in &transcript.segments {
+        let timestamp = match times {
+            TranscriptTimes::Offset => Some(format_timestamp(segment.start_ms)),
+            TranscriptTimes::Clock => Some(
+                segment.format_clock_timestamp(&meeting.started_at.with_timezone(&chrono::Local)),
+            ),
+            TranscriptTimes::Hidden => None,
+        };
+        let line = match (&segment.speaker, timestamp) {
+            (Some(speaker), Some(timestamp)) => {
+                format!("**[{}] {}:** {}", timestamp, speaker, segment.text)
             }
-        }
+            (None, Some(timestamp)) => format!("**[{}]** {}", timestamp, segment.text),
+            (Some(speaker), None) => format!("**{}:** {}", speaker, segment.text),
+            (None, None) => segment.text.clone(),
+        };
+        content.push_str(&line);
+        content.push_str("\n\n");
+    }
+    if collapsed {
         content.push_str("</details>\n");
-
-        let notes_path = self.notes_dir.join(format!("{}.md", meeting.id));
-        fs::write(&notes_path, content)?;
-
-        Ok(notes_path)
     }
+    content
 }
 
 fn format_timestamp(ms: u64) -> String {
@@ -107,6 +243,7 @@ mod tests {
         let mut meeting = Meeting::new("Test Meeting".to_string());
         meeting.duration_seconds = Some(3661);
         meeting.detected_app = Some("Zoom".to_string());
+        meeting.participants = vec!["Alice".to_string()];
 
         let segments = vec![TranscriptSegment {
             start_ms: 0,
@@ -114,6 +251,7 @@ mod tests {
             text: "Hello everyone".to_string(),
             speaker: Some("Alice".to_string()),
             confidence: Some(0.95),
+            language: None,
         }];
 
         let transcript = Transcript::new(segments);
@@ -129,9 +267,90 @@ mod tests {
 
         let content = fs::read_to_string(&notes_path).unwrap();
         assert!(content.contains("title: \"Test Meeting\""));
+        assert!(content.contains("attendees:\n  - \"Alice\"\n"));
         assert!(content.contains("## TL;DR"));
         assert!(content.contains("Hello everyone"));
 
         let _ = fs::remove_dir_all(temp_dir);
     }
+
+    #[test]
+    fn test_generate_includes_summary_not_placeholder() {
+        let meeting = Meeting::new("Summary".to_string());
+        let transcript = Transcript::new(vec![TranscriptSegment::new(
+            0,
+            5000,
+            "Hello everyone".to_string(),
+        )]);
+        let summary = SummaryResult {
+            markdown: "## TL;DR\nTest meeting summary.".to_string(),
+        };
+
+        let content = NoteGenerator::new(PathBuf::new()).render(&meeting, &transcript, &summary);
+        assert!(content.contains("Test meeting summary."));
+        assert!(!content.contains("will be generated here"));
+        let summary_at = content.find("## TL;DR").unwrap();
+        assert!(summary_at < content.find("Hello everyone").unwrap());
+    }
+
+    #[test]
+    fn test_clock_timestamps() {
+        let meeting = Meeting::new("Clock".to_string());
+        let mut segment = TranscriptSegment::new(750_000, 755_000, "Later on".to_string());
+        segment.speaker = Some("SPEAKER_0".to_string());
+        let transcript = Transcript::new(vec![segment.clone()]);
+        let summary = SummaryResult {
+            markdown: String::new(),
+        };
+
+        let expected =
+            segment.format_clock_timestamp(&meeting.started_at.with_timezone(&chrono::Local));
+        let generator = NoteGenerator::new(PathBuf::new()).with_clock_timestamps(true);
+        let content = generator.render(&meeting, &transcript, &summary);
+        assert!(content.contains(&format!("**[{}] SPEAKER_0:** Later on", expected)));
+
+        let relative = NoteGenerator::new(PathBuf::new()).render(&meeting, &transcript, &summary);
+        assert!(relative.contains("**[12:30] SPEAKER_0:** Later on"));
+
+        let plain = NoteGenerator::new(PathBuf::new())
+            .with_clock_timestamps(true)
+            .with_timestamps(false)
+            .render(&meeting, &transcript, &summary);
+        assert!(plain.contains("\n**SPEAKER_0:** Later on\n"));
+        assert!(!plain.contains(&expected));
+    }
+
+    #[test]
+    fn test_notes_transcript_modes() {
+        let meeting = Meeting::new("Modes".to_string());
+        let transcript = Transcript::new(vec![TranscriptSegment::new(0, 1000, "Hi".to_string())]);
+        let summary = SummaryResult {
+            markdown: "## TL;DR\nShort.".to_string(),
+        };
+        let render = |mode| {
+            NoteGenerator::new(PathBuf::new())
+                .with_transcript(mode)
+                .render(&meeting, &transcript, &summary)
+        };
+
+        let collapsed = render(NotesTranscript::Collapsed);
+        assert!(collapsed.contains("<details>"));
+        assert!(collapsed.contains("**[00:00]** Hi"));
+
+        let full = render(NotesTranscript::Full);
+        assert!(full.contains("## Full Transcript"));
+        assert!(full.contains("**[00:00]** Hi"));
+        assert!(!full.contains("<details>"));
+
+        let omitted = render(NotesTranscript::Omitted);
+        assert!(omitted.contains("Short."));
+        assert!(!omitted.contains("Full Transcript"));
+        assert!(!omitted.contains("Hi"));
+
+        assert_eq!(
+            NotesTranscript::parse("none"),
+            Some(NotesTranscript::Omitted)
+        );
+        assert_eq!(NotesTranscript::parse("hidden"), None);
+    }
 }
@@ -1,3 +1,7 @@
+pub mod diff;
+pub mod email;
+pub mod export;
 pub mod markdown;
 pub mod processor;
+pub mod subtitles;
 pub mod templates;
@@ -0,0 +1,150 @@
+//! Transcript subtitles (SRT and WebVTT), for overlaying a meeting's
+//! transcript on a screen recording of it. Both `muesli export` and
+//! `muesli transcript --format` write cues through here.
+
+use crate::transcription::TranscriptSegment;
+
+/// SubRip cues, numbered from 1, with `HH:MM:SS,mmm` times and the speaker
+/// as a `Speaker:` prefix
+pub fn to_srt(segments: &[TranscriptSegment]) -> String {
+    let mut srt = String::new();
+    for (number, (start, end, speaker, text)) in cues(segments).enumerate() {
+        let text = match speaker {
+            Some(speaker) => format!("{}: {}", speaker, text),
+            None => text,
+        };
+        srt.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            number + 1,
+            timestamp(start, ','),
+            timestamp(end, ','),
+            text
+        ));
+    }
+    srt
+}
+
+/// WebVTT cues, numbered from 1, with `HH:MM:SS.mmm` times and a visible
+/// `Speaker:` prefix. The speaker is also given as a `<v Speaker>` voice tag,
+/// which players don't show but `muesli import-transcript` reads back.
+pub fn to_vtt(segments: &[TranscriptSegment]) -> String {
+    let mut vtt = String::from("WEBVTT\n\n");
+    for (number, (start, end, speaker, text)) in cues(segments).enumerate() {
+        let text = match speaker {
+            Some(speaker) => format!(
+                "<v {}>{}: {}",
+                escape_vtt(speaker),
+                escape_vtt(speaker),
+                escape_vtt(&text)
+            ),
+            None => escape_vtt(&text),
+        };
+        vtt.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            number + 1,
+            timestamp(start, '.'),
+            timestamp(end, '.'),
+            text
+        ));
+    }
+    vtt
+}
+
+/// Start, end, speaker and one-line text of each non-empty segment. A cue
+/// never ends before it starts, which players reject.
+fn cues(
+    segments: &[TranscriptSegment],
+) -> impl Iterator<Item = (u64, u64, Option<&str>, String)> + '_ {
+    segments.iter().filter_map(|segment| {
+        let text = segment
+            .text
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if text.is_empty() {
+            return None;
+        }
+        Some((
+            segment.start_ms,
+            segment.end_ms.max(segment.start_ms),
+            segment.speaker.as_deref(),
+            text,
+        ))
+    })
+}
+
+/// Cue text with `&`, `<` and `>` as entities: `<` would start a tag and
+/// `-->` end the cue early
+fn escape_vtt(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// `HH:MM:SS` then `separator` and milliseconds (hours aren't capped at 99)
+fn timestamp(ms: u64, separator: char) -> String {
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        separator,
+        ms % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments() -> Vec<TranscriptSegment> {
+        let mut first = TranscriptSegment::new(0, 2500, "Hello everyone.".to_string());
+        first.speaker = Some("Alice".to_string());
+        vec![
+            first,
+            TranscriptSegment::new(2500, 2500, "   ".to_string()),
+            TranscriptSegment::new(3_723_004, 3_725_999, "Let's\nstart --> now".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_timestamp() {
+        assert_eq!(timestamp(0, ','), "00:00:00,000");
+        assert_eq!(timestamp(3_723_004, ','), "01:02:03,004");
+        assert_eq!(timestamp(59_999, '.'), "00:00:59.999");
+        assert_eq!(timestamp(360_000_000, '.'), "100:00:00.000");
+    }
+
+    #[test]
+    fn test_to_srt() {
+        assert_eq!(
+            to_srt(&segments()),
+            "1\n00:00:00,000 --> 00:00:02,500\nAlice: Hello everyone.\n\n\
+             2\n01:02:03,004 --> 01:02:05,999\nLet's start --> now\n\n"
+        );
+        assert_eq!(to_srt(&[]), "");
+    }
+
+    #[test]
+    fn test_to_vtt() {
+        assert_eq!(
+            to_vtt(&segments()),
+            "WEBVTT\n\n\
+             1\n00:00:00.000 --> 00:00:02.500\n<v Alice>Alice: Hello everyone.\n\n\
+             2\n01:02:03.004 --> 01:02:05.999\nLet's start --&gt; now\n\n"
+        );
+    }
+
+    #[test]
+    fn test_vtt_escapes_markup() {
+        let mut segment = TranscriptSegment::new(0, 1000, "Q&A <after> lunch".to_string());
+        segment.speaker = Some("R&D".to_string());
+        assert!(to_vtt(&[segment]).contains("<v R&amp;D>R&amp;D: Q&amp;A &lt;after&gt; lunch\n"));
+    }
+
+    #[test]
+    fn test_cue_never_ends_before_start() {
+        let segments = vec![TranscriptSegment::new(5000, 4000, "Oops".to_string())];
+        assert!(to_srt(&segments).contains("00:00:05,000 --> 00:00:05,000"));
+    }
+}
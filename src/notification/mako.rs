@@ -28,6 +28,23 @@ pub fn notify_recording_started(meeting_title: &str) -> Result<()> {
     Ok(())
 }
 
+/// Notification id reused by live preview updates so each one replaces the last
+const PREVIEW_NOTIFICATION_ID: u32 = 0x6d75_0001;
+
+/// Show (or update in place) the latest transcribed line while recording
+pub fn notify_transcript_preview(meeting_title: &str, line: &str) -> Result<()> {
+    Notification::new()
+        .id(PREVIEW_NOTIFICATION_ID)
+        .summary(&format!("Recording: {}", meeting_title))
+        .body(line)
+        .icon("media-record")
+        .urgency(Urgency::Low)
+        .timeout(5000)
+        .show()
+        .map_err(|e| crate::error::MuesliError::Notification(e.to_string()))?;
+    Ok(())
+}
+
 /// Show notification when recording stops
 pub fn notify_recording_stopped(meeting_title: &str, duration_mins: u64) -> Result<()> {
     Notification::new()
@@ -44,6 +61,35 @@ pub fn notify_recording_stopped(meeting_title: &str, duration_mins: u64) -> Resu
     Ok(())
 }
 
+/// Show notification when processing a finished recording begins
+pub fn notify_processing_started(meeting_title: &str) -> Result<()> {
+    Notification::new()
+        .summary("Processing Meeting")
+        .body(&format!(
+            "{}\nThis may take a few minutes for a long recording.",
+            meeting_title
+        ))
+        .icon("system-run")
+        .urgency(Urgency::Low)
+        .timeout(5000)
+        .show()
+        .map_err(|e| crate::error::MuesliError::Notification(e.to_string()))?;
+    Ok(())
+}
+
+/// Show notification when processing moves on to `stage` (e.g. "Summarizing")
+pub fn notify_processing_stage(meeting_title: &str, stage: &str) -> Result<()> {
+    Notification::new()
+        .summary("Processing Meeting")
+        .body(&format!("{}\n{}...", meeting_title, stage))
+        .icon("system-run")
+        .urgency(Urgency::Low)
+        .timeout(3000)
+        .show()
+        .map_err(|e| crate::error::MuesliError::Notification(e.to_string()))?;
+    Ok(())
+}
+
 #[allow(dead_code)]
 pub fn notify_notes_ready(meeting_title: &str, notes_path: &str) -> Result<()> {
     Notification::new()
@@ -57,7 +103,6 @@ pub fn notify_notes_ready(meeting_title: &str, notes_path: &str) -> Result<()> {
     Ok(())
 }
 
-#[allow(dead_code)]
 pub fn notify_error(title: &str, message: &str) -> Result<()> {
     Notification::new()
         .summary(title)
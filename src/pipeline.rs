@@ -0,0 +1,482 @@
+//! Post-recording processing shared by the daemon and the CLI: transcribe the
+//! recording, label speakers, then summarize and write the notes file.
+
+use crate::config::settings::{LlmConfig, MuesliConfig, TranscriptionConfig};
+use crate::error::{MuesliError, Result};
+use crate::llm::prompts::SummaryTemplate;
+use crate::llm::SummaryResult;
+use crate::storage::database::Database;
+use crate::storage::Meeting;
+use crate::transcription::diarization::{speaker_label_at, Diarizer, SpeakerSegment};
+use crate::transcription::diarization_models::{DiarizationModel, DiarizationModelManager};
+use crate::transcription::models::{ModelManager, WhisperModel};
+use crate::transcription::whisper::{transcribe_wav_file, WhisperEngine};
+use crate::transcription::{Transcript, TranscriptSegment};
+use std::path::{Path, PathBuf};
+
+/// Title given to meetings recorded without one
+const UNTITLED: &str = "Untitled Meeting";
+
+/// Transcribe a full recording with the configured Whisper model.
+pub fn transcribe(audio_path: &Path, config: &MuesliConfig) -> Result<Transcript> {
+    let manager = ModelManager::new(crate::config::loader::models_dir()?);
+    if !config.transcription.has_model() {
+        tracing::warn!("No transcription.model configured; using the default (base)");
+    }
+    let model =
+        WhisperModel::parse(config.transcription.effective_model()).unwrap_or(WhisperModel::Base);
+
+    if !manager.model_exists(model) {
+        return Err(MuesliError::Config(format!(
+            "Whisper model {:?} not found. Run: muesli models whisper download {}",
+            model,
+            config.transcription.effective_model()
+        )));
+    }
+
+    let engine = WhisperEngine::new(manager.model_path(model), config.transcription.use_gpu)?;
+    transcribe_wav_file(&engine, audio_path, config.audio.auto_normalize)
+}
+
+/// Run speaker diarization on the recording and label `segments` with the result.
+/// Speakers that sound like an enrolled profile get its name instead of `SPEAKER_n`.
+///
+/// Fails when the diarization model isn't installed; callers decide whether to
+/// download it first or skip the step.
+pub fn diarize_and_label(
+    segments: &[TranscriptSegment],
+    audio_path: &Path,
+    config: &MuesliConfig,
+) -> Result<Vec<TranscriptSegment>> {
+    let manager = DiarizationModelManager::new(crate::config::loader::models_dir()?);
+    let model = DiarizationModel::SortformerV2;
+    if !manager.model_exists(model) {
+        return Err(MuesliError::Config(
+            "Diarization model not installed. Run: muesli models diarization download sortformer"
+                .to_string(),
+        ));
+    }
+
+    let samples = crate::audio::wav::read_mono_16k(audio_path)?;
+    let profiles = enrolled_speaker_profiles();
+    let mut diarizer = Diarizer::new(manager.model_path(model))?;
+    // The samples are only needed again for matching against profiles
+    let (speaker_segments, samples) = if profiles.is_empty() {
+        (diarizer.diarize(samples, 16000)?, None)
+    } else {
+        (diarizer.diarize(samples.clone(), 16000)?, Some(samples))
+    };
+    tracing::info!(
+        "Diarization complete: {} speaker segments",
+        speaker_segments.len()
+    );
+
+    let mut labeled = label_speakers(segments, &speaker_segments, &config.transcription);
+    let names: std::collections::HashMap<String, String> = samples
+        .map(|samples| enrolled_speaker_names(&samples, &speaker_segments, &profiles, config))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(id, name)| (format!("SPEAKER_{}", id + 1), name))
+        .collect();
+    for seg in labeled.iter_mut() {
+        if let Some(name) = seg.speaker.as_ref().and_then(|label| names.get(label)) {
+            seg.speaker = Some(name.clone());
+        }
+    }
+    Ok(labeled)
+}
+
+/// Enrolled (name, voiceprint) pairs. Problems reading the profiles only cost
+/// the names, never the diarization, so they come back empty.
+fn enrolled_speaker_profiles() -> Vec<(String, Vec<f32>)> {
+    let profiles = crate::config::loader::database_path()
+        .and_then(Database::open)
+        .and_then(|db| db.list_speaker_profiles());
+    match profiles {
+        Ok(profiles) => profiles
+            .into_iter()
+            .map(|(name, voiceprint, _)| (name, voiceprint))
+            .collect(),
+        Err(e) => {
+            tracing::warn!("Could not load speaker profiles: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Speaker id to enrolled name for the speakers that match one of `profiles`
+fn enrolled_speaker_names(
+    samples: &[f32],
+    speaker_segments: &[SpeakerSegment],
+    profiles: &[(String, Vec<f32>)],
+    config: &MuesliConfig,
+) -> std::collections::HashMap<usize, String> {
+    let names = crate::transcription::voiceprint::match_speakers(
+        samples,
+        speaker_segments,
+        profiles,
+        config.transcription.speaker_match_threshold,
+    );
+    for (id, name) in &names {
+        tracing::info!("SPEAKER_{} matches enrolled profile {}", id + 1, name);
+    }
+    names
+}
+
+/// Apply diarization turns to transcript segments. Same-speaker segments are
+/// merged per `merge_gap_ms` when the transcript is stored.
+///
+/// Segments no speaker overlaps keep whatever label they already had.
+pub fn label_speakers(
+    segments: &[TranscriptSegment],
+    speaker_segments: &[SpeakerSegment],
+    config: &TranscriptionConfig,
+) -> Vec<TranscriptSegment> {
+    let mut labeled = segments.to_vec();
+    for seg in labeled.iter_mut() {
+        if let Some(speaker) = speaker_label_at(
+            speaker_segments,
+            seg.start_ms,
+            seg.end_ms,
+            config.speaker_ambiguity_threshold,
+        ) {
+            seg.speaker = Some(speaker);
+        }
+    }
+    labeled
+}
+
+/// The summary template stored with the meeting (the general layout if unset or unknown)
+pub fn summary_template(meeting: &Meeting) -> SummaryTemplate {
+    meeting
+        .template
+        .as_deref()
+        .and_then(SummaryTemplate::parse)
+        .unwrap_or_default()
+}
+
+/// The LLM settings to summarize a meeting with: the configured ones, writing
+/// in the meeting's stored output language when it has one
+pub fn summary_llm_config(meeting: &Meeting, config: &LlmConfig) -> LlmConfig {
+    let mut llm = config.clone();
+    if let Some(language) = &meeting.output_language {
+        llm.output_language = language.clone();
+    }
+    llm
+}
+
+/// Held while a meeting is being (re)processed so the daemon and `muesli redo`
+/// never rewrite the same meeting at once. Released, and its lock file
+/// removed, when dropped.
+pub struct ProcessingLock {
+    _file: std::fs::File,
+    path: PathBuf,
+}
+
+impl ProcessingLock {
+    /// Take the meeting's lock, or `None` if another process is processing it
+    pub fn try_acquire(meeting_id: &str) -> Result<Option<Self>> {
+        let dir = crate::config::loader::data_dir()?.join("locks");
+        std::fs::create_dir_all(&dir)?;
+        Self::try_acquire_at(dir.join(format!("{}.lock", meeting_id)))
+    }
+
+    fn try_acquire_at(path: PathBuf) -> Result<Option<Self>> {
+        use fs2::FileExt;
+        use std::os::unix::fs::MetadataExt;
+
+        loop {
+            let file = std::fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(false)
+                .open(&path)?;
+            match file.try_lock_exclusive() {
+                Ok(()) => {}
+                Err(e) if e.kind() == fs2::lock_contended_error().kind() => return Ok(None),
+                Err(e) => return Err(e.into()),
+            }
+
+            // The previous holder unlinks the file on release; if that happened
+            // after we opened it, our lock is on an orphan and we go again
+            let locked = file.metadata()?;
+            match std::fs::metadata(&path) {
+                Ok(current) if current.dev() == locked.dev() && current.ino() == locked.ino() => {
+                    return Ok(Some(Self { _file: file, path }));
+                }
+                Ok(_) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+impl Drop for ProcessingLock {
+    fn drop(&mut self) {
+        // Unlink before unlocking (the file closes after this), so no one can
+        // lock the old file once it's gone
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Result of [`summarize_and_generate_notes`]
+pub struct GeneratedNotes {
+    /// The meeting as stored afterwards (new title, notes path)
+    pub meeting: Meeting,
+    pub summary: SummaryResult,
+    pub notes_path: PathBuf,
+}
+
+/// Summarize the transcript with the meeting's template, store the summary,
+/// title the meeting and write its notes file.
+///
+/// The meeting is only retitled from the summary when `retitle` is set or it
+/// still has the default title. A failed title request keeps the old title.
+pub async fn summarize_and_generate_notes(
+    db: &Database,
+    meeting: &Meeting,
+    transcript: &Transcript,
+    config: &MuesliConfig,
+    retitle: bool,
+) -> Result<GeneratedNotes> {
+    let template = summary_template(meeting);
+    let llm = summary_llm_config(meeting, &config.llm);
+    let summary = crate::llm::summarize_transcript(&llm, transcript, template, Some(db))
+        .await
+        .map_err(|e| MuesliError::Api(format!("LLM error: {}", e)))?;
+    db.insert_summary(&meeting.id, &summary)?;
+    tracing::info!("Summary saved for meeting {}", meeting.id);
+
+    let mut meeting = meeting.clone();
+    if retitle || meeting.title == UNTITLED {
+        match crate::llm::generate_title(&llm, &summary.markdown).await {
+            Ok(title) => {
+                tracing::info!("Generated title: {}", title);
+                meeting.title = title;
+            }
+            Err(e) => tracing::warn!("Title generation failed: {}", e),
+        }
+    }
+
+    store_action_items(db, &meeting, &llm, &summary).await?;
+
+    let generator = crate::notes::markdown::NoteGenerator::from_config(
+        crate::config::loader::notes_dir()?,
+        &config.storage,
+    );
+    let notes_path = generator.generate(&meeting, transcript, &summary)?;
+    tracing::info!("Generated notes: {}", notes_path.display());
+
+    meeting.notes_path = Some(notes_path.clone());
+    db.update_meeting(&meeting)?;
+
+    Ok(GeneratedNotes {
+        meeting,
+        summary,
+        notes_path,
+    })
+}
+
+/// Extract the action items from a meeting's summary into the database for
+/// `muesli todos`. A failed extraction is logged and leaves the old items.
+pub async fn store_action_items(
+    db: &Database,
+    meeting: &Meeting,
+    llm: &LlmConfig,
+    summary: &SummaryResult,
+) -> Result<()> {
+    let meeting_date = meeting
+        .started_at
+        .with_timezone(&chrono::Local)
+        .date_naive();
+    match crate::llm::extract_action_items(llm, &summary.markdown, meeting_date).await {
+        Ok(items) => {
+            db.insert_action_items(&meeting.id, &items)?;
+            tracing::info!(
+                "Saved {} action items for meeting {}",
+                items.len(),
+                meeting.id
+            );
+        }
+        Err(e) => tracing::warn!("Action item extraction failed: {}", e),
+    }
+    Ok(())
+}
+
+/// Replace a processed meeting's WAV with an Opus encoding of it
+/// (`[audio] compress_after_recording`), returning the new audio path. The WAV
+/// is only deleted once the meeting points at the verified Opus file.
+pub fn compress_recording(db: &Database, meeting: &mut Meeting) -> Result<Option<PathBuf>> {
+    let Some(wav_path) = meeting.audio_path.clone() else {
+        return Ok(None);
+    };
+    if crate::audio::compress::is_opus(&wav_path) || !wav_path.exists() {
+        return Ok(None);
+    }
+
+    let opus_path = crate::audio::compress::compress(&wav_path)?;
+    meeting.audio_path = Some(opus_path.clone());
+    if let Err(e) = db.update_meeting(meeting) {
+        meeting.audio_path = Some(wav_path);
+        let _ = std::fs::remove_file(&opus_path);
+        return Err(e);
+    }
+    if let Err(e) = std::fs::remove_file(&wav_path) {
+        tracing::warn!("Failed to delete {:?} after compressing: {}", wav_path, e);
+    }
+    tracing::info!("Compressed recording to {:?}", opus_path);
+    Ok(Some(opus_path))
+}
+
+/// Delete a meeting from the database along with its notes, transcript and
+/// (unless `keep_audio`) recording files. Returns the files removed; ones
+/// already missing are skipped.
+pub fn delete_meeting(db: &Database, meeting: &Meeting, keep_audio: bool) -> Result<Vec<PathBuf>> {
+    db.delete_meeting(&meeting.id)?;
+
+    let audio = meeting.audio_path.as_ref().filter(|_| !keep_audio);
+    let mut removed = Vec::new();
+    for path in [&meeting.notes_path, &meeting.transcript_path]
+        .into_iter()
+        .flatten()
+        .chain(audio)
+    {
+        match std::fs::remove_file(path) {
+            Ok(()) => removed.push(path.clone()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turn(speaker_id: usize, start_ms: u64, end_ms: u64) -> SpeakerSegment {
+        SpeakerSegment {
+            speaker_id,
+            start_ms,
+            end_ms,
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_label_speakers() {
+        let segments = vec![
+            TranscriptSegment::new(0, 1000, "Hello".to_string()),
+            TranscriptSegment::new(1100, 2000, " there".to_string()),
+            TranscriptSegment::new(2500, 3500, "Hi".to_string()),
+            TranscriptSegment::new(8000, 9000, "Anyone?".to_string()),
+        ];
+        let speakers = vec![turn(0, 0, 2200), turn(1, 2200, 4000)];
+        let config = TranscriptionConfig {
+            merge_gap_ms: 500,
+            ..TranscriptionConfig::default()
+        };
+
+        let labeled = label_speakers(&segments, &speakers, &config);
+
+        assert_eq!(labeled.len(), 4);
+        assert_eq!(labeled[0].speaker.as_deref(), Some("SPEAKER_1"));
+        assert_eq!(labeled[1].speaker.as_deref(), Some("SPEAKER_1"));
+        assert_eq!(labeled[2].speaker.as_deref(), Some("SPEAKER_2"));
+        assert_eq!(labeled[3].speaker, None);
+
+        // Merged when stored
+        let merged = crate::transcription::merge_speaker_turns(&labeled, config.merge_gap_ms);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0].text, "Hello there");
+    }
+
+    #[test]
+    fn test_compress_recording() {
+        let dir = tempfile::tempdir().unwrap();
+        let wav_path = dir.path().join("meeting.wav");
+        crate::audio::wav::write_mono_16k(
+            &wav_path,
+            &vec![0.1; 16000],
+            crate::audio::recorder::BitDepth::I16,
+        )
+        .unwrap();
+
+        let db = Database::open_in_memory().unwrap();
+        let mut meeting = Meeting::new("Compressed".to_string());
+        meeting.audio_path = Some(wav_path.clone());
+        db.insert_meeting(&meeting).unwrap();
+
+        let opus_path = compress_recording(&db, &mut meeting).unwrap().unwrap();
+        assert!(opus_path.exists());
+        assert!(!wav_path.exists());
+        let stored = db.get_meeting(&meeting.id).unwrap().unwrap();
+        assert_eq!(stored.audio_path, Some(opus_path));
+
+        // Already compressed
+        assert_eq!(compress_recording(&db, &mut meeting).unwrap(), None);
+    }
+
+    #[test]
+    fn test_processing_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("m1.lock");
+
+        let lock = ProcessingLock::try_acquire_at(path.clone())
+            .unwrap()
+            .unwrap();
+        assert!(ProcessingLock::try_acquire_at(path.clone())
+            .unwrap()
+            .is_none());
+
+        drop(lock);
+        assert!(!path.exists());
+        assert!(ProcessingLock::try_acquire_at(path).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_delete_meeting() {
+        let dir = tempfile::tempdir().unwrap();
+        let audio_path = dir.path().join("meeting.wav");
+        let notes_path = dir.path().join("meeting.md");
+        std::fs::write(&audio_path, b"RIFF").unwrap();
+        std::fs::write(&notes_path, "# Notes").unwrap();
+
+        let db = Database::open_in_memory().unwrap();
+        let mut meeting = Meeting::new("Delete me".to_string());
+        meeting.audio_path = Some(audio_path.clone());
+        meeting.notes_path = Some(notes_path.clone());
+        meeting.transcript_path = Some(dir.path().join("never-written.txt"));
+        db.insert_meeting(&meeting).unwrap();
+        db.insert_transcript_segments(
+            &meeting.id,
+            &[TranscriptSegment::new(0, 1000, "Hello".to_string())],
+            0,
+        )
+        .unwrap();
+        let item = crate::storage::ActionItem {
+            text: "Send the recap".to_string(),
+            owner: None,
+            due_date: None,
+            done: false,
+        };
+        db.insert_action_items(&meeting.id, &[item]).unwrap();
+
+        let removed = delete_meeting(&db, &meeting, false).unwrap();
+        assert_eq!(removed, vec![notes_path.clone(), audio_path.clone()]);
+        assert!(!audio_path.exists());
+        assert!(!notes_path.exists());
+        assert!(db.get_meeting(&meeting.id).unwrap().is_none());
+        assert!(db.get_transcript_segments(&meeting.id).unwrap().is_empty());
+        assert!(db.get_open_action_items().unwrap().is_empty());
+
+        let mut kept = Meeting::new("Keep the audio".to_string());
+        kept.audio_path = Some(audio_path.clone());
+        std::fs::write(&audio_path, b"RIFF").unwrap();
+        db.insert_meeting(&kept).unwrap();
+        assert!(delete_meeting(&db, &kept, true).unwrap().is_empty());
+        assert!(audio_path.exists());
+        assert!(db.get_meeting(&kept.id).unwrap().is_none());
+    }
+}
@@ -1,51 +1,71 @@
+use crate::cli::output::status;
 use crate::config::loader::load_config;
 use crate::error::{MuesliError, Result};
 
-pub async fn ask(question: &str) -> Result<()> {
+/// What [`ask`] came back with
+pub enum AskReply {
+    /// The LLM's answer (already passed to `on_token`)
+    Answer(String),
+    /// The matching notes, when no LLM provider is configured to answer from them
+    Notes(String),
+}
+
+/// Answer `question` from the notes qmd finds for it. `history` holds the earlier
+/// turns of an `ask --session`, oldest first (empty for a one-off question).
+/// The answer is passed to `on_token` as it streams in.
+pub async fn ask(
+    question: &str,
+    history: &[(String, String)],
+    on_token: impl FnMut(&str),
+) -> Result<AskReply> {
     let config = load_config()?;
 
     if !config.qmd.enabled {
-        eprintln!("qmd search is not enabled. Run 'muesli setup' to configure.");
-        return Ok(());
+        return Err(MuesliError::NotConfigured(
+            "qmd search is not enabled. Run 'muesli setup' to configure.".to_string(),
+        ));
     }
 
-    println!("Searching meeting notes...\n");
+    status!("Searching meeting notes...\n");
 
-    let search_results =
-        crate::qmd::search::search(question, &config.qmd.collection_name, 5, false)?;
+    // Follow-ups like "who owns it?" need the previous question to find the right notes
+    let query = match history.last() {
+        Some((previous, _)) => format!("{} {}", previous, question),
+        None => question.to_string(),
+    };
+    let search_results = crate::qmd::search::search(&query, &config.qmd.collection_name, 5, false)?;
 
     if search_results.trim().is_empty() {
-        println!("No relevant meeting notes found for: {}", question);
-        return Ok(());
+        return Err(MuesliError::NoResults(question.to_string()));
     }
 
     if config.llm.provider == "none" {
-        println!("Relevant meeting notes:\n");
-        println!("{}", search_results);
-        println!("\n---");
-        println!("Tip: Configure an LLM provider to get AI-powered answers.");
-        println!("     Run: muesli setup (step 7)");
-        return Ok(());
+        return Ok(AskReply::Notes(search_results));
     }
 
-    println!("Asking LLM...\n");
+    status!("Asking LLM...\n");
 
+    let history = crate::llm::bounded_history(&config.llm, history);
     let prompt = format!(
         "You are a meeting notes assistant. Answer questions using ONLY the meeting notes provided below. \
          Do NOT use any outside knowledge. If the notes do not contain enough information to answer \
          the question, say so clearly.\n\n\
-         Question: {}\n\n\
+         {}Question: {}\n\n\
          Meeting Notes:\n{}\n\n\
          Provide a clear, direct answer. Reference which meeting(s) the information comes from. \
          If the answer cannot be determined from these notes alone, state that explicitly.",
-        question, search_results
+        if history.is_empty() {
+            String::new()
+        } else {
+            format!("{}\n", history)
+        },
+        question,
+        search_results
     );
 
-    let answer = crate::llm::ask(&config.llm, &prompt)
+    let answer = crate::llm::ask(&config.llm, &prompt, on_token)
         .await
         .map_err(|e| MuesliError::Qmd(format!("LLM error: {}", e)))?;
 
-    println!("{}", answer);
-
-    Ok(())
+    Ok(AskReply::Answer(answer))
 }
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::path::Path;
 use std::process::Command;
 use std::time::Duration;
@@ -146,6 +147,139 @@ pub fn reindex(collection_name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Differences between the qmd collection and the notes on disk
+#[derive(Debug, Default)]
+pub struct IndexReport {
+    /// Indexed documents whose notes file no longer exists
+    pub orphaned: Vec<String>,
+    /// Notes files that are not in the index yet
+    pub unindexed: Vec<String>,
+    /// Notes files whose meeting is no longer in the database
+    pub untracked: Vec<String>,
+}
+
+impl IndexReport {
+    pub fn is_clean(&self) -> bool {
+        self.orphaned.is_empty() && self.unindexed.is_empty() && self.untracked.is_empty()
+    }
+
+    /// Whether `qmd update` would change anything
+    pub fn needs_update(&self) -> bool {
+        !self.orphaned.is_empty() || !self.unindexed.is_empty()
+    }
+}
+
+/// List documents in a collection, as paths relative to the collection root
+pub fn list_indexed_documents(collection_name: &str) -> Result<Vec<String>> {
+    ensure_qmd()?;
+
+    let output = run_qmd_command(&["ls", collection_name], SETUP_TIMEOUT)?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(MuesliError::Qmd(format!(
+            "qmd ls failed: {}",
+            stderr.trim()
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_ls_output(&stdout, collection_name))
+}
+
+fn parse_ls_output(output: &str, collection_name: &str) -> Vec<String> {
+    let prefix = format!("qmd://{}/", collection_name);
+
+    output
+        .lines()
+        .filter_map(|line| {
+            line.split_whitespace()
+                .rev()
+                .find(|token| token.ends_with(".md"))
+        })
+        .map(|path| {
+            path.strip_prefix(&prefix)
+                .or_else(|| path.strip_prefix(&format!("{}/", collection_name)))
+                .unwrap_or(path)
+                .to_string()
+        })
+        .collect()
+}
+
+fn list_notes_files(notes_dir: &Path) -> Result<Vec<String>> {
+    fn walk(dir: &Path, root: &Path, out: &mut Vec<String>) -> std::io::Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                walk(&path, root, out)?;
+            } else if path.extension().is_some_and(|ext| ext == "md") {
+                if let Ok(rel) = path.strip_prefix(root) {
+                    out.push(rel.to_string_lossy().to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    if notes_dir.exists() {
+        walk(notes_dir, notes_dir, &mut files)?;
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Cross-check the collection against the notes directory and the meeting IDs
+/// known to the database (notes files are named `<meeting-id>.md`).
+pub fn verify_index(
+    notes_dir: &Path,
+    collection_name: &str,
+    meeting_ids: &HashSet<String>,
+) -> Result<IndexReport> {
+    let indexed = list_indexed_documents(collection_name)?;
+    let on_disk = list_notes_files(notes_dir)?;
+    Ok(diff_index(&indexed, &on_disk, meeting_ids))
+}
+
+fn diff_index(
+    indexed: &[String],
+    on_disk: &[String],
+    meeting_ids: &HashSet<String>,
+) -> IndexReport {
+    // qmd may normalise file names (e.g. lowercasing), so compare case-insensitively
+    let indexed_set: HashSet<String> = indexed.iter().map(|p| p.to_lowercase()).collect();
+    let disk_set: HashSet<String> = on_disk.iter().map(|p| p.to_lowercase()).collect();
+
+    let mut report = IndexReport {
+        orphaned: indexed
+            .iter()
+            .filter(|p| !disk_set.contains(&p.to_lowercase()))
+            .cloned()
+            .collect(),
+        unindexed: on_disk
+            .iter()
+            .filter(|p| !indexed_set.contains(&p.to_lowercase()))
+            .cloned()
+            .collect(),
+        untracked: on_disk
+            .iter()
+            .filter(|p| {
+                let stem = Path::new(p)
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                !meeting_ids.contains(&stem)
+            })
+            .cloned()
+            .collect(),
+    };
+
+    report.orphaned.sort();
+    report.unindexed.sort();
+    report.untracked.sort();
+    report
+}
+
 pub(crate) fn run_qmd_command(args: &[&str], timeout: Duration) -> Result<std::process::Output> {
     use std::process::Stdio;
 
@@ -170,3 +304,31 @@ pub(crate) fn run_qmd_command(args: &[&str], timeout: Duration) -> Result<std::p
 
     Ok(output)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ls_output() {
+        let output = "qmd://muesli-meetings/abc.md  1.2 KB  2024-01-01\nqmd://muesli-meetings/sub/def.md\n\nCollection: muesli-meetings\n";
+        assert_eq!(
+            parse_ls_output(output, "muesli-meetings"),
+            vec!["abc.md".to_string(), "sub/def.md".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_diff_index() {
+        let indexed = vec!["a.md".to_string(), "gone.md".to_string()];
+        let on_disk = vec!["a.md".to_string(), "new.md".to_string()];
+        let ids: HashSet<String> = ["a".to_string()].into_iter().collect();
+
+        let report = diff_index(&indexed, &on_disk, &ids);
+        assert_eq!(report.orphaned, vec!["gone.md".to_string()]);
+        assert_eq!(report.unindexed, vec!["new.md".to_string()]);
+        assert_eq!(report.untracked, vec!["new.md".to_string()]);
+        assert!(!report.is_clean());
+        assert!(report.needs_update());
+    }
+}
@@ -2,6 +2,6 @@ pub mod ask;
 pub mod indexer;
 pub mod search;
 
-pub use ask::ask;
+pub use ask::{ask, AskReply};
 pub use indexer::reindex;
 pub use search::{search, status};
@@ -1,6 +1,6 @@
 use crate::error::Result;
 use crate::storage::migrations;
-use crate::storage::{Meeting, MeetingId, MeetingStatus};
+use crate::storage::{ActionItem, Meeting, MeetingId, MeetingPart, MeetingStatus};
 use crate::transcription::TranscriptSegment;
 use rusqlite::{params, Connection};
 use std::path::Path;
@@ -9,6 +9,9 @@ pub struct Database {
     conn: Connection,
 }
 
+/// Order of the open action item list; `todos done <n>` numbers items by it
+const OPEN_ACTION_ITEMS_ORDER: &str = "ORDER BY a.due_date IS NULL, a.due_date, m.started_at, a.id";
+
 impl Database {
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
@@ -33,8 +36,8 @@ impl Database {
 
     pub fn insert_meeting(&self, meeting: &Meeting) -> Result<()> {
         self.conn.execute(
-            "INSERT INTO meetings (id, title, started_at, ended_at, duration_seconds, audio_path, transcript_path, notes_path, status, detected_app)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            "INSERT INTO meetings (id, title, started_at, ended_at, duration_seconds, audio_path, transcript_path, notes_path, status, detected_app, participants, template, output_language)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
             params![
                 meeting.id.0,
                 meeting.title,
@@ -46,6 +49,9 @@ impl Database {
                 meeting.notes_path.as_ref().map(|p| p.to_string_lossy().to_string()),
                 meeting.status.to_string(),
                 meeting.detected_app,
+                join_participants(&meeting.participants),
+                meeting.template,
+                meeting.output_language,
             ],
         )?;
         Ok(())
@@ -53,7 +59,7 @@ impl Database {
 
     pub fn get_meeting(&self, id: &MeetingId) -> Result<Option<Meeting>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, title, started_at, ended_at, duration_seconds, audio_path, transcript_path, notes_path, status, detected_app
+            "SELECT id, title, started_at, ended_at, duration_seconds, audio_path, transcript_path, notes_path, status, detected_app, participants, template, output_language
              FROM meetings WHERE id = ?1"
         )?;
 
@@ -81,6 +87,9 @@ impl Database {
                         .map(std::path::PathBuf::from),
                     status: parse_status(&row.get::<_, String>(8)?),
                     detected_app: row.get(9)?,
+                    participants: split_participants(row.get(10)?),
+                    template: row.get(11)?,
+                    output_language: row.get(12)?,
                 })
             })
             .optional()?;
@@ -92,7 +101,8 @@ impl Database {
         self.conn.execute(
             "UPDATE meetings SET 
                 title = ?2, ended_at = ?3, duration_seconds = ?4, audio_path = ?5,
-                transcript_path = ?6, notes_path = ?7, status = ?8, detected_app = ?9
+                transcript_path = ?6, notes_path = ?7, status = ?8, detected_app = ?9,
+                participants = ?10, template = ?11, output_language = ?12
              WHERE id = ?1",
             params![
                 meeting.id.0,
@@ -113,26 +123,64 @@ impl Database {
                     .map(|p| p.to_string_lossy().to_string()),
                 meeting.status.to_string(),
                 meeting.detected_app,
+                join_participants(&meeting.participants),
+                meeting.template,
+                meeting.output_language,
             ],
         )?;
         Ok(())
     }
 
-    #[allow(dead_code)]
+    /// Delete a meeting with its transcript, summary, action items and parts,
+    /// all or nothing. Foreign keys aren't enforced, so nothing cascades on its own.
     pub fn delete_meeting(&self, id: &MeetingId) -> Result<()> {
-        self.conn
-            .execute("DELETE FROM meetings WHERE id = ?1", [&id.0])?;
+        let tx = self.conn.unchecked_transaction()?;
+        for table in ["transcripts", "summaries", "action_items", "meeting_parts"] {
+            tx.execute(
+                &format!("DELETE FROM {} WHERE meeting_id = ?1", table),
+                [&id.0],
+            )?;
+        }
+        tx.execute("DELETE FROM meetings WHERE id = ?1", [&id.0])?;
+        tx.commit()?;
         Ok(())
     }
 
     pub fn list_meetings(&self, limit: usize) -> Result<Vec<Meeting>> {
+        self.list_meetings_filtered(limit, None, None, None)
+    }
+
+    /// List meetings newest first, optionally restricted to a status, to
+    /// meetings started at or after `since`, and/or to meetings whose title or
+    /// summary contains `search` (case-insensitive for ASCII).
+    pub fn list_meetings_filtered(
+        &self,
+        limit: usize,
+        status: Option<MeetingStatus>,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        search: Option<&str>,
+    ) -> Result<Vec<Meeting>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, title, started_at, ended_at, duration_seconds, audio_path, transcript_path, notes_path, status, detected_app
-             FROM meetings ORDER BY started_at DESC LIMIT ?1"
+            "SELECT m.id, m.title, m.started_at, m.ended_at, m.duration_seconds, m.audio_path, m.transcript_path, m.notes_path, m.status, m.detected_app, m.participants, m.template, m.output_language
+             FROM meetings m
+             LEFT JOIN summaries s ON s.meeting_id = m.id
+             WHERE (?2 IS NULL OR m.status = ?2) AND (?3 IS NULL OR m.started_at >= ?3)
+               AND (?4 IS NULL OR m.title LIKE ?4 ESCAPE '\\' OR s.meeting_notes LIKE ?4 ESCAPE '\\')
+             ORDER BY m.started_at DESC LIMIT ?1"
         )?;
 
+        let status = status.map(|s| s.to_string());
+        let since = since.map(|t| t.to_rfc3339());
+        let pattern = search.map(|text| {
+            let escaped = text
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            format!("%{}%", escaped)
+        });
+
         let meetings = stmt
-            .query_map([limit], |row| {
+            .query_map(params![limit, status, since, pattern], |row| {
                 Ok(Meeting {
                     id: MeetingId::from_string(row.get(0)?),
                     title: row.get(1)?,
@@ -155,6 +203,9 @@ impl Database {
                         .map(std::path::PathBuf::from),
                     status: parse_status(&row.get::<_, String>(8)?),
                     detected_app: row.get(9)?,
+                    participants: split_participants(row.get(10)?),
+                    template: row.get(11)?,
+                    output_language: row.get(12)?,
                 })
             })?
             .collect::<std::result::Result<Vec<_>, _>>()?;
@@ -162,14 +213,30 @@ impl Database {
         Ok(meetings)
     }
 
+    pub fn list_meeting_ids(&self) -> Result<Vec<MeetingId>> {
+        let mut stmt = self.conn.prepare("SELECT id FROM meetings")?;
+        let ids = stmt
+            .query_map([], |row| Ok(MeetingId::from_string(row.get(0)?)))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(ids)
+    }
+
+    /// Stores segments, normalizing timestamps so they never run backwards and
+    /// merging consecutive segments labeled with the same speaker closer than
+    /// `merge_gap_ms` (`[transcription] merge_gap_ms` after diarization; 0 for
+    /// raw transcripts, which are stored as they are)
     pub fn insert_transcript_segments(
         &self,
         meeting_id: &MeetingId,
         segments: &[TranscriptSegment],
+        merge_gap_ms: u64,
     ) -> Result<()> {
+        let segments = crate::transcription::normalize_timestamps(segments.to_vec());
+        let segments = crate::transcription::merge_speaker_turns(&segments, merge_gap_ms);
+
         let mut stmt = self.conn.prepare(
-            "INSERT INTO transcripts (meeting_id, segment_index, start_ms, end_ms, text, speaker, confidence)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"
+            "INSERT INTO transcripts (meeting_id, segment_index, start_ms, end_ms, text, speaker, confidence, language)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"
         )?;
 
         for (i, segment) in segments.iter().enumerate() {
@@ -181,6 +248,7 @@ impl Database {
                 segment.text,
                 segment.speaker,
                 segment.confidence,
+                segment.language,
             ])?;
         }
 
@@ -192,7 +260,7 @@ impl Database {
         meeting_id: &MeetingId,
     ) -> Result<Vec<TranscriptSegment>> {
         let mut stmt = self.conn.prepare(
-            "SELECT start_ms, end_ms, text, speaker, confidence
+            "SELECT start_ms, end_ms, text, speaker, confidence, language
              FROM transcripts WHERE meeting_id = ?1 ORDER BY segment_index",
         )?;
 
@@ -204,6 +272,7 @@ impl Database {
                     text: row.get(2)?,
                     speaker: row.get(3)?,
                     confidence: row.get(4)?,
+                    language: row.get(5)?,
                 })
             })?
             .collect::<std::result::Result<Vec<_>, _>>()?;
@@ -211,6 +280,49 @@ impl Database {
         Ok(segments)
     }
 
+    /// Keyword search over transcripts and meeting titles (SQLite FTS5). Returns
+    /// up to `limit` meetings, best match first, as id, title and a snippet of
+    /// the best matching segment with the hits in `[brackets]`.
+    pub fn search_transcripts(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<(MeetingId, String, String)>> {
+        let query = fts_query(query);
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT meeting_id, title, snippet(transcript_fts, 2, '[', ']', '...', 12)
+             FROM transcript_fts WHERE transcript_fts MATCH ?1 ORDER BY rank",
+        )?;
+        let mut rows = stmt.query([query])?;
+
+        let mut results: Vec<(MeetingId, String, String)> = Vec::new();
+        while let Some(row) = rows.next()? {
+            if results.len() >= limit {
+                break;
+            }
+            let id = MeetingId::from_string(row.get(0)?);
+            // One result per meeting: its best-ranked segment
+            if results.iter().any(|(seen, _, _)| *seen == id) {
+                continue;
+            }
+            results.push((id, row.get(1)?, row.get(2)?));
+        }
+        Ok(results)
+    }
+
+    /// Rename a speaker label across a meeting's transcript; returns segments changed
+    pub fn relabel_speaker(&self, meeting_id: &MeetingId, from: &str, to: &str) -> Result<usize> {
+        let changed = self.conn.execute(
+            "UPDATE transcripts SET speaker = ?3 WHERE meeting_id = ?1 AND speaker = ?2",
+            params![meeting_id.0, from, to],
+        )?;
+        Ok(changed)
+    }
+
     pub fn delete_transcript_segments(&self, meeting_id: &MeetingId) -> Result<()> {
         self.conn.execute(
             "DELETE FROM transcripts WHERE meeting_id = ?1",
@@ -219,6 +331,7 @@ impl Database {
         Ok(())
     }
 
+    /// Store a meeting's summary, replacing any previous one
     pub fn insert_summary(
         &self,
         meeting_id: &MeetingId,
@@ -252,6 +365,285 @@ impl Database {
             None => Ok(None),
         }
     }
+
+    pub fn get_cached_chunk_summary(&self, cache_key: &str) -> Result<Option<String>> {
+        let summary = self
+            .conn
+            .query_row(
+                "SELECT summary FROM summary_chunk_cache WHERE cache_key = ?1",
+                [cache_key],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(summary)
+    }
+
+    pub fn insert_cached_chunk_summary(
+        &self,
+        cache_key: &str,
+        model: &str,
+        prompt_version: u32,
+        summary: &str,
+    ) -> Result<()> {
+        let now = chrono::Utc::now().to_rfc3339();
+
+        self.conn.execute(
+            "INSERT OR REPLACE INTO summary_chunk_cache (cache_key, model, prompt_version, summary, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![cache_key, model, prompt_version, summary, &now],
+        )?;
+        Ok(())
+    }
+
+    /// Drops cached chunk summaries from an older chunk prompt, whose keys can
+    /// never match again, and any cached more than `max_age` ago. Entries for
+    /// other models are kept so switching back and forth stays cached.
+    pub fn prune_chunk_summary_cache(
+        &self,
+        prompt_version: u32,
+        max_age: chrono::Duration,
+    ) -> Result<usize> {
+        let cutoff = (chrono::Utc::now() - max_age).to_rfc3339();
+        let removed = self.conn.execute(
+            "DELETE FROM summary_chunk_cache WHERE prompt_version != ?1 OR created_at < ?2",
+            params![prompt_version, &cutoff],
+        )?;
+        Ok(removed)
+    }
+
+    /// Question/answer turns of an `ask --session`, oldest first
+    pub fn get_ask_turns(&self, session: &str) -> Result<Vec<(String, String)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT question, answer FROM ask_sessions WHERE session = ?1 ORDER BY id")?;
+        let turns = stmt
+            .query_map([session], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(turns)
+    }
+
+    pub fn insert_ask_turn(&self, session: &str, question: &str, answer: &str) -> Result<()> {
+        let now = chrono::Utc::now().to_rfc3339();
+
+        self.conn.execute(
+            "INSERT INTO ask_sessions (session, question, answer, asked_at) VALUES (?1, ?2, ?3, ?4)",
+            params![session, question, answer, &now],
+        )?;
+        Ok(())
+    }
+
+    /// Enroll `name`'s voiceprint, replacing any earlier enrollment under that name
+    pub fn upsert_speaker_profile(&self, name: &str, voiceprint: &[f32]) -> Result<()> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let voiceprint = serde_json::to_string(voiceprint)?;
+
+        self.conn.execute(
+            "INSERT INTO speaker_profiles (name, voiceprint, enrolled_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(name) DO UPDATE SET voiceprint = ?2, enrolled_at = ?3",
+            params![name, &voiceprint, &now],
+        )?;
+        Ok(())
+    }
+
+    /// Enrolled speakers as (name, voiceprint, enrolled_at), by name
+    pub fn list_speaker_profiles(&self) -> Result<Vec<(String, Vec<f32>, String)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name, voiceprint, enrolled_at FROM speaker_profiles ORDER BY name")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        rows.into_iter()
+            .map(|(name, voiceprint, enrolled_at)| {
+                Ok((name, serde_json::from_str(&voiceprint)?, enrolled_at))
+            })
+            .collect()
+    }
+
+    /// Record which meetings' recordings `meeting_id` was joined from
+    pub fn insert_meeting_parts(
+        &self,
+        meeting_id: &MeetingId,
+        parts: &[MeetingPart],
+    ) -> Result<()> {
+        let mut stmt = self.conn.prepare(
+            "INSERT OR REPLACE INTO meeting_parts (meeting_id, part_index, source_meeting_id, offset_ms, duration_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+        )?;
+
+        for (i, part) in parts.iter().enumerate() {
+            stmt.execute(params![
+                meeting_id.0,
+                i as i32,
+                part.source_id.0,
+                part.offset_ms as i64,
+                part.duration_ms as i64,
+            ])?;
+        }
+
+        Ok(())
+    }
+
+    /// The recordings a joined meeting was made from, in order (empty for a normal meeting)
+    #[allow(dead_code)]
+    pub fn get_meeting_parts(&self, meeting_id: &MeetingId) -> Result<Vec<MeetingPart>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT source_meeting_id, offset_ms, duration_ms FROM meeting_parts
+             WHERE meeting_id = ?1 ORDER BY part_index",
+        )?;
+        let parts = stmt
+            .query_map([&meeting_id.0], |row| {
+                Ok(MeetingPart {
+                    source_id: MeetingId::from_string(row.get(0)?),
+                    offset_ms: row.get::<_, i64>(1)? as u64,
+                    duration_ms: row.get::<_, i64>(2)? as u64,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(parts)
+    }
+
+    /// Store a meeting's action items, replacing any extracted earlier. Items
+    /// whose text matches one already marked done stay done, so re-extracting
+    /// (`muesli redo`) doesn't reopen them.
+    pub fn insert_action_items(&self, meeting_id: &MeetingId, items: &[ActionItem]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        let done: Vec<String> = tx
+            .prepare("SELECT text FROM action_items WHERE meeting_id = ?1 AND done = 1")?
+            .query_map([&meeting_id.0], |row| row.get::<_, String>(0))?
+            .map(|text| text.map(|t| t.trim().to_lowercase()))
+            .collect::<std::result::Result<_, _>>()?;
+        tx.execute(
+            "DELETE FROM action_items WHERE meeting_id = ?1",
+            [&meeting_id.0],
+        )?;
+
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO action_items (meeting_id, text, owner, due_date, done) VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            for item in items {
+                stmt.execute(params![
+                    meeting_id.0,
+                    item.text,
+                    item.owner,
+                    item.due_date,
+                    item.done || done.contains(&item.text.trim().to_lowercase()),
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Open action items of every meeting as (meeting id, meeting title, item),
+    /// soonest due first and undated ones last
+    pub fn get_open_action_items(&self) -> Result<Vec<(MeetingId, String, ActionItem)>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT a.meeting_id, m.title, a.text, a.owner, a.due_date, a.done
+             FROM action_items a JOIN meetings m ON m.id = a.meeting_id
+             WHERE a.done = 0
+             {}",
+            OPEN_ACTION_ITEMS_ORDER
+        ))?;
+        let items = stmt
+            .query_map([], |row| {
+                Ok((
+                    MeetingId::from_string(row.get(0)?),
+                    row.get(1)?,
+                    ActionItem {
+                        text: row.get(2)?,
+                        owner: row.get(3)?,
+                        due_date: row.get(4)?,
+                        done: row.get(5)?,
+                    },
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(items)
+    }
+
+    /// Mark the `number`th (from 1) item of [`Self::get_open_action_items`]
+    /// done, returning its text, or `None` if there is no such item
+    pub fn complete_open_action_item(&self, number: usize) -> Result<Option<String>> {
+        let Some(offset) = number.checked_sub(1) else {
+            return Ok(None);
+        };
+        let item = self
+            .conn
+            .query_row(
+                &format!(
+                    "SELECT a.id, a.text
+                     FROM action_items a JOIN meetings m ON m.id = a.meeting_id
+                     WHERE a.done = 0
+                     {} LIMIT 1 OFFSET ?1",
+                    OPEN_ACTION_ITEMS_ORDER
+                ),
+                [offset as i64],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()?;
+        let Some((id, text)) = item else {
+            return Ok(None);
+        };
+        self.conn
+            .execute("UPDATE action_items SET done = 1 WHERE id = ?1", [id])?;
+        Ok(Some(text))
+    }
+
+    /// Copy the database to `dest` using SQLite's online backup API, which is
+    /// safe while another connection (e.g. the daemon) is writing.
+    pub fn backup_to<P: AsRef<Path>>(&self, dest: P) -> Result<()> {
+        let mut dest = Connection::open(dest)?;
+        let backup = rusqlite::backup::Backup::new(&self.conn, &mut dest)?;
+        backup.run_to_completion(64, std::time::Duration::from_millis(50), None)?;
+        Ok(())
+    }
+
+    /// Replace the contents of this database with `src`, then bring the schema
+    /// up to date in case the backup predates newer migrations.
+    pub fn restore_from<P: AsRef<Path>>(&mut self, src: P) -> Result<()> {
+        let src = Connection::open_with_flags(src, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+
+        let is_muesli_db: bool = src
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'meetings'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(false);
+        if !is_muesli_db {
+            return Err(crate::error::MuesliError::Storage(
+                "Not a muesli database backup".to_string(),
+            ));
+        }
+
+        {
+            let backup = rusqlite::backup::Backup::new(&src, &mut self.conn)?;
+            backup.run_to_completion(64, std::time::Duration::from_millis(50), None)?;
+        }
+
+        migrations::run_migrations(&self.conn)?;
+        Ok(())
+    }
+}
+
+/// Participants are stored one per line; NULL when there are none
+fn join_participants(participants: &[String]) -> Option<String> {
+    (!participants.is_empty()).then(|| participants.join("\n"))
+}
+
+fn split_participants(stored: Option<String>) -> Vec<String> {
+    stored
+        .map(|s| s.lines().map(str::to_string).collect())
+        .unwrap_or_default()
 }
 
 fn parse_status(s: &str) -> MeetingStatus {
@@ -278,6 +670,16 @@ impl<T> OptionalExt<T> for std::result::Result<T, rusqlite::Error> {
     }
 }
 
+/// `query` as FTS5 terms that must all match. Each word is quoted, so
+/// punctuation and operators in what the user typed are searched as text.
+fn fts_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -306,9 +708,55 @@ mod tests {
 
         let reloaded = db.get_meeting(&id).unwrap().unwrap();
         assert_eq!(reloaded.title, "Updated Title");
+        assert!(reloaded.participants.is_empty());
+
+        let mut with_people = reloaded;
+        with_people.participants = vec!["Alice".to_string(), "Bob".to_string()];
+        db.update_meeting(&with_people).unwrap();
+        assert_eq!(
+            db.get_meeting(&id).unwrap().unwrap().participants,
+            vec!["Alice", "Bob"]
+        );
+
+        let mut with_template = db.get_meeting(&id).unwrap().unwrap();
+        assert!(with_template.template.is_none());
+        with_template.template = Some("standup".to_string());
+        db.update_meeting(&with_template).unwrap();
+        assert_eq!(
+            db.get_meeting(&id).unwrap().unwrap().template.as_deref(),
+            Some("standup")
+        );
+
+        let mut with_language = db.get_meeting(&id).unwrap().unwrap();
+        assert!(with_language.output_language.is_none());
+        with_language.output_language = Some("German".to_string());
+        db.update_meeting(&with_language).unwrap();
+        assert_eq!(
+            db.get_meeting(&id)
+                .unwrap()
+                .unwrap()
+                .output_language
+                .as_deref(),
+            Some("German")
+        );
 
         let meetings = db.list_meetings(10).unwrap();
         assert_eq!(meetings.len(), 1);
+        assert_eq!(db.list_meeting_ids().unwrap(), vec![id.clone()]);
+
+        let recording = db
+            .list_meetings_filtered(10, Some(MeetingStatus::Recording), None, None)
+            .unwrap();
+        assert_eq!(recording.len(), 1);
+        let complete = db
+            .list_meetings_filtered(10, Some(MeetingStatus::Complete), None, None)
+            .unwrap();
+        assert!(complete.is_empty());
+        let future = chrono::Utc::now() + chrono::Duration::days(1);
+        assert!(db
+            .list_meetings_filtered(10, None, Some(future), None)
+            .unwrap()
+            .is_empty());
 
         db.delete_meeting(&id).unwrap();
         let deleted = db.get_meeting(&id).unwrap();
@@ -327,12 +775,363 @@ mod tests {
             TranscriptSegment::new(5000, 10000, "World".to_string()),
         ];
 
-        db.insert_transcript_segments(&meeting.id, &segments)
+        db.insert_transcript_segments(&meeting.id, &segments, 0)
             .unwrap();
 
         let loaded = db.get_transcript_segments(&meeting.id).unwrap();
         assert_eq!(loaded.len(), 2);
         assert_eq!(loaded[0].text, "Hello");
         assert_eq!(loaded[1].text, "World");
+        assert!(loaded[0].language.is_none());
+
+        // Merging leaves unlabeled segments alone and joins same-speaker ones
+        db.delete_transcript_segments(&meeting.id).unwrap();
+        db.insert_transcript_segments(&meeting.id, &segments, 800)
+            .unwrap();
+        assert_eq!(db.get_transcript_segments(&meeting.id).unwrap().len(), 2);
+
+        let labeled: Vec<TranscriptSegment> = segments
+            .iter()
+            .cloned()
+            .map(|mut s| {
+                s.speaker = Some("SPEAKER_1".to_string());
+                s
+            })
+            .collect();
+        db.delete_transcript_segments(&meeting.id).unwrap();
+        db.insert_transcript_segments(&meeting.id, &labeled, 800)
+            .unwrap();
+        let loaded = db.get_transcript_segments(&meeting.id).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].text, "Hello World");
+        assert_eq!((loaded[0].start_ms, loaded[0].end_ms), (0, 10000));
+    }
+
+    #[test]
+    fn test_summary_crud() {
+        let db = Database::open_in_memory().unwrap();
+        let first = Meeting::new("First".to_string());
+        let second = Meeting::new("Second".to_string());
+        db.insert_meeting(&first).unwrap();
+        db.insert_meeting(&second).unwrap();
+
+        let summary = |markdown: &str| crate::llm::SummaryResult {
+            markdown: markdown.to_string(),
+        };
+        assert!(db.get_summary(&first.id).unwrap().is_none());
+
+        db.insert_summary(&first.id, &summary("v1")).unwrap();
+        db.insert_summary(&first.id, &summary("v2")).unwrap();
+        db.insert_summary(&second.id, &summary("other")).unwrap();
+        assert_eq!(db.get_summary(&first.id).unwrap().unwrap().markdown, "v2");
+
+        let search = |text: &str| {
+            db.list_meetings_filtered(10, None, None, Some(text))
+                .unwrap()
+        };
+        assert_eq!(search("SECOND").len(), 1);
+        assert_eq!(search("v2")[0].id, first.id);
+        assert_eq!(search("r").len(), 2);
+        assert!(search("%").is_empty());
+        assert!(search("nowhere").is_empty());
+
+        db.delete_meeting(&first.id).unwrap();
+        assert!(db.get_summary(&first.id).unwrap().is_none());
+        assert!(db.get_summary(&second.id).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_relabel_speaker() {
+        let db = Database::open_in_memory().unwrap();
+        let meeting = Meeting::new("Test".to_string());
+        db.insert_meeting(&meeting).unwrap();
+
+        let mut segments = vec![
+            TranscriptSegment::new(0, 1000, "a".to_string()),
+            TranscriptSegment::new(1000, 2000, "b".to_string()),
+            TranscriptSegment::new(2000, 3000, "c".to_string()),
+        ];
+        segments[0].speaker = Some("SPEAKER_1".to_string());
+        segments[1].speaker = Some("SPEAKER_2".to_string());
+        segments[2].speaker = Some("SPEAKER_2".to_string());
+        db.insert_transcript_segments(&meeting.id, &segments, 0)
+            .unwrap();
+
+        assert_eq!(
+            db.relabel_speaker(&meeting.id, "SPEAKER_2", "SPEAKER_1")
+                .unwrap(),
+            2
+        );
+        let loaded = db.get_transcript_segments(&meeting.id).unwrap();
+        assert!(loaded
+            .iter()
+            .all(|s| s.speaker.as_deref() == Some("SPEAKER_1")));
+        assert_eq!(
+            db.relabel_speaker(&meeting.id, "SPEAKER_2", "SPEAKER_1")
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_transcript_segment_language_roundtrip() {
+        let db = Database::open_in_memory().unwrap();
+
+        let meeting = Meeting::new("Test".to_string());
+        db.insert_meeting(&meeting).unwrap();
+
+        let mut segment = TranscriptSegment::new(0, 5000, "Bonjour".to_string());
+        segment.language = Some("fr".to_string());
+        db.insert_transcript_segments(&meeting.id, &[segment], 0)
+            .unwrap();
+
+        let loaded = db.get_transcript_segments(&meeting.id).unwrap();
+        assert_eq!(loaded[0].language.as_deref(), Some("fr"));
+    }
+
+    #[test]
+    fn test_chunk_summary_cache() {
+        let db = Database::open_in_memory().unwrap();
+
+        assert!(db.get_cached_chunk_summary("abc").unwrap().is_none());
+
+        db.insert_cached_chunk_summary("abc", "model-a", 1, "notes")
+            .unwrap();
+        assert_eq!(
+            db.get_cached_chunk_summary("abc").unwrap().as_deref(),
+            Some("notes")
+        );
+
+        db.insert_cached_chunk_summary("def", "model-b", 1, "other model")
+            .unwrap();
+        let month = chrono::Duration::days(30);
+        assert_eq!(db.prune_chunk_summary_cache(1, month).unwrap(), 0);
+        assert!(db.get_cached_chunk_summary("def").unwrap().is_some());
+
+        db.insert_cached_chunk_summary("old", "model-a", 0, "old prompt")
+            .unwrap();
+        assert_eq!(db.prune_chunk_summary_cache(1, month).unwrap(), 1);
+        assert!(db.get_cached_chunk_summary("old").unwrap().is_none());
+
+        assert_eq!(
+            db.prune_chunk_summary_cache(1, chrono::Duration::seconds(-1))
+                .unwrap(),
+            2
+        );
+        assert!(db.get_cached_chunk_summary("abc").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_ask_turns() {
+        let db = Database::open_in_memory().unwrap();
+        assert!(db.get_ask_turns("roadmap").unwrap().is_empty());
+
+        db.insert_ask_turn("roadmap", "When is the launch?", "March")
+            .unwrap();
+        db.insert_ask_turn("other", "Who owns QA?", "Dana").unwrap();
+        db.insert_ask_turn("roadmap", "Who decided?", "Bo").unwrap();
+
+        let turns = db.get_ask_turns("roadmap").unwrap();
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].0, "When is the launch?");
+        assert_eq!(turns[1].1, "Bo");
+    }
+
+    #[test]
+    fn test_speaker_profiles() {
+        let db = Database::open_in_memory().unwrap();
+        assert!(db.list_speaker_profiles().unwrap().is_empty());
+
+        db.upsert_speaker_profile("Bo", &[0.5, 0.25]).unwrap();
+        db.upsert_speaker_profile("Andrea", &[1.0]).unwrap();
+        db.upsert_speaker_profile("Bo", &[0.75]).unwrap();
+
+        let profiles = db.list_speaker_profiles().unwrap();
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles[0].0, "Andrea");
+        assert_eq!(profiles[1].1, vec![0.75]);
+    }
+
+    #[test]
+    fn test_meeting_parts() {
+        let db = Database::open_in_memory().unwrap();
+        let joined = Meeting::new("Joined".to_string());
+        db.insert_meeting(&joined).unwrap();
+        assert!(db.get_meeting_parts(&joined.id).unwrap().is_empty());
+
+        let parts = vec![
+            MeetingPart {
+                source_id: MeetingId::from_string("a".to_string()),
+                offset_ms: 0,
+                duration_ms: 1500,
+            },
+            MeetingPart {
+                source_id: MeetingId::from_string("b".to_string()),
+                offset_ms: 1500,
+                duration_ms: 700,
+            },
+        ];
+        db.insert_meeting_parts(&joined.id, &parts).unwrap();
+        assert_eq!(db.get_meeting_parts(&joined.id).unwrap(), parts);
+    }
+
+    #[test]
+    fn test_search_transcripts() {
+        let db = Database::open_in_memory().unwrap();
+        let pricing = Meeting::new("Pricing review".to_string());
+        let standup = Meeting::new("Standup".to_string());
+        db.insert_meeting(&pricing).unwrap();
+        db.insert_meeting(&standup).unwrap();
+        db.insert_transcript_segments(
+            &pricing.id,
+            &[
+                TranscriptSegment::new(0, 1000, "Let's raise the enterprise tier".to_string()),
+                TranscriptSegment::new(1000, 2000, "Agreed, starting in March".to_string()),
+            ],
+            0,
+        )
+        .unwrap();
+        db.insert_transcript_segments(
+            &standup.id,
+            &[TranscriptSegment::new(
+                0,
+                1000,
+                "The deploy is blocked".to_string(),
+            )],
+            0,
+        )
+        .unwrap();
+
+        let results = db.search_transcripts("enterprise", 5).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, pricing.id);
+        assert_eq!(results[0].1, "Pricing review");
+        assert!(results[0].2.contains("[enterprise]"));
+
+        // Titles match too, and punctuation in the query is just text
+        assert_eq!(
+            db.search_transcripts("standup", 5).unwrap()[0].0,
+            standup.id
+        );
+        assert_eq!(
+            db.search_transcripts("blocked\"", 5).unwrap()[0].0,
+            standup.id
+        );
+        assert!(db.search_transcripts("roadmap", 5).unwrap().is_empty());
+        assert!(db.search_transcripts("  ", 5).unwrap().is_empty());
+
+        let mut renamed = pricing.clone();
+        renamed.title = "Q3 pricing".to_string();
+        db.update_meeting(&renamed).unwrap();
+        assert_eq!(db.search_transcripts("Q3", 5).unwrap()[0].1, "Q3 pricing");
+
+        db.delete_meeting(&standup.id).unwrap();
+        assert!(db.search_transcripts("deploy", 5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_action_items() {
+        let db = Database::open_in_memory().unwrap();
+        let planning = Meeting::new("Planning".to_string());
+        let retro = Meeting::new("Retro".to_string());
+        db.insert_meeting(&planning).unwrap();
+        db.insert_meeting(&retro).unwrap();
+
+        let item = |text: &str, due_date: Option<&str>, done: bool| ActionItem {
+            text: text.to_string(),
+            owner: Some("Bo".to_string()),
+            due_date: due_date.map(str::to_string),
+            done,
+        };
+        db.insert_action_items(
+            &planning.id,
+            &[
+                item("Draft the spec", None, false),
+                item("Book the room", Some("2024-06-03"), true),
+            ],
+        )
+        .unwrap();
+        db.insert_action_items(&retro.id, &[item("Fix CI", Some("2024-05-20"), false)])
+            .unwrap();
+
+        let open = db.get_open_action_items().unwrap();
+        let texts: Vec<_> = open.iter().map(|(_, _, item)| item.text.as_str()).collect();
+        assert_eq!(texts, vec!["Fix CI", "Draft the spec"]);
+        assert_eq!(open[0].1, "Retro");
+        assert_eq!(open[1].0, planning.id);
+
+        // Extracting again replaces the meeting's items
+        db.insert_action_items(&planning.id, &[]).unwrap();
+        assert_eq!(db.get_open_action_items().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_complete_action_item_survives_reextraction() {
+        let db = Database::open_in_memory().unwrap();
+        let meeting = Meeting::new("Planning".to_string());
+        db.insert_meeting(&meeting).unwrap();
+        let item = |text: &str| ActionItem {
+            text: text.to_string(),
+            owner: None,
+            due_date: None,
+            done: false,
+        };
+        db.insert_action_items(
+            &meeting.id,
+            &[item("Draft the spec"), item("Book the room")],
+        )
+        .unwrap();
+
+        assert_eq!(db.complete_open_action_item(0).unwrap(), None);
+        assert_eq!(db.complete_open_action_item(3).unwrap(), None);
+        assert_eq!(
+            db.complete_open_action_item(2).unwrap().as_deref(),
+            Some("Book the room")
+        );
+        let open = db.get_open_action_items().unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].2.text, "Draft the spec");
+
+        // `redo` extracts the items again; the finished one stays done
+        db.insert_action_items(
+            &meeting.id,
+            &[item("Draft the spec"), item(" book the room")],
+        )
+        .unwrap();
+        assert_eq!(db.get_open_action_items().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_backup_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let backup_path = dir.path().join("backup.db");
+
+        let db = Database::open_in_memory().unwrap();
+        let meeting = Meeting::new("Backed Up".to_string());
+        db.insert_meeting(&meeting).unwrap();
+        db.backup_to(&backup_path).unwrap();
+
+        let mut restored = Database::open_in_memory().unwrap();
+        restored
+            .insert_meeting(&Meeting::new("Replaced".to_string()))
+            .unwrap();
+        restored.restore_from(&backup_path).unwrap();
+
+        let meetings = restored.list_meetings(10).unwrap();
+        assert_eq!(meetings.len(), 1);
+        assert_eq!(meetings[0].title, "Backed Up");
+    }
+
+    #[test]
+    fn test_restore_rejects_foreign_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let other = dir.path().join("other.db");
+        Connection::open(&other)
+            .unwrap()
+            .execute_batch("CREATE TABLE foo (id INTEGER);")
+            .unwrap();
+
+        let mut db = Database::open_in_memory().unwrap();
+        assert!(db.restore_from(&other).is_err());
     }
 }
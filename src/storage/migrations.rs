@@ -3,7 +3,7 @@
 use crate::error::Result;
 use rusqlite::Connection;
 
-pub const SCHEMA_VERSION: i32 = 3;
+pub const SCHEMA_VERSION: i32 = 14;
 
 pub fn run_migrations(conn: &Connection) -> Result<()> {
     let version = get_schema_version(conn)?;
@@ -17,6 +17,39 @@ pub fn run_migrations(conn: &Connection) -> Result<()> {
     if version < 3 {
         migrate_v3(conn)?;
     }
+    if version < 4 {
+        migrate_v4(conn)?;
+    }
+    if version < 5 {
+        migrate_v5(conn)?;
+    }
+    if version < 6 {
+        migrate_v6(conn)?;
+    }
+    if version < 7 {
+        migrate_v7(conn)?;
+    }
+    if version < 8 {
+        migrate_v8(conn)?;
+    }
+    if version < 9 {
+        migrate_v9(conn)?;
+    }
+    if version < 10 {
+        migrate_v10(conn)?;
+    }
+    if version < 11 {
+        migrate_v11(conn)?;
+    }
+    if version < 12 {
+        migrate_v12(conn)?;
+    }
+    if version < 13 {
+        migrate_v13(conn)?;
+    }
+    if version < 14 {
+        migrate_v14(conn)?;
+    }
 
     Ok(())
 }
@@ -82,19 +115,21 @@ fn migrate_v1(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// Current shape of the `summaries` table, created by v2 and again by v6 for
+/// databases that lost it
+const SUMMARIES_DDL: &str = "
+    CREATE TABLE IF NOT EXISTS summaries (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        meeting_id TEXT NOT NULL UNIQUE REFERENCES meetings(id) ON DELETE CASCADE,
+        meeting_notes TEXT NOT NULL,
+        generated_at TEXT NOT NULL
+    );
+
+    CREATE INDEX IF NOT EXISTS idx_summaries_meeting ON summaries(meeting_id);
+";
+
 fn migrate_v2(conn: &Connection) -> Result<()> {
-    conn.execute_batch(
-        "
-        CREATE TABLE IF NOT EXISTS summaries (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            meeting_id TEXT NOT NULL UNIQUE REFERENCES meetings(id) ON DELETE CASCADE,
-            meeting_notes TEXT NOT NULL,
-            generated_at TEXT NOT NULL
-        );
-        
-        CREATE INDEX IF NOT EXISTS idx_summaries_meeting ON summaries(meeting_id);
-        ",
-    )?;
+    conn.execute_batch(SUMMARIES_DDL)?;
 
     set_schema_version(conn, 2)?;
     Ok(())
@@ -140,3 +175,216 @@ fn migrate_v3(conn: &Connection) -> Result<()> {
     set_schema_version(conn, 3)?;
     Ok(())
 }
+
+fn migrate_v4(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS summary_chunk_cache (
+            cache_key TEXT PRIMARY KEY,
+            model TEXT NOT NULL,
+            prompt_version INTEGER NOT NULL,
+            summary TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+        ",
+    )?;
+
+    set_schema_version(conn, 4)?;
+    Ok(())
+}
+
+fn migrate_v5(conn: &Connection) -> Result<()> {
+    let has_language: bool = conn
+        .query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('transcripts') WHERE name = 'language'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap_or(false);
+
+    if !has_language {
+        conn.execute_batch("ALTER TABLE transcripts ADD COLUMN language TEXT;")?;
+    }
+
+    set_schema_version(conn, 5)?;
+    Ok(())
+}
+
+/// Databases restored or copied from partial installs can carry a schema
+/// version without the summaries table; recreate it if it is missing.
+fn migrate_v6(conn: &Connection) -> Result<()> {
+    conn.execute_batch(SUMMARIES_DDL)?;
+
+    set_schema_version(conn, 6)?;
+    Ok(())
+}
+
+fn migrate_v7(conn: &Connection) -> Result<()> {
+    let has_participants: bool = conn
+        .query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('meetings') WHERE name = 'participants'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap_or(false);
+
+    if !has_participants {
+        conn.execute_batch("ALTER TABLE meetings ADD COLUMN participants TEXT;")?;
+    }
+
+    set_schema_version(conn, 7)?;
+    Ok(())
+}
+
+fn migrate_v8(conn: &Connection) -> Result<()> {
+    let has_template: bool = conn
+        .query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('meetings') WHERE name = 'template'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap_or(false);
+
+    if !has_template {
+        conn.execute_batch("ALTER TABLE meetings ADD COLUMN template TEXT;")?;
+    }
+
+    set_schema_version(conn, 8)?;
+    Ok(())
+}
+
+fn migrate_v9(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS ask_sessions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session TEXT NOT NULL,
+            question TEXT NOT NULL,
+            answer TEXT NOT NULL,
+            asked_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_ask_sessions_session ON ask_sessions(session);
+        ",
+    )?;
+
+    set_schema_version(conn, 9)?;
+    Ok(())
+}
+
+fn migrate_v10(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS speaker_profiles (
+            name TEXT PRIMARY KEY,
+            voiceprint TEXT NOT NULL,
+            enrolled_at TEXT NOT NULL
+        );
+        ",
+    )?;
+
+    set_schema_version(conn, 10)?;
+    Ok(())
+}
+
+fn migrate_v11(conn: &Connection) -> Result<()> {
+    let has_output_language: bool = conn
+        .query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('meetings') WHERE name = 'output_language'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap_or(false);
+
+    if !has_output_language {
+        conn.execute_batch("ALTER TABLE meetings ADD COLUMN output_language TEXT;")?;
+    }
+
+    set_schema_version(conn, 11)?;
+    Ok(())
+}
+
+fn migrate_v12(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS meeting_parts (
+            meeting_id TEXT NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+            part_index INTEGER NOT NULL,
+            source_meeting_id TEXT NOT NULL,
+            offset_ms INTEGER NOT NULL,
+            duration_ms INTEGER NOT NULL,
+            PRIMARY KEY (meeting_id, part_index)
+        );
+        ",
+    )?;
+
+    set_schema_version(conn, 12)?;
+    Ok(())
+}
+
+/// Full-text index over transcript segments, each row carrying its meeting's
+/// title so a search matches either. Rows share the segment's rowid; triggers
+/// keep the index in step with the transcripts and meeting titles.
+fn migrate_v13(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "
+        CREATE VIRTUAL TABLE IF NOT EXISTS transcript_fts USING fts5(
+            meeting_id UNINDEXED,
+            title,
+            text
+        );
+
+        DELETE FROM transcript_fts;
+        INSERT INTO transcript_fts (rowid, meeting_id, title, text)
+            SELECT t.id, t.meeting_id, m.title, t.text
+            FROM transcripts t JOIN meetings m ON m.id = t.meeting_id;
+
+        CREATE TRIGGER IF NOT EXISTS transcripts_fts_insert AFTER INSERT ON transcripts BEGIN
+            INSERT INTO transcript_fts (rowid, meeting_id, title, text)
+                VALUES (new.id, new.meeting_id,
+                        (SELECT title FROM meetings WHERE id = new.meeting_id), new.text);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS transcripts_fts_delete AFTER DELETE ON transcripts BEGIN
+            DELETE FROM transcript_fts WHERE rowid = old.id;
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS transcripts_fts_update AFTER UPDATE OF text ON transcripts BEGIN
+            UPDATE transcript_fts SET text = new.text WHERE rowid = old.id;
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS meetings_fts_title AFTER UPDATE OF title ON meetings
+        WHEN new.title IS NOT old.title BEGIN
+            UPDATE transcript_fts SET title = new.title WHERE meeting_id = new.id;
+        END;
+
+        -- Foreign keys are not enforced, so a deleted meeting's segments may linger
+        CREATE TRIGGER IF NOT EXISTS meetings_fts_delete AFTER DELETE ON meetings BEGIN
+            DELETE FROM transcript_fts WHERE meeting_id = old.id;
+        END;
+        ",
+    )?;
+
+    set_schema_version(conn, 13)?;
+    Ok(())
+}
+
+fn migrate_v14(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS action_items (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            meeting_id TEXT NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+            text TEXT NOT NULL,
+            owner TEXT,
+            due_date TEXT,
+            done INTEGER NOT NULL DEFAULT 0
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_action_items_meeting ON action_items(meeting_id);
+        ",
+    )?;
+
+    set_schema_version(conn, 14)?;
+    Ok(())
+}
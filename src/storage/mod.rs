@@ -52,6 +52,23 @@ impl std::fmt::Display for MeetingStatus {
     }
 }
 
+impl std::str::FromStr for MeetingStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "recording" => Ok(MeetingStatus::Recording),
+            "processing" => Ok(MeetingStatus::Processing),
+            "complete" => Ok(MeetingStatus::Complete),
+            "failed" => Ok(MeetingStatus::Failed),
+            other => Err(format!(
+                "unknown status '{}' (expected recording, processing, complete or failed)",
+                other
+            )),
+        }
+    }
+}
+
 /// A recorded meeting
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Meeting {
@@ -65,6 +82,38 @@ pub struct Meeting {
     pub notes_path: Option<std::path::PathBuf>,
     pub status: MeetingStatus,
     pub detected_app: Option<String>,
+    /// Attendees, e.g. from the calendar event the meeting was started from
+    #[serde(default)]
+    pub participants: Vec<String>,
+    /// Summary template the notes are generated with (None = the general layout)
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Language the notes are written in (None = `llm.output_language`)
+    #[serde(default)]
+    pub output_language: Option<String>,
+}
+
+/// One source recording of a meeting joined with `muesli audio concat`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MeetingPart {
+    pub source_id: MeetingId,
+    /// Where the part starts in the joined recording
+    pub offset_ms: u64,
+    pub duration_ms: u64,
+}
+
+/// A task from a meeting's notes, as extracted by the LLM
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionItem {
+    #[serde(alias = "task")]
+    pub text: String,
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// YYYY-MM-DD when the meeting set a date
+    #[serde(default, alias = "due")]
+    pub due_date: Option<String>,
+    #[serde(default)]
+    pub done: bool,
 }
 
 impl Meeting {
@@ -80,6 +129,9 @@ impl Meeting {
             notes_path: None,
             status: MeetingStatus::Recording,
             detected_app: None,
+            participants: Vec::new(),
+            template: None,
+            output_language: None,
         }
     }
 }
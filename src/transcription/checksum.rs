@@ -0,0 +1,217 @@
+use crate::error::{MuesliError, Result};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Result of checking a downloaded model file against its recorded checksum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    /// Hash matches the checksum recorded at download time
+    Ok,
+    /// File is not on disk
+    Missing,
+    /// File exists but no checksum was recorded (downloaded by an older version)
+    Unrecorded,
+    /// File is smaller than it should be (an interrupted download)
+    Truncated { expected: u64, actual: u64 },
+    /// Some files of a multi-file model are not on disk
+    Incomplete { missing: Vec<String> },
+    /// File does not match its recorded checksum (truncated or corrupted)
+    Mismatch { expected: String, actual: String },
+}
+
+/// Path of the `<file>.sha256` sidecar written next to a downloaded model
+pub fn sidecar_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".sha256");
+    path.with_file_name(name)
+}
+
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    Ok(hex(&hasher.finalize()))
+}
+
+pub fn sha256_bytes(data: &[u8]) -> String {
+    hex(&Sha256::digest(data))
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Record a downloaded file's hash and size in its sidecar (`<sha256> <bytes>`)
+pub fn write_checksum(path: &Path, sha256: &str, size: u64) -> Result<()> {
+    fs::write(sidecar_path(path), format!("{} {}\n", sha256, size))?;
+    Ok(())
+}
+
+pub fn read_checksum(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(sidecar_path(path)).ok()?;
+    parse_sha256(content.split_whitespace().next()?)
+}
+
+/// Size recorded at download time (sidecars written by older versions have none)
+pub fn read_recorded_size(path: &Path) -> Option<u64> {
+    let content = fs::read_to_string(sidecar_path(path)).ok()?;
+    content.split_whitespace().nth(1)?.parse().ok()
+}
+
+pub fn remove_checksum(path: &Path) -> Result<()> {
+    let sidecar = sidecar_path(path);
+    if sidecar.exists() {
+        fs::remove_file(sidecar)?;
+    }
+    Ok(())
+}
+
+/// Normalize a SHA-256 hex digest, e.g. from a quoted ETag header
+pub fn parse_sha256(value: &str) -> Option<String> {
+    let value = value.trim().trim_start_matches("W/").trim_matches('"');
+    if value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(value.to_ascii_lowercase())
+    } else {
+        None
+    }
+}
+
+/// SHA-256 published by Hugging Face for LFS files (`X-Linked-Etag`, or the
+/// CDN's `ETag` once the redirect has been followed)
+pub fn expected_sha256(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    ["x-linked-etag", "etag"]
+        .iter()
+        .filter_map(|name| headers.get(*name)?.to_str().ok())
+        .find_map(parse_sha256)
+}
+
+pub fn verify_file(path: &Path) -> Result<Verification> {
+    if !path.exists() {
+        return Ok(Verification::Missing);
+    }
+    // Cheaper than hashing, and names the problem when a download was cut short
+    if let Some(expected) = read_recorded_size(path) {
+        let actual = fs::metadata(path)?.len();
+        if actual < expected {
+            return Ok(Verification::Truncated { expected, actual });
+        }
+    }
+    let Some(expected) = read_checksum(path) else {
+        return Ok(Verification::Unrecorded);
+    };
+    let actual = sha256_file(path)?;
+    if actual == expected {
+        Ok(Verification::Ok)
+    } else {
+        Ok(Verification::Mismatch { expected, actual })
+    }
+}
+
+/// Check a finished download before it replaces the model file: the byte count
+/// must match `Content-Length` and the hash must match the published checksum.
+pub fn check_download(
+    downloaded: u64,
+    content_length: Option<u64>,
+    expected: Option<&str>,
+    actual: &str,
+) -> Result<()> {
+    if let Some(total) = content_length {
+        if downloaded != total {
+            return Err(MuesliError::Api(format!(
+                "Download incomplete: received {} of {} bytes",
+                downloaded, total
+            )));
+        }
+    }
+    if let Some(expected) = expected {
+        if expected != actual {
+            return Err(MuesliError::Api(format!(
+                "Checksum mismatch: expected {}, got {}",
+                expected, actual
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sha256() {
+        let digest = "A".repeat(64);
+        assert_eq!(
+            parse_sha256(&format!("\"{}\"", digest)),
+            Some("a".repeat(64))
+        );
+        assert_eq!(parse_sha256("\"abc123\""), None);
+        assert_eq!(parse_sha256(&"z".repeat(64)), None);
+    }
+
+    #[test]
+    fn test_sha256_bytes() {
+        assert_eq!(
+            sha256_bytes(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_sidecar_roundtrip_and_verify() {
+        let dir = std::env::temp_dir().join(format!("muesli_checksum_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("model.onnx");
+        fs::write(&path, b"hello").unwrap();
+
+        assert_eq!(verify_file(&path).unwrap(), Verification::Unrecorded);
+
+        let digest = sha256_file(&path).unwrap();
+        assert_eq!(
+            digest,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        write_checksum(&path, &digest, 5).unwrap();
+        assert_eq!(read_recorded_size(&path), Some(5));
+        assert_eq!(verify_file(&path).unwrap(), Verification::Ok);
+
+        fs::write(&path, b"hell").unwrap();
+        assert_eq!(
+            verify_file(&path).unwrap(),
+            Verification::Truncated {
+                expected: 5,
+                actual: 4
+            }
+        );
+        fs::write(&path, b"hullo").unwrap();
+        assert!(matches!(
+            verify_file(&path).unwrap(),
+            Verification::Mismatch { .. }
+        ));
+
+        // Sidecars from older versions hold only the hash
+        fs::write(sidecar_path(&path), format!("{}\n", digest)).unwrap();
+        assert_eq!(read_recorded_size(&path), None);
+        assert_eq!(read_checksum(&path), Some(digest));
+
+        fs::remove_dir_all(&dir).ok();
+        assert_eq!(verify_file(&path).unwrap(), Verification::Missing);
+    }
+
+    #[test]
+    fn test_check_download() {
+        assert!(check_download(10, Some(10), Some("ab"), "ab").is_ok());
+        assert!(check_download(9, Some(10), None, "ab").is_err());
+        assert!(check_download(10, None, Some("ab"), "cd").is_err());
+        assert!(check_download(10, None, None, "cd").is_ok());
+    }
+}
@@ -33,6 +33,9 @@ struct DeepgramWord {
     start: f64,
     end: f64,
     confidence: f64,
+    /// Present when multilingual transcription (`language=multi`) is enabled
+    #[serde(default)]
+    language: Option<String>,
 }
 
 /// Transcribe audio file via Deepgram API
@@ -85,6 +88,7 @@ pub async fn transcribe_bytes(api_key: &str, audio_data: &[u8]) -> Result<Transc
             if let Some(words) = &alt.words {
                 // Group words into segments (by pauses or sentence boundaries)
                 let mut current_segment = Vec::new();
+                let mut current_languages: Vec<&str> = Vec::new();
                 let mut segment_start: Option<f64> = None;
                 let mut last_end: f64 = 0.0;
 
@@ -102,12 +106,17 @@ pub async fn transcribe_bytes(api_key: &str, audio_data: &[u8]) -> Result<Transc
                             text,
                             speaker: None,
                             confidence: None,
+                            language: dominant_language(&current_languages),
                         });
                         current_segment.clear();
+                        current_languages.clear();
                         segment_start = Some(word.start);
                     }
 
                     current_segment.push(word.word.clone());
+                    if let Some(lang) = &word.language {
+                        current_languages.push(lang);
+                    }
                     last_end = word.end;
                 }
 
@@ -119,6 +128,7 @@ pub async fn transcribe_bytes(api_key: &str, audio_data: &[u8]) -> Result<Transc
                         text: current_segment.join(" "),
                         speaker: None,
                         confidence: None,
+                        language: dominant_language(&current_languages),
                     });
                 }
             }
@@ -128,10 +138,43 @@ pub async fn transcribe_bytes(api_key: &str, audio_data: &[u8]) -> Result<Transc
     Ok(Transcript::new(segments))
 }
 
+/// Most frequent word language within a segment
+fn dominant_language(languages: &[&str]) -> Option<String> {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for lang in languages {
+        match counts.iter_mut().find(|(l, _)| l == lang) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((lang, 1)),
+        }
+    }
+    // First-seen language wins ties
+    let mut best: Option<(&str, usize)> = None;
+    for (lang, n) in counts {
+        match best {
+            Some((_, best_n)) if best_n >= n => {}
+            _ => best = Some((lang, n)),
+        }
+    }
+    best.map(|(lang, _)| lang.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
     fn test_deepgram_url() {
         assert!(super::DEEPGRAM_API_URL.starts_with("https://"));
     }
+
+    #[test]
+    fn test_dominant_language() {
+        assert_eq!(
+            super::dominant_language(&["en", "es", "es"]),
+            Some("es".to_string())
+        );
+        assert_eq!(
+            super::dominant_language(&["en", "es"]),
+            Some("en".to_string())
+        );
+        assert_eq!(super::dominant_language(&[]), None);
+    }
 }
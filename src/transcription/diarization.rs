@@ -6,10 +6,16 @@ use std::path::Path;
 const DIAR_CHUNK_SECS: usize = 600; // 10 minutes per chunk
 const DIAR_OVERLAP_SECS: usize = 30; // 30 second overlap
 
+/// Label for transcript segments whose speaker could not be determined reliably
+pub const AMBIGUOUS_SPEAKER: &str = "SPEAKER_?";
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct SpeakerSegment {
     pub speaker_id: usize,
     pub start_ms: u64,
     pub end_ms: u64,
+    /// Model confidence for this turn, when the backend reports one
+    pub confidence: Option<f32>,
 }
 
 pub struct Diarizer {
@@ -30,7 +36,9 @@ impl Diarizer {
         let overlap_samples = DIAR_OVERLAP_SECS * sample_rate as usize;
 
         if samples.len() <= chunk_samples {
-            return self.diarize_single_chunk(&samples, sample_rate, 0);
+            let mut segments = self.diarize_single_chunk(&samples, sample_rate, 0)?;
+            relabel_by_first_appearance(&mut segments);
+            return Ok(segments);
         }
 
         let mut all_segments = Vec::new();
@@ -74,6 +82,7 @@ impl Diarizer {
         }
 
         merge_adjacent_segments(&mut all_segments);
+        relabel_by_first_appearance(&mut all_segments);
 
         Ok(all_segments)
     }
@@ -95,6 +104,8 @@ impl Diarizer {
                 speaker_id: seg.speaker_id,
                 start_ms: (seg.start * 1000.0) as u64 + time_offset_ms,
                 end_ms: (seg.end * 1000.0) as u64 + time_offset_ms,
+                // Sortformer only reports hard speaker turns
+                confidence: None,
             })
             .collect())
     }
@@ -113,6 +124,10 @@ fn merge_adjacent_segments(segments: &mut Vec<SpeakerSegment>) {
     for seg in segments.drain(..) {
         if seg.speaker_id == current.speaker_id && seg.start_ms <= current.end_ms + 500 {
             current.end_ms = current.end_ms.max(seg.end_ms);
+            current.confidence = match (current.confidence, seg.confidence) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
         } else {
             merged.push(current);
             current = seg;
@@ -123,16 +138,101 @@ fn merge_adjacent_segments(segments: &mut Vec<SpeakerSegment>) {
     *segments = merged;
 }
 
-pub fn assign_speakers(transcript: &mut Transcript, speaker_segments: &[SpeakerSegment]) {
-    for segment in &mut transcript.segments {
-        let mid_point = (segment.start_ms + segment.end_ms) / 2;
+/// Renumber speaker ids in order of first appearance, so `SPEAKER_1` is always
+/// whoever speaks first. The model's cluster ordering is not stable between
+/// runs, and this keeps labels (and any aliases keyed by them) the same on redo.
+pub fn relabel_by_first_appearance(segments: &mut [SpeakerSegment]) {
+    segments.sort_by_key(|s| (s.start_ms, s.end_ms, s.speaker_id));
+
+    let mut order: Vec<usize> = Vec::new();
+    for seg in segments.iter() {
+        if !order.contains(&seg.speaker_id) {
+            order.push(seg.speaker_id);
+        }
+    }
 
-        let speaker = speaker_segments
+    for seg in segments.iter_mut() {
+        seg.speaker_id = order
             .iter()
-            .find(|s| mid_point >= s.start_ms && mid_point <= s.end_ms)
+            .position(|id| *id == seg.speaker_id)
+            .unwrap_or(seg.speaker_id);
+    }
+}
+
+/// `SPEAKER_N` label for the speaker who overlaps a transcript segment the most.
+///
+/// When the runner-up's overlap is within `ambiguity_threshold` (a fraction of
+/// the segment length) of the winner's, speaker confidence breaks the tie; if
+/// that doesn't settle it the segment is labeled [`AMBIGUOUS_SPEAKER`]. A
+/// threshold of 0 disables ambiguity detection.
+pub fn speaker_label_at(
+    speaker_segments: &[SpeakerSegment],
+    start_ms: u64,
+    end_ms: u64,
+    ambiguity_threshold: f32,
+) -> Option<String> {
+    // Per speaker: total overlap and the best confidence among overlapping turns
+    let mut candidates: Vec<(usize, u64, Option<f32>)> = Vec::new();
+    for s in speaker_segments {
+        let overlap = end_ms
+            .min(s.end_ms)
+            .saturating_sub(start_ms.max(s.start_ms));
+        if overlap == 0 {
+            continue;
+        }
+        match candidates.iter_mut().find(|(id, _, _)| *id == s.speaker_id) {
+            Some((_, total, confidence)) => {
+                *total += overlap;
+                *confidence = match (*confidence, s.confidence) {
+                    (Some(a), Some(b)) => Some(a.max(b)),
+                    (a, b) => a.or(b),
+                };
+            }
+            None => candidates.push((s.speaker_id, overlap, s.confidence)),
+        }
+    }
+
+    if candidates.is_empty() {
+        // Zero-length segment: fall back to whoever is speaking at that instant
+        return speaker_segments
+            .iter()
+            .find(|s| start_ms >= s.start_ms && start_ms <= s.end_ms)
             .map(|s| format!("SPEAKER_{}", s.speaker_id + 1));
+    }
 
-        segment.speaker = speaker;
+    candidates.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    let (best_id, best_overlap, best_confidence) = candidates[0];
+    let label = |id: usize| Some(format!("SPEAKER_{}", id + 1));
+
+    let Some(&(rival_id, rival_overlap, rival_confidence)) = candidates.get(1) else {
+        return label(best_id);
+    };
+
+    let duration = end_ms.saturating_sub(start_ms).max(1) as f32;
+    let margin = (best_overlap - rival_overlap) as f32 / duration;
+    if ambiguity_threshold <= 0.0 || margin > ambiguity_threshold {
+        return label(best_id);
+    }
+
+    match (best_confidence, rival_confidence) {
+        (Some(a), Some(b)) if a > b => label(best_id),
+        (Some(a), Some(b)) if b > a => label(rival_id),
+        _ => Some(AMBIGUOUS_SPEAKER.to_string()),
+    }
+}
+
+pub fn assign_speakers(
+    transcript: &mut Transcript,
+    speaker_segments: &[SpeakerSegment],
+    ambiguity_threshold: f32,
+) {
+    for segment in &mut transcript.segments {
+        segment.speaker = speaker_label_at(
+            speaker_segments,
+            segment.start_ms,
+            segment.end_ms,
+            ambiguity_threshold,
+        );
     }
 }
 
@@ -141,9 +241,102 @@ pub fn diarize_transcript<P: AsRef<Path>>(
     samples: &[f32],
     sample_rate: u32,
     transcript: &mut Transcript,
+    ambiguity_threshold: f32,
 ) -> Result<()> {
     let mut diarizer = Diarizer::new(model_path)?;
     let speaker_segments = diarizer.diarize(samples.to_vec(), sample_rate)?;
-    assign_speakers(transcript, &speaker_segments);
+    assign_speakers(transcript, &speaker_segments, ambiguity_threshold);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(speaker_id: usize, start_ms: u64, end_ms: u64) -> SpeakerSegment {
+        SpeakerSegment {
+            speaker_id,
+            start_ms,
+            end_ms,
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_relabel_by_first_appearance() {
+        let mut segments = vec![seg(2, 5000, 8000), seg(0, 9000, 12000), seg(2, 0, 4000)];
+        relabel_by_first_appearance(&mut segments);
+
+        let ids: Vec<usize> = segments.iter().map(|s| s.speaker_id).collect();
+        assert_eq!(ids, vec![0, 0, 1]);
+        assert_eq!(segments[0].start_ms, 0);
+    }
+
+    #[test]
+    fn test_relabel_is_stable_across_runs() {
+        // Same speech, but the model numbered its clusters differently on the second run
+        let first_run = vec![seg(0, 0, 4000), seg(1, 4000, 9000), seg(0, 9000, 12000)];
+        let second_run = vec![seg(1, 0, 4000), seg(0, 4000, 9000), seg(1, 9000, 12000)];
+
+        let mut a = first_run.clone();
+        let mut b = second_run;
+        relabel_by_first_appearance(&mut a);
+        relabel_by_first_appearance(&mut b);
+        assert_eq!(a, b);
+
+        let mut again = first_run;
+        relabel_by_first_appearance(&mut again);
+        assert_eq!(a, again);
+
+        let labels: Vec<Option<String>> = [(0, 2000), (5000, 6000), (10000, 11000)]
+            .iter()
+            .map(|(start, end)| speaker_label_at(&a, *start, *end, 0.1))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                Some("SPEAKER_1".to_string()),
+                Some("SPEAKER_2".to_string()),
+                Some("SPEAKER_1".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_speaker_label_prefers_largest_overlap() {
+        let speakers = vec![seg(0, 0, 3000), seg(1, 3000, 10000)];
+        // 1s with speaker 0, 3s with speaker 1
+        assert_eq!(
+            speaker_label_at(&speakers, 2000, 6000, 0.1),
+            Some("SPEAKER_2".to_string())
+        );
+        assert_eq!(speaker_label_at(&speakers, 20000, 21000, 0.1), None);
+    }
+
+    #[test]
+    fn test_speaker_label_ambiguous_overlap() {
+        let speakers = vec![seg(0, 0, 5000), seg(1, 5000, 10000)];
+
+        assert_eq!(
+            speaker_label_at(&speakers, 4000, 6100, 0.1),
+            Some(AMBIGUOUS_SPEAKER.to_string())
+        );
+        // Threshold 0 disables the ambiguity label
+        assert_eq!(
+            speaker_label_at(&speakers, 4000, 6100, 0.0),
+            Some("SPEAKER_2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_speaker_label_confidence_breaks_tie() {
+        let mut speakers = vec![seg(0, 0, 5000), seg(1, 5000, 10000)];
+        speakers[0].confidence = Some(0.9);
+        speakers[1].confidence = Some(0.4);
+
+        assert_eq!(
+            speaker_label_at(&speakers, 4000, 6100, 0.1),
+            Some("SPEAKER_1".to_string())
+        );
+    }
+}
@@ -1,6 +1,7 @@
-use crate::error::{MuesliError, Result};
+use crate::error::Result;
+use crate::transcription::checksum::{self, Verification};
+use crate::transcription::download::{self, DownloadOptions};
 use std::fs;
-use std::io::{Read, Write};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,18 +17,19 @@ impl DiarizationModel {
         }
     }
 
-    pub fn filename(&self) -> &'static str {
+    /// Files the model needs, the one passed to the diarizer first
+    pub fn files(&self) -> &'static [ModelFile] {
         match self {
-            Self::SortformerV2 => "diar_streaming_sortformer_4spk-v2.onnx",
+            Self::SortformerV2 => &[ModelFile {
+                name: "diar_streaming_sortformer_4spk-v2.onnx",
+                url: "https://huggingface.co/altunenes/parakeet-rs/resolve/main/diar_streaming_sortformer_4spk-v2.onnx",
+                min_bytes: 10 * 1024 * 1024,
+            }],
         }
     }
 
-    pub fn download_url(&self) -> &'static str {
-        match self {
-            Self::SortformerV2 => {
-                "https://huggingface.co/altunenes/parakeet-rs/resolve/main/diar_streaming_sortformer_4spk-v2.onnx"
-            }
-        }
+    pub fn filename(&self) -> &'static str {
+        self.files()[0].name
     }
 
     pub fn size_mb(&self) -> u64 {
@@ -41,6 +43,15 @@ impl DiarizationModel {
     }
 }
 
+/// One file of a (possibly multi-file) model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelFile {
+    pub name: &'static str,
+    pub url: &'static str,
+    /// Anything smaller is an interrupted download, even without a recorded size
+    pub min_bytes: u64,
+}
+
 impl std::fmt::Display for DiarizationModel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -51,19 +62,49 @@ impl std::fmt::Display for DiarizationModel {
 
 pub struct DiarizationModelManager {
     models_dir: PathBuf,
+    download: DownloadOptions,
 }
 
 impl DiarizationModelManager {
     pub fn new(models_dir: PathBuf) -> Self {
-        Self { models_dir }
+        Self {
+            models_dir,
+            download: DownloadOptions::from_env(),
+        }
+    }
+
+    /// Override rate limit / mirror for downloads
+    pub fn with_download_options(mut self, download: DownloadOptions) -> Self {
+        self.download = download;
+        self
     }
 
     pub fn model_path(&self, model: DiarizationModel) -> PathBuf {
         self.models_dir.join(model.filename())
     }
 
+    /// Whether every file of the model is on disk at full size. A half-downloaded
+    /// model counts as missing, so it gets downloaded again rather than failing
+    /// to load in every meeting.
     pub fn model_exists(&self, model: DiarizationModel) -> bool {
-        self.model_path(model).exists()
+        model.files().iter().all(|file| self.file_complete(file))
+    }
+
+    /// Whether any file of the model is on disk, complete or not
+    pub fn has_any_files(&self, model: DiarizationModel) -> bool {
+        model
+            .files()
+            .iter()
+            .any(|file| self.models_dir.join(file.name).exists())
+    }
+
+    fn file_complete(&self, file: &ModelFile) -> bool {
+        let path = self.models_dir.join(file.name);
+        let Ok(metadata) = fs::metadata(&path) else {
+            return false;
+        };
+        let expected = checksum::read_recorded_size(&path).unwrap_or(file.min_bytes);
+        metadata.len() >= expected
     }
 
     pub fn list_all(&self) -> Vec<(DiarizationModel, bool, u64)> {
@@ -83,57 +124,131 @@ impl DiarizationModelManager {
         F: Fn(u64, u64),
     {
         self.ensure_dir()?;
-        let file_path = self.model_path(model);
-
-        if file_path.exists() {
-            let size = model.size_mb() * 1024 * 1024;
-            progress(size, size);
-            return Ok(file_path);
-        }
-
-        let url = model.download_url();
-        let temp_path = file_path.with_extension("tmp");
+        let estimated_size = model.size_mb() * 1024 * 1024 / model.files().len() as u64;
+
+        // Files already complete are kept, so an interrupted bundle resumes
+        for file in model.files() {
+            let file_path = self.models_dir.join(file.name);
+            if self.file_complete(file) {
+                let size = fs::metadata(&file_path)?.len();
+                progress(size, size);
+                continue;
+            }
 
-        let response = reqwest::blocking::Client::new()
-            .get(url)
-            .send()
-            .map_err(|e| MuesliError::Api(format!("Download failed: {}", e)))?;
+            let url = self.download.resolve_url(file.url);
+            let temp_path = file_path.with_extension("tmp");
+
+            let fetched = download::fetch_to_file(
+                &url,
+                &temp_path,
+                &self.download,
+                estimated_size,
+                &progress,
+            )?;
+
+            if let Err(e) = checksum::check_download(
+                fetched.size,
+                fetched.expected_size,
+                fetched.expected_sha256.as_deref(),
+                &fetched.sha256,
+            ) {
+                let _ = fs::remove_file(&temp_path);
+                return Err(e);
+            }
 
-        if !response.status().is_success() {
-            return Err(MuesliError::Api(format!(
-                "Failed to download: HTTP {}",
-                response.status()
-            )));
+            fs::rename(&temp_path, &file_path)?;
+            checksum::write_checksum(&file_path, &fetched.sha256, fetched.size)?;
         }
+        Ok(self.model_path(model))
+    }
 
-        let total_size = response
-            .content_length()
-            .unwrap_or(model.size_mb() * 1024 * 1024);
-
-        let mut file = fs::File::create(&temp_path)?;
-        let mut downloaded: u64 = 0;
-        let mut reader = response;
-        let mut buffer = [0u8; 8192];
-
-        loop {
-            let bytes_read = reader.read(&mut buffer).map_err(MuesliError::Io)?;
-            if bytes_read == 0 {
-                break;
+    /// Check every file of an installed model: present, at full size, and
+    /// matching the checksum recorded when it was downloaded. Reports the first
+    /// problem found.
+    pub fn verify_model(&self, model: DiarizationModel) -> Result<Verification> {
+        let mut missing = Vec::new();
+        let mut unrecorded = false;
+        for file in model.files() {
+            let path = self.models_dir.join(file.name);
+            match checksum::verify_file(&path)? {
+                Verification::Missing => missing.push(file.name.to_string()),
+                Verification::Ok | Verification::Unrecorded
+                    if fs::metadata(&path)?.len() < file.min_bytes =>
+                {
+                    return Ok(Verification::Truncated {
+                        expected: file.min_bytes,
+                        actual: fs::metadata(&path)?.len(),
+                    });
+                }
+                Verification::Ok => {}
+                Verification::Unrecorded => unrecorded = true,
+                problem => return Ok(problem),
             }
-            file.write_all(&buffer[..bytes_read])?;
-            downloaded += bytes_read as u64;
-            progress(downloaded, total_size);
         }
 
-        fs::rename(&temp_path, &file_path)?;
-        Ok(file_path)
+        Ok(if missing.len() == model.files().len() {
+            Verification::Missing
+        } else if !missing.is_empty() {
+            Verification::Incomplete { missing }
+        } else if unrecorded {
+            Verification::Unrecorded
+        } else {
+            Verification::Ok
+        })
     }
 
     pub fn delete_model(&self, model: DiarizationModel) -> Result<()> {
-        let path = self.model_path(model);
-        if path.exists() {
-            fs::remove_file(path)?;
+        for file in model.files() {
+            let path = self.models_dir.join(file.name);
+            if path.exists() {
+                fs::remove_file(&path)?;
+            }
+            checksum::remove_checksum(&path)?;
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncated_model_is_not_installed() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = DiarizationModelManager::new(dir.path().to_path_buf());
+        let model = DiarizationModel::SortformerV2;
+        assert!(!manager.has_any_files(model));
+        assert_eq!(manager.verify_model(model).unwrap(), Verification::Missing);
+
+        // Present but cut short, with no sidecar to say how big it should be
+        let path = manager.model_path(model);
+        fs::write(&path, vec![0u8; 1024]).unwrap();
+        assert!(manager.has_any_files(model));
+        assert!(!manager.model_exists(model));
+        assert_eq!(
+            manager.verify_model(model).unwrap(),
+            Verification::Truncated {
+                expected: model.files()[0].min_bytes,
+                actual: 1024
+            }
+        );
+
+        // A recorded size is checked exactly
+        let file = fs::File::create(&path).unwrap();
+        file.set_len(model.files()[0].min_bytes + 10).unwrap();
+        checksum::write_checksum(&path, &"0".repeat(64), model.files()[0].min_bytes + 20).unwrap();
+        assert!(!manager.model_exists(model));
+        assert!(matches!(
+            manager.verify_model(model).unwrap(),
+            Verification::Truncated { actual, .. } if actual == model.files()[0].min_bytes + 10
+        ));
+
+        file.set_len(model.files()[0].min_bytes + 20).unwrap();
+        assert!(manager.model_exists(model));
+        assert!(matches!(
+            manager.verify_model(model).unwrap(),
+            Verification::Mismatch { .. }
+        ));
+    }
+}
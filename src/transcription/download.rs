@@ -0,0 +1,331 @@
+//! Network settings shared by the model downloaders.
+//!
+//! Proxies come from the standard `HTTP_PROXY` / `HTTPS_PROXY` / `NO_PROXY`
+//! environment variables, which reqwest honors by default.
+
+use crate::error::{MuesliError, Result};
+use crate::transcription::checksum;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const HUGGINGFACE_BASE: &str = "https://huggingface.co";
+
+/// Options for a model download
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DownloadOptions {
+    /// Cap on the transfer rate in bytes per second
+    pub max_rate: Option<u64>,
+    /// Replacement for `https://huggingface.co` in model URLs
+    pub mirror: Option<String>,
+}
+
+impl DownloadOptions {
+    /// Defaults, with the mirror taken from `HF_ENDPOINT` (as used by the
+    /// HuggingFace tooling) when set
+    pub fn from_env() -> Self {
+        Self {
+            max_rate: None,
+            mirror: std::env::var("HF_ENDPOINT")
+                .ok()
+                .filter(|m| !m.trim().is_empty()),
+        }
+    }
+
+    /// Rate limit in MB/s (ignored unless positive)
+    pub fn with_max_rate_mbps(mut self, mbps: Option<f64>) -> Self {
+        if let Some(mbps) = mbps.filter(|r| *r > 0.0) {
+            self.max_rate = Some((mbps * 1024.0 * 1024.0) as u64);
+        }
+        self
+    }
+
+    pub fn with_mirror(mut self, mirror: Option<String>) -> Self {
+        if mirror.is_some() {
+            self.mirror = mirror;
+        }
+        self
+    }
+
+    /// `url` with the HuggingFace host swapped for the configured mirror
+    pub fn resolve_url(&self, url: &str) -> String {
+        match (&self.mirror, url.strip_prefix(HUGGINGFACE_BASE)) {
+            (Some(mirror), Some(path)) => format!("{}{}", mirror.trim_end_matches('/'), path),
+            _ => url.to_string(),
+        }
+    }
+}
+
+/// Blocking client for model downloads (proxy settings come from the environment)
+pub fn client() -> Result<reqwest::blocking::Client> {
+    reqwest::blocking::Client::builder()
+        .build()
+        .map_err(|e| MuesliError::Api(format!("Failed to create HTTP client: {}", e)))
+}
+
+/// What [`fetch_to_file`] wrote
+pub struct Fetched {
+    /// Bytes in the file, including any resumed from an earlier attempt
+    pub size: u64,
+    /// Full size reported by the server
+    pub expected_size: Option<u64>,
+    /// Checksum published by the server
+    pub expected_sha256: Option<String>,
+    /// Checksum of the whole file
+    pub sha256: String,
+}
+
+/// Download `url` into `temp_path`, continuing a partial file left by an
+/// interrupted attempt when the server supports range requests.
+///
+/// Ctrl-C (see [`crate::interrupt`]) stops between reads and keeps the partial
+/// file so the next attempt resumes. `fallback_size` is used for progress when
+/// the server doesn't report a length.
+pub fn fetch_to_file<F>(
+    url: &str,
+    temp_path: &Path,
+    options: &DownloadOptions,
+    fallback_size: u64,
+    progress: F,
+) -> Result<Fetched>
+where
+    F: Fn(u64, u64),
+{
+    let partial = fs::metadata(temp_path).map(|m| m.len()).unwrap_or(0);
+
+    let mut request = client()?.get(url);
+    if partial > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", partial));
+    }
+    let mut response = request
+        .send()
+        .map_err(|e| MuesliError::Api(format!("Download failed: {}", e)))?;
+
+    if partial > 0 && response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        // The partial file doesn't match what the server has now; start over
+        fs::remove_file(temp_path)?;
+        return fetch_to_file(url, temp_path, options, fallback_size, progress);
+    }
+    if !response.status().is_success() {
+        return Err(MuesliError::Api(format!(
+            "Failed to download {}: HTTP {}",
+            url,
+            response.status()
+        )));
+    }
+
+    let resumed = partial > 0 && response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    let start = if resumed { partial } else { 0 };
+    let expected_size = response.content_length().map(|len| start + len);
+    let expected_sha256 = checksum::expected_sha256(response.headers());
+    let total_size = expected_size.unwrap_or(fallback_size);
+
+    let mut hasher = Sha256::new();
+    let mut file = if resumed {
+        tracing::info!("Resuming download at {} bytes", partial);
+        std::io::copy(&mut fs::File::open(temp_path)?, &mut hasher)?;
+        fs::OpenOptions::new().append(true).open(temp_path)?
+    } else {
+        fs::File::create(temp_path)?
+    };
+
+    let mut downloaded = start;
+    let mut buffer = [0u8; 8192];
+    let throttle = Throttle::new(options.max_rate);
+
+    loop {
+        crate::interrupt::check(|| {
+            format!(
+                "download paused at {} of {} MB; run the same command again to resume",
+                downloaded / 1024 / 1024,
+                total_size / 1024 / 1024
+            )
+        })?;
+
+        let bytes_read = response.read(&mut buffer).map_err(MuesliError::Io)?;
+        if bytes_read == 0 {
+            break;
+        }
+        file.write_all(&buffer[..bytes_read])?;
+        hasher.update(&buffer[..bytes_read]);
+        downloaded += bytes_read as u64;
+        progress(downloaded, total_size);
+        throttle.pace(downloaded - start);
+    }
+
+    Ok(Fetched {
+        size: downloaded,
+        expected_size,
+        expected_sha256,
+        sha256: checksum::hex(&hasher.finalize()),
+    })
+}
+
+/// Sleeps in the read loop to keep the average rate under a limit
+pub struct Throttle {
+    max_rate: Option<u64>,
+    started: Instant,
+}
+
+impl Throttle {
+    pub fn new(max_rate: Option<u64>) -> Self {
+        Self {
+            max_rate,
+            started: Instant::now(),
+        }
+    }
+
+    /// Call after each read with the running byte total
+    pub fn pace(&self, downloaded: u64) {
+        if let Some(max_rate) = self.max_rate {
+            let delay = delay_for(downloaded, self.started.elapsed(), max_rate);
+            if !delay.is_zero() {
+                std::thread::sleep(delay);
+            }
+        }
+    }
+}
+
+/// How long to wait so that `downloaded` bytes take at least `downloaded / max_rate`
+fn delay_for(downloaded: u64, elapsed: Duration, max_rate: u64) -> Duration {
+    let target = Duration::from_secs_f64(downloaded as f64 / max_rate.max(1) as f64);
+    target.saturating_sub(elapsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+
+    const BODY: &[u8] = b"0123456789abcdef";
+
+    /// Serve `BODY` on a local port for `requests` connections, honoring
+    /// `Range: bytes=N-` like a real file server (206, or 416 past the end).
+    /// The thread returns the range offset each request asked for.
+    fn serve(requests: usize) -> (String, std::thread::JoinHandle<Vec<Option<u64>>>) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/model.bin", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut ranges = Vec::new();
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut range = None;
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    let lower = line.to_ascii_lowercase();
+                    if let Some(spec) = lower.strip_prefix("range: bytes=") {
+                        range = spec.trim().trim_end_matches('-').parse::<u64>().ok();
+                    }
+                }
+                ranges.push(range);
+
+                let len = BODY.len() as u64;
+                let (status, headers, body) = match range {
+                    Some(start) if start < len => (
+                        "206 Partial Content",
+                        format!("Content-Range: bytes {}-{}/{}\r\n", start, len - 1, len),
+                        &BODY[start as usize..],
+                    ),
+                    Some(_) => (
+                        "416 Range Not Satisfiable",
+                        format!("Content-Range: bytes */{}\r\n", len),
+                        &b""[..],
+                    ),
+                    None => ("200 OK", String::new(), BODY),
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    headers,
+                    body.len()
+                )
+                .unwrap();
+                stream.write_all(body).unwrap();
+            }
+            ranges
+        });
+        (url, handle)
+    }
+
+    fn body_sha256() -> String {
+        checksum::hex(&Sha256::digest(BODY))
+    }
+
+    #[test]
+    fn test_fetch_resumes_partial_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let temp_path = dir.path().join("model.bin.tmp");
+        fs::write(&temp_path, &BODY[..6]).unwrap();
+
+        let (url, server) = serve(1);
+        let fetched =
+            fetch_to_file(&url, &temp_path, &DownloadOptions::default(), 0, |_, _| {}).unwrap();
+
+        assert_eq!(server.join().unwrap(), vec![Some(6)]);
+        assert_eq!(fs::read(&temp_path).unwrap(), BODY);
+        assert_eq!(fetched.size, BODY.len() as u64);
+        assert_eq!(fetched.expected_size, Some(BODY.len() as u64));
+        assert_eq!(fetched.sha256, body_sha256());
+    }
+
+    #[test]
+    fn test_fetch_restarts_on_unsatisfiable_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let temp_path = dir.path().join("model.bin.tmp");
+        fs::write(&temp_path, b"stale partial file, longer than the model").unwrap();
+
+        let (url, server) = serve(2);
+        let fetched =
+            fetch_to_file(&url, &temp_path, &DownloadOptions::default(), 0, |_, _| {}).unwrap();
+
+        assert_eq!(server.join().unwrap(), vec![Some(41), None]);
+        assert_eq!(fs::read(&temp_path).unwrap(), BODY);
+        assert_eq!(fetched.size, BODY.len() as u64);
+        assert_eq!(fetched.sha256, body_sha256());
+    }
+
+    #[test]
+    fn test_resolve_url_mirror() {
+        let url = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.bin";
+        assert_eq!(DownloadOptions::default().resolve_url(url), url);
+
+        let mirrored =
+            DownloadOptions::default().with_mirror(Some("https://hf-mirror.com/".into()));
+        assert_eq!(
+            mirrored.resolve_url(url),
+            "https://hf-mirror.com/ggerganov/whisper.cpp/resolve/main/ggml-base.bin"
+        );
+        assert_eq!(
+            mirrored.resolve_url("https://example.com/model.bin"),
+            "https://example.com/model.bin"
+        );
+    }
+
+    #[test]
+    fn test_max_rate_mbps() {
+        let opts = DownloadOptions::default().with_max_rate_mbps(Some(2.0));
+        assert_eq!(opts.max_rate, Some(2 * 1024 * 1024));
+        assert_eq!(
+            DownloadOptions::default()
+                .with_max_rate_mbps(Some(0.0))
+                .max_rate,
+            None
+        );
+    }
+
+    #[test]
+    fn test_delay_for() {
+        // 2 MB at 1 MB/s should take 2s; after 0.5s we owe 1.5s
+        let delay = delay_for(2 * 1024 * 1024, Duration::from_millis(500), 1024 * 1024);
+        assert_eq!(delay, Duration::from_millis(1500));
+        assert!(delay_for(1024, Duration::from_secs(1), 1024 * 1024).is_zero());
+    }
+}
@@ -0,0 +1,125 @@
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+/// Compute backend whisper.cpp runs on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuBackend {
+    Cuda,
+    Vulkan,
+    Metal,
+    Cpu,
+}
+
+impl GpuBackend {
+    pub fn is_gpu(&self) -> bool {
+        !matches!(self, GpuBackend::Cpu)
+    }
+}
+
+impl std::fmt::Display for GpuBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GpuBackend::Cuda => write!(f, "CUDA"),
+            GpuBackend::Vulkan => write!(f, "Vulkan"),
+            GpuBackend::Metal => write!(f, "Metal"),
+            GpuBackend::Cpu => write!(f, "CPU"),
+        }
+    }
+}
+
+/// GPU backend this binary was built with (the `cuda`/`vulkan`/`metal` features)
+pub fn compiled_backend() -> GpuBackend {
+    if cfg!(feature = "cuda") {
+        GpuBackend::Cuda
+    } else if cfg!(feature = "vulkan") {
+        GpuBackend::Vulkan
+    } else if cfg!(feature = "metal") {
+        GpuBackend::Metal
+    } else {
+        GpuBackend::Cpu
+    }
+}
+
+fn command_succeeds(program: &str, args: &[&str]) -> bool {
+    Command::new(program)
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+/// Whether a working Vulkan driver is present (probes `vulkaninfo`)
+pub fn vulkan_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| command_succeeds("vulkaninfo", &[]))
+}
+
+/// Whether an NVIDIA driver usable by CUDA is present
+pub fn cuda_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        std::path::Path::new("/proc/driver/nvidia/version").exists()
+            || command_succeeds("nvidia-smi", &["-L"])
+    })
+}
+
+fn backend_available(backend: GpuBackend) -> bool {
+    match backend {
+        GpuBackend::Cuda => cuda_available(),
+        GpuBackend::Vulkan => vulkan_available(),
+        GpuBackend::Metal => cfg!(target_os = "macos"),
+        GpuBackend::Cpu => true,
+    }
+}
+
+/// Backend whisper.cpp will actually use for the given `use_gpu` setting
+pub fn effective_backend(use_gpu: bool) -> GpuBackend {
+    let compiled = compiled_backend();
+    if use_gpu && compiled.is_gpu() && backend_available(compiled) {
+        compiled
+    } else {
+        GpuBackend::Cpu
+    }
+}
+
+/// Resolve and log the backend for engine init. Falls back to CPU (with a
+/// warning) when GPU is requested but unavailable, rather than failing.
+pub fn resolve_backend(use_gpu: bool) -> GpuBackend {
+    let backend = effective_backend(use_gpu);
+    let compiled = compiled_backend();
+
+    if use_gpu && !backend.is_gpu() {
+        if compiled.is_gpu() {
+            tracing::warn!(
+                "use_gpu = true but no {} device was found; transcribing on CPU",
+                compiled
+            );
+        } else {
+            tracing::warn!(
+                "use_gpu = true but this build has no GPU support (rebuild with --features vulkan or cuda); transcribing on CPU"
+            );
+        }
+    }
+
+    tracing::info!("Whisper backend: {}", backend);
+    backend
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpu_when_gpu_disabled() {
+        assert_eq!(effective_backend(false), GpuBackend::Cpu);
+    }
+
+    #[test]
+    fn test_backend_display() {
+        assert_eq!(GpuBackend::Vulkan.to_string(), "Vulkan");
+        assert!(GpuBackend::Cuda.is_gpu());
+        assert!(!GpuBackend::Cpu.is_gpu());
+    }
+}
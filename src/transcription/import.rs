@@ -0,0 +1,267 @@
+//! Transcripts made elsewhere (platform captions, other tools) read back into
+//! segments: SubRip (`.srt`), WebVTT (`.vtt`) and muesli's own JSON segments.
+//! [`render`] writes segments out in the same formats for sharing, with the
+//! subtitle formats going through [`crate::notes::subtitles`].
+
+use crate::error::{MuesliError, Result};
+use crate::transcription::TranscriptSegment;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptFormat {
+    Srt,
+    Vtt,
+    /// A JSON array of segments, or a transcript object with a `segments` array
+    Json,
+}
+
+impl TranscriptFormat {
+    /// Format from the file extension
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+        match ext.as_deref() {
+            Some("srt") => Ok(Self::Srt),
+            Some("vtt") => Ok(Self::Vtt),
+            Some("json") => Ok(Self::Json),
+            _ => Err(MuesliError::Usage(format!(
+                "Unsupported transcript file {} (expected .srt, .vtt or .json)",
+                path.display()
+            ))),
+        }
+    }
+}
+
+/// Parse `contents` and check the segments are usable: at least one, none
+/// ending before it starts, none without text.
+pub fn parse(contents: &str, format: TranscriptFormat) -> Result<Vec<TranscriptSegment>> {
+    let mut segments = match format {
+        TranscriptFormat::Srt | TranscriptFormat::Vtt => parse_cues(contents)?,
+        TranscriptFormat::Json => parse_json(contents)?,
+    };
+    segments.sort_by_key(|s| s.start_ms);
+    validate(&segments)?;
+    Ok(segments)
+}
+
+/// Segments as a file in `format`. VTT cues carry the speaker as a voice tag,
+/// SRT cues as a `Speaker:` prefix.
+pub fn render(segments: &[TranscriptSegment], format: TranscriptFormat) -> Result<String> {
+    Ok(match format {
+        TranscriptFormat::Srt => crate::notes::subtitles::to_srt(segments),
+        TranscriptFormat::Vtt => crate::notes::subtitles::to_vtt(segments),
+        TranscriptFormat::Json => serde_json::to_string_pretty(segments)? + "\n",
+    })
+}
+
+fn parse_json(contents: &str) -> Result<Vec<TranscriptSegment>> {
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum Document {
+        Segments(Vec<TranscriptSegment>),
+        Transcript { segments: Vec<TranscriptSegment> },
+    }
+
+    match serde_json::from_str(contents)? {
+        Document::Segments(segments) | Document::Transcript { segments } => Ok(segments),
+    }
+}
+
+/// SRT and VTT share the cue layout: an optional identifier line, a
+/// `start --> end` line, then text lines up to a blank line. VTT voice tags
+/// (`<v Alice>`) become the segment's speaker.
+fn parse_cues(contents: &str) -> Result<Vec<TranscriptSegment>> {
+    let mut segments = Vec::new();
+    let mut lines = contents
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .enumerate()
+        .peekable();
+
+    while let Some((index, line)) = lines.next() {
+        let Some((start, end)) = line.split_once("-->") else {
+            continue;
+        };
+        let line_no = index + 1;
+        let start_ms = parse_cue_time(start, line_no)?;
+        // VTT allows cue settings after the end time
+        let end = end.split_whitespace().next().unwrap_or_default();
+        let end_ms = parse_cue_time(end, line_no)?;
+
+        let mut text = Vec::new();
+        while let Some((_, next)) = lines.next_if(|(_, l)| !l.trim().is_empty()) {
+            text.push(next.trim());
+        }
+        let (speaker, text) = split_voice(&text.join(" "));
+
+        let mut segment = TranscriptSegment::new(start_ms, end_ms, text);
+        segment.speaker = speaker;
+        segments.push(segment);
+    }
+
+    Ok(segments)
+}
+
+/// `00:01:02,345` (SRT), `00:01:02.345` or `01:02.345` (VTT) to milliseconds
+fn parse_cue_time(raw: &str, line_no: usize) -> Result<u64> {
+    let invalid = || {
+        MuesliError::Transcription(format!(
+            "line {}: invalid timestamp '{}'",
+            line_no,
+            raw.trim()
+        ))
+    };
+    let raw = raw.trim().replace(',', ".");
+    let (clock, millis) = raw.split_once('.').ok_or_else(invalid)?;
+    if millis.len() != 3 {
+        return Err(invalid());
+    }
+    let millis: u64 = millis.parse().map_err(|_| invalid())?;
+
+    let parts = clock
+        .split(':')
+        .map(|p| p.parse::<u64>().map_err(|_| invalid()))
+        .collect::<Result<Vec<_>>>()?;
+    let (hours, minutes, seconds) = match parts[..] {
+        [h, m, s] => (h, m, s),
+        [m, s] => (0, m, s),
+        _ => return Err(invalid()),
+    };
+    if minutes >= 60 || seconds >= 60 {
+        return Err(invalid());
+    }
+    Ok(((hours * 60 + minutes) * 60 + seconds) * 1000 + millis)
+}
+
+/// Strip a leading `<v Speaker>` voice tag (and any other markup) from cue text,
+/// and the visible `Speaker:` prefix muesli writes after it
+fn split_voice(text: &str) -> (Option<String>, String) {
+    let speaker = text
+        .strip_prefix("<v ")
+        .or_else(|| text.strip_prefix("<v."))
+        .and_then(|rest| rest.split_once('>'))
+        .map(|(name, _)| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+
+    let mut plain = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => plain.push(c),
+            _ => {}
+        }
+    }
+    let speaker = speaker.map(|s| unescape(&s));
+    let plain = unescape(&plain);
+    let plain = plain.trim();
+    let plain = speaker
+        .as_deref()
+        .and_then(|s| plain.strip_prefix(s)?.strip_prefix(':'))
+        .unwrap_or(plain);
+    (speaker, plain.trim().to_string())
+}
+
+/// The entities WebVTT cue text escapes
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+fn validate(segments: &[TranscriptSegment]) -> Result<()> {
+    if segments.is_empty() {
+        return Err(MuesliError::Transcription(
+            "no transcript segments found in the file".to_string(),
+        ));
+    }
+    for segment in segments {
+        if segment.end_ms < segment.start_ms {
+            return Err(MuesliError::Transcription(format!(
+                "segment at {} ends before it starts",
+                segment.format_timestamp()
+            )));
+        }
+        if segment.text.trim().is_empty() {
+            return Err(MuesliError::Transcription(format!(
+                "segment at {} has no text",
+                segment.format_timestamp()
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_srt_and_vtt() {
+        let srt = "1\n00:00:01,000 --> 00:00:02,500\nHello\nthere\n\n2\n00:00:03,000 --> 00:00:04,000\nHi\n";
+        let segments = parse(srt, TranscriptFormat::Srt).unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].start_ms, 1000);
+        assert_eq!(segments[0].end_ms, 2500);
+        assert_eq!(segments[0].text, "Hello there");
+
+        let vtt = "WEBVTT\n\n00:05.000 --> 00:06.000 align:start\n<v Alice>Ship it</v>\n\n01:00:00.000 --> 01:00:01.000\n<i>Bye</i>\n";
+        let segments = parse(vtt, TranscriptFormat::Vtt).unwrap();
+        assert_eq!(segments[0].start_ms, 5000);
+        assert_eq!(segments[0].speaker.as_deref(), Some("Alice"));
+        assert_eq!(segments[0].text, "Ship it");
+        assert_eq!(segments[1].start_ms, 3_600_000);
+        assert_eq!(segments[1].text, "Bye");
+
+        assert!(parse("00:00:01 --> 00:00:02\nHi\n", TranscriptFormat::Srt).is_err());
+        assert!(parse("00:00:02,000 --> 00:00:01,000\nHi\n", TranscriptFormat::Srt).is_err());
+        assert!(parse("WEBVTT\n", TranscriptFormat::Vtt).is_err());
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let json = r#"[{"start_ms": 0, "end_ms": 900, "text": "Hi", "speaker": "SPEAKER_1", "confidence": null}]"#;
+        let segments = parse(json, TranscriptFormat::Json).unwrap();
+        assert_eq!(segments[0].speaker.as_deref(), Some("SPEAKER_1"));
+
+        let wrapped = format!(r#"{{"segments": {}}}"#, json);
+        assert_eq!(parse(&wrapped, TranscriptFormat::Json).unwrap().len(), 1);
+        assert!(parse("[]", TranscriptFormat::Json).is_err());
+
+        assert_eq!(
+            TranscriptFormat::from_path(Path::new("call.VTT")).unwrap(),
+            TranscriptFormat::Vtt
+        );
+        assert!(TranscriptFormat::from_path(Path::new("call.txt")).is_err());
+    }
+
+    #[test]
+    fn test_render_roundtrip() {
+        let mut first = TranscriptSegment::new(1500, 3_723_004, "Ship it".to_string());
+        first.speaker = Some("Alice".to_string());
+        let segments = vec![
+            first,
+            TranscriptSegment::new(3_724_000, 3_725_000, "Bye".to_string()),
+        ];
+
+        let srt = render(&segments, TranscriptFormat::Srt).unwrap();
+        assert!(srt.starts_with("1\n00:00:01,500 --> 01:02:03,004\nAlice: Ship it\n\n2\n"));
+
+        let vtt = render(&segments, TranscriptFormat::Vtt).unwrap();
+        let parsed = parse(&vtt, TranscriptFormat::Vtt).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].end_ms, 3_723_004);
+        assert_eq!(parsed[0].speaker.as_deref(), Some("Alice"));
+        assert_eq!(parsed[0].text, "Ship it");
+        assert_eq!(parsed[1].text, "Bye");
+
+        let json = render(&segments, TranscriptFormat::Json).unwrap();
+        assert_eq!(
+            parse(&json, TranscriptFormat::Json).unwrap()[1].start_ms,
+            3_724_000
+        );
+    }
+}
@@ -2,12 +2,17 @@
 
 use serde::{Deserialize, Serialize};
 
+pub mod checksum;
 pub mod deepgram;
 pub mod diarization;
 pub mod diarization_models;
+pub mod download;
+pub mod gpu;
+pub mod import;
 pub mod models;
 pub mod openai;
 pub mod streaming;
+pub mod voiceprint;
 pub mod whisper;
 
 /// A segment of transcribed text
@@ -18,6 +23,9 @@ pub struct TranscriptSegment {
     pub text: String,
     pub speaker: Option<String>,
     pub confidence: Option<f32>,
+    /// Spoken language of this segment, when the engine reports it per segment
+    #[serde(default)]
+    pub language: Option<String>,
 }
 
 impl TranscriptSegment {
@@ -28,6 +36,7 @@ impl TranscriptSegment {
             text,
             speaker: None,
             confidence: None,
+            language: None,
         }
     }
 
@@ -37,6 +46,158 @@ impl TranscriptSegment {
         let start_sec = start_sec % 60;
         format!("{:02}:{:02}", start_min, start_sec)
     }
+
+    /// Wall-clock time of this segment, given when the meeting started
+    pub fn format_clock_timestamp<Tz: chrono::TimeZone>(
+        &self,
+        meeting_start: &chrono::DateTime<Tz>,
+    ) -> String
+    where
+        Tz::Offset: std::fmt::Display,
+    {
+        format_clock_timestamp(meeting_start, self.start_ms)
+    }
+}
+
+/// Render `offset_ms` into a meeting as the absolute time it was spoken (`14:42:30`)
+pub fn format_clock_timestamp<Tz: chrono::TimeZone>(
+    meeting_start: &chrono::DateTime<Tz>,
+    offset_ms: u64,
+) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let at = meeting_start.clone() + chrono::Duration::milliseconds(offset_ms as i64);
+    at.format("%H:%M:%S").to_string()
+}
+
+/// Merge consecutive segments from the same speaker that are separated by less
+/// than `gap_ms`. Text is joined with a space and the time span extended; a gap of
+/// 0 disables merging.
+pub fn merge_segments(segments: &[TranscriptSegment], gap_ms: u64) -> Vec<TranscriptSegment> {
+    merge_segments_where(segments, gap_ms, false)
+}
+
+/// [`merge_segments`] for diarized transcripts: only segments labeled with the
+/// same speaker are merged, so unlabeled ones (which may be anyone) keep their
+/// own timestamps.
+pub fn merge_speaker_turns(segments: &[TranscriptSegment], gap_ms: u64) -> Vec<TranscriptSegment> {
+    merge_segments_where(segments, gap_ms, true)
+}
+
+fn merge_segments_where(
+    segments: &[TranscriptSegment],
+    gap_ms: u64,
+    labeled_only: bool,
+) -> Vec<TranscriptSegment> {
+    let mut merged: Vec<TranscriptSegment> = Vec::with_capacity(segments.len());
+
+    for segment in segments {
+        if let Some(last) = merged.last_mut() {
+            let gap = segment.start_ms.saturating_sub(last.end_ms);
+            if gap_ms > 0
+                && last.speaker == segment.speaker
+                && (!labeled_only || last.speaker.is_some())
+                && last.language == segment.language
+                && gap < gap_ms
+            {
+                last.confidence = match (last.confidence, segment.confidence) {
+                    (Some(a), Some(b)) => {
+                        let wa = last.end_ms.saturating_sub(last.start_ms).max(1) as f32;
+                        let wb = segment.end_ms.saturating_sub(segment.start_ms).max(1) as f32;
+                        Some((a * wa + b * wb) / (wa + wb))
+                    }
+                    _ => None,
+                };
+                let text = segment.text.trim();
+                if !text.is_empty() {
+                    if !last.text.is_empty() {
+                        last.text.push(' ');
+                    }
+                    last.text.push_str(text);
+                }
+                last.end_ms = last.end_ms.max(segment.end_ms);
+                continue;
+            }
+        }
+
+        let mut segment = segment.clone();
+        segment.text = segment.text.trim().to_string();
+        merged.push(segment);
+    }
+
+    merged
+}
+
+/// Concatenate the transcripts of several recordings into one timeline. Each part
+/// is shifted to start where the previous one ended (`part_durations_ms`, or the
+/// last segment end when unknown), so timestamps stay monotonic across parts.
+pub fn concat_segments(
+    parts: &[Vec<TranscriptSegment>],
+    part_durations_ms: &[Option<u64>],
+) -> Vec<TranscriptSegment> {
+    let mut combined = Vec::with_capacity(parts.iter().map(Vec::len).sum());
+    let mut offset_ms = 0u64;
+
+    for (i, part) in parts.iter().enumerate() {
+        let last_end = part.iter().map(|s| s.end_ms).max().unwrap_or(0);
+        for segment in part {
+            let mut segment = segment.clone();
+            segment.start_ms += offset_ms;
+            segment.end_ms += offset_ms;
+            combined.push(segment);
+        }
+
+        let duration = part_durations_ms
+            .get(i)
+            .copied()
+            .flatten()
+            .unwrap_or(0)
+            .max(last_end);
+        offset_ms += duration;
+    }
+
+    combined
+}
+
+/// A segment starting this close to zero after later ones restarts the clock
+const RESTART_NEAR_ZERO_MS: u64 = 250;
+/// A step back further than this is a restarted clock, not jitter
+const RESTART_BACKSTEP_MS: u64 = 2000;
+
+/// Make segment timestamps monotonic. A segment whose clock clearly restarted
+/// (it starts near zero, or well before the previous one, e.g. a resumed or
+/// concatenated recording) begins a new block, and that block is shifted to
+/// follow the previous segment. Smaller backward steps and overlaps, such as
+/// overlapping streaming windows, are clamped on that segment alone so
+/// `start_ms`/`end_ms` never go backwards.
+pub fn normalize_timestamps(segments: Vec<TranscriptSegment>) -> Vec<TranscriptSegment> {
+    let mut offset_ms = 0u64;
+    let mut last_start = 0u64;
+    let mut last_end = 0u64;
+
+    segments
+        .into_iter()
+        .map(|mut segment| {
+            let mut start = segment.start_ms + offset_ms;
+            let restarted = start < last_start
+                && (segment.start_ms <= RESTART_NEAR_ZERO_MS
+                    || last_start - start > RESTART_BACKSTEP_MS);
+            if restarted {
+                let shift = last_end - start;
+                offset_ms += shift;
+                start += shift;
+            }
+
+            let end = (segment.end_ms + offset_ms).max(start);
+            segment.start_ms = start.max(last_end).min(end);
+            segment.end_ms = end.max(segment.start_ms);
+
+            last_start = segment.start_ms;
+            last_end = segment.end_ms;
+            segment
+        })
+        .collect()
 }
 
 /// Full transcript
@@ -64,6 +225,59 @@ impl Transcript {
             .collect::<Vec<_>>()
             .join(" ")
     }
+
+    /// Distinct per-segment languages, in order of first appearance
+    pub fn segment_languages(&self) -> Vec<String> {
+        segment_languages(&self.segments)
+    }
+}
+
+/// Distinct languages reported on `segments`, in order of first appearance
+pub fn segment_languages(segments: &[TranscriptSegment]) -> Vec<String> {
+    let mut languages: Vec<String> = Vec::new();
+    for lang in segments.iter().filter_map(|s| s.language.as_deref()) {
+        if !languages.iter().any(|l| l.eq_ignore_ascii_case(lang)) {
+            languages.push(lang.to_string());
+        }
+    }
+    languages
+}
+
+/// Parse a meeting offset (`HH:MM:SS`, `MM:SS` or plain seconds) into milliseconds
+pub fn parse_offset(value: &str) -> Option<u64> {
+    let parts: Vec<&str> = value.trim().split(':').collect();
+    if parts.len() > 3 || parts.iter().any(|p| p.is_empty()) {
+        return None;
+    }
+
+    let mut seconds = 0u64;
+    for (i, part) in parts.iter().enumerate() {
+        let n: u64 = part.parse().ok()?;
+        if i > 0 && n >= 60 {
+            return None;
+        }
+        seconds = seconds * 60 + n;
+    }
+    Some(seconds * 1000)
+}
+
+/// Index range of the segment at `at_ms` plus up to `before`/`after` segments
+/// around it. `segments` must be sorted by `start_ms`; a point between two
+/// segments centers on the earlier one.
+pub fn segment_window(
+    segments: &[TranscriptSegment],
+    at_ms: u64,
+    before: usize,
+    after: usize,
+) -> std::ops::Range<usize> {
+    if segments.is_empty() {
+        return 0..0;
+    }
+
+    let center = segments
+        .partition_point(|s| s.start_ms <= at_ms)
+        .saturating_sub(1);
+    center.saturating_sub(before)..(center + after + 1).min(segments.len())
 }
 
 /// Transcription engine selection
@@ -76,3 +290,210 @@ pub enum TranscriptionEngine {
     /// OpenAI Whisper API
     OpenAI,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(start_ms: u64, end_ms: u64, text: &str, speaker: Option<&str>) -> TranscriptSegment {
+        let mut s = TranscriptSegment::new(start_ms, end_ms, text.to_string());
+        s.speaker = speaker.map(str::to_string);
+        s
+    }
+
+    #[test]
+    fn test_merge_segments_same_speaker() {
+        let segments = vec![
+            seg(0, 1000, "Hello", Some("SPEAKER_1")),
+            seg(1200, 2000, " there", Some("SPEAKER_1")),
+            seg(2100, 3000, "Hi", Some("SPEAKER_2")),
+        ];
+
+        let merged = merge_segments(&segments, 800);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].text, "Hello there");
+        assert_eq!(merged[0].start_ms, 0);
+        assert_eq!(merged[0].end_ms, 2000);
+        assert_eq!(merged[1].speaker.as_deref(), Some("SPEAKER_2"));
+    }
+
+    #[test]
+    fn test_merge_segments_respects_gap() {
+        let segments = vec![
+            seg(0, 1000, "One", Some("SPEAKER_1")),
+            seg(2000, 3000, "Two", Some("SPEAKER_1")),
+        ];
+
+        assert_eq!(merge_segments(&segments, 800).len(), 2);
+        assert_eq!(merge_segments(&segments, 1500).len(), 1);
+        assert_eq!(merge_segments(&segments, 0).len(), 2);
+    }
+
+    #[test]
+    fn test_merge_segments_confidence() {
+        let mut a = seg(0, 1000, "a", None);
+        a.confidence = Some(1.0);
+        let mut b = seg(1000, 2000, "b", None);
+        b.confidence = Some(0.5);
+
+        let merged = merge_segments(&[a, b], 800);
+        assert_eq!(merged.len(), 1);
+        assert!((merged[0].confidence.unwrap() - 0.75).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_merge_speaker_turns_skips_unlabeled() {
+        let segments = vec![
+            seg(0, 1000, "a", None),
+            seg(1000, 2000, "b", None),
+            seg(2000, 3000, "c", Some("SPEAKER_1")),
+            seg(3000, 4000, "d", Some("SPEAKER_1")),
+        ];
+
+        let merged = merge_speaker_turns(&segments, 800);
+        assert_eq!(merged.len(), 3);
+        assert_eq!((merged[1].start_ms, merged[1].end_ms), (1000, 2000));
+        assert_eq!(merged[2].text, "c d");
+    }
+
+    #[test]
+    fn test_merge_segments_keeps_language_boundaries() {
+        let mut a = seg(0, 1000, "Hello", Some("SPEAKER_1"));
+        a.language = Some("en".to_string());
+        let mut b = seg(1100, 2000, "Hola", Some("SPEAKER_1"));
+        b.language = Some("es".to_string());
+
+        let merged = merge_segments(&[a, b], 800);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[1].language.as_deref(), Some("es"));
+    }
+
+    #[test]
+    fn test_concat_segments_offsets_parts() {
+        let part1 = vec![seg(0, 1000, "a", None), seg(1000, 2000, "b", None)];
+        let part2 = vec![seg(0, 500, "c", None)];
+        let part3 = vec![seg(100, 900, "d", None)];
+
+        let combined = concat_segments(&[part1, part2, part3], &[Some(3000), None, None]);
+        let starts: Vec<u64> = combined.iter().map(|s| s.start_ms).collect();
+        assert_eq!(starts, vec![0, 1000, 3000, 3600]);
+        assert_eq!(combined[3].end_ms, 4400);
+    }
+
+    #[test]
+    fn test_normalize_timestamps_offsets_restarted_block() {
+        let segments = vec![
+            seg(0, 1000, "a", None),
+            seg(1000, 2000, "b", None),
+            seg(0, 500, "c", None),
+            seg(600, 900, "d", None),
+        ];
+
+        let normalized = normalize_timestamps(segments);
+        let spans: Vec<(u64, u64)> = normalized.iter().map(|s| (s.start_ms, s.end_ms)).collect();
+        assert_eq!(
+            spans,
+            vec![(0, 1000), (1000, 2000), (2000, 2500), (2600, 2900)]
+        );
+    }
+
+    #[test]
+    fn test_normalize_timestamps_clamps_overlap() {
+        let segments = vec![seg(0, 1500, "a", None), seg(1200, 2000, "b", None)];
+        let normalized = normalize_timestamps(segments);
+        assert_eq!(normalized[1].start_ms, 1500);
+        assert_eq!(normalized[1].end_ms, 2000);
+
+        let inverted = normalize_timestamps(vec![seg(1000, 500, "x", None)]);
+        assert_eq!((inverted[0].start_ms, inverted[0].end_ms), (1000, 1000));
+    }
+
+    #[test]
+    fn test_normalize_timestamps_clamps_small_backstep_locally() {
+        let segments = vec![
+            seg(0, 1000, "a", None),
+            seg(1000, 2000, "b", None),
+            seg(700, 1200, "c", None),
+            seg(2500, 3000, "d", None),
+            seg(4000, 5000, "e", None),
+        ];
+
+        let normalized = normalize_timestamps(segments);
+        let spans: Vec<(u64, u64)> = normalized.iter().map(|s| (s.start_ms, s.end_ms)).collect();
+        assert_eq!(
+            spans,
+            vec![
+                (0, 1000),
+                (1000, 2000),
+                (2000, 2000),
+                (2500, 3000),
+                (4000, 5000)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normalize_timestamps_keeps_valid_transcript() {
+        let segments = vec![seg(0, 1000, "a", None), seg(1500, 2000, "b", None)];
+        let normalized = normalize_timestamps(segments.clone());
+        for (a, b) in segments.iter().zip(&normalized) {
+            assert_eq!((a.start_ms, a.end_ms), (b.start_ms, b.end_ms));
+        }
+    }
+
+    #[test]
+    fn test_segment_languages() {
+        let mut a = seg(0, 1000, "Hello", None);
+        a.language = Some("en".to_string());
+        let b = seg(1000, 2000, "...", None);
+        let mut c = seg(2000, 3000, "Hallo", None);
+        c.language = Some("de".to_string());
+        let mut d = seg(3000, 4000, "Right", None);
+        d.language = Some("EN".to_string());
+
+        assert_eq!(segment_languages(&[a, b, c, d]), vec!["en", "de"]);
+        assert!(segment_languages(&[seg(0, 1, "x", None)]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_offset() {
+        assert_eq!(parse_offset("00:12:30"), Some(750_000));
+        assert_eq!(parse_offset("12:30"), Some(750_000));
+        assert_eq!(parse_offset("90"), Some(90_000));
+        assert_eq!(parse_offset("1:02:03"), Some(3_723_000));
+        assert_eq!(parse_offset("12:75"), None);
+        assert_eq!(parse_offset("1:2:3:4"), None);
+        assert_eq!(parse_offset("12:"), None);
+        assert_eq!(parse_offset("abc"), None);
+    }
+
+    #[test]
+    fn test_segment_window() {
+        let segments: Vec<_> = (0..10)
+            .map(|i| seg(i * 10_000, i * 10_000 + 8_000, "x", None))
+            .collect();
+
+        assert_eq!(segment_window(&segments, 45_000, 2, 2), 2..7);
+        assert_eq!(
+            segment_window(&segments, 49_000, 2, 2),
+            2..7,
+            "gap centers on earlier"
+        );
+        assert_eq!(segment_window(&segments, 0, 3, 1), 0..2);
+        assert_eq!(segment_window(&segments, 999_000, 1, 5), 8..10);
+        assert_eq!(segment_window(&[], 1_000, 1, 1), 0..0);
+    }
+
+    #[test]
+    fn test_format_clock_timestamp() {
+        use chrono::TimeZone;
+        let start = chrono::Utc.with_ymd_and_hms(2024, 3, 1, 14, 30, 0).unwrap();
+
+        assert_eq!(format_clock_timestamp(&start, 0), "14:30:00");
+        assert_eq!(format_clock_timestamp(&start, 750_500), "14:42:30");
+        assert_eq!(
+            seg(3_600_000, 3_601_000, "x", None).format_clock_timestamp(&start),
+            "15:30:00"
+        );
+    }
+}
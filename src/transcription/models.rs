@@ -1,6 +1,7 @@
 use crate::error::{MuesliError, Result};
+use crate::transcription::checksum::{self, Verification};
+use crate::transcription::download::{self, DownloadOptions};
 use std::fs;
-use std::io::{Read, Write};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -103,11 +104,21 @@ impl std::fmt::Display for WhisperModel {
 
 pub struct ModelManager {
     models_dir: PathBuf,
+    download: DownloadOptions,
 }
 
 impl ModelManager {
     pub fn new(models_dir: PathBuf) -> Self {
-        Self { models_dir }
+        Self {
+            models_dir,
+            download: DownloadOptions::from_env(),
+        }
+    }
+
+    /// Override rate limit / mirror for downloads
+    pub fn with_download_options(mut self, download: DownloadOptions) -> Self {
+        self.download = download;
+        self
     }
 
     pub fn model_path(&self, model: WhisperModel) -> PathBuf {
@@ -152,46 +163,44 @@ impl ModelManager {
             return Ok(path);
         }
 
-        let url = model.download_url();
+        let url = self.download.resolve_url(model.download_url());
         let temp_path = path.with_extension("bin.tmp");
 
-        let response = reqwest::blocking::Client::new()
-            .get(url)
-            .send()
-            .map_err(|e| MuesliError::Api(format!("Download failed: {}", e)))?;
-
-        let total_size = response
-            .content_length()
-            .unwrap_or(model.size_mb() * 1024 * 1024);
-
-        let mut file = fs::File::create(&temp_path)?;
-        let mut downloaded: u64 = 0;
-
-        let mut reader = response;
-        let mut buffer = [0u8; 8192];
-
-        loop {
-            let bytes_read = reader.read(&mut buffer).map_err(MuesliError::Io)?;
-
-            if bytes_read == 0 {
-                break;
-            }
-
-            file.write_all(&buffer[..bytes_read])?;
-            downloaded += bytes_read as u64;
-            progress(downloaded, total_size);
+        let fetched = download::fetch_to_file(
+            &url,
+            &temp_path,
+            &self.download,
+            model.size_mb() * 1024 * 1024,
+            progress,
+        )?;
+
+        if let Err(e) = checksum::check_download(
+            fetched.size,
+            fetched.expected_size,
+            fetched.expected_sha256.as_deref(),
+            &fetched.sha256,
+        ) {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e);
         }
 
         fs::rename(&temp_path, &path)?;
+        checksum::write_checksum(&path, &fetched.sha256, fetched.size)?;
 
         Ok(path)
     }
 
+    /// Check an installed model against the checksum recorded when it was downloaded
+    pub fn verify_model(&self, model: WhisperModel) -> Result<Verification> {
+        checksum::verify_file(&self.model_path(model))
+    }
+
     pub fn delete_model(&self, model: WhisperModel) -> Result<()> {
         let path = self.model_path(model);
         if path.exists() {
-            fs::remove_file(path)?;
+            fs::remove_file(&path)?;
         }
+        checksum::remove_checksum(&path)?;
         Ok(())
     }
 }
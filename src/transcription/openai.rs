@@ -83,6 +83,7 @@ pub async fn transcribe_bytes(
                 text: s.text.trim().to_string(),
                 speaker: None,
                 confidence: None,
+                language: None,
             })
             .collect()
     } else {
@@ -93,6 +94,7 @@ pub async fn transcribe_bytes(
             text: result.text,
             speaker: None,
             confidence: None,
+            language: None,
         }]
     };
 
@@ -14,12 +14,65 @@ const WHISPER_STEP_SAMPLES: usize = WHISPER_CHUNK_SAMPLES - WHISPER_OVERLAP_SAMP
 pub struct WhisperStreamingConfig {
     pub model_path: std::path::PathBuf,
     pub use_gpu: bool,
+    /// Regroup captured audio into windows of this many ms before feeding the
+    /// model (0 = pass capture buffers through as they arrive)
+    pub feed_chunk_ms: u64,
 }
 
 pub struct StreamingTranscriber {
     audio_tx: mpsc::Sender<AudioCommand>,
     segment_rx: mpsc::Receiver<TranscriptSegment>,
     handle: Option<thread::JoinHandle<()>>,
+    batcher: SampleBatcher,
+}
+
+/// Accumulates samples into fixed-size windows, independent of how large the
+/// capture callback's buffers happen to be. A window of 0 passes buffers through.
+#[derive(Debug)]
+pub struct SampleBatcher {
+    window: usize,
+    pending: Vec<f32>,
+}
+
+impl SampleBatcher {
+    pub fn new(window_ms: u64, sample_rate: usize) -> Self {
+        let window = window_ms as usize * sample_rate / 1000;
+        Self {
+            window,
+            pending: Vec::with_capacity(window),
+        }
+    }
+
+    /// Add samples, returning every window completed by them
+    pub fn push(&mut self, samples: &[f32]) -> Vec<Vec<f32>> {
+        if self.window == 0 {
+            return if samples.is_empty() {
+                Vec::new()
+            } else {
+                vec![samples.to_vec()]
+            };
+        }
+
+        self.pending.extend_from_slice(samples);
+        if self.pending.len() < self.window {
+            return Vec::new();
+        }
+
+        let complete = self.pending.len() / self.window * self.window;
+        let rest = self.pending.split_off(complete);
+        let windows = self
+            .pending
+            .chunks(self.window)
+            .map(<[f32]>::to_vec)
+            .collect();
+        self.pending = rest;
+        windows
+    }
+
+    /// Take whatever is left over as a final, shorter window
+    pub fn flush(&mut self) -> Option<Vec<f32>> {
+        (!self.pending.is_empty()).then(|| std::mem::take(&mut self.pending))
+    }
 }
 
 enum AudioCommand {
@@ -32,6 +85,7 @@ impl StreamingTranscriber {
     pub fn new(config: WhisperStreamingConfig) -> Result<Self> {
         let (audio_tx, audio_rx) = mpsc::channel::<AudioCommand>();
         let (segment_tx, segment_rx) = mpsc::channel::<TranscriptSegment>();
+        let batcher = SampleBatcher::new(config.feed_chunk_ms, SAMPLE_RATE);
 
         let handle = thread::spawn(move || {
             if let Err(e) = run_transcription_worker(config, audio_rx, segment_tx) {
@@ -43,17 +97,29 @@ impl StreamingTranscriber {
             audio_tx,
             segment_rx,
             handle: Some(handle),
+            batcher,
         })
     }
 
-    pub fn feed_samples(&self, samples: &[f32]) -> Result<()> {
+    pub fn feed_samples(&mut self, samples: &[f32]) -> Result<()> {
+        for window in self.batcher.push(samples) {
+            self.send_samples(window)?;
+        }
+        Ok(())
+    }
+
+    fn send_samples(&self, samples: Vec<f32>) -> Result<()> {
         self.audio_tx
-            .send(AudioCommand::Samples(samples.to_vec()))
+            .send(AudioCommand::Samples(samples))
             .map_err(|_| MuesliError::Transcription("Worker channel closed".to_string()))?;
         Ok(())
     }
 
-    pub fn flush(&self) -> Result<()> {
+    /// Send any partially filled window, then have the worker transcribe what it has
+    pub fn flush(&mut self) -> Result<()> {
+        if let Some(rest) = self.batcher.flush() {
+            self.send_samples(rest)?;
+        }
         self.audio_tx
             .send(AudioCommand::Flush)
             .map_err(|_| MuesliError::Transcription("Worker channel closed".to_string()))?;
@@ -61,6 +127,9 @@ impl StreamingTranscriber {
     }
 
     pub fn stop(mut self) -> Result<Vec<TranscriptSegment>> {
+        if let Some(rest) = self.batcher.flush() {
+            let _ = self.send_samples(rest);
+        }
         let _ = self.audio_tx.send(AudioCommand::Stop);
 
         if let Some(handle) = self.handle.take() {
@@ -204,3 +273,30 @@ fn transcribe_whisper_chunk(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_batcher_windows() {
+        // 10ms at 16kHz = 160 samples
+        let mut batcher = SampleBatcher::new(10, SAMPLE_RATE);
+
+        assert!(batcher.push(&[0.0; 100]).is_empty());
+        let windows = batcher.push(&[0.0; 300]);
+        assert_eq!(windows.len(), 2);
+        assert!(windows.iter().all(|w| w.len() == 160));
+
+        assert_eq!(batcher.flush().map(|r| r.len()), Some(80));
+        assert!(batcher.flush().is_none());
+    }
+
+    #[test]
+    fn test_sample_batcher_passthrough() {
+        let mut batcher = SampleBatcher::new(0, SAMPLE_RATE);
+        let windows = batcher.push(&[1.0, 2.0, 3.0]);
+        assert_eq!(windows, vec![vec![1.0, 2.0, 3.0]]);
+        assert!(batcher.flush().is_none());
+    }
+}
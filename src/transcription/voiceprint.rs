@@ -0,0 +1,312 @@
+//! Voiceprints for recognizing enrolled speakers across meetings.
+//!
+//! Sortformer only tells speakers apart within one recording, so names come
+//! from a separate, model-free signature: the mean and spread of each
+//! speaker's MFCCs. It is coarse, but stable enough for the handful of voices
+//! that show up in someone's regular meetings.
+
+use crate::transcription::diarization::SpeakerSegment;
+use rustfft::{num_complex::Complex, FftPlanner};
+use std::collections::HashMap;
+
+const SAMPLE_RATE: usize = 16000;
+const FRAME_LEN: usize = 400; // 25 ms
+const FRAME_HOP: usize = 160; // 10 ms
+const FFT_LEN: usize = 512;
+const MEL_BANDS: usize = 40;
+const CEPSTRA: usize = 20;
+
+/// Frames quieter than this (RMS) are silence and left out of the voiceprint
+const SILENCE_RMS: f32 = 0.01;
+
+/// Least speech a voiceprint is computed from
+pub const MIN_SPEECH_SECS: usize = 3;
+
+/// Speech per speaker used when matching a meeting (more adds little)
+const MAX_MATCH_SECS: usize = 60;
+
+/// Voiceprint of 16 kHz mono `samples`: per-coefficient mean then standard
+/// deviation of MFCCs 1..=20 over the voiced frames. `None` when there is less
+/// than [`MIN_SPEECH_SECS`] of speech.
+pub fn voiceprint(samples: &[f32]) -> Option<Vec<f32>> {
+    let fft = FftPlanner::<f32>::new().plan_fft_forward(FFT_LEN);
+    let filters = mel_filters();
+    let window: Vec<f32> = (0..FRAME_LEN)
+        .map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / (FRAME_LEN - 1) as f32).cos())
+        .collect();
+
+    let mut frames: Vec<Vec<f32>> = Vec::new();
+    let mut start = 0;
+    while start + FRAME_LEN <= samples.len() {
+        let frame = &samples[start..start + FRAME_LEN];
+        start += FRAME_HOP;
+
+        let rms = (frame.iter().map(|s| s * s).sum::<f32>() / FRAME_LEN as f32).sqrt();
+        if rms < SILENCE_RMS {
+            continue;
+        }
+
+        let mut buffer: Vec<Complex<f32>> = frame
+            .iter()
+            .zip(&window)
+            .map(|(s, w)| Complex::new(s * w, 0.0))
+            .chain(std::iter::repeat(Complex::new(0.0, 0.0)))
+            .take(FFT_LEN)
+            .collect();
+        fft.process(&mut buffer);
+        let power: Vec<f32> = buffer[..FFT_LEN / 2 + 1]
+            .iter()
+            .map(|c| c.norm_sqr())
+            .collect();
+
+        let log_mel: Vec<f32> = filters
+            .iter()
+            .map(|f| f.iter().zip(&power).map(|(w, p)| w * p).sum::<f32>())
+            .map(|e| (e + 1e-10).ln())
+            .collect();
+        frames.push(dct(&log_mel));
+    }
+
+    if frames.len() * FRAME_HOP < MIN_SPEECH_SECS * SAMPLE_RATE {
+        return None;
+    }
+
+    let n = frames.len() as f32;
+    let mean: Vec<f32> = (0..CEPSTRA)
+        .map(|c| frames.iter().map(|f| f[c]).sum::<f32>() / n)
+        .collect();
+    let std: Vec<f32> = (0..CEPSTRA)
+        .map(|c| (frames.iter().map(|f| (f[c] - mean[c]).powi(2)).sum::<f32>() / n).sqrt())
+        .collect();
+    Some(mean.into_iter().chain(std).collect())
+}
+
+/// Cosine similarity of two voiceprints (0 when their lengths differ)
+pub fn similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let denom = norm(a) * norm(b);
+    if denom == 0.0 {
+        0.0
+    } else {
+        dot / denom
+    }
+}
+
+/// Name diarized speakers after the enrolled profiles they sound like.
+///
+/// Each speaker's voiceprint is taken from up to a minute of their turns in
+/// `samples`; the closest profiles at or above `threshold` are assigned best
+/// match first, so no two speakers get the same name. Returns speaker id to name.
+pub fn match_speakers(
+    samples: &[f32],
+    speaker_segments: &[SpeakerSegment],
+    profiles: &[(String, Vec<f32>)],
+    threshold: f32,
+) -> HashMap<usize, String> {
+    let mut speech: HashMap<usize, Vec<f32>> = HashMap::new();
+    for turn in speaker_segments {
+        let buffer = speech.entry(turn.speaker_id).or_default();
+        if buffer.len() >= MAX_MATCH_SECS * SAMPLE_RATE {
+            continue;
+        }
+        let start = (turn.start_ms as usize * SAMPLE_RATE / 1000).min(samples.len());
+        let end = (turn.end_ms as usize * SAMPLE_RATE / 1000).min(samples.len());
+        buffer.extend_from_slice(&samples[start..end.max(start)]);
+    }
+
+    let mut candidates: Vec<(f32, usize, &str)> = Vec::new();
+    for (speaker_id, audio) in &speech {
+        let Some(print) = voiceprint(audio) else {
+            continue;
+        };
+        for (name, profile) in profiles {
+            let score = similarity(&print, profile);
+            tracing::debug!(
+                "Speaker {} vs profile {}: {:.3}",
+                speaker_id + 1,
+                name,
+                score
+            );
+            if score >= threshold {
+                candidates.push((score, *speaker_id, name));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut names = HashMap::new();
+    for (_, speaker_id, name) in candidates {
+        if names.contains_key(&speaker_id) || names.values().any(|n: &String| n == name) {
+            continue;
+        }
+        names.insert(speaker_id, name.to_string());
+    }
+    names
+}
+
+/// Triangular mel filters over the FFT bins, 0 Hz to Nyquist
+fn mel_filters() -> Vec<Vec<f32>> {
+    let to_mel = |hz: f32| 2595.0 * (1.0 + hz / 700.0).log10();
+    let to_hz = |mel: f32| 700.0 * (10f32.powf(mel / 2595.0) - 1.0);
+    let max_mel = to_mel(SAMPLE_RATE as f32 / 2.0);
+    let bins: Vec<f32> = (0..MEL_BANDS + 2)
+        .map(|i| to_hz(max_mel * i as f32 / (MEL_BANDS + 1) as f32))
+        .map(|hz| hz * FFT_LEN as f32 / SAMPLE_RATE as f32)
+        .collect();
+
+    (0..MEL_BANDS)
+        .map(|band| {
+            let (lo, mid, hi) = (bins[band], bins[band + 1], bins[band + 2]);
+            (0..FFT_LEN / 2 + 1)
+                .map(|bin| {
+                    let bin = bin as f32;
+                    if bin <= lo || bin >= hi {
+                        0.0
+                    } else if bin <= mid {
+                        (bin - lo) / (mid - lo)
+                    } else {
+                        (hi - bin) / (hi - mid)
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// DCT-II of the log mel energies, keeping coefficients 1..=CEPSTRA (c0 is loudness)
+fn dct(log_mel: &[f32]) -> Vec<f32> {
+    let n = log_mel.len() as f32;
+    (1..=CEPSTRA)
+        .map(|k| {
+            log_mel
+                .iter()
+                .enumerate()
+                .map(|(i, e)| e * (std::f32::consts::PI * k as f32 * (i as f32 + 0.5) / n).cos())
+                .sum()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(hz: f32, secs: usize) -> Vec<f32> {
+        (0..secs * SAMPLE_RATE)
+            .map(|i| {
+                let t = i as f32 / SAMPLE_RATE as f32;
+                0.3 * (2.0 * std::f32::consts::PI * hz * t).sin()
+                    + 0.1 * (2.0 * std::f32::consts::PI * hz * 3.0 * t).sin()
+            })
+            .collect()
+    }
+
+    /// Vowel-like speech: a glottal pulse train at `f0` through three formant
+    /// resonators per vowel, cycling `vowels` from `first` in 250 ms syllables
+    fn speech(f0: f32, vowels: &[[f32; 3]], secs: usize, first: usize) -> Vec<f32> {
+        let syllable = SAMPLE_RATE / 4;
+        let period = (SAMPLE_RATE as f32 / f0) as usize;
+        let mut out = Vec::new();
+        let mut vowel = first;
+        while out.len() < secs * SAMPLE_RATE {
+            let pulses: Vec<f32> = (0..syllable)
+                .map(|i| if i % period == 0 { 1.0 } else { 0.0 })
+                .collect();
+            let mut signal = vec![0.0f32; syllable];
+            for &formant in &vowels[vowel % vowels.len()] {
+                let r = (-std::f32::consts::PI * 100.0 / SAMPLE_RATE as f32).exp();
+                let theta = 2.0 * std::f32::consts::PI * formant / SAMPLE_RATE as f32;
+                let (mut y1, mut y2) = (0.0f32, 0.0f32);
+                for (out, pulse) in signal.iter_mut().zip(&pulses) {
+                    let y = (1.0 - r) * pulse + 2.0 * r * theta.cos() * y1 - r * r * y2;
+                    *out += y;
+                    y2 = y1;
+                    y1 = y;
+                }
+            }
+            let peak = signal.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+            out.extend(signal.iter().enumerate().map(|(i, s)| {
+                0.3 * s / peak * (std::f32::consts::PI * i as f32 / syllable as f32).sin()
+            }));
+            out.extend(std::iter::repeat(0.0).take(SAMPLE_RATE / 20));
+            vowel += 1;
+        }
+        out.truncate(secs * SAMPLE_RATE);
+        out
+    }
+
+    fn turn(speaker_id: usize, start_ms: u64, end_ms: u64) -> SpeakerSegment {
+        SpeakerSegment {
+            speaker_id,
+            start_ms,
+            end_ms,
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_voiceprint() {
+        assert!(voiceprint(&tone(150.0, 1)).is_none());
+        assert!(voiceprint(&vec![0.0; 5 * SAMPLE_RATE]).is_none());
+
+        let low = voiceprint(&tone(150.0, 4)).unwrap();
+        let low_again = voiceprint(&tone(150.0, 5)).unwrap();
+        let high = voiceprint(&tone(900.0, 4)).unwrap();
+        assert_eq!(low.len(), 2 * CEPSTRA);
+        assert!(similarity(&low, &low_again) > similarity(&low, &high));
+    }
+
+    #[test]
+    fn test_match_speakers() {
+        let mut samples = tone(150.0, 5);
+        samples.extend(tone(900.0, 5));
+        let turns = vec![turn(0, 0, 5000), turn(1, 5000, 10000)];
+        let profiles = vec![
+            ("Bo".to_string(), voiceprint(&tone(900.0, 4)).unwrap()),
+            ("Andrea".to_string(), voiceprint(&tone(150.0, 4)).unwrap()),
+        ];
+
+        let names = match_speakers(&samples, &turns, &profiles, 0.99);
+        assert_eq!(names.get(&0).map(String::as_str), Some("Andrea"));
+        assert_eq!(names.get(&1).map(String::as_str), Some("Bo"));
+
+        assert!(match_speakers(&samples, &turns, &[], 0.5).is_empty());
+    }
+
+    #[test]
+    fn test_default_threshold_on_speech() {
+        let threshold =
+            crate::config::settings::TranscriptionConfig::default().speaker_match_threshold;
+        let low = [
+            [730.0, 1090.0, 2440.0],
+            [270.0, 2290.0, 3010.0],
+            [300.0, 870.0, 2240.0],
+        ];
+        let high = [
+            [850.0, 1220.0, 2810.0],
+            [370.0, 2950.0, 3650.0],
+            [430.0, 1020.0, 2900.0],
+        ];
+
+        let enrolled = voiceprint(&speech(110.0, &low, 5, 0)).unwrap();
+        let same = voiceprint(&speech(115.0, &low, 5, 1)).unwrap();
+        let other = voiceprint(&speech(210.0, &high, 5, 0)).unwrap();
+        let other_vowels = voiceprint(&speech(110.0, &high, 5, 0)).unwrap();
+
+        assert!(similarity(&enrolled, &same) >= threshold);
+        assert!(similarity(&enrolled, &other) < threshold);
+        assert!(similarity(&enrolled, &other_vowels) < threshold);
+    }
+
+    #[test]
+    fn test_match_speakers_inverted_turn() {
+        let samples = tone(150.0, 5);
+        let turns = vec![turn(0, 3000, 1000)];
+        let profiles = vec![("Andrea".to_string(), voiceprint(&tone(150.0, 4)).unwrap())];
+        assert!(match_speakers(&samples, &turns, &profiles, 0.5).is_empty());
+    }
+}
@@ -12,8 +12,9 @@ pub struct WhisperEngine {
 
 impl WhisperEngine {
     pub fn new<P: AsRef<Path>>(model_path: P, use_gpu: bool) -> Result<Self> {
+        let backend = crate::transcription::gpu::resolve_backend(use_gpu);
         let params = WhisperContextParameters {
-            use_gpu,
+            use_gpu: backend.is_gpu(),
             ..Default::default()
         };
 
@@ -117,26 +118,33 @@ impl WhisperEngine {
     }
 }
 
-/// Transcribe a WAV file
+/// Transcribe a WAV file, optionally evening out its loudness first
 pub fn transcribe_wav_file<P: AsRef<Path>>(
     engine: &WhisperEngine,
     wav_path: P,
+    normalize: bool,
 ) -> Result<Transcript> {
-    let reader = hound::WavReader::open(wav_path.as_ref())
-        .map_err(|e| MuesliError::Audio(format!("Failed to open WAV: {}", e)))?;
-
-    let spec = reader.spec();
-    if spec.sample_rate != 16000 || spec.channels != 1 {
-        return Err(MuesliError::Audio(format!(
-            "WAV must be 16kHz mono, got {}Hz {} channels",
-            spec.sample_rate, spec.channels
-        )));
+    // Compressed recordings always decode to 16kHz mono
+    if !crate::audio::compress::is_opus(wav_path.as_ref()) {
+        let reader = hound::WavReader::open(wav_path.as_ref())
+            .map_err(|e| MuesliError::Audio(format!("Failed to open WAV: {}", e)))?;
+
+        let spec = reader.spec();
+        if spec.sample_rate != 16000 || spec.channels != 1 {
+            return Err(MuesliError::Audio(format!(
+                "WAV must be 16kHz mono, got {}Hz {} channels",
+                spec.sample_rate, spec.channels
+            )));
+        }
     }
 
-    let samples: Vec<f32> = reader
-        .into_samples::<f32>()
-        .map(|s| s.unwrap_or(0.0))
-        .collect();
+    // Recordings may be 16-bit PCM or 32-bit float (`[audio] bit_depth`)
+    let (mut samples, sample_rate) = crate::audio::wav::read_mono(wav_path.as_ref())?;
+
+    if normalize {
+        let gain = crate::audio::convert::normalize_loudness(&mut samples, sample_rate);
+        tracing::debug!("Loudness normalized (average gain {:.2}x)", gain);
+    }
 
     engine.transcribe(&samples)
 }
@@ -1,9 +1,16 @@
 use crate::config::settings::WaybarConfig;
+use crate::daemon::protocol::DaemonStatus;
 use crate::error::{MuesliError, Result};
 use serde::Serialize;
 use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Serializes status writes, so a write decided before a state change can't
+/// land after the one that follows it
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
 
 #[derive(Debug, Clone, Serialize)]
 pub struct WaybarStatus {
@@ -12,13 +19,14 @@ pub struct WaybarStatus {
     pub class: String,
     pub alt: String,
     pub percentage: u8,
+    /// Elapsed recording time, for [`WaybarStatus::with_text`]
+    #[serde(skip)]
+    elapsed: Option<String>,
 }
 
 impl WaybarStatus {
     pub fn recording(title: &str, duration_secs: u64) -> Self {
-        let mins = duration_secs / 60;
-        let secs = duration_secs % 60;
-        let duration_text = format!("{:02}:{:02}", mins, secs);
+        let duration_text = format_elapsed(duration_secs);
 
         Self {
             text: String::new(),
@@ -26,9 +34,55 @@ impl WaybarStatus {
             class: "recording".to_string(),
             alt: "recording".to_string(),
             percentage: 100,
+            elapsed: Some(duration_text),
+        }
+    }
+
+    pub fn paused(title: &str, duration_secs: u64) -> Self {
+        let duration_text = format_elapsed(duration_secs);
+
+        Self {
+            text: String::new(),
+            tooltip: format!("Paused: {} ({})", title, duration_text),
+            class: "paused".to_string(),
+            alt: "paused".to_string(),
+            percentage: 50,
+            elapsed: Some(duration_text),
         }
     }
 
+    /// The status for what the daemon reports (`None` when it isn't running)
+    pub fn from_daemon_status(status: Option<&DaemonStatus>) -> Self {
+        match status {
+            Some(status) if status.recording && status.paused => Self::paused(
+                status.current_meeting.as_deref().unwrap_or("Recording"),
+                status.recording_seconds.unwrap_or(0),
+            ),
+            Some(status) if status.recording => Self::recording(
+                status.current_meeting.as_deref().unwrap_or("Recording"),
+                status.recording_seconds.unwrap_or(0),
+            ),
+            _ => Self::idle(),
+        }
+    }
+
+    /// With `[waybar] show_text`, also put the elapsed time in `text` for bars
+    /// that show the module's text rather than an icon per `alt`
+    pub fn with_text(mut self, config: &WaybarConfig) -> Self {
+        if config.show_text {
+            if let Some(elapsed) = &self.elapsed {
+                self.text = elapsed.clone();
+            }
+        }
+        self
+    }
+
+    /// Append the latest transcribed line to the tooltip
+    pub fn with_preview(mut self, line: &str) -> Self {
+        self.tooltip.push_str(&format!("\n{}", line));
+        self
+    }
+
     pub fn idle() -> Self {
         Self {
             text: String::new(),
@@ -36,6 +90,7 @@ impl WaybarStatus {
             class: "idle".to_string(),
             alt: "idle".to_string(),
             percentage: 0,
+            elapsed: None,
         }
     }
 
@@ -44,6 +99,10 @@ impl WaybarStatus {
     }
 }
 
+fn format_elapsed(duration_secs: u64) -> String {
+    format!("{:02}:{:02}", duration_secs / 60, duration_secs % 60)
+}
+
 pub fn waybar_status_path(config: &WaybarConfig) -> Result<PathBuf> {
     if let Some(ref custom_path) = config.status_file {
         return Ok(custom_path.clone());
@@ -58,11 +117,30 @@ pub fn waybar_status_path(config: &WaybarConfig) -> Result<PathBuf> {
 }
 
 pub fn update_waybar_status(config: &WaybarConfig, status: &WaybarStatus) {
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    write_and_signal(config, status);
+}
+
+/// [`update_waybar_status`] unless `active` has been cleared (checked under the
+/// write lock). For periodic writes made off the daemon's state lock: once the
+/// owner clears `active` and writes its own status, no tick can overwrite it.
+pub fn update_waybar_status_while(
+    config: &WaybarConfig,
+    status: &WaybarStatus,
+    active: &AtomicBool,
+) {
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    if active.load(Ordering::Relaxed) {
+        write_and_signal(config, status);
+    }
+}
+
+fn write_and_signal(config: &WaybarConfig, status: &WaybarStatus) {
     if !config.enabled {
         return;
     }
 
-    if let Err(e) = write_status_file(config, status) {
+    if let Err(e) = write_status_file(config, &status.clone().with_text(config)) {
         tracing::warn!("Failed to update waybar status file: {}", e);
     }
 
@@ -89,11 +167,14 @@ fn write_status_file(config: &WaybarConfig, status: &WaybarStatus) -> Result<()>
 }
 
 fn signal_waybar() {
+    // Wait for pkill rather than spawning it: the daemon signals every second
+    // while recording, and unreaped children would pile up as zombies
     match Command::new("pkill")
         .args(["-SIGRTMIN+8", "waybar"])
-        .spawn()
+        .status()
     {
-        Ok(_) => tracing::debug!("Sent SIGRTMIN+8 to waybar"),
+        Ok(status) if status.success() => tracing::debug!("Sent SIGRTMIN+8 to waybar"),
+        Ok(_) => tracing::debug!("Could not signal waybar (not running)"),
         Err(e) => tracing::debug!("Could not signal waybar (may not be running): {}", e),
     }
 }
@@ -115,6 +196,14 @@ mod tests {
         assert!(status.tooltip.contains("02:05"));
     }
 
+    #[test]
+    fn test_waybar_status_paused() {
+        let status = WaybarStatus::paused("Test Meeting", 125);
+        assert_eq!(status.alt, "paused");
+        assert_eq!(status.class, "paused");
+        assert_eq!(status.tooltip, "Paused: Test Meeting (02:05)");
+    }
+
     #[test]
     fn test_waybar_status_idle() {
         let status = WaybarStatus::idle();
@@ -122,6 +211,22 @@ mod tests {
         assert_eq!(status.alt, "idle");
     }
 
+    #[test]
+    fn test_waybar_status_show_text() {
+        let shown = WaybarConfig {
+            show_text: true,
+            ..WaybarConfig::default()
+        };
+        let status = WaybarStatus::recording("Test Meeting", 125).with_text(&shown);
+        assert_eq!(status.text, "02:05");
+        assert!(!status.to_json().contains("elapsed"));
+
+        assert_eq!(WaybarStatus::idle().with_text(&shown).text, "");
+        let hidden =
+            WaybarStatus::recording("Test Meeting", 125).with_text(&WaybarConfig::default());
+        assert_eq!(hidden.text, "");
+    }
+
     #[test]
     fn test_waybar_status_json() {
         let status = WaybarStatus::idle();